@@ -1 +1,153 @@
+//! ABA routing transit numbers, identifying US financial institutions for
+//! Fedwire and ACH payments.
 
+use crate::code::Code;
+use crate::error::{check_digits, Error, Reason};
+use crate::result::Result;
+use arraystring::typenum::U9;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `WEIGHTS` are the weights of the check digit, repeated from the left.
+const WEIGHTS: [u32; 3] = [3, 7, 1];
+
+/// [`RoutingNumber`] is an ABA routing number: a four digits Federal
+/// Reserve routing symbol, a four digits institution identifier and a
+/// check digit, e.g. "021000021".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RoutingNumber(Code<U9>);
+
+impl RoutingNumber {
+    pub fn new(code: &str) -> Result<RoutingNumber> {
+        RoutingNumber::check(code)?;
+
+        Ok(RoutingNumber(Code::<U9>::from(code)))
+    }
+
+    /// `as_str` returns the `RoutingNumber` as a string slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `routing_symbol` returns the four digits Federal Reserve routing
+    /// symbol, e.g. "0210".
+    pub fn routing_symbol(&self) -> &str {
+        &self.as_str()[0..4]
+    }
+
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_digits("routing number", code, 9, 9)?;
+
+        if !matches!(
+            &code[0..2].parse::<u8>(),
+            Ok(0..=12) | Ok(21..=32) | Ok(61..=72) | Ok(80)
+        ) {
+            return Err(Error::invalid(
+                "routing number",
+                code,
+                Reason::Format(format!("no Federal Reserve district {}", &code[0..2])),
+            ));
+        }
+
+        let sum: u32 = code[0..8]
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .zip(WEIGHTS.iter().cycle())
+            .map(|(d, w)| d * w)
+            .sum();
+        let expected = (10 - sum % 10) % 10;
+
+        if code[8..].parse() != Ok(expected) {
+            return Err(Error::invalid(
+                "routing number",
+                code,
+                Reason::Checksum {
+                    expected: expected.to_string(),
+                    actual: code[8..].into(),
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `is_valid` returns if a provided code is valid by checking length,
+    /// kind of chars used, the Federal Reserve district of its first two
+    /// digits and the check digit.
+    pub fn is_valid(code: &str) -> bool {
+        RoutingNumber::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`RoutingNumber`].
+    pub fn validate(&self) -> Result<()> {
+        RoutingNumber::check(self.as_str())
+    }
+}
+
+impl fmt::Display for RoutingNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for RoutingNumber {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        RoutingNumber::new(code)
+    }
+}
+
+impl From<&str> for RoutingNumber {
+    fn from(code: &str) -> Self {
+        RoutingNumber::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for RoutingNumber {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(self.0.as_str(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for RoutingNumber {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::RoutingNumber;
+
+    #[test]
+    fn is_valid() {
+        const WRONG_CODES: &[&str] = &[
+            "021000022",
+            "02100002",
+            "0210000211",
+            "02100002A",
+            "991000009",
+        ];
+        const VALID_CODES: &[&str] = &["021000021", "011000015", "121000358", "322271627"];
+
+        for code in WRONG_CODES.iter() {
+            assert!(!RoutingNumber::is_valid(code), "{}", code);
+        }
+
+        for code in VALID_CODES.iter() {
+            assert!(RoutingNumber::is_valid(code), "{}", code);
+        }
+
+        assert_eq!(
+            serde_json::from_str::<RoutingNumber>("\"021000021\"").ok(),
+            Some(RoutingNumber::from("021000021"))
+        );
+        assert!(serde_json::from_str::<RoutingNumber>("\"021000022\"").is_err());
+    }
+}
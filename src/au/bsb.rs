@@ -0,0 +1,265 @@
+//! Australian Bank-State-Branch (BSB) numbers, as listed by the APCA
+//! (Australian Payments Clearing Association, now AusPayNet).
+
+use crate::code::Code;
+use crate::csv;
+use crate::error::{check_digits, Error, Reason};
+use crate::result::Result;
+use arraystring::typenum::U6;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// [`Bsb`] is a BSB number: six digits identifying a bank branch, the
+/// institution (2 digits), the state (1 digit) and the branch (3 digits),
+/// printed "062-000".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bsb(Code<U6>);
+
+impl Bsb {
+    /// `new` creates a new `Bsb`, with or without the '-' or ' ' separator.
+    pub fn new(code: &str) -> Result<Bsb> {
+        let cd: String = code.chars().filter(|c| *c != '-' && *c != ' ').collect();
+
+        Bsb::check(&cd)?;
+
+        Ok(Bsb(Code::<U6>::from(cd.as_str())))
+    }
+
+    /// `as_str` returns the `Bsb` as a string slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `institution_code` returns the two digits code of the financial
+    /// institution, e.g. "06".
+    pub fn institution_code(&self) -> &str {
+        &self.as_str()[0..2]
+    }
+
+    /// `state_code` returns the digit of the state of the branch, e.g. "2"
+    /// for New South Wales and ACT.
+    pub fn state_code(&self) -> &str {
+        &self.as_str()[2..3]
+    }
+
+    /// `branch_code` returns the three digits code of the branch.
+    pub fn branch_code(&self) -> &str {
+        &self.as_str()[3..6]
+    }
+
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_digits("BSB", code, 6, 6)
+    }
+
+    /// `is_valid` returns if a provided code is valid
+    /// by checking length and kind of chars used.
+    pub fn is_valid(code: &str) -> bool {
+        Bsb::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`Bsb`]. Here only length and digits used
+    /// are checked, see [`BsbDirectory`] to check that the branch exists.
+    pub fn validate(&self) -> Result<()> {
+        Bsb::check(self.as_str())
+    }
+
+    /// `print` returns the `Bsb` in print format, e.g. "062-000".
+    pub fn print(&self) -> String {
+        let code = self.as_str();
+
+        format!("{}-{}", &code[0..3], &code[3..6])
+    }
+}
+
+impl fmt::Display for Bsb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Bsb {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        Bsb::new(code)
+    }
+}
+
+impl From<&str> for Bsb {
+    fn from(code: &str) -> Self {
+        Bsb::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for Bsb {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(self.0.as_str(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for Bsb {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// [`Branch`] is a bank branch of the BSB directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Branch {
+    pub bsb: Bsb,
+    /// `mnemonic` is the three letters code of the institution, e.g. "CBA".
+    pub mnemonic: String,
+    pub name: String,
+    pub address: String,
+    pub suburb: String,
+    pub state: String,
+    pub post_code: String,
+    /// `payments` are the payment flows of the branch: 'P' for paper, 'E'
+    /// for electronic and 'H' for high value.
+    pub payments: String,
+}
+
+impl Branch {
+    /// `accepts_electronic` returns if the branch accepts electronic
+    /// payments, e.g. Direct Entry.
+    pub fn accepts_electronic(&self) -> bool {
+        self.payments.contains('E')
+    }
+}
+
+/// [`BsbDirectory`] maps a BSB with its branch, as listed by the APCA.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BsbDirectory(BTreeMap<Bsb, Branch>);
+
+impl BsbDirectory {
+    /// `parse` parses the CSV BSB directory of the APCA, without header,
+    /// whose columns are the BSB, the mnemonic, the name, the address, the
+    /// suburb, the state, the post code and the payment flows.
+    pub fn parse(content: &str) -> Result<BsbDirectory> {
+        let mut res = BTreeMap::new();
+
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields = csv::fields(line);
+
+            if fields.len() < 8 {
+                return Err(Error::invalid_format(
+                    "BSB directory",
+                    Some(i + 1),
+                    Reason::Format(format!("expected 8 columns, found {}", fields.len())),
+                ));
+            }
+
+            let bsb = Bsb::new(&fields[0])?;
+
+            res.insert(
+                bsb.clone(),
+                Branch {
+                    bsb,
+                    mnemonic: fields[1].clone(),
+                    name: fields[2].clone(),
+                    address: fields[3].clone(),
+                    suburb: fields[4].clone(),
+                    state: fields[5].clone(),
+                    post_code: fields[6].clone(),
+                    payments: fields[7].clone(),
+                },
+            );
+        }
+
+        Ok(BsbDirectory(res))
+    }
+
+    /// `load` loads the directory from a local CSV file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<BsbDirectory> {
+        let content = std::fs::read_to_string(path.as_ref()).map_err(Error::Io)?;
+
+        BsbDirectory::parse(&content)
+    }
+
+    /// `find` finds the branch of a BSB.
+    pub fn find(&self, bsb: &Bsb) -> Option<&Branch> {
+        self.0.get(bsb)
+    }
+
+    /// `validate` validates that a BSB is listed in the directory.
+    pub fn validate(&self, code: &str) -> Result<()> {
+        if self.find(&Bsb::new(code)?).is_none() {
+            return Err(Error::not_found("BSB", code));
+        }
+
+        Ok(())
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Bsb, BsbDirectory, Result};
+
+    #[test]
+    fn is_valid() -> Result<()> {
+        const WRONG_CODES: &[&str] = &["06200", "0620001", "06200A", "062_00"];
+        const VALID_CODES: &[&str] = &["062000", "012002", "733000"];
+
+        for code in WRONG_CODES.iter() {
+            assert!(!Bsb::is_valid(code), "{}", code);
+        }
+
+        for code in VALID_CODES.iter() {
+            assert!(Bsb::is_valid(code), "{}", code);
+        }
+
+        let bsb = Bsb::new("062-000")?;
+        assert_eq!(bsb.as_str(), "062000");
+        assert_eq!(bsb.print(), "062-000");
+        assert_eq!(bsb.institution_code(), "06");
+        assert_eq!(bsb.state_code(), "2");
+        assert_eq!(bsb.branch_code(), "000");
+
+        assert_eq!(
+            serde_json::to_string(&bsb).unwrap_or_default(),
+            "\"062000\""
+        );
+        assert_eq!(serde_json::from_str::<Bsb>("\"062000\"").ok(), Some(bsb));
+        assert!(serde_json::from_str::<Bsb>("\"06200A\"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn directory() -> Result<()> {
+        let directory = BsbDirectory::parse(
+            "\"012-002\",\"ANZ\",\"ANZ Smart Choice\",\"Level 16, 833 Collins Street\",\"Docklands\",\"VIC\",\"3008\",\"PEH\"\n\
+             \"062-000\",\"CBA\",\"Sydney\",\"Ground Floor Tower 1, 201 Sussex St\",\"Sydney\",\"NSW\",\"2000\",\"PEH\"\n\
+             \"082-991\",\"NAB\",\"Closed\",\"\",\"\",\"\",\"\",\" \"\n",
+        )?;
+
+        let branch = directory.find(&"012002".into());
+        assert_eq!(
+            branch.map(|b| b.address.as_str()),
+            Some("Level 16, 833 Collins Street")
+        );
+        assert_eq!(branch.map(|b| b.accepts_electronic()), Some(true));
+        assert_eq!(
+            directory
+                .find(&"082991".into())
+                .map(|b| b.accepts_electronic()),
+            Some(false)
+        );
+        assert!(directory.validate("062-000").is_ok());
+        assert!(directory.validate("062-001").is_err());
+        assert!(BsbDirectory::parse("\"062-000\",\"CBA\"\n").is_err());
+
+        Ok(())
+    }
+}
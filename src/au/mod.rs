@@ -0,0 +1,5 @@
+//! Australian payment standards.
+
+pub mod bsb;
+
+pub use bsb::*;
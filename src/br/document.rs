@@ -0,0 +1,285 @@
+//! Brazilian taxpayer numbers: CPF for individuals and CNPJ for companies.
+
+use crate::code::Code;
+use crate::error::{check_chars, check_digits, check_length, Error, Reason};
+use crate::result::Result;
+use arraystring::typenum::{U11, U14};
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `CPF_LENGTH` is the length of a `Cpf`.
+pub const CPF_LENGTH: usize = 11;
+
+/// `CNPJ_LENGTH` is the length of a `Cnpj`.
+pub const CNPJ_LENGTH: usize = 14;
+
+/// `strip` removes the punctuation of the print format, '.', '-' and '/'.
+fn strip(code: &str) -> String {
+    code.chars()
+        .filter(|c| !".-/ ".contains(*c))
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// `mod11` returns a modulus 11 check digit of values weighted from the
+/// left, as used by the CPF and the CNPJ.
+fn mod11(values: &[u32], weights: &[u32]) -> u32 {
+    let sum: u32 = values.iter().zip(weights).map(|(v, w)| v * w).sum();
+
+    match sum % 11 {
+        0 | 1 => 0,
+        r => 11 - r,
+    }
+}
+
+/// `check_sum` checks that a code ends with the `expected` check digits.
+fn check_sum(kind: &'static str, code: &str, expected: &[u32]) -> Result<()> {
+    let expected: String = expected.iter().map(|d| d.to_string()).collect();
+    let actual = &code[code.len() - expected.len()..];
+
+    if actual != expected {
+        return Err(Error::invalid(
+            kind,
+            code,
+            Reason::Checksum {
+                expected,
+                actual: actual.into(),
+            },
+        ));
+    }
+
+    Ok(())
+}
+
+/// `repeated` is the reason of codes made of a repeated char, which pass
+/// the check digits but are not valid.
+fn repeated() -> Reason {
+    Reason::Format("repeated char".into())
+}
+
+/// [`Cpf`] is a CPF (Cadastro de Pessoas Físicas): nine digits and two
+/// check digits, printed "529.982.247-25".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cpf(Code<U11>);
+
+impl Cpf {
+    /// `new` creates a new `Cpf` from a string code, in print format or
+    /// digits only.
+    pub fn new(code: &str) -> Result<Cpf> {
+        let cd = strip(code);
+
+        Cpf::check(&cd)?;
+
+        Ok(Cpf(Code::<U11>::from(cd.as_str())))
+    }
+
+    /// `as_str` returns the `Cpf` as a string slice, digits only.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `check` checks a provided code, digits only, returning why it is
+    /// invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_digits("CPF", code, CPF_LENGTH, CPF_LENGTH)?;
+
+        let mut digits: Vec<u32> = code.chars().filter_map(|c| c.to_digit(10)).collect();
+
+        if digits.iter().all(|d| *d == digits[0]) {
+            return Err(Error::invalid("CPF", code, repeated()));
+        }
+
+        digits.truncate(9);
+        digits.push(mod11(&digits, &[10, 9, 8, 7, 6, 5, 4, 3, 2]));
+        digits.push(mod11(&digits, &[11, 10, 9, 8, 7, 6, 5, 4, 3, 2]));
+        check_sum("CPF", code, &digits[9..])
+    }
+
+    /// `is_valid` returns if a provided code is valid by checking length,
+    /// kind of chars used and the check digits. Codes made of a repeated
+    /// digit pass the check digits but are not valid.
+    pub fn is_valid(code: &str) -> bool {
+        Cpf::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`Cpf`].
+    pub fn validate(&self) -> Result<()> {
+        Cpf::check(self.as_str())
+    }
+
+    /// `print` returns the `Cpf` in print format, e.g. "529.982.247-25".
+    pub fn print(&self) -> String {
+        let cd = self.as_str();
+
+        format!("{}.{}.{}-{}", &cd[0..3], &cd[3..6], &cd[6..9], &cd[9..])
+    }
+}
+
+impl fmt::Display for Cpf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Cpf {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        Cpf::new(code)
+    }
+}
+
+impl From<&str> for Cpf {
+    fn from(code: &str) -> Self {
+        Cpf::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for Cpf {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for Cpf {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// [`Cnpj`] is a CNPJ (Cadastro Nacional da Pessoa Jurídica): twelve chars
+/// and two check digits, printed "11.222.333/0001-81". Since July 2026,
+/// the first twelve chars may be upper-case letters as well as digits.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cnpj(Code<U14>);
+
+impl Cnpj {
+    /// `new` creates a new `Cnpj` from a string code, in print format or
+    /// chars only.
+    pub fn new(code: &str) -> Result<Cnpj> {
+        let cd = strip(code);
+
+        Cnpj::check(&cd)?;
+
+        Ok(Cnpj(Code::<U14>::from(cd.as_str())))
+    }
+
+    /// `as_str` returns the `Cnpj` as a string slice, chars only.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `check` checks a provided code, chars only, returning why it is
+    /// invalid. Chars are valued by their ASCII code less 48, so that
+    /// digits keep their value.
+    pub fn check(code: &str) -> Result<()> {
+        check_length("CNPJ", code, CNPJ_LENGTH, CNPJ_LENGTH)?;
+        check_chars("CNPJ", code, "an upper-case letter or a digit", |c| {
+            c.is_ascii_digit() || c.is_ascii_uppercase()
+        })?;
+        check_digits("CNPJ check digits", &code[12..], 2, 2)
+            .map_err(|e| e.within("CNPJ", code, 12))?;
+
+        let mut values: Vec<u32> = code.chars().map(|c| c as u32 - 48).collect();
+
+        if values.iter().all(|v| *v == values[0]) {
+            return Err(Error::invalid("CNPJ", code, repeated()));
+        }
+
+        let weights = [6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+        values.truncate(12);
+        values.push(mod11(&values, &weights[1..]));
+        values.push(mod11(&values, &weights));
+        check_sum("CNPJ", code, &values[12..])
+    }
+
+    /// `is_valid` returns if a provided code is valid by checking length,
+    /// kind of chars used and the check digits.
+    pub fn is_valid(code: &str) -> bool {
+        Cnpj::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`Cnpj`].
+    pub fn validate(&self) -> Result<()> {
+        Cnpj::check(self.as_str())
+    }
+
+    /// `print` returns the `Cnpj` in print format, e.g. "11.222.333/0001-81".
+    pub fn print(&self) -> String {
+        let cd = self.as_str();
+
+        format!(
+            "{}.{}.{}/{}-{}",
+            &cd[0..2],
+            &cd[2..5],
+            &cd[5..8],
+            &cd[8..12],
+            &cd[12..]
+        )
+    }
+}
+
+impl fmt::Display for Cnpj {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Cnpj {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        Cnpj::new(code)
+    }
+}
+
+impl From<&str> for Cnpj {
+    fn from(code: &str) -> Self {
+        Cnpj::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for Cnpj {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for Cnpj {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Cnpj, Cpf, Result};
+
+    #[test]
+    fn is_valid() -> Result<()> {
+        const WRONG_CPFS: &[&str] = &["52998224726", "11111111111", "5299822472", "5299822472A"];
+        const WRONG_CNPJS: &[&str] = &["11222333000182", "00000000000000", "12ABC34501DE53"];
+
+        for code in WRONG_CPFS.iter() {
+            assert!(!Cpf::is_valid(code), "{}", code);
+        }
+
+        for code in WRONG_CNPJS.iter() {
+            assert!(!Cnpj::is_valid(code), "{}", code);
+        }
+
+        let cpf = Cpf::new("529.982.247-25")?;
+        assert_eq!(cpf.as_str(), "52998224725");
+        assert_eq!(cpf.print(), "529.982.247-25");
+        assert!(Cpf::is_valid("12345678909"));
+
+        let cnpj = Cnpj::new("11.222.333/0001-81")?;
+        assert_eq!(cnpj.as_str(), "11222333000181");
+        assert_eq!(cnpj.print(), "11.222.333/0001-81");
+        assert_eq!(Cnpj::new("12.abc.345/01de-35")?.as_str(), "12ABC34501DE35");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+//! Brazilian payment standards.
+
+pub mod document;
+pub mod pix;
+
+pub use document::*;
+pub use pix::*;
@@ -0,0 +1,308 @@
+//! Brazilian instant payments (Pix): the keys registered in the DICT
+//! directory and the bank accounts they point to.
+
+use crate::br::document::{Cnpj, Cpf, CPF_LENGTH};
+use crate::error::{check_digits, check_length, Error, Reason};
+use crate::result::Result;
+use crate::swift::Uetr;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `EMAIL_MAX_LENGTH` is the max length of an e-mail key.
+pub const EMAIL_MAX_LENGTH: usize = 77;
+
+/// `PHONE_PREFIX` is the E.164 country code of phone keys.
+pub const PHONE_PREFIX: &str = "+55";
+
+/// [`PixKey`] is a Pix key: a CPF, a CNPJ, an e-mail, a phone number or a
+/// random key (EVP, "endereço virtual de pagamento").
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PixKey {
+    Cpf(Cpf),
+    Cnpj(Cnpj),
+    /// `Email` is a lower-case e-mail address.
+    Email(String),
+    /// `Phone` is a Brazilian phone number in E.164 format, e.g.
+    /// "+5511987654321".
+    Phone(String),
+    /// `Evp` is a random key, a lower-case version 4 UUID.
+    Evp(String),
+}
+
+impl PixKey {
+    /// `new` creates a new `PixKey`, detecting its type: a phone number
+    /// starts with '+', an e-mail contains '@', a random key is a UUID, and
+    /// CPF and CNPJ are told apart by their length.
+    pub fn new(key: &str) -> Result<PixKey> {
+        let key = key.trim();
+
+        if key.starts_with('+') {
+            PixKey::phone(key)
+        } else if key.contains('@') {
+            PixKey::email(key)
+        } else if key.len() == 36 && key.contains('-') {
+            PixKey::evp(key)
+        } else if key.chars().filter(|c| c.is_ascii_alphanumeric()).count() == CPF_LENGTH {
+            Ok(PixKey::Cpf(Cpf::new(key)?))
+        } else {
+            Ok(PixKey::Cnpj(Cnpj::new(key)?))
+        }
+    }
+
+    /// `email` creates a new e-mail `PixKey`.
+    pub fn email(email: &str) -> Result<PixKey> {
+        let email = email.to_lowercase();
+        let valid = match email.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+                    && !domain.contains('@')
+                    && email.chars().all(|c| c.is_ascii_graphic())
+            }
+            None => false,
+        };
+
+        if !valid {
+            return Err(Error::invalid(
+                "Pix key",
+                &email,
+                Reason::Format("expected an email address".into()),
+            ));
+        }
+
+        check_length("Pix key", &email, 0, EMAIL_MAX_LENGTH)?;
+
+        Ok(PixKey::Email(email))
+    }
+
+    /// `phone` creates a new phone `PixKey`: "+55", a two digits area code
+    /// and an eight or nine digits number.
+    pub fn phone(phone: &str) -> Result<PixKey> {
+        let number = phone.strip_prefix(PHONE_PREFIX).ok_or_else(|| {
+            Error::invalid(
+                "Pix key",
+                phone,
+                Reason::Format(format!("expected a {} phone number", PHONE_PREFIX)),
+            )
+        })?;
+
+        check_digits("Pix key", number, 10, 11)
+            .map_err(|e| e.within("Pix key", phone, PHONE_PREFIX.len()))?;
+
+        if number.starts_with('0') {
+            return Err(Error::invalid(
+                "Pix key",
+                phone,
+                Reason::Character {
+                    index: PHONE_PREFIX.len(),
+                    found: '0',
+                    expected: "a non-zero digit",
+                },
+            ));
+        }
+
+        Ok(PixKey::Phone(phone.into()))
+    }
+
+    /// `evp` creates a new random `PixKey`.
+    pub fn evp(evp: &str) -> Result<PixKey> {
+        let evp = evp.to_lowercase();
+
+        // NOTE: a random key is a UUID just like a UETR
+        Uetr::check(&evp).map_err(|e| e.within("Pix key", &evp, 0))?;
+
+        Ok(PixKey::Evp(evp))
+    }
+
+    /// `generate` creates a new random `PixKey`.
+    pub fn generate() -> Result<PixKey> {
+        Ok(PixKey::Evp(Uetr::generate()?.to_string()))
+    }
+
+    /// `key_type` returns the DICT key type: "CPF", "CNPJ", "EMAIL",
+    /// "PHONE" or "EVP".
+    pub fn key_type(&self) -> &str {
+        match self {
+            PixKey::Cpf(_) => "CPF",
+            PixKey::Cnpj(_) => "CNPJ",
+            PixKey::Email(_) => "EMAIL",
+            PixKey::Phone(_) => "PHONE",
+            PixKey::Evp(_) => "EVP",
+        }
+    }
+
+    /// `validate` validates the [`PixKey`].
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            PixKey::Cpf(cpf) => cpf.validate(),
+            PixKey::Cnpj(cnpj) => cnpj.validate(),
+            PixKey::Email(email) => PixKey::email(email).map(|_| ()),
+            PixKey::Phone(phone) => PixKey::phone(phone).map(|_| ()),
+            PixKey::Evp(evp) => PixKey::evp(evp).map(|_| ()),
+        }
+    }
+}
+
+impl fmt::Display for PixKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PixKey::Cpf(cpf) => write!(f, "{}", cpf),
+            PixKey::Cnpj(cnpj) => write!(f, "{}", cnpj),
+            PixKey::Email(key) | PixKey::Phone(key) | PixKey::Evp(key) => write!(f, "{}", key),
+        }
+    }
+}
+
+impl FromStr for PixKey {
+    type Err = Error;
+
+    fn from_str(key: &str) -> StdResult<Self, Self::Err> {
+        PixKey::new(key)
+    }
+}
+
+impl From<&str> for PixKey {
+    fn from(key: &str) -> Self {
+        PixKey::from_str(key).unwrap()
+    }
+}
+
+impl Serialize for PixKey {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for PixKey {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// [`AccountType`] is the type of a Brazilian bank account, as coded in
+/// the DICT directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountType {
+    /// Checking account, "CACC".
+    Checking,
+    /// Savings account, "SVGS".
+    Savings,
+    /// Salary account, "SLRY".
+    Salary,
+    /// Payment account, "TRAN".
+    Payment,
+}
+
+impl AccountType {
+    /// `as_str` returns the code of the account type.
+    pub fn as_str(&self) -> &str {
+        match self {
+            AccountType::Checking => "CACC",
+            AccountType::Savings => "SVGS",
+            AccountType::Salary => "SLRY",
+            AccountType::Payment => "TRAN",
+        }
+    }
+}
+
+impl FromStr for AccountType {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        match code {
+            "CACC" => Ok(AccountType::Checking),
+            "SVGS" => Ok(AccountType::Savings),
+            "SLRY" => Ok(AccountType::Salary),
+            "TRAN" => Ok(AccountType::Payment),
+            _ => Err(Error::invalid("account type", code, Reason::Unknown)),
+        }
+    }
+}
+
+/// [`BankAccount`] is a Brazilian bank account, as pointed to by a Pix key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BankAccount {
+    /// `ispb` is the eight digits code of the institution at the central
+    /// bank.
+    pub ispb: String,
+    /// `branch` is the four digits branch (agência), absent for payment
+    /// institutions without branches.
+    pub branch: Option<String>,
+    /// `number` is the account number, its check digit included.
+    pub number: String,
+    pub account_type: AccountType,
+}
+
+impl BankAccount {
+    /// `validate` validates the format of the [`BankAccount`]. The check
+    /// digit of the account number is specific to each bank, and is not
+    /// checked.
+    pub fn validate(&self) -> Result<()> {
+        check_digits("ISPB", &self.ispb, 8, 8)?;
+
+        if let Some(branch) = &self.branch {
+            check_digits("branch", branch, 4, 4)?;
+        }
+
+        check_digits("account number", &self.number, 1, 20)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{AccountType, BankAccount, PixKey, Result};
+
+    #[test]
+    fn new() -> Result<()> {
+        const WRONG_KEYS: &[&str] = &[
+            "52998224726",
+            "+5511987",
+            "+4915112345678",
+            "user@localhost",
+            "user@@example.com",
+            "eb6305c9-1f7f-39de-aed0-16487c27b42d",
+        ];
+        const VALID_KEYS: &[(&str, &str)] = &[
+            ("529.982.247-25", "CPF"),
+            ("11222333000181", "CNPJ"),
+            ("12ABC34501DE35", "CNPJ"),
+            ("Fulano@Example.com.br", "EMAIL"),
+            ("+5511987654321", "PHONE"),
+            ("EB6305C9-1F7F-49DE-AED0-16487C27B42D", "EVP"),
+        ];
+
+        for key in WRONG_KEYS.iter() {
+            assert!(PixKey::new(key).is_err(), "{}", key);
+        }
+
+        for (key, key_type) in VALID_KEYS.iter() {
+            assert_eq!(PixKey::new(key)?.key_type(), *key_type);
+        }
+
+        assert_eq!(PixKey::new("529.982.247-25")?.to_string(), "52998224725");
+        assert_eq!(
+            PixKey::new("Fulano@Example.com.br")?.to_string(),
+            "fulano@example.com.br"
+        );
+        assert!(PixKey::generate()?.validate().is_ok());
+
+        let key: PixKey = serde_json::from_str("\"+5511987654321\"").unwrap();
+        assert_eq!(key, PixKey::Phone("+5511987654321".into()));
+
+        let mut account = BankAccount {
+            ispb: "00000000".into(),
+            branch: Some("0001".into()),
+            number: "123456".into(),
+            account_type: AccountType::Checking,
+        };
+        assert!(account.validate().is_ok());
+        account.branch = Some("1".into());
+        assert!(account.validate().is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,564 @@
+//! Payments Canada Standard 005 (CPA-005) files of electronic funds
+//! transfers: a header record "A", credit records "C" and debit records "D"
+//! of up to six transactions each, and a trailer record "Z", all of 1464
+//! chars.
+
+use crate::ca::routing::RoutingNumber;
+use crate::date::Date;
+use crate::error::{check_chars, check_digits, check_length, Error, Reason};
+use crate::iso4217::CurrencyCode;
+use crate::money::Money;
+use crate::result::Result;
+use serde::{Deserialize, Serialize};
+
+/// `RECORD_LENGTH` is the length of a logical record.
+pub const RECORD_LENGTH: usize = 1464;
+
+/// `SEGMENT_LENGTH` is the length of a transaction segment of the "C" and
+/// "D" records.
+pub const SEGMENT_LENGTH: usize = 240;
+
+/// `SEGMENTS` is the number of transaction segments of a "C" or "D"
+/// record.
+pub const SEGMENTS: usize = 6;
+
+/// `CURRENCIES` are the currencies of CPA-005 files.
+pub const CURRENCIES: &[&str] = &["CAD", "USD"];
+
+/// `HEADER_LENGTH` is the length of the fields common to all records.
+const HEADER_LENGTH: usize = 24;
+
+/// `MAX_AMOUNT` is the max amount of a transaction, in cents.
+const MAX_AMOUNT: i64 = 9_999_999_999;
+
+/// `field` returns the field of a record, from and to 1-based positions, as
+/// in the CPA-005 documentation.
+fn field(record: &str, from: usize, to: usize) -> &str {
+    record.get(from - 1..to).unwrap_or_default()
+}
+
+/// `invalid` returns an error for an invalid CPA-005 file, at a 1-based
+/// record if known.
+fn invalid(record: Option<usize>, reason: Reason) -> Error {
+    Error::invalid_format("CPA-005 file", record, reason)
+}
+
+/// `check_text` checks a text field: ASCII and no longer than its field.
+fn check_text(name: &'static str, text: &str, length: usize) -> Result<()> {
+    check_length(name, text, 0, length)?;
+    check_chars(name, text, "a printable ASCII char", |c| {
+        c.is_ascii() && !c.is_ascii_control()
+    })
+}
+
+/// `check_currency` checks that a currency is one of the [`CURRENCIES`].
+fn check_currency(kind: &'static str, currency: &CurrencyCode) -> Result<()> {
+    if !CURRENCIES.contains(&currency.as_str()) {
+        return Err(Error::invalid(
+            kind,
+            currency.as_str(),
+            Reason::Format(format!("expected one of {}", CURRENCIES.join(", "))),
+        ));
+    }
+
+    Ok(())
+}
+
+/// `from_julian` parses a "0YYDDD" date.
+fn from_julian(date: &str) -> Result<Date> {
+    match date.strip_prefix('0') {
+        Some(yyddd) => Date::from_yyddd(yyddd).map_err(|e| e.within("date", date, 1)),
+        None => Err(Error::invalid(
+            "date",
+            date,
+            Reason::Character {
+                index: 0,
+                found: date.chars().next().unwrap_or(' '),
+                expected: "'0'",
+            },
+        )),
+    }
+}
+
+/// [`Transaction`] is a credit or debit transaction, a segment of a "C" or
+/// "D" record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transaction {
+    /// `is_debit` is true for a debit (pre-authorized debit) of the payor
+    /// account, false for a credit (e.g. payroll deposit) of the payee
+    /// account.
+    pub is_debit: bool,
+    /// `transaction_type` is the three digits CPA transaction code, e.g.
+    /// "200" for a payroll deposit.
+    pub transaction_type: String,
+    pub amount: Money,
+    /// `due_date` is the date the funds are available.
+    pub due_date: Date,
+    pub routing_number: RoutingNumber,
+    /// `account` is the account number of the payee or payor, up to 12
+    /// digits.
+    pub account: String,
+    /// `name` is the name of the payee or payor, up to 30 chars.
+    pub name: String,
+    /// `cross_reference` is the reference of the originator, up to 19
+    /// chars.
+    pub cross_reference: String,
+    /// `sundry` is free information of the originator, up to 15 chars.
+    pub sundry: String,
+}
+
+impl Transaction {
+    /// `validate` validates the fields of the transaction.
+    pub fn validate(&self) -> Result<()> {
+        check_digits("transaction type", &self.transaction_type, 3, 3)?;
+
+        if self.amount.amount <= 0 || self.amount.amount > MAX_AMOUNT {
+            return Err(Error::invalid(
+                "amount",
+                &self.amount.to_string(),
+                Reason::Format("expected 0.01 to 99999999.99".into()),
+            ));
+        }
+
+        check_digits("account", &self.account, 1, 12)?;
+        check_text("name", &self.name, 30)?;
+        check_text("cross reference", &self.cross_reference, 19)?;
+        check_text("sundry", &self.sundry, 15)?;
+
+        if self.name.trim().is_empty() {
+            return Err(Error::MissingElement("name".into()));
+        }
+
+        Ok(())
+    }
+}
+
+/// [`Totals`] are the totals of the transactions of a file, as written in
+/// the "Z" record.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Totals {
+    /// `debit_value` is the total of the debits, in cents.
+    pub debit_value: i64,
+    pub debit_count: u32,
+    /// `credit_value` is the total of the credits, in cents.
+    pub credit_value: i64,
+    pub credit_count: u32,
+}
+
+/// [`Cpa005`] is a CPA-005 file of a single currency.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cpa005 {
+    /// `originator_id` is the ten chars client number of the originator,
+    /// given by its financial institution.
+    pub originator_id: String,
+    /// `file_creation_number` is the four digits number of the file,
+    /// incremented by file sent.
+    pub file_creation_number: String,
+    pub creation_date: Date,
+    /// `data_centre` is the five digits destination data centre of the
+    /// financial institution.
+    pub data_centre: String,
+    pub currency: CurrencyCode,
+    /// `short_name` is the short name of the originator, up to 15 chars.
+    pub short_name: String,
+    /// `long_name` is the long name of the originator, up to 30 chars.
+    pub long_name: String,
+    /// `return_routing_number` is the routing number of the account of the
+    /// originator, where returned transactions are sent.
+    pub return_routing_number: RoutingNumber,
+    /// `return_account` is the account number of the originator.
+    pub return_account: String,
+    pub transactions: Vec<Transaction>,
+}
+
+impl Cpa005 {
+    /// `totals` returns the totals of the transactions.
+    pub fn totals(&self) -> Totals {
+        self.transactions
+            .iter()
+            .fold(Totals::default(), |mut totals, transaction| {
+                if transaction.is_debit {
+                    totals.debit_value += transaction.amount.amount;
+                    totals.debit_count += 1;
+                } else {
+                    totals.credit_value += transaction.amount.amount;
+                    totals.credit_count += 1;
+                }
+
+                totals
+            })
+    }
+
+    /// `validate` validates the header fields and the transactions.
+    pub fn validate(&self) -> Result<()> {
+        check_length("originator id", &self.originator_id, 10, 10)?;
+        check_chars(
+            "originator id",
+            &self.originator_id,
+            "a letter or a digit",
+            |c| c.is_ascii_alphanumeric(),
+        )?;
+
+        check_digits("file creation number", &self.file_creation_number, 4, 4)?;
+        check_digits("data centre", &self.data_centre, 5, 5)?;
+
+        check_currency("currency", &self.currency)?;
+
+        check_text("short name", &self.short_name, 15)?;
+        check_text("long name", &self.long_name, 30)?;
+        check_digits("return account", &self.return_account, 1, 12)?;
+
+        if self.transactions.is_empty() {
+            return Err(Error::MissingElement("transaction".into()));
+        }
+
+        for transaction in self.transactions.iter() {
+            if transaction.amount.currency != self.currency {
+                return Err(Error::invalid(
+                    "transaction currency",
+                    transaction.amount.currency.as_str(),
+                    Reason::Format(format!("expected {}", self.currency.as_str())),
+                ));
+            }
+
+            transaction.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// `header` returns the fields common to all records.
+    fn header(&self, record_type: char, count: usize) -> String {
+        format!(
+            "{}{:09}{}{}",
+            record_type, count, self.originator_id, self.file_creation_number
+        )
+    }
+
+    /// `segment` returns the segment of 240 chars of a transaction.
+    fn segment(&self, transaction: &Transaction) -> String {
+        format!(
+            "{}{:010}0{}{}{:<12}{:022}000{:<15}{:<30}{:<30}{:10}{:<19}{}{:<12}{:<15}{:22}{:2}{:011}",
+            transaction.transaction_type,
+            transaction.amount.amount,
+            transaction.due_date.to_yyddd(),
+            transaction.routing_number.electronic(),
+            transaction.account,
+            0,
+            self.short_name,
+            transaction.name,
+            self.long_name,
+            "",
+            transaction.cross_reference,
+            self.return_routing_number.electronic(),
+            self.return_account,
+            transaction.sundry,
+            "",
+            "",
+            0
+        )
+    }
+
+    /// `to_file` builds the file, one record per line. Consecutive
+    /// transactions of the same direction share a record, unused segments
+    /// are left blank.
+    pub fn to_file(&self) -> Result<String> {
+        self.validate()?;
+
+        let mut res = vec![format!(
+            "{}0{}{}{:20}{}",
+            self.header('A', 1),
+            self.creation_date.to_yyddd(),
+            self.data_centre,
+            "",
+            self.currency.as_str()
+        )];
+        let mut start = 0;
+
+        while start < self.transactions.len() {
+            let is_debit = self.transactions[start].is_debit;
+            let end = self.transactions[start..]
+                .iter()
+                .take(SEGMENTS)
+                .position(|t| t.is_debit != is_debit)
+                .map(|i| start + i)
+                .unwrap_or_else(|| (start + SEGMENTS).min(self.transactions.len()));
+            let mut record = self.header(if is_debit { 'D' } else { 'C' }, res.len() + 1);
+
+            for transaction in self.transactions[start..end].iter() {
+                record.push_str(&self.segment(transaction));
+            }
+
+            res.push(record);
+            start = end;
+        }
+
+        let totals = self.totals();
+        res.push(format!(
+            "{}{:014}{:08}{:014}{:08}{:014}{:08}{:014}{:08}",
+            self.header('Z', res.len() + 1),
+            totals.debit_value,
+            totals.debit_count,
+            totals.credit_value,
+            totals.credit_count,
+            0,
+            0,
+            0,
+            0
+        ));
+
+        Ok(res
+            .iter()
+            .map(|record| format!("{:<1464}", record))
+            .collect::<Vec<String>>()
+            .join("\n"))
+    }
+
+    /// `parse` parses a file, with its records on separate lines (LF or CR
+    /// LF) or not, checking the record counts and the totals of its "Z"
+    /// record.
+    pub fn parse(file: &str) -> Result<Cpa005> {
+        if let Some((offset, found)) = file.char_indices().find(|(_, c)| !c.is_ascii()) {
+            let start = file[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+            return Err(invalid(
+                Some(file[..offset].matches('\n').count() + 1),
+                Reason::Character {
+                    index: offset - start,
+                    found,
+                    expected: "an ASCII char",
+                },
+            ));
+        }
+
+        let records: Vec<&str> = if file.contains('\n') {
+            file.lines()
+                .map(|line| line.trim_end_matches('\r'))
+                .filter(|line| !line.is_empty())
+                .collect()
+        } else {
+            (0..file.len())
+                .step_by(RECORD_LENGTH)
+                .map(|i| &file[i..(i + RECORD_LENGTH).min(file.len())])
+                .collect()
+        };
+
+        if records.len() < 3 {
+            return Err(invalid(
+                None,
+                Reason::Format("expected an A record, C or D records and a Z record".into()),
+            ));
+        }
+
+        let header = records[0];
+        let trailer = records[records.len() - 1];
+
+        for (i, record) in records.iter().enumerate() {
+            let (record_types, expected): (&[&str], _) = match i {
+                0 => (&["A"], "'A'"),
+                _ if i == records.len() - 1 => (&["Z"], "'Z'"),
+                _ => (&["C", "D"], "'C' or 'D'"),
+            };
+            let line = Some(i + 1);
+
+            if record.len() != RECORD_LENGTH {
+                return Err(invalid(
+                    line,
+                    Reason::Length {
+                        min: RECORD_LENGTH,
+                        max: RECORD_LENGTH,
+                        actual: record.len(),
+                    },
+                ));
+            }
+
+            if !record_types.contains(&field(record, 1, 1)) {
+                return Err(invalid(
+                    line,
+                    Reason::Character {
+                        index: 0,
+                        found: record.chars().next().unwrap_or(' '),
+                        expected,
+                    },
+                ));
+            }
+
+            if field(record, 2, 10).parse::<usize>().ok() != Some(i + 1) {
+                return Err(invalid(
+                    line,
+                    Reason::Format(format!("expected record count {:09}", i + 1)),
+                ));
+            }
+
+            if field(record, 11, 24) != field(header, 11, 24) {
+                return Err(invalid(
+                    line,
+                    Reason::Format(
+                        "originator id and file creation number not matching the A record".into(),
+                    ),
+                ));
+            }
+        }
+
+        let currency = CurrencyCode::new(field(header, 56, 58))?;
+        let mut res: Option<Cpa005> = None;
+        let mut transactions = Vec::new();
+
+        for record in records[1..records.len() - 1].iter() {
+            let segments = (HEADER_LENGTH..RECORD_LENGTH)
+                .step_by(SEGMENT_LENGTH)
+                .map(|i| &record[i..i + SEGMENT_LENGTH])
+                .filter(|segment| !segment.trim().is_empty());
+
+            for segment in segments {
+                check_digits("amount", field(segment, 4, 13), 10, 10)?;
+
+                let amount: i64 = field(segment, 4, 13).parse().unwrap_or_default();
+
+                if field(segment, 1, 3) == "000" && amount == 0 {
+                    continue;
+                }
+
+                if res.is_none() {
+                    res = Some(Cpa005 {
+                        originator_id: field(header, 11, 20).into(),
+                        file_creation_number: field(header, 21, 24).into(),
+                        creation_date: from_julian(field(header, 25, 30))?,
+                        data_centre: field(header, 31, 35).into(),
+                        currency: currency.clone(),
+                        short_name: field(segment, 66, 80).trim_end().into(),
+                        long_name: field(segment, 111, 140).trim_end().into(),
+                        return_routing_number: field(segment, 170, 178).parse()?,
+                        return_account: field(segment, 179, 190).trim_end().into(),
+                        transactions: Vec::new(),
+                    });
+                }
+
+                transactions.push(Transaction {
+                    is_debit: field(record, 1, 1) == "D",
+                    transaction_type: field(segment, 1, 3).into(),
+                    amount: Money::new(amount, currency.clone()),
+                    due_date: from_julian(field(segment, 14, 19))?,
+                    routing_number: field(segment, 20, 28).parse()?,
+                    account: field(segment, 29, 40).trim_end().into(),
+                    name: field(segment, 81, 110).trim_end().into(),
+                    cross_reference: field(segment, 151, 169).trim_end().into(),
+                    sundry: field(segment, 191, 205).trim_end().into(),
+                });
+            }
+        }
+
+        let mut res = res.ok_or_else(|| Error::MissingElement("transaction".into()))?;
+        res.transactions = transactions;
+        res.validate()?;
+
+        let line = Some(records.len());
+        let number = |from: usize, to: usize| -> Result<i64> {
+            check_digits(
+                "Z record total",
+                field(trailer, from, to),
+                to - from + 1,
+                to - from + 1,
+            )?;
+
+            Ok(field(trailer, from, to).parse().unwrap_or_default())
+        };
+        let totals = res.totals();
+
+        if number(25, 38)? != totals.debit_value
+            || number(39, 46)? != i64::from(totals.debit_count)
+            || number(47, 60)? != totals.credit_value
+            || number(61, 68)? != i64::from(totals.credit_count)
+        {
+            return Err(invalid(
+                line,
+                Reason::Format("totals not matching the transactions".into()),
+            ));
+        }
+
+        Ok(res)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Cpa005, Transaction, RECORD_LENGTH};
+    #[allow(unused_imports)] // TODO
+    use crate::date::Date;
+    #[allow(unused_imports)] // TODO
+    use crate::money::Money;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[allow(dead_code)]
+    fn transaction(is_debit: bool, amount: i64, name: &str) -> Transaction {
+        Transaction {
+            is_debit,
+            transaction_type: if is_debit { "430" } else { "200" }.into(),
+            amount: Money::new(amount, "CAD".into()),
+            due_date: Date::new(2024, 2, 1).unwrap(),
+            routing_number: "12345-001".into(),
+            account: "1234567".into(),
+            name: name.into(),
+            cross_reference: "PAYROLL 2024-02".into(),
+            sundry: "".into(),
+        }
+    }
+
+    #[test]
+    fn build_and_parse() -> Result<()> {
+        let mut file = Cpa005 {
+            originator_id: "ACME000001".into(),
+            file_creation_number: "0042".into(),
+            creation_date: Date::new(2024, 1, 29)?,
+            data_centre: "00120".into(),
+            currency: "CAD".into(),
+            short_name: "ACME".into(),
+            long_name: "ACME INDUSTRIES LTD".into(),
+            return_routing_number: "000254321".into(),
+            return_account: "987654321".into(),
+            transactions: Vec::new(),
+        };
+        assert!(file.to_file().is_err());
+
+        for i in 1..=7 {
+            file.transactions
+                .push(transaction(false, i * 100_000, &format!("EMPLOYEE {}", i)));
+        }
+        file.transactions.push(transaction(true, 5_000, "CUSTOMER"));
+
+        let content = file.to_file()?;
+        let records: Vec<&str> = content.lines().collect();
+        assert_eq!(records.len(), 5);
+        assert!(records.iter().all(|record| record.len() == RECORD_LENGTH));
+        assert_eq!(
+            &records[0][..58],
+            "A000000001ACME000001004202402900120                    CAD"
+        );
+        assert_eq!(&records[1][..10], "C000000002");
+        assert_eq!(&records[2][..10], "C000000003");
+        assert_eq!(&records[3][..10], "D000000004");
+        assert!(records[2][264..].trim().is_empty());
+        assert_eq!(
+            &records[1][24..134],
+            "20000001000000240320001123451234567     0000000000000000000000000ACME           EMPLOYEE 1                    "
+        );
+        assert_eq!(
+            &records[4][..68],
+            "Z000000005ACME000001004200000000005000000000010000000280000000000007"
+        );
+
+        assert_eq!(Cpa005::parse(&content)?, file);
+        assert_eq!(Cpa005::parse(&content.replace('\n', ""))?, file);
+        assert!(Cpa005::parse(&content.replace("Z000000005", "Z000000006")).is_err());
+        assert!(Cpa005::parse(
+            &content.replace("0000000280000000000007", "0000000290000000000007")
+        )
+        .is_err());
+
+        file.transactions[0].account = "1234-567".into();
+        assert!(file.to_file().is_err());
+
+        Ok(())
+    }
+}
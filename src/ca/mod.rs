@@ -0,0 +1,7 @@
+//! Canadian payment standards.
+
+pub mod cpa005;
+pub mod routing;
+
+pub use cpa005::*;
+pub use routing::*;
@@ -0,0 +1,160 @@
+//! Canadian routing numbers, as assigned by Payments Canada.
+
+use crate::code::Code;
+use crate::error::{check_digits, Error, Reason};
+use crate::result::Result;
+use arraystring::typenum::U8;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// [`RoutingNumber`] is a Canadian routing number: a three digits
+/// institution number and a five digits transit (branch) number. It is
+/// written "0XXXYYYYY" in electronic payments and "YYYYY-XXX" on paper
+/// (MICR line of cheques).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RoutingNumber(Code<U8>);
+
+impl RoutingNumber {
+    /// `new` creates a new `RoutingNumber` from its electronic form,
+    /// "0XXXYYYYY", or its paper form, "YYYYY-XXX".
+    pub fn new(code: &str) -> Result<RoutingNumber> {
+        let code = code.trim();
+
+        match code.split_once('-') {
+            Some((transit, institution)) => RoutingNumber::from_parts(institution, transit),
+            None if code.len() == 9 && code.starts_with('0') => {
+                check_digits("routing number", code, 9, 9)?;
+                RoutingNumber::from_parts(&code[1..4], &code[4..9])
+            }
+            None => Err(Error::invalid(
+                "routing number",
+                code,
+                Reason::Format("expected 0XXXYYYYY or YYYYY-XXX".into()),
+            )),
+        }
+    }
+
+    /// `from_parts` creates a new `RoutingNumber` from its institution and
+    /// transit numbers.
+    pub fn from_parts(institution: &str, transit: &str) -> Result<RoutingNumber> {
+        let cd = format!("{}{}", institution, transit);
+
+        check_digits("institution number", institution, 3, 3)?;
+        check_digits("transit number", transit, 5, 5)?;
+
+        Ok(RoutingNumber(Code::<U8>::from(cd.as_str())))
+    }
+
+    /// `as_str` returns the `RoutingNumber` as a string slice, the
+    /// institution number followed by the transit number.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `institution_number` returns the three digits institution number,
+    /// e.g. "001" for the Bank of Montreal.
+    pub fn institution_number(&self) -> &str {
+        &self.as_str()[0..3]
+    }
+
+    /// `transit_number` returns the five digits transit number.
+    pub fn transit_number(&self) -> &str {
+        &self.as_str()[3..8]
+    }
+
+    /// `electronic` returns the electronic form, e.g. "000112345".
+    pub fn electronic(&self) -> String {
+        format!("0{}{}", self.institution_number(), self.transit_number())
+    }
+
+    /// `paper` returns the paper form, e.g. "12345-001".
+    pub fn paper(&self) -> String {
+        format!("{}-{}", self.transit_number(), self.institution_number())
+    }
+
+    /// `check` checks a provided code, the institution number followed by
+    /// the transit number, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_digits("routing number", code, 8, 8)
+    }
+
+    /// `is_valid` returns if a provided code, the institution number
+    /// followed by the transit number, is valid by checking length and
+    /// kind of chars used.
+    pub fn is_valid(code: &str) -> bool {
+        RoutingNumber::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`RoutingNumber`].
+    pub fn validate(&self) -> Result<()> {
+        RoutingNumber::check(self.as_str())
+    }
+}
+
+impl fmt::Display for RoutingNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.electronic())
+    }
+}
+
+impl FromStr for RoutingNumber {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        RoutingNumber::new(code)
+    }
+}
+
+impl From<&str> for RoutingNumber {
+    fn from(code: &str) -> Self {
+        RoutingNumber::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for RoutingNumber {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for RoutingNumber {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Result, RoutingNumber};
+
+    #[test]
+    fn new() -> Result<()> {
+        const WRONG_CODES: &[&str] = &[
+            "100112345",
+            "00011234",
+            "1234-001",
+            "12345-01",
+            "12345-0A1",
+            "012é3456",
+        ];
+
+        for code in WRONG_CODES.iter() {
+            assert!(RoutingNumber::new(code).is_err(), "{}", code);
+        }
+
+        let routing = RoutingNumber::new("12345-001")?;
+        assert_eq!(routing, RoutingNumber::new("000112345")?);
+        assert_eq!(routing.institution_number(), "001");
+        assert_eq!(routing.transit_number(), "12345");
+        assert_eq!(routing.electronic(), "000112345");
+        assert_eq!(routing.paper(), "12345-001");
+        assert_eq!(
+            serde_json::to_string(&routing).unwrap_or_default(),
+            "\"000112345\""
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+//! Account entries, as reported by camt.052, camt.053 and camt.054 messages.
+
+use crate::date::{Date, DateTime};
+use crate::error::{Error, Reason};
+use crate::iso13616::IBAN;
+use crate::iso3166::CountryCode;
+use crate::iso4217::CurrencyCode;
+use crate::money::Money;
+use crate::result::Result;
+use crate::xml::Element;
+use serde::{Deserialize, Serialize};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// [`CreditDebit`] tells if an amount is a credit or a debit of the account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CreditDebit {
+    Credit,
+    Debit,
+}
+
+impl CreditDebit {
+    /// `as_str` returns the ISO 20022 code of the indicator.
+    pub fn as_str(&self) -> &str {
+        match self {
+            CreditDebit::Credit => "CRDT",
+            CreditDebit::Debit => "DBIT",
+        }
+    }
+}
+
+impl FromStr for CreditDebit {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        match code {
+            "CRDT" => Ok(CreditDebit::Credit),
+            "DBIT" => Ok(CreditDebit::Debit),
+            _ => Err(Error::invalid(
+                "credit debit indicator",
+                code,
+                Reason::Unknown,
+            )),
+        }
+    }
+}
+
+/// [`DateOrDateTime`] is a date that can be reported with or without time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DateOrDateTime {
+    Date(Date),
+    DateTime(DateTime),
+}
+
+impl DateOrDateTime {
+    /// `date` returns the date, without time.
+    pub fn date(&self) -> Date {
+        match self {
+            DateOrDateTime::Date(date) => *date,
+            DateOrDateTime::DateTime(datetime) => datetime.date,
+        }
+    }
+
+    pub(crate) fn from_element(element: &Element) -> Result<DateOrDateTime> {
+        if let Some(date) = element.find_parsed("Dt")? {
+            return Ok(DateOrDateTime::Date(date));
+        }
+
+        element.require_parsed("DtTm").map(DateOrDateTime::DateTime)
+    }
+}
+
+/// [`Account`] is a cash account, identified by IBAN or by another
+/// identification.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub struct Account {
+    pub iban: Option<IBAN>,
+    pub other: Option<String>,
+    pub currency: Option<CurrencyCode>,
+    pub name: Option<String>,
+}
+
+impl Account {
+    pub(crate) fn from_element(element: &Element) -> Result<Account> {
+        Ok(Account {
+            iban: element.find_parsed("Id/IBAN")?,
+            other: element.find_text("Id/Othr/Id").map(String::from),
+            currency: element.find_parsed("Ccy")?,
+            name: element.find_text("Nm").map(String::from),
+        })
+    }
+
+    pub(crate) fn to_element(&self, name: &str) -> Element {
+        let id = match (&self.iban, &self.other) {
+            (Some(iban), _) => Element::new("Id").with_leaf("IBAN", &iban.to_string()),
+            (None, other) => Element::new("Id").with_child(
+                Element::new("Othr").with_leaf("Id", other.as_deref().unwrap_or_default()),
+            ),
+        };
+
+        Element::new(name)
+            .with_child(id)
+            .with_optional_leaf("Ccy", self.currency.as_ref().map(|c| c.as_str()))
+            .with_optional_leaf("Nm", self.name.as_ref())
+    }
+}
+
+/// [`Party`] is a debtor or a creditor of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub struct Party {
+    pub name: Option<String>,
+    pub country: Option<CountryCode>,
+    pub address_lines: Vec<String>,
+}
+
+impl Party {
+    pub(crate) fn from_element(element: &Element) -> Result<Party> {
+        // NOTE: from camt version 08 the party is wrapped in a `Pty` element
+        let element = element.child("Pty").unwrap_or(element);
+
+        Ok(Party {
+            name: element.find_text("Nm").map(String::from),
+            country: element.find_parsed("PstlAdr/Ctry")?,
+            address_lines: element
+                .find_all("PstlAdr/AdrLine")
+                .iter()
+                .map(|line| line.text().into())
+                .collect(),
+        })
+    }
+}
+
+/// [`EntryStatus`] is the booking status of an [`Entry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EntryStatus {
+    Booked,
+    Pending,
+    Information,
+    Future,
+}
+
+impl FromStr for EntryStatus {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        match code {
+            "BOOK" => Ok(EntryStatus::Booked),
+            "PDNG" => Ok(EntryStatus::Pending),
+            "INFO" => Ok(EntryStatus::Information),
+            "FUTR" => Ok(EntryStatus::Future),
+            _ => Err(Error::invalid("entry status", code, Reason::Unknown)),
+        }
+    }
+}
+
+/// [`BankTransactionCode`] classifies an entry, either with the ISO
+/// domain/family/sub-family structure or with a proprietary code
+/// (e.g. a SWIFT transaction type or a German GVC).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub struct BankTransactionCode {
+    pub domain: Option<String>,
+    pub family: Option<String>,
+    pub sub_family: Option<String>,
+    pub proprietary: Option<String>,
+    pub issuer: Option<String>,
+}
+
+impl BankTransactionCode {
+    pub(crate) fn from_element(element: &Element) -> BankTransactionCode {
+        BankTransactionCode {
+            domain: element.find_text("Domn/Cd").map(String::from),
+            family: element.find_text("Domn/Fmly/Cd").map(String::from),
+            sub_family: element.find_text("Domn/Fmly/SubFmlyCd").map(String::from),
+            proprietary: element.find_text("Prtry/Cd").map(String::from),
+            issuer: element.find_text("Prtry/Issr").map(String::from),
+        }
+    }
+}
+
+/// [`TransactionDetails`] are the details of one of the transactions
+/// booked in an [`Entry`]. Batch bookings have more than one.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct TransactionDetails {
+    pub message_id: Option<String>,
+    pub account_servicer_reference: Option<String>,
+    pub payment_information_id: Option<String>,
+    pub instruction_id: Option<String>,
+    pub end_to_end_id: Option<String>,
+    pub mandate_id: Option<String>,
+    pub amount: Option<Money>,
+    pub credit_debit: Option<CreditDebit>,
+    pub bank_transaction_code: Option<BankTransactionCode>,
+    pub debtor: Option<Party>,
+    pub debtor_account: Option<Account>,
+    pub creditor: Option<Party>,
+    pub creditor_account: Option<Account>,
+    pub ultimate_debtor: Option<Party>,
+    pub ultimate_creditor: Option<Party>,
+    pub remittance_information: Vec<String>,
+    pub creditor_reference: Option<String>,
+    pub return_reason: Option<String>,
+    pub additional_information: Option<String>,
+}
+
+impl TransactionDetails {
+    pub(crate) fn from_element(element: &Element) -> Result<TransactionDetails> {
+        let party = |path: &str| element.find(path).map(Party::from_element).transpose();
+        let account = |path: &str| element.find(path).map(Account::from_element).transpose();
+        let text = |path: &str| element.find_text(path).map(String::from);
+
+        let amount = match element.find_money("Amt")? {
+            Some(amount) => Some(amount),
+            None => element.find_money("AmtDtls/TxAmt/Amt")?,
+        };
+
+        Ok(TransactionDetails {
+            message_id: text("Refs/MsgId"),
+            account_servicer_reference: text("Refs/AcctSvcrRef"),
+            payment_information_id: text("Refs/PmtInfId"),
+            instruction_id: text("Refs/InstrId"),
+            end_to_end_id: text("Refs/EndToEndId"),
+            mandate_id: text("Refs/MndtId"),
+            amount,
+            credit_debit: element.find_parsed("CdtDbtInd")?,
+            bank_transaction_code: element
+                .find("BkTxCd")
+                .map(BankTransactionCode::from_element),
+            debtor: party("RltdPties/Dbtr")?,
+            debtor_account: account("RltdPties/DbtrAcct")?,
+            creditor: party("RltdPties/Cdtr")?,
+            creditor_account: account("RltdPties/CdtrAcct")?,
+            ultimate_debtor: party("RltdPties/UltmtDbtr")?,
+            ultimate_creditor: party("RltdPties/UltmtCdtr")?,
+            remittance_information: element
+                .find_all("RmtInf/Ustrd")
+                .iter()
+                .map(|line| line.text().into())
+                .collect(),
+            creditor_reference: text("RmtInf/Strd/CdtrRefInf/Ref"),
+            return_reason: text("RtrInf/Rsn/Cd"),
+            additional_information: text("AddtlTxInf"),
+        })
+    }
+}
+
+/// [`Entry`] is a booking (or a pending booking) on the account. This is the
+/// common model of camt.052 reports, camt.053 statements and camt.054
+/// notifications.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub reference: Option<String>,
+    pub amount: Money,
+    pub credit_debit: CreditDebit,
+    pub reversal: bool,
+    pub status: EntryStatus,
+    pub booking_date: Option<DateOrDateTime>,
+    pub value_date: Option<DateOrDateTime>,
+    pub account_servicer_reference: Option<String>,
+    pub bank_transaction_code: Option<BankTransactionCode>,
+    pub additional_information: Option<String>,
+    pub details: Vec<TransactionDetails>,
+}
+
+impl Entry {
+    /// `signed_amount` returns the amount, negative if the entry is a debit.
+    /// The indicator of a reversal already is its booking direction, e.g. a
+    /// reversed debit is a credit, so reversals are not negated again.
+    pub fn signed_amount(&self) -> Money {
+        let debit = self.credit_debit == CreditDebit::Debit;
+
+        Money::new(
+            if debit {
+                -self.amount.amount
+            } else {
+                self.amount.amount
+            },
+            self.amount.currency.clone(),
+        )
+    }
+
+    pub(crate) fn from_element(element: &Element) -> Result<Entry> {
+        // NOTE: from camt version 08 the status is wrapped in a `Cd` element
+        let status = match element.find_text("Sts/Cd") {
+            Some(status) => status.parse()?,
+            None => element.require_parsed("Sts")?,
+        };
+
+        Ok(Entry {
+            reference: element.find_text("NtryRef").map(String::from),
+            amount: element.require_money("Amt")?,
+            credit_debit: element.require_parsed("CdtDbtInd")?,
+            // NOTE: RvslInd is an xs:boolean, either "true" or "1"
+            reversal: matches!(element.find_text("RvslInd"), Some("true" | "1")),
+            status,
+            booking_date: element
+                .find("BookgDt")
+                .map(DateOrDateTime::from_element)
+                .transpose()?,
+            value_date: element
+                .find("ValDt")
+                .map(DateOrDateTime::from_element)
+                .transpose()?,
+            account_servicer_reference: element.find_text("AcctSvcrRef").map(String::from),
+            bank_transaction_code: element
+                .find("BkTxCd")
+                .map(BankTransactionCode::from_element),
+            additional_information: element.find_text("AddtlNtryInf").map(String::from),
+            details: element
+                .find_all("NtryDtls/TxDtls")
+                .into_iter()
+                .map(TransactionDetails::from_element)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
@@ -0,0 +1,7 @@
+//! Bank to customer cash management messages, as defined by ISO 20022.
+
+pub mod entry;
+pub mod report;
+
+pub use entry::*;
+pub use report::*;
@@ -0,0 +1,5 @@
+//! Swiss payment standards.
+
+pub mod qrbill;
+
+pub use qrbill::*;
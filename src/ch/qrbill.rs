@@ -0,0 +1,748 @@
+//! Swiss QR-bill, as defined by the Swiss Implementation Guidelines for the
+//! QR-bill: the "SPC" text payload of the Swiss QR Code, with QR-IBANs, QR
+//! references (QRR) and ISO 11649 creditor references (SCOR). Only
+//! structured addresses (type "S") are supported, the combined ones (type
+//! "K") being no longer accepted since November 2025.
+
+use crate::error::{check_digits, Error, Reason};
+use crate::iso11649::CreditorReference;
+use crate::iso13616::IBAN;
+use crate::iso20022::{AddressProfile, PostalAddress};
+use crate::iso4217::CurrencyCode;
+use crate::money::Money;
+use crate::result::Result;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `QR_TYPE` is the type of the Swiss QR Code payload.
+pub const QR_TYPE: &str = "SPC";
+
+/// `VERSION` is the version of the payload.
+pub const VERSION: &str = "0200";
+
+/// `CODING` is the character set of the payload, UTF-8 restricted to the
+/// Latin character set.
+pub const CODING: &str = "1";
+
+/// `TRAILER` is the end of payment data.
+pub const TRAILER: &str = "EPD";
+
+/// `QR_IIDS` is the range of the institution ids (IID) of QR-IBANs.
+const QR_IIDS: std::ops::RangeInclusive<u32> = 30000..=31999;
+
+/// `MAX_AMOUNT` is the max amount of a QR-bill, in minor units.
+const MAX_AMOUNT: i64 = 99_999_999_999;
+
+/// `MESSAGE_MAX_LENGTH` is the max length of the unstructured message and
+/// the bill information together.
+const MESSAGE_MAX_LENGTH: usize = 140;
+
+/// `CREDITOR_LINE` is the 1-based line of the creditor in the payload.
+const CREDITOR_LINE: usize = 5;
+
+/// `DEBTOR_LINE` is the 1-based line of the ultimate debtor in the payload.
+const DEBTOR_LINE: usize = 21;
+
+/// `ALTERNATIVE_PROCEDURES` is the max number of alternative procedures.
+const ALTERNATIVE_PROCEDURES: usize = 2;
+
+/// `MOD10_TABLE` is the table of the modulo 10 recursive check digit.
+const MOD10_TABLE: [u32; 10] = [0, 9, 4, 6, 8, 2, 7, 1, 3, 5];
+
+/// `is_qr_iban` returns if an IBAN is a QR-IBAN: a Swiss or Liechtenstein
+/// IBAN with an institution id from 30000 to 31999.
+pub fn is_qr_iban(iban: &IBAN) -> bool {
+    matches!(iban.country.as_str(), "CH" | "LI")
+        && iban
+            .bban
+            .as_str()
+            .get(..5)
+            .and_then(|iid| iid.parse().ok())
+            .map(|iid: u32| QR_IIDS.contains(&iid))
+            .unwrap_or(false)
+}
+
+/// `mod10_recursive` returns the modulo 10 recursive check digit of a
+/// string of digits, or `None` if it contains any other char.
+pub fn mod10_recursive(digits: &str) -> Option<u32> {
+    let mut carry = 0;
+
+    for c in digits.chars() {
+        carry = MOD10_TABLE[((carry + c.to_digit(10)?) % 10) as usize];
+    }
+
+    Some((10 - carry) % 10)
+}
+
+/// [`QrReference`] is a QR reference (QRR): 26 digits and a modulo 10
+/// recursive check digit, e.g. "210000000003139471430009017".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QrReference(String);
+
+impl QrReference {
+    /// `new` creates a new `QrReference` from a string code, with or without
+    /// spaces.
+    pub fn new(code: &str) -> Result<QrReference> {
+        let cd: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+
+        QrReference::check(&cd)?;
+
+        Ok(QrReference(cd))
+    }
+
+    /// `generate` creates a new `QrReference` from up to 26 digits, e.g. a
+    /// customer and an invoice number, left padded with zeros.
+    pub fn generate(reference: &str) -> Result<QrReference> {
+        check_digits("QR reference", reference, 1, 26)?;
+
+        let digits = format!("{:0>26}", reference);
+        let check = mod10_recursive(&digits).unwrap_or_default();
+
+        Ok(QrReference(format!("{}{}", digits, check)))
+    }
+
+    /// `as_str` returns the `QrReference` as a string slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `check` checks a provided code, with or without spaces, returning
+    /// why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        let cd: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+
+        check_digits("QR reference", &cd, 27, 27)?;
+
+        let expected = mod10_recursive(&cd[..26]).unwrap_or_default();
+
+        if cd[26..].parse::<u32>().ok() != Some(expected) {
+            return Err(Error::invalid(
+                "QR reference",
+                &cd,
+                Reason::Checksum {
+                    expected: expected.to_string(),
+                    actual: cd[26..].into(),
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `is_valid` returns if a provided code is valid by checking length,
+    /// kind of chars used and the check digit.
+    pub fn is_valid(code: &str) -> bool {
+        QrReference::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`QrReference`].
+    pub fn validate(&self) -> Result<()> {
+        QrReference::check(self.as_str())
+    }
+
+    /// `print` returns the `QrReference` in print format: two digits, then
+    /// groups of five digits.
+    pub fn print(&self) -> String {
+        let mut res = self.0[..2].to_string();
+
+        for group in self.0.as_bytes()[2..].chunks(5) {
+            res.push(' ');
+            res.push_str(std::str::from_utf8(group).unwrap_or_default());
+        }
+
+        res
+    }
+}
+
+impl fmt::Display for QrReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for QrReference {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        QrReference::new(code)
+    }
+}
+
+impl From<&str> for QrReference {
+    fn from(code: &str) -> Self {
+        QrReference::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for QrReference {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for QrReference {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// [`Reference`] is the payment reference of a QR-bill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Reference {
+    /// QR reference, required with a QR-IBAN.
+    Qr(QrReference),
+    /// ISO 11649 creditor reference, with an IBAN only.
+    Creditor(CreditorReference),
+    /// No reference, with an IBAN only.
+    None,
+}
+
+impl Reference {
+    /// `code` returns the type of the reference.
+    pub fn code(&self) -> &str {
+        match self {
+            Reference::Qr(_) => "QRR",
+            Reference::Creditor(_) => "SCOR",
+            Reference::None => "NON",
+        }
+    }
+}
+
+/// [`Party`] is the creditor or the debtor of a QR-bill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Party {
+    pub name: String,
+    /// `address` is a structured address: street, building number, post
+    /// code, town and country only.
+    pub address: PostalAddress,
+}
+
+impl Party {
+    /// `validate` validates the name and the address of the party, whose
+    /// lines start at the 1-based `line` of the payload.
+    fn validate(&self, line: usize) -> Result<()> {
+        let actual = self.name.chars().count();
+
+        if actual == 0 || actual > 70 {
+            return Err(invalid(
+                Some(line + 1),
+                Reason::Length {
+                    min: 1,
+                    max: 70,
+                    actual,
+                },
+            ));
+        }
+
+        let address = &self.address;
+        let supported = PostalAddress {
+            street_name: address.street_name.clone(),
+            building_number: address.building_number.clone(),
+            post_code: address.post_code.clone(),
+            town_name: address.town_name.clone(),
+            country: address.country.clone(),
+            ..Default::default()
+        };
+
+        if *address != supported {
+            return Err(invalid(
+                Some(line),
+                Reason::Format(
+                    "only street, building number, post code, town and country are supported"
+                        .into(),
+                ),
+            ));
+        }
+
+        // NOTE: the post code is optional in ISO 20022 but mandatory for
+        // the creditor and the ultimate debtor of a QR-bill.
+        if address.post_code.as_deref().unwrap_or_default().is_empty() {
+            return Err(invalid(
+                Some(line + 4),
+                Reason::Length {
+                    min: 1,
+                    max: 16,
+                    actual: 0,
+                },
+            ));
+        }
+
+        address.validate(AddressProfile::Structured)
+    }
+
+    /// `to_lines` returns the 7 lines of the party in the payload.
+    fn to_lines(party: Option<&Party>) -> Vec<String> {
+        let party = match party {
+            Some(party) => party,
+            None => return vec![String::new(); 7],
+        };
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+
+        vec![
+            "S".into(),
+            party.name.clone(),
+            text(&party.address.street_name),
+            text(&party.address.building_number),
+            text(&party.address.post_code),
+            text(&party.address.town_name),
+            party
+                .address
+                .country
+                .as_ref()
+                .map(|c| c.as_str().to_string())
+                .unwrap_or_default(),
+        ]
+    }
+
+    /// `from_lines` reads a party from its 7 lines in the payload, starting
+    /// at the 1-based `line`.
+    fn from_lines(lines: &[&str], line: usize) -> Result<Option<Party>> {
+        if lines.iter().all(|line| line.is_empty()) {
+            return Ok(None);
+        }
+
+        if lines[0] != "S" {
+            return Err(invalid(
+                Some(line),
+                Reason::Format(format!("expected address type S, found {:?}", lines[0])),
+            ));
+        }
+
+        let text = |line: &str| Some(line.to_string()).filter(|line| !line.is_empty());
+
+        Ok(Some(Party {
+            name: lines[1].into(),
+            address: PostalAddress {
+                street_name: text(lines[2]),
+                building_number: text(lines[3]),
+                post_code: text(lines[4]),
+                town_name: text(lines[5]),
+                country: Some(lines[6].parse()?),
+                ..Default::default()
+            },
+        }))
+    }
+}
+
+/// [`QrBill`] is the payment part of a Swiss QR-bill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QrBill {
+    /// `iban` is the account of the creditor, a QR-IBAN with QR references.
+    pub iban: IBAN,
+    pub creditor: Party,
+    /// `amount` is the amount in minor units, `None` if left to the debtor.
+    pub amount: Option<i64>,
+    /// `currency` is CHF or EUR.
+    pub currency: CurrencyCode,
+    pub debtor: Option<Party>,
+    pub reference: Reference,
+    /// `message` is the unstructured message to the creditor.
+    pub message: Option<String>,
+    /// `bill_information` is the structured bill information, e.g. the
+    /// "//S1/" Swico syntax.
+    pub bill_information: Option<String>,
+    /// `alternative_procedures` are the parameters of up to two alternative
+    /// payment procedures, e.g. "eBill/B/peter@sample.ch".
+    pub alternative_procedures: Vec<String>,
+}
+
+/// `invalid` returns an error for an invalid QR-bill element, at a 1-based
+/// line of the payload if known.
+fn invalid(line: Option<usize>, reason: Reason) -> Error {
+    Error::invalid_format("QR-bill", line, reason)
+}
+
+impl QrBill {
+    /// `new` creates a new `QrBill` without amount, debtor nor message.
+    pub fn new(
+        iban: IBAN,
+        creditor: Party,
+        currency: CurrencyCode,
+        reference: Reference,
+    ) -> QrBill {
+        QrBill {
+            iban,
+            creditor,
+            amount: None,
+            currency,
+            debtor: None,
+            reference,
+            message: None,
+            bill_information: None,
+            alternative_procedures: vec![],
+        }
+    }
+
+    /// `money` returns the amount with its currency, if any.
+    pub fn money(&self) -> Option<Money> {
+        self.amount
+            .map(|amount| Money::new(amount, self.currency.clone()))
+    }
+
+    /// `validate` validates the QR-bill: a Swiss or Liechtenstein IBAN,
+    /// a QR reference if and only if it is a QR-IBAN, a CHF or EUR amount
+    /// and the structured addresses of the parties.
+    pub fn validate(&self) -> Result<()> {
+        if !matches!(self.iban.country.as_str(), "CH" | "LI") {
+            return Err(invalid(
+                Some(4),
+                Reason::Format(format!(
+                    "expected a CH or LI IBAN, found {}",
+                    self.iban.country.as_str()
+                )),
+            ));
+        }
+
+        match (is_qr_iban(&self.iban), &self.reference) {
+            (true, Reference::Qr(_))
+            | (false, Reference::Creditor(_))
+            | (false, Reference::None) => {}
+            (true, _) => {
+                return Err(invalid(
+                    Some(28),
+                    Reason::Format("QR-IBAN without QR reference".into()),
+                ))
+            }
+            (false, _) => {
+                return Err(invalid(
+                    Some(28),
+                    Reason::Format("QR reference without QR-IBAN".into()),
+                ))
+            }
+        }
+
+        if !matches!(self.currency.as_str(), "CHF" | "EUR") {
+            return Err(invalid(
+                Some(20),
+                Reason::Format(format!(
+                    "expected CHF or EUR, found {}",
+                    self.currency.as_str()
+                )),
+            ));
+        }
+
+        if let Some(amount) = self.amount {
+            if amount <= 0 || amount > MAX_AMOUNT {
+                return Err(invalid(
+                    Some(19),
+                    Reason::Format("expected an amount of 0.01 to 999999999.99".into()),
+                ));
+            }
+        }
+
+        self.creditor.validate(CREDITOR_LINE)?;
+        if let Some(ref debtor) = self.debtor {
+            debtor.validate(DEBTOR_LINE)?;
+        }
+
+        let length = self.message.as_ref().map_or(0, |m| m.chars().count())
+            + self
+                .bill_information
+                .as_ref()
+                .map_or(0, |b| b.chars().count());
+        if length > MESSAGE_MAX_LENGTH {
+            return Err(invalid(
+                Some(30),
+                Reason::Format(format!(
+                    "message and bill information longer than {} chars",
+                    MESSAGE_MAX_LENGTH
+                )),
+            ));
+        }
+
+        if self.alternative_procedures.len() > ALTERNATIVE_PROCEDURES {
+            return Err(invalid(
+                Some(33),
+                Reason::Format(format!(
+                    "more than {} alternative procedures",
+                    ALTERNATIVE_PROCEDURES
+                )),
+            ));
+        }
+
+        if let Some(i) = self
+            .alternative_procedures
+            .iter()
+            .position(|procedure| procedure.chars().count() > 100)
+        {
+            return Err(invalid(
+                Some(33 + i),
+                Reason::Length {
+                    min: 0,
+                    max: 100,
+                    actual: self.alternative_procedures[i].chars().count(),
+                },
+            ));
+        }
+
+        for (i, line) in self.to_lines().iter().enumerate() {
+            if let Some((index, found)) = line
+                .chars()
+                .enumerate()
+                .find(|(_, c)| *c == '\r' || *c == '\n')
+            {
+                return Err(invalid(
+                    Some(i + 1),
+                    Reason::Character {
+                        index,
+                        found,
+                        expected: "a char other than a line break",
+                    },
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `to_lines` returns the lines of the payload.
+    fn to_lines(&self) -> Vec<String> {
+        let mut res: Vec<String> = vec![
+            QR_TYPE.into(),
+            VERSION.into(),
+            CODING.into(),
+            self.iban.to_string(),
+        ];
+
+        res.extend(Party::to_lines(Some(&self.creditor)));
+        // NOTE: the ultimate creditor is reserved for future use
+        res.extend(Party::to_lines(None));
+        res.push(self.money().map(|m| m.format('.')).unwrap_or_default());
+        res.push(self.currency.as_str().into());
+        res.extend(Party::to_lines(self.debtor.as_ref()));
+        res.push(self.reference.code().into());
+        res.push(match self.reference {
+            Reference::Qr(ref reference) => reference.to_string(),
+            Reference::Creditor(ref reference) => reference.to_string(),
+            Reference::None => String::new(),
+        });
+        res.push(self.message.clone().unwrap_or_default());
+        res.push(TRAILER.into());
+
+        if self.bill_information.is_some() || !self.alternative_procedures.is_empty() {
+            res.push(self.bill_information.clone().unwrap_or_default());
+            res.extend(self.alternative_procedures.iter().cloned());
+        }
+
+        res
+    }
+
+    /// `to_payload` builds the text payload of the Swiss QR Code, with
+    /// CR LF line separators.
+    pub fn to_payload(&self) -> Result<String> {
+        self.validate()?;
+
+        Ok(self.to_lines().join("\r\n"))
+    }
+
+    /// `parse` parses the text payload of a Swiss QR Code, with CR LF or LF
+    /// line separators, and validates it.
+    pub fn parse(payload: &str) -> Result<QrBill> {
+        let lines: Vec<&str> = payload
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+
+        let expected = |line: usize, value: &str| {
+            invalid(
+                Some(line),
+                Reason::Format(format!("expected {}, found {:?}", value, lines[line - 1])),
+            )
+        };
+
+        if lines.len() < 31 || lines.len() > 34 {
+            return Err(invalid(
+                None,
+                Reason::Format(format!("expected 31 to 34 lines, found {}", lines.len())),
+            ));
+        }
+
+        if lines[0] != QR_TYPE {
+            return Err(expected(1, QR_TYPE));
+        }
+
+        // NOTE: minor versions are compatible
+        if !lines[1].starts_with("02") || lines[1].len() != 4 {
+            return Err(expected(2, "version 02xx"));
+        }
+
+        if lines[2] != CODING {
+            return Err(expected(3, CODING));
+        }
+
+        if lines[30] != TRAILER {
+            return Err(expected(31, TRAILER));
+        }
+
+        if let Some(i) = lines[11..18].iter().position(|line| !line.is_empty()) {
+            return Err(expected(
+                12 + i,
+                "an empty line for the reserved ultimate creditor",
+            ));
+        }
+
+        let currency: CurrencyCode = lines[19].parse()?;
+        let text = |line: &str| Some(line.to_string()).filter(|line| !line.is_empty());
+
+        let reference = match (lines[27], lines[28]) {
+            ("QRR", reference) => Reference::Qr(reference.parse()?),
+            ("SCOR", reference) => Reference::Creditor(reference.parse()?),
+            ("NON", "") => Reference::None,
+            ("NON", _) => return Err(expected(29, "no reference with NON")),
+            _ => return Err(expected(28, "QRR, SCOR or NON")),
+        };
+
+        let res = QrBill {
+            iban: lines[3].parse()?,
+            creditor: Party::from_lines(&lines[4..11], CREDITOR_LINE)?
+                .ok_or_else(|| Error::MissingElement("creditor".into()))?,
+            amount: match lines[18] {
+                "" => None,
+                amount => Some(Money::parse(amount, currency.clone())?.amount),
+            },
+            currency,
+            debtor: Party::from_lines(&lines[20..27], DEBTOR_LINE)?,
+            reference,
+            message: text(lines[29]),
+            bill_information: lines.get(31).and_then(|line| text(line)),
+            alternative_procedures: lines
+                .get(32..)
+                .unwrap_or_default()
+                .iter()
+                .map(|line| line.to_string())
+                .collect(),
+        };
+
+        res.validate()?;
+
+        Ok(res)
+    }
+}
+
+impl FromStr for QrBill {
+    type Err = Error;
+
+    fn from_str(payload: &str) -> StdResult<Self, Self::Err> {
+        QrBill::parse(payload)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{is_qr_iban, Party, QrBill, QrReference, Reference};
+    #[allow(unused_imports)] // TODO
+    use crate::iso11649::CreditorReference;
+    #[allow(unused_imports)] // TODO
+    use crate::iso13616::IBAN;
+    #[allow(unused_imports)] // TODO
+    use crate::iso20022::PostalAddress;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[allow(dead_code)]
+    const PAYLOAD: &str = "SPC\r\n0200\r\n1\r\nCH4431999123000889012\r\n\
+        S\r\nRobert Schneider AG\r\nRue du Lac\r\n1268\r\n2501\r\nBiel\r\nCH\r\n\
+        \r\n\r\n\r\n\r\n\r\n\r\n\r\n\
+        1949.75\r\nCHF\r\n\
+        S\r\nPia-Maria Rutschmann-Schnyder\r\nGrosse Marktgasse\r\n28\r\n9400\r\nRorschach\r\nCH\r\n\
+        QRR\r\n210000000003139471430009017\r\n\
+        Order of 15 June 2020\r\nEPD\r\n\
+        //S1/10/10201409/11/200701/20/140.000-53/30/102673831/31/200615/32/7.7/33/7.7:0\r\n\
+        eBill/B/peter@sample.ch";
+
+    #[test]
+    fn qr_reference() -> Result<()> {
+        let reference = QrReference::new("21 00000 00003 13947 14300 09017")?;
+
+        assert_eq!(reference.as_str(), "210000000003139471430009017");
+        assert_eq!(reference.print(), "21 00000 00003 13947 14300 09017");
+        assert_eq!(
+            QrReference::generate("21000000000313947143000901")?,
+            reference
+        );
+        assert!(!QrReference::is_valid("210000000003139471430009018"));
+        assert!(!QrReference::is_valid("21000000000313947143000901"));
+
+        assert!(is_qr_iban(&IBAN::new("CH44 3199 9123 0008 8901 2")?));
+        assert!(!is_qr_iban(&IBAN::new("CH93 0076 2011 6238 5295 7")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_and_build() -> Result<()> {
+        let bill = QrBill::parse(PAYLOAD)?;
+
+        assert_eq!(bill.creditor.name, "Robert Schneider AG");
+        assert_eq!(bill.creditor.address.town_name.as_deref(), Some("Biel"));
+        assert_eq!(
+            bill.money().map(|m| m.to_string()),
+            Some("1949.75 CHF".into())
+        );
+        assert_eq!(
+            bill.debtor.as_ref().map(|d| d.name.as_str()),
+            Some("Pia-Maria Rutschmann-Schnyder")
+        );
+        assert_eq!(bill.reference.code(), "QRR");
+        assert_eq!(bill.alternative_procedures, vec!["eBill/B/peter@sample.ch"]);
+        assert_eq!(bill.to_payload()?, PAYLOAD);
+        assert_eq!(QrBill::parse(&PAYLOAD.replace("\r\n", "\n"))?, bill);
+
+        let mut bill = QrBill::new(
+            IBAN::new("CH9300762011623852957")?,
+            Party {
+                name: "Robert Schneider AG".into(),
+                address: PostalAddress {
+                    street_name: Some("Rue du Lac".into()),
+                    building_number: Some("1268".into()),
+                    post_code: Some("2501".into()),
+                    town_name: Some("Biel".into()),
+                    country: Some("CH".into()),
+                    ..Default::default()
+                },
+            },
+            "EUR".into(),
+            Reference::Creditor(CreditorReference::new("RF18539007547034")?),
+        );
+        assert_eq!(QrBill::parse(&bill.to_payload()?)?, bill);
+
+        bill.reference = Reference::Qr(QrReference::new("210000000003139471430009017")?);
+        assert!(bill.to_payload().is_err());
+
+        bill.reference = Reference::None;
+        bill.currency = "USD".into();
+        assert!(bill.to_payload().is_err());
+
+        bill.currency = "CHF".into();
+        bill.creditor.address.post_code = None;
+        assert_eq!(
+            bill.to_payload().unwrap_err().to_string(),
+            "invalid QR-bill at line 9: wrong length, expected 1 to 16 chars, found 0"
+        );
+
+        bill.creditor.address.post_code = Some("2501".into());
+        bill.creditor.address.address_lines = vec!["Rue du Lac 1268".into()];
+        assert!(bill.to_payload().is_err());
+        assert_eq!(
+            QrBill::parse(&PAYLOAD.replace("\r\n9400\r\n", "\r\n\r\n"))
+                .unwrap_err()
+                .to_string(),
+            "invalid QR-bill at line 25: wrong length, expected 1 to 16 chars, found 0"
+        );
+
+        assert_eq!(
+            QrBill::parse(&PAYLOAD.replace("\r\nS\r\nRobert", "\r\nK\r\nRobert"))
+                .unwrap_err()
+                .to_string(),
+            "invalid QR-bill at line 5: expected address type S, found \"K\""
+        );
+        assert!(
+            QrBill::parse(&PAYLOAD.replace("CH4431999123000889012", "CH9300762011623852957"))
+                .is_err()
+        );
+
+        Ok(())
+    }
+}
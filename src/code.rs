@@ -1,24 +1,21 @@
 //! Generic string code used in account identifiers.
 
-use crate::error::Error;
+use crate::error::{check_chars, check_length, Error};
 use crate::result::Result;
 use arraystring::{prelude::Capacity, ArrayString};
 use serde::{Deserialize, Serialize};
-use std::char;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
 /// [`Code`] is a fixed length array string.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct Code<N: Capacity>(ArrayString<N>);
 
 impl<N: Capacity> Code<N> {
     /// `new` creates a new `Code<N>` from a string code
     pub fn new(code: &str) -> Result<Code<N>> {
-        if !Code::<N>::is_valid(code) {
-            return Err(Error::InvalidCode);
-        }
+        Code::<N>::check(code)?;
 
         Ok(Code::<N>(ArrayString::<N>::from(code)))
     }
@@ -28,20 +25,25 @@ impl<N: Capacity> Code<N> {
         &self.0
     }
 
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        let length = ArrayString::<N>::capacity().into();
+
+        check_length("code", code, length, length)?;
+        check_chars("code", code, "an upper-case letter or a digit", |c| {
+            c.is_ascii_uppercase() || c.is_ascii_digit()
+        })
+    }
+
     /// `is_valid` returns if a provided code is valid
     /// by checking length and kind of chars used.
     pub fn is_valid(code: &str) -> bool {
-        (code.len() as u8) == ArrayString::<N>::capacity()
-            && code.find(|c: char| c.is_lowercase() || !c.is_ascii_alphanumeric()) == None
+        Code::<N>::check(code).is_ok()
     }
 
     /// `validate` validates the `Code`.
     pub fn validate(&self) -> Result<()> {
-        if !Code::<N>::is_valid(&self.0) {
-            return Err(Error::InvalidCode);
-        }
-
-        Ok(())
+        Code::<N>::check(&self.0)
     }
 }
 
@@ -53,7 +55,7 @@ impl<N: Capacity> FromStr for Code<N> {
     }
 }
 
-impl<'a, N: Capacity> From<&'a str> for Code<N> {
+impl<N: Capacity> From<&str> for Code<N> {
     fn from(code: &str) -> Self {
         Code::<N>::from_str(code).unwrap()
     }
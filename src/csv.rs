@@ -0,0 +1,40 @@
+//! Minimal CSV reading, used to load the reference directories published
+//! as CSV files.
+
+/// `fields` splits a CSV line in its fields, trimmed. Fields may be double
+/// quoted, with commas and doubled quotes inside, but not line breaks.
+pub fn fields(line: &str) -> Vec<String> {
+    let mut res = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => res.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    res.push(field);
+
+    res.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::fields;
+
+    #[test]
+    fn split() {
+        assert_eq!(fields("a, b ,c"), vec!["a", "b", "c"]);
+        assert_eq!(
+            fields("\"012-002\",\"Level 16, 833 Collins St\",\"\"\"A\"\" B\","),
+            vec!["012-002", "Level 16, 833 Collins St", "\"A\" B", ""]
+        );
+    }
+}
@@ -0,0 +1,520 @@
+//! Calendar dates and date times as used in payment messages (ISO 8601).
+
+use crate::error::{check_digits, check_length, Error, Reason};
+use crate::result::Result;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// [`Date`] is a proleptic gregorian calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+/// [`Weekday`] is a day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Date {
+    /// `new` creates a new `Date`, checking that the day exists.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Date> {
+        if year == 0 || month == 0 || month > 12 || day == 0 || day > days_in_month(year, month) {
+            return Err(Error::invalid(
+                "date",
+                &format!("{:04}-{:02}-{:02}", year, month, day),
+                Reason::Format("no such day".into()),
+            ));
+        }
+
+        Ok(Date { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// `from_yymmdd` parses a "YYMMDD" date, as used in SWIFT messages.
+    /// Two digits years are in the range 1980-2079.
+    pub fn from_yymmdd(date: &str) -> Result<Date> {
+        check_digits("date", date, 6, 6)?;
+
+        let year: u16 = parse_number("date", date, 0, 2)?;
+
+        Date::new(
+            if year < 80 { 2000 + year } else { 1900 + year },
+            parse_number("date", date, 2, 4)?,
+            parse_number("date", date, 4, 6)?,
+        )
+    }
+
+    /// `to_yymmdd` formats the date as "YYMMDD".
+    pub fn to_yymmdd(&self) -> String {
+        format!("{:02}{:02}{:02}", self.year % 100, self.month, self.day)
+    }
+
+    /// `from_yyddd` parses a "YYDDD" ordinal date, as used in Bacs files.
+    /// Two digits years are in the range 1980-2079.
+    pub fn from_yyddd(date: &str) -> Result<Date> {
+        check_digits("date", date, 5, 5)?;
+
+        let year: u16 = parse_number("date", date, 0, 2)?;
+        let year = if year < 80 { 2000 + year } else { 1900 + year };
+        let day: i64 = parse_number::<u16>("date", date, 2, 5)?.into();
+
+        if day == 0 || day > if is_leap_year(year) { 366 } else { 365 } {
+            return Err(Error::invalid(
+                "date",
+                date,
+                Reason::Format("no such day of the year".into()),
+            ));
+        }
+
+        Date::new(year, 1, 1)?.add_days(day - 1)
+    }
+
+    /// `to_yyddd` formats the date as "YYDDD".
+    pub fn to_yyddd(&self) -> String {
+        let start = Date {
+            year: self.year,
+            month: 1,
+            day: 1,
+        };
+
+        format!(
+            "{:02}{:03}",
+            self.year % 100,
+            self.days() - start.days() + 1
+        )
+    }
+
+    /// `from_days` returns the date that is `days` days after 1970-01-01.
+    pub fn from_days(days: i64) -> Result<Date> {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        if year < 1 || year > i64::from(u16::MAX) {
+            return Err(Error::invalid(
+                "date",
+                &format!("{} days from 1970-01-01", days),
+                Reason::Format("year out of range".into()),
+            ));
+        }
+
+        Date::new(year as u16, month as u8, day as u8)
+    }
+
+    /// `days` returns the number of days since 1970-01-01.
+    pub fn days(&self) -> i64 {
+        // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let month = i64::from(self.month);
+        let year = i64::from(self.year) - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let mp = (month + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era * 146_097 + doe - 719_468
+    }
+
+    /// `add_days` returns the date `days` days after (or before) this one.
+    pub fn add_days(&self, days: i64) -> Result<Date> {
+        Date::from_days(self.days() + days)
+    }
+
+    /// `add_months` returns the date `months` months after this one. The day
+    /// is clamped to the last day of the resulting month if needed.
+    pub fn add_months(&self, months: u32) -> Result<Date> {
+        let total = u32::from(self.month) - 1 + months;
+        let year = u32::from(self.year) + total / 12;
+
+        if year > u32::from(u16::MAX) {
+            return Err(Error::invalid(
+                "date",
+                &format!("{} + {} months", self, months),
+                Reason::Format("year out of range".into()),
+            ));
+        }
+
+        let year = year as u16;
+        let month = (total % 12) as u8 + 1;
+
+        Date::new(year, month, self.day.min(days_in_month(year, month)))
+    }
+
+    /// `weekday` returns the day of the week of the date.
+    pub fn weekday(&self) -> Weekday {
+        match (self.days() + 3).rem_euclid(7) {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
+
+    /// `is_weekend` returns if the date is a saturday or a sunday.
+    pub fn is_weekend(&self) -> bool {
+        self.weekday() == Weekday::Saturday || self.weekday() == Weekday::Sunday
+    }
+}
+
+/// `is_leap_year` returns if a year is a leap year.
+pub fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// `days_in_month` returns the number of days of a month.
+pub fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// `check_pattern` checks the chars of a date from the char `offset` on
+/// against a pattern, where 'n' is a digit, 's' a sign and any other char
+/// itself, e.g. "nnnn-nn-nn". Chars past the end of either are not checked.
+fn check_pattern(kind: &'static str, date: &str, offset: usize, pattern: &str) -> Result<()> {
+    let invalid = date
+        .chars()
+        .skip(offset)
+        .zip(pattern.chars())
+        .enumerate()
+        .find(|(_, (c, p))| match p {
+            'n' => !c.is_ascii_digit(),
+            's' => *c != '+' && *c != '-',
+            _ => c != p,
+        });
+
+    match invalid {
+        Some((index, (found, p))) => Err(Error::invalid(
+            kind,
+            date,
+            Reason::Character {
+                index: offset + index,
+                found,
+                expected: match p {
+                    'n' => "a digit",
+                    's' => "'+' or '-'",
+                    '-' => "'-'",
+                    ':' => "':'",
+                    _ => "'T'",
+                },
+            },
+        )),
+        None => Ok(()),
+    }
+}
+
+/// `parse_number` parses the digits at `from..to` of the date `date`.
+fn parse_number<T: FromStr>(kind: &'static str, date: &str, from: usize, to: usize) -> Result<T> {
+    check_pattern(kind, date, from, &"n".repeat(to - from))?;
+
+    date[from..to]
+        .parse()
+        .map_err(|_| Error::invalid(kind, date, Reason::Format("number out of range".into())))
+}
+
+impl FromStr for Date {
+    type Err = Error;
+
+    /// Parses an ISO 8601 date ("2024-01-31").
+    fn from_str(date: &str) -> StdResult<Self, Self::Err> {
+        check_length("date", date, 10, 10)?;
+        check_pattern("date", date, 0, "nnnn-nn-nn")?;
+
+        Date::new(
+            parse_number("date", date, 0, 4)?,
+            parse_number("date", date, 5, 7)?,
+            parse_number("date", date, 8, 10)?,
+        )
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Serialize for Date {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(&self.to_string(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for Date {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        String::deserialize(des).and_then(|s| s.parse().map_err(de::Error::custom))
+    }
+}
+
+/// [`DateTime`] is a date with a time of the day and an optional UTC offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub date: Date,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+    /// `offset` is the UTC offset in minutes, `None` if the time is local.
+    pub offset: Option<i16>,
+}
+
+impl DateTime {
+    /// `new` creates a new `DateTime` without fractional seconds and offset.
+    pub fn new(date: Date, hour: u8, minute: u8, second: u8) -> Result<DateTime> {
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(Error::invalid(
+                "date-time",
+                &format!("{}T{:02}:{:02}:{:02}", date, hour, minute, second),
+                Reason::Format("no such time".into()),
+            ));
+        }
+
+        Ok(DateTime {
+            date,
+            hour,
+            minute,
+            second,
+            nanosecond: 0,
+            offset: None,
+        })
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = Error;
+
+    /// Parses an ISO 8601 date time ("2024-01-31T17:30:00.000+01:00").
+    fn from_str(datetime: &str) -> StdResult<Self, Self::Err> {
+        const KIND: &str = "date-time";
+        const LENGTH: usize = 19;
+
+        // NOTE: up to 9 fraction digits and a "+hh:mm" offset
+        check_length(KIND, datetime, LENGTH, LENGTH + 10 + 6)?;
+        check_pattern(KIND, datetime, 0, "nnnn-nn-nnTnn:nn:nn")?;
+
+        let date = datetime[..10]
+            .parse()
+            .map_err(|e: Error| e.within(KIND, datetime, 0))?;
+        let mut res = DateTime::new(
+            date,
+            parse_number(KIND, datetime, 11, 13)?,
+            parse_number(KIND, datetime, 14, 16)?,
+            parse_number(KIND, datetime, 17, 19)?,
+        )
+        .map_err(|e| e.within(KIND, datetime, 0))?;
+
+        let mut offset = LENGTH;
+
+        if datetime[offset..].starts_with('.') {
+            let fraction = &datetime[offset + 1..];
+            let len = fraction
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(fraction.len());
+
+            if len == 0 || len > 9 {
+                return Err(Error::invalid(
+                    KIND,
+                    datetime,
+                    Reason::Format("expected 1 to 9 fraction digits".into()),
+                ));
+            }
+
+            let nanos: u32 = parse_number(KIND, datetime, offset + 1, offset + 1 + len)?;
+            res.nanosecond = nanos * 10u32.pow(9 - len as u32);
+            offset += 1 + len;
+        }
+
+        res.offset = match &datetime[offset..] {
+            "" => None,
+            "Z" => Some(0),
+            rest => {
+                check_pattern(KIND, datetime, offset, "snn:nn")?;
+
+                if rest.len() != 6 {
+                    return Err(Error::invalid(
+                        KIND,
+                        datetime,
+                        Reason::Format("expected a \"+hh:mm\" UTC offset".into()),
+                    ));
+                }
+
+                let sign = if rest.starts_with('-') { -1 } else { 1 };
+                let hours: i16 = parse_number(KIND, datetime, offset + 1, offset + 3)?;
+                let minutes: i16 = parse_number(KIND, datetime, offset + 4, offset + 6)?;
+
+                if hours > 23 || minutes > 59 {
+                    return Err(Error::invalid(
+                        KIND,
+                        datetime,
+                        Reason::Format("UTC offset out of range".into()),
+                    ));
+                }
+
+                Some(sign * (hours * 60 + minutes))
+            }
+        };
+
+        Ok(res)
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}T{:02}:{:02}:{:02}",
+            self.date, self.hour, self.minute, self.second
+        )?;
+
+        if self.nanosecond != 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+
+        match self.offset {
+            None => Ok(()),
+            Some(0) => write!(f, "Z"),
+            Some(offset) => write!(
+                f,
+                "{}{:02}:{:02}",
+                if offset < 0 { '-' } else { '+' },
+                offset.abs() / 60,
+                offset.abs() % 60
+            ),
+        }
+    }
+}
+
+impl Serialize for DateTime {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(&self.to_string(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for DateTime {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        String::deserialize(des).and_then(|s| s.parse().map_err(de::Error::custom))
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Date, DateTime, Result, Weekday};
+    #[allow(unused_imports)] // TODO
+    use crate::error::Reason;
+
+    #[test]
+    fn date() -> Result<()> {
+        const WRONG_DATES: &[&str] = &["2023-02-29", "2024-13-01", "2024-1-01", "20240101", ""];
+
+        for date in WRONG_DATES.iter() {
+            assert!(date.parse::<Date>().is_err());
+        }
+
+        let date: Date = "2024-02-29".parse()?;
+
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(date.weekday(), Weekday::Thursday);
+        assert_eq!(date.add_days(1)?.to_string(), "2024-03-01");
+        assert_eq!(date.add_days(-60)?.to_string(), "2023-12-31");
+        assert_eq!(Date::from_days(date.days())?, date);
+        assert_eq!(Date::new(1970, 1, 1)?.days(), 0);
+        assert_eq!(Date::from_yymmdd("240229")?, date);
+        assert_eq!(Date::from_yymmdd("991231")?.to_string(), "1999-12-31");
+        assert_eq!(date.to_yymmdd(), "240229");
+        assert!(Date::from_yymmdd("240230").is_err());
+        assert_eq!(date.to_yyddd(), "24060");
+        assert_eq!(Date::from_yyddd("24366")?.to_string(), "2024-12-31");
+        assert!(Date::from_yyddd("23366").is_err());
+        assert_eq!(date.add_months(12)?.to_string(), "2025-02-28");
+        assert_eq!(date.add_months(13)?.to_string(), "2025-03-29");
+
+        Ok(())
+    }
+
+    #[test]
+    fn datetime() -> Result<()> {
+        const VALID_DATETIMES: &[&str] = &[
+            "2024-01-31T17:30:00",
+            "2024-01-31T17:30:00Z",
+            "2024-01-31T17:30:00.123+01:00",
+            "2024-01-31T17:30:00.5-05:30",
+        ];
+        const WRONG_DATETIMES: &[&str] = &[
+            "2024-01-31",
+            "2024-01-31T25:00:00",
+            "2024-01-31T17:30:00.+01:00",
+            "2024-01-31T17:30:00+0100",
+        ];
+
+        for datetime in VALID_DATETIMES.iter() {
+            assert_eq!(&datetime.parse::<DateTime>()?.to_string(), datetime);
+        }
+
+        for datetime in WRONG_DATETIMES.iter() {
+            assert!(datetime.parse::<DateTime>().is_err());
+        }
+
+        assert_eq!(
+            "2024-01-31T17:3O:00"
+                .parse::<DateTime>()
+                .unwrap_err()
+                .reason(),
+            Some(&Reason::Character {
+                index: 15,
+                found: 'O',
+                expected: "a digit"
+            })
+        );
+        assert_eq!(
+            "2024-01-31T17:30:00+01-00"
+                .parse::<DateTime>()
+                .unwrap_err()
+                .to_string(),
+            "invalid date-time \"2024-01-31T17:30:00+01-00\": \
+             invalid char '-' at index 22, expected ':'"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+//! German bank codes (Bankleitzahlen), as published by the Deutsche
+//! Bundesbank in the BLZ file.
+
+use crate::de::method::{CheckMethod, ACCOUNT_LENGTH};
+use crate::error::{check_digits, check_length, Error, Reason};
+use crate::iso13616::{BBAN, IBAN};
+use crate::iso9362::BIC;
+use crate::result::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// `BANK_CODE_LENGTH` is the length of a bank code (BLZ).
+pub const BANK_CODE_LENGTH: usize = 8;
+
+/// `RECORD_LENGTH` is the minimum length of a record of the BLZ file.
+pub const RECORD_LENGTH: usize = 168;
+
+/// [`Bank`] is a record of the BLZ file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bank {
+    /// `code` is the bank code (Bankleitzahl).
+    pub code: String,
+    /// `main` is set for the main record of the bank code (Merkmal 1), the
+    /// other ones being branches.
+    pub main: bool,
+    pub name: String,
+    pub post_code: String,
+    pub city: String,
+    pub short_name: String,
+    pub bic: Option<BIC>,
+    pub method: CheckMethod,
+    /// `successor` is the bank code replacing a deleted one, if any.
+    pub successor: Option<String>,
+}
+
+impl Bank {
+    /// `parse` parses a fixed length record of the BLZ file.
+    pub fn parse(record: &str) -> Result<Bank> {
+        let chars: Vec<char> = record.chars().collect();
+
+        if chars.len() < RECORD_LENGTH {
+            return Err(Error::invalid_format(
+                "BLZ file",
+                None,
+                Reason::Format(format!(
+                    "record of {} chars, shorter than {}",
+                    chars.len(),
+                    RECORD_LENGTH
+                )),
+            ));
+        }
+
+        // NOTE: positions are 1-based, as in the Bundesbank documentation
+        let field = |from: usize, to: usize| -> String {
+            chars[from - 1..to].iter().collect::<String>().trim().into()
+        };
+
+        let code = field(1, 8);
+        check_bank_code(&code)?;
+
+        let bic = match field(140, 150) {
+            bic if bic.is_empty() => None,
+            bic => Some(bic.parse()?),
+        };
+
+        let successor = match field(161, 168) {
+            successor if successor.is_empty() || successor == "00000000" => None,
+            successor => Some(successor),
+        };
+
+        Ok(Bank {
+            code,
+            main: field(9, 9) == "1",
+            name: field(10, 67),
+            post_code: field(68, 72),
+            city: field(73, 107),
+            short_name: field(108, 134),
+            bic,
+            method: field(151, 152).parse()?,
+            successor,
+        })
+    }
+}
+
+/// `check_bank_code` checks that a bank code is made of 8 digits,
+/// returning why it is invalid.
+pub fn check_bank_code(code: &str) -> Result<()> {
+    check_digits("bank code", code, BANK_CODE_LENGTH, BANK_CODE_LENGTH)
+}
+
+/// `is_valid_bank_code` returns if a bank code is made of 8 digits.
+pub fn is_valid_bank_code(code: &str) -> bool {
+    check_bank_code(code).is_ok()
+}
+
+/// [`BankCodes`] maps a bank code with its main record in the BLZ file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BankCodes(BTreeMap<String, Bank>);
+
+impl BankCodes {
+    /// `parse` parses the content of the BLZ file, one record per line.
+    pub fn parse(content: &str) -> Result<BankCodes> {
+        let mut res = BTreeMap::new();
+
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let bank = Bank::parse(line).map_err(|e| e.at_line(i + 1))?;
+
+            if bank.main || !res.contains_key(&bank.code) {
+                res.insert(bank.code.clone(), bank);
+            }
+        }
+
+        Ok(BankCodes(res))
+    }
+
+    /// `from_bytes` parses the BLZ file as published, encoded in
+    /// ISO 8859-1.
+    pub fn from_bytes(bytes: &[u8]) -> Result<BankCodes> {
+        BankCodes::parse(&bytes.iter().map(|b| *b as char).collect::<String>())
+    }
+
+    /// `load` loads the BLZ file from a local path.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<BankCodes> {
+        let bytes = std::fs::read(path.as_ref()).map_err(Error::Io)?;
+
+        BankCodes::from_bytes(&bytes)
+    }
+
+    /// `find` finds the bank of a bank code.
+    pub fn find(&self, code: &str) -> Option<&Bank> {
+        self.0.get(code)
+    }
+
+    /// `validate_account` validates an account number with the check digit
+    /// method of its bank code.
+    pub fn validate_account(&self, code: &str, account: &str) -> Result<()> {
+        self.find(code)
+            .ok_or_else(|| Error::not_found("bank code", code))?
+            .method
+            .check_in_bank(code, account)
+    }
+
+    /// `validate_bban` validates a German BBAN: a bank code, then a 10
+    /// digits account number.
+    pub fn validate_bban(&self, bban: &BBAN) -> Result<()> {
+        let code = bban.as_str();
+
+        check_length(
+            "BBAN",
+            code,
+            BANK_CODE_LENGTH + ACCOUNT_LENGTH,
+            BANK_CODE_LENGTH + ACCOUNT_LENGTH,
+        )?;
+
+        self.validate_account(&code[..BANK_CODE_LENGTH], &code[BANK_CODE_LENGTH..])
+    }
+
+    /// `validate_iban` validates a German IBAN, its check digits then its
+    /// BBAN.
+    pub fn validate_iban(&self, iban: &IBAN) -> Result<()> {
+        if iban.country.as_str() != "DE" {
+            return Err(Error::invalid(
+                "IBAN",
+                &iban.to_string(),
+                Reason::Format("expected a DE IBAN".into()),
+            ));
+        }
+
+        iban.validate()?;
+        self.validate_bban(&iban.bban)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Bank, BankCodes, Result};
+    #[allow(unused_imports)] // TODO
+    use crate::iso13616::IBAN;
+
+    #[allow(dead_code)]
+    fn record(code: &str, main: bool, name: &str, bic: &str, method: &str) -> String {
+        format!(
+            "{}{}{:<58}{:<5}{:<35}{:<27.27}{:<5}{:<11}{}{:06}{}{}{:08}{:<6}",
+            code,
+            if main { "1" } else { "2" },
+            name,
+            "10117",
+            "Berlin",
+            name,
+            "",
+            bic,
+            method,
+            1,
+            "U",
+            "0",
+            0,
+            "000000"
+        )
+    }
+
+    #[test]
+    fn parse() -> Result<()> {
+        let content = [
+            record(
+                "10010010",
+                true,
+                "Postbank Ndl der Deutsche Bank",
+                "PBNKDEFFXXX",
+                "24",
+            ),
+            record(
+                "10010010",
+                false,
+                "Postbank Ndl der Deutsche Bank",
+                "",
+                "24",
+            ),
+            record("37040044", true, "Commerzbank", "COBADEFFXXX", "13"),
+            record(
+                "12030000",
+                true,
+                "Deutsche Kreditbank Berlin",
+                "BYLADEM1001",
+                "00",
+            ),
+        ]
+        .join("\r\n");
+
+        let bank = Bank::parse(&record("37040044", true, "Commerzbank", "", "13"))?;
+        assert_eq!(bank.name, "Commerzbank");
+        assert_eq!(bank.city, "Berlin");
+        assert!(bank.bic.is_none());
+        assert!(Bank::parse("37040044").is_err());
+
+        let codes = BankCodes::parse(&content)?;
+        assert_eq!(codes.find("10010010").map(|b| b.main), Some(true));
+        assert_eq!(
+            codes
+                .find("37040044")
+                .and_then(|b| b.bic.as_ref())
+                .map(|b| b.to_string()),
+            Some("COBADEFFXXX".into())
+        );
+
+        assert!(codes.validate_account("12030000", "9290701").is_ok());
+        assert!(codes.validate_account("12030000", "9290702").is_err());
+        assert!(codes.validate_account("99999999", "9290701").is_err());
+        // NOTE: method 24 is not supported
+        assert!(codes.validate_account("10010010", "9290701").is_err());
+
+        let iban = IBAN::new("DE89370400440532013000")?;
+        assert!(codes.validate_iban(&iban).is_ok());
+        assert!(codes.validate_bban(&"370400440532113000".into()).is_err());
+        assert!(codes
+            .validate_iban(&IBAN::new("BE71096123456769")?)
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1434 @@
+//! German account number check digit methods (Prüfzifferberechnungsmethoden),
+//! as published by the Deutsche Bundesbank.
+//!
+//! All the methods are implemented but 12, which is not assigned: checking
+//! an account number against it is an error.
+
+use crate::code::Code;
+use crate::error::{check_digits, Error, Reason};
+use crate::result::Result;
+use arraystring::typenum::U2;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `ACCOUNT_LENGTH` is the length of an account number (Kontonummer), left
+/// padded with zeros.
+pub const ACCOUNT_LENGTH: usize = 10;
+
+/// `Digits` are the digits of an account number, position 1 being the left
+/// most one.
+type Digits = [u32; ACCOUNT_LENGTH];
+
+/// [`Ten`] is the way a modulus 11 method handles a check digit of 10.
+#[derive(Debug, Clone, Copy)]
+enum Ten {
+    /// The check digit is 0.
+    Zero,
+    /// The account number is invalid.
+    Invalid,
+    /// The check digit is the given digit.
+    Digit(u32),
+    /// The check digit is the digit before it.
+    Repeat,
+}
+
+/// [`Rule`] is the way the check digit is computed from the weighted digits.
+#[derive(Debug, Clone, Copy)]
+enum Rule {
+    /// Modulus 7 of the sum of the products, subtracted from 7.
+    Mod7,
+    /// Modulus 7 of the sum of the cross sums of the products, subtracted
+    /// from 7.
+    Mod7Cross,
+    /// Modulus 9 of the sum of the products, subtracted from 9.
+    Mod9,
+    /// Modulus 10 of the sum of the products.
+    Mod10,
+    /// Modulus 10 of the sum of the cross sums of the products.
+    Mod10Cross,
+    /// Modulus 10 of the sum of the last digits of the products.
+    Mod10Last,
+    /// Modulus 10 of the sum of the cross sums of the products, reduced to
+    /// a single digit by cross sums.
+    Mod10Reduced,
+    /// Modulus 10 of the sum of the digits transformed by the table of
+    /// their position (M10H), the weights being ignored.
+    Mod10Table,
+    /// Modulus 11 of the sum of the products, subtracted from 11.
+    Mod11(Ten),
+    /// Modulus 11 of the sum of the cross sums of the products, subtracted
+    /// from 11.
+    Mod11Cross(Ten),
+    /// Modulus 11 of the sum of the products, as is.
+    Remainder(Ten),
+    /// Modulus 11 of the sum of the cross sums of the products, less one,
+    /// subtracted from 10.
+    Decremented,
+}
+
+/// [`Scheme`] weights the digits `first` to `last`, from right to left, to
+/// compute the check digit at position `check`.
+#[derive(Debug, Clone, Copy)]
+struct Scheme {
+    first: usize,
+    last: usize,
+    check: usize,
+    weights: &'static [u32],
+    rule: Rule,
+}
+
+/// `TRANSFORMATION` is the table of [`Rule::Mod10Table`], a row per
+/// position from the right, modulo 4.
+const TRANSFORMATION: [[u32; 10]; 4] = [
+    [0, 1, 5, 9, 3, 7, 4, 8, 2, 6],
+    [0, 1, 7, 6, 9, 8, 3, 2, 5, 4],
+    [0, 1, 8, 4, 6, 2, 9, 5, 7, 3],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+];
+
+/// `cross_sum` returns the sum of the digits of a number.
+fn cross_sum(mut n: u32) -> u32 {
+    let mut res = 0;
+
+    while n > 0 {
+        res += n % 10;
+        n /= 10;
+    }
+
+    res
+}
+
+/// `scheme` returns a [`Scheme`], checking the usual check digit (position
+/// 10) from the digits 1 to 9.
+const fn scheme(weights: &'static [u32], rule: Rule) -> Scheme {
+    Scheme {
+        first: 1,
+        last: 9,
+        check: 10,
+        weights,
+        rule,
+    }
+}
+
+/// `partial` returns a [`Scheme`] on the given positions.
+const fn partial(
+    first: usize,
+    last: usize,
+    check: usize,
+    weights: &'static [u32],
+    rule: Rule,
+) -> Scheme {
+    Scheme {
+        first,
+        last,
+        check,
+        weights,
+        rule,
+    }
+}
+
+impl Scheme {
+    /// `sum` returns the sum of the weighted digits.
+    fn sum(&self, digits: &Digits) -> u32 {
+        (self.first..=self.last)
+            .rev()
+            .zip(self.weights.iter().cycle())
+            .enumerate()
+            .map(|(i, (position, weight))| {
+                let digit = digits[position - 1];
+                let product = digit * weight;
+
+                match self.rule {
+                    Rule::Mod7Cross
+                    | Rule::Mod10Cross
+                    | Rule::Mod10Reduced
+                    | Rule::Mod11Cross(_)
+                    | Rule::Decremented => cross_sum(product),
+                    Rule::Mod10Last => product % 10,
+                    Rule::Mod10Table => TRANSFORMATION[i % 4][digit as usize],
+                    _ => product,
+                }
+            })
+            .sum()
+    }
+
+    /// `is_valid` checks the check digit of an account number.
+    fn is_valid(&self, digits: &Digits) -> bool {
+        let check = digits[self.check - 1];
+        let sum = self.sum(digits);
+        let ten = |ten: Ten| match ten {
+            Ten::Zero => check == 0,
+            Ten::Invalid => false,
+            Ten::Digit(digit) => check == digit,
+            Ten::Repeat => digits[self.check - 2] == check,
+        };
+
+        match self.rule {
+            Rule::Mod7 | Rule::Mod7Cross => (7 - sum % 7) % 7 == check,
+            Rule::Mod9 => (9 - sum % 9) % 9 == check,
+            Rule::Mod10 | Rule::Mod10Cross | Rule::Mod10Last | Rule::Mod10Table => {
+                (10 - sum % 10) % 10 == check
+            }
+            Rule::Mod10Reduced => {
+                let mut sum = sum;
+                while sum > 9 {
+                    sum = cross_sum(sum);
+                }
+
+                (10 - sum) % 10 == check
+            }
+            Rule::Mod11(t) | Rule::Mod11Cross(t) => match sum % 11 {
+                0 => check == 0,
+                1 => ten(t),
+                r => 11 - r == check,
+            },
+            Rule::Remainder(t) => match sum % 11 {
+                10 => ten(t),
+                r => r == check,
+            },
+            Rule::Decremented => match (sum + 10) % 11 {
+                0 => check == 0,
+                r => 10 - r == check,
+            },
+        }
+    }
+}
+
+/// `MOD10_21` is the scheme of method 00.
+const MOD10_21: Scheme = scheme(&[2, 1], Rule::Mod10Cross);
+
+/// `MOD10_371` is the scheme of method 01.
+const MOD10_371: Scheme = scheme(&[3, 7, 1], Rule::Mod10);
+
+/// `LEDGER` is the scheme of the ledger accounts (Sachkonten) of several
+/// methods, from the digits 3 to 9.
+const LEDGER: Scheme = partial(3, 9, 10, &[2, 3, 4, 5, 6, 7, 8], Rule::Mod11(Ten::Zero));
+
+/// `ESER_WEIGHTS` are the weights of the ESER account numbers of method 52,
+/// from right to left.
+const ESER_WEIGHTS: [u32; 12] = [2, 4, 8, 5, 10, 9, 7, 3, 6, 1, 2, 4];
+
+/// `C6_CONSTANTS` are the constants of method C6, by first digit.
+const C6_CONSTANTS: [u64; 10] = [
+    4_451_970, 4_451_981, 4_451_992, 4_451_993, 4_344_992, 4_344_990, 4_344_991, 5_499_570,
+    4_451_994, 5_499_579,
+];
+
+/// `shift` shifts the account number to the left, e.g. when the sub
+/// account number has been omitted.
+fn shift(digits: &Digits, n: usize) -> Digits {
+    let mut res = [0; ACCOUNT_LENGTH];
+    res[..ACCOUNT_LENGTH - n].copy_from_slice(&digits[n..]);
+
+    res
+}
+
+/// `number` returns the value of the account number.
+fn number(digits: &Digits) -> u64 {
+    digits.iter().fold(0, |n, d| n * 10 + u64::from(*d))
+}
+
+/// `length` returns the length of the account number, without its leading
+/// zeros.
+fn length(digits: &Digits) -> usize {
+    ACCOUNT_LENGTH - digits.iter().take_while(|d| **d == 0).count()
+}
+
+/// `method` checks the account number against another method, for the
+/// methods made of variants of other ones.
+fn method(code: &str, digits: &Digits) -> bool {
+    CheckMethod::from(code)
+        .verify(None, digits)
+        .unwrap_or_default()
+}
+
+/// `ledger` checks the ledger accounts (Sachkonten) of methods 51, 73, 80,
+/// 81, 84, 86, 87 and A8, the third digit being 9.
+fn ledger(digits: &Digits) -> bool {
+    LEDGER.is_valid(digits) || method("10", digits)
+}
+
+/// `extended` checks the methods 61 and 65: the digits 9 and 10 are part of
+/// the sum when the digit 9 is the marker.
+fn extended(digits: &Digits, marker: u32) -> bool {
+    let mut sum = partial(1, 7, 8, &[2, 1], Rule::Mod10Cross).sum(digits);
+    if digits[8] == marker {
+        sum += cross_sum(digits[8]) + cross_sum(digits[9] * 2);
+    }
+
+    (10 - sum % 10) % 10 == digits[7]
+}
+
+/// `prefixed` checks the account number from the digit `first` as method
+/// 00 does, the constant being put in front of it.
+fn prefixed(constant: u64, digits: &Digits, first: usize) -> bool {
+    let mut sum = partial(first, 9, 10, &[2, 1], Rule::Mod10Cross).sum(digits);
+    let mut weight = [2, 1][(ACCOUNT_LENGTH - first) % 2];
+    let mut constant = constant;
+
+    while constant > 0 {
+        sum += cross_sum((constant % 10) as u32 * weight);
+        weight = 3 - weight;
+        constant /= 10;
+    }
+
+    (10 - sum % 10) % 10 == digits[9]
+}
+
+/// `bank_digits` returns the digits of a bank code, none if it is not made
+/// of 8 digits.
+fn bank_digits(bank_code: &str) -> Option<Vec<u32>> {
+    let bank: Vec<u32> = bank_code.chars().filter_map(|c| c.to_digit(10)).collect();
+
+    Some(bank).filter(|bank| bank.len() == 8 && bank_code.len() == 8)
+}
+
+/// `eser` checks an 8 digits account number against the ESER number built
+/// from the bank code, as method 52 does.
+fn eser(bank_code: &str, digits: &Digits) -> bool {
+    let bank = match bank_digits(bank_code) {
+        Some(bank) if digits[..2] == [0, 0] && digits[2] != 0 => bank,
+        _ => return false,
+    };
+
+    let mut eser = bank[4..].to_vec();
+    eser.push(digits[2]);
+    let check = eser.len();
+    eser.push(0);
+    eser.extend(digits[4..].iter().skip_while(|d| **d == 0));
+
+    eser_check(&eser, check, digits[3])
+}
+
+/// `eser_9` checks a 9 digits account number against the ESER number built
+/// from the bank code, as method 53 does: the digits 5, 6 and 8 of the
+/// bank code are mixed with the digits 2 and 3 of the account number.
+fn eser_9(bank_code: &str, digits: &Digits) -> bool {
+    let bank = match bank_digits(bank_code) {
+        Some(bank) if digits[0] == 0 && digits[1] != 0 => bank,
+        _ => return false,
+    };
+
+    let mut eser = vec![bank[4], bank[5], digits[2], bank[7], digits[1]];
+    let check = eser.len();
+    eser.push(0);
+    eser.extend(digits[4..].iter().skip_while(|d| **d == 0));
+
+    eser_check(&eser, check, digits[3])
+}
+
+/// `eser_check` checks the check digit of an ESER number, at the index
+/// `check` and set to 0, the weighted sum having to leave 10 modulo 11.
+fn eser_check(eser: &[u32], check: usize, digit: u32) -> bool {
+    let weight = ESER_WEIGHTS[eser.len() - 1 - check];
+    let sum: u32 = eser
+        .iter()
+        .rev()
+        .zip(ESER_WEIGHTS.iter())
+        .map(|(d, w)| d * w)
+        .sum();
+
+    (0..10).find(|p| (sum + p * weight) % 11 == 10) == Some(digit)
+}
+
+/// `method_87a` checks the variant A of method 87, as published in pseudo
+/// code.
+fn method_87a(digits: &Digits) -> bool {
+    let mut i = 4;
+    while i < 10 && digits[i - 1] == 0 {
+        i += 1;
+    }
+
+    let (mut c2, mut d2, mut a5) = (i % 2, 0, 0_i32);
+    while i < 10 {
+        let x = match digits[i - 1] {
+            0 => 5,
+            1 => 6,
+            5 => 10,
+            6 => 1,
+            d => d as i32,
+        };
+
+        if c2 == d2 {
+            if x <= 5 {
+                c2 = 1 - c2;
+                a5 += x;
+            } else if c2 == 0 {
+                c2 = 1;
+                d2 = 1;
+                a5 += 6 - (x - 6);
+            } else {
+                c2 = 0;
+                d2 = 0;
+                a5 += x;
+            }
+        } else if x <= 5 {
+            c2 = 1 - c2;
+            a5 -= x;
+        } else if c2 == 0 {
+            c2 = 1;
+            d2 = 0;
+            a5 += -6 + (x - 6);
+        } else {
+            c2 = 0;
+            d2 = 1;
+            a5 -= x;
+        }
+
+        i += 1;
+    }
+
+    let table = if d2 == 0 {
+        [0, 4, 3, 2, 6]
+    } else {
+        [7, 1, 5, 9, 8]
+    };
+    let p = table[a5.rem_euclid(5) as usize];
+
+    p == digits[9] || (digits[3] == 0 && (p + 5) % 10 == digits[9])
+}
+
+/// `check_method` checks a check digit method code, returning why it is
+/// invalid.
+fn check_method(code: &str) -> Result<()> {
+    Code::<U2>::check(code).map_err(|e| e.within("check digit method", code, 0))?;
+    check_digits("check digit method", &code[1..], 1, 1)
+        .map_err(|e| e.within("check digit method", code, 1))
+}
+
+/// [`CheckMethod`] is a Bundesbank check digit method, "00" to "E4", as
+/// assigned to each bank code in the BLZ file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CheckMethod(Code<U2>);
+
+impl CheckMethod {
+    pub fn new(code: &str) -> Result<CheckMethod> {
+        check_method(code)?;
+
+        Ok(CheckMethod(Code::<U2>::from(code)))
+    }
+
+    /// `as_str` returns the `CheckMethod` as a string slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `is_valid` returns if a provided code is valid
+    /// by checking length and kind of chars used: a digit or
+    /// an upper-case letter, then a digit.
+    pub fn is_valid(code: &str) -> bool {
+        check_method(code).is_ok()
+    }
+
+    /// `validate` validates the [`CheckMethod`].
+    pub fn validate(&self) -> Result<()> {
+        check_method(self.as_str())
+    }
+
+    /// `scheme` returns the scheme of the methods made of a single one.
+    fn scheme(&self) -> Option<Scheme> {
+        let res = match self.as_str() {
+            "00" => MOD10_21,
+            "01" => MOD10_371,
+            "02" => scheme(&[2, 3, 4, 5, 6, 7, 8, 9], Rule::Mod11(Ten::Invalid)),
+            "03" => scheme(&[2, 1], Rule::Mod10),
+            "04" => scheme(&[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Invalid)),
+            "05" => scheme(&[7, 3, 1], Rule::Mod10),
+            "06" => scheme(&[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Zero)),
+            "07" => scheme(&[2, 3, 4, 5, 6, 7, 8, 9, 10], Rule::Mod11(Ten::Invalid)),
+            "10" => scheme(&[2, 3, 4, 5, 6, 7, 8, 9, 10], Rule::Mod11(Ten::Zero)),
+            "11" => scheme(&[2, 3, 4, 5, 6, 7, 8, 9, 10], Rule::Mod11(Ten::Digit(9))),
+            "14" => partial(4, 9, 10, &[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Invalid)),
+            "15" => partial(6, 9, 10, &[2, 3, 4, 5], Rule::Mod11(Ten::Zero)),
+            "16" => scheme(&[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Repeat)),
+            "17" => partial(2, 7, 8, &[2, 1], Rule::Decremented),
+            "18" => scheme(&[3, 9, 7, 1], Rule::Mod10),
+            "19" => scheme(&[2, 3, 4, 5, 6, 7, 8, 9, 1], Rule::Mod11(Ten::Zero)),
+            "20" => scheme(&[2, 3, 4, 5, 6, 7, 8, 9, 3], Rule::Mod11(Ten::Zero)),
+            "21" => scheme(&[2, 1], Rule::Mod10Reduced),
+            "22" => scheme(&[3, 1], Rule::Mod10Last),
+            "23" => partial(1, 6, 7, &[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Repeat)),
+            "28" => partial(1, 7, 8, &[2, 3, 4, 5, 6, 7, 8], Rule::Mod11(Ten::Zero)),
+            "29" => scheme(&[1], Rule::Mod10Table),
+            "30" => scheme(&[2, 1, 2, 1, 0, 0, 0, 0, 2], Rule::Mod10),
+            "31" => scheme(&[9, 8, 7, 6, 5, 4, 3, 2, 1], Rule::Remainder(Ten::Invalid)),
+            "32" => partial(4, 9, 10, &[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Zero)),
+            "33" => partial(5, 9, 10, &[2, 3, 4, 5, 6], Rule::Mod11(Ten::Zero)),
+            "34" => partial(1, 7, 8, &[2, 4, 8, 5, 10, 9, 7], Rule::Mod11(Ten::Zero)),
+            "35" => scheme(&[2, 3, 4, 5, 6, 7, 8, 9, 10], Rule::Remainder(Ten::Repeat)),
+            "36" => partial(6, 9, 10, &[2, 4, 8, 5], Rule::Mod11(Ten::Zero)),
+            "37" => partial(5, 9, 10, &[2, 4, 8, 5, 10], Rule::Mod11(Ten::Zero)),
+            "38" => partial(4, 9, 10, &[2, 4, 8, 5, 10, 9], Rule::Mod11(Ten::Zero)),
+            "39" => partial(3, 9, 10, &[2, 4, 8, 5, 10, 9, 7], Rule::Mod11(Ten::Zero)),
+            "40" => scheme(&[2, 4, 8, 5, 10, 9, 7, 3, 6], Rule::Mod11(Ten::Zero)),
+            "42" => partial(2, 9, 10, &[2, 3, 4, 5, 6, 7, 8, 9], Rule::Mod11(Ten::Zero)),
+            "43" => scheme(&[1, 2, 3, 4, 5, 6, 7, 8, 9], Rule::Mod10),
+            "44" => partial(5, 9, 10, &[2, 4, 8, 5, 10], Rule::Mod11(Ten::Zero)),
+            "46" => partial(3, 7, 8, &[2, 3, 4, 5, 6], Rule::Mod11(Ten::Zero)),
+            "47" => partial(4, 8, 9, &[2, 3, 4, 5, 6], Rule::Mod11(Ten::Zero)),
+            "48" => partial(3, 8, 9, &[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Zero)),
+            "55" => scheme(&[2, 3, 4, 5, 6, 7, 8, 7, 8], Rule::Mod11(Ten::Zero)),
+            "58" => partial(5, 9, 10, &[2, 3, 4, 5, 6], Rule::Mod11(Ten::Invalid)),
+            "60" => partial(3, 9, 10, &[2, 1], Rule::Mod10Cross),
+            "62" => partial(3, 7, 8, &[2, 1], Rule::Mod10Cross),
+            "64" => partial(1, 6, 7, &[2, 4, 8, 5, 10, 9], Rule::Mod11(Ten::Zero)),
+            "67" => partial(1, 7, 8, &[2, 1], Rule::Mod10Cross),
+            "71" => partial(2, 7, 10, &[1, 2, 3, 4, 5, 6], Rule::Mod11(Ten::Digit(1))),
+            "72" => partial(4, 9, 10, &[2, 1], Rule::Mod10Cross),
+            "92" => partial(4, 9, 10, &[3, 7, 1], Rule::Mod10),
+            "94" => scheme(&[1, 2], Rule::Mod10Cross),
+            _ => return None,
+        };
+
+        Some(res)
+    }
+
+    /// `is_supported` returns if the method is implemented: all of them
+    /// but 12, which is not assigned.
+    pub fn is_supported(&self) -> bool {
+        self.as_str() <= "E4" && self.as_str() != "12"
+    }
+
+    /// `check` checks an account number (up to 10 digits) against the
+    /// method. An unsupported method is an error, not a success, as is a
+    /// method needing the bank code, see [`CheckMethod::check_in_bank`].
+    pub fn check(&self, account: &str) -> Result<()> {
+        self.check_account(None, account)
+    }
+
+    /// `check_in_bank` checks an account number (up to 10 digits) of the
+    /// given bank code against the method.
+    pub fn check_in_bank(&self, bank_code: &str, account: &str) -> Result<()> {
+        self.check_account(Some(bank_code), account)
+    }
+
+    /// `check_account` checks an account number, with its bank code if
+    /// any.
+    fn check_account(&self, bank_code: Option<&str>, account: &str) -> Result<()> {
+        check_digits("account number", account, 1, ACCOUNT_LENGTH)?;
+
+        let mut digits = [0; ACCOUNT_LENGTH];
+        for (i, c) in account.chars().rev().enumerate() {
+            digits[ACCOUNT_LENGTH - 1 - i] = c.to_digit(10).unwrap_or_default();
+        }
+
+        if !self.verify(bank_code, &digits)? {
+            return Err(Error::invalid(
+                "account number",
+                account,
+                Reason::Format(format!("fails the check digit method {}", self.as_str())),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `verify` returns if the digits of an account number are valid.
+    fn verify(&self, bank_code: Option<&str>, digits: &Digits) -> Result<bool> {
+        let bank_code = || {
+            bank_code.ok_or_else(|| {
+                Error::MissingElement(format!(
+                    "bank code for check digit method {}",
+                    self.as_str()
+                ))
+            })
+        };
+        let d = digits;
+
+        let valid = match (self.as_str(), self.scheme()) {
+            (_, Some(scheme)) => scheme.is_valid(d),
+            ("08", _) => number(d) < 60_000 || MOD10_21.is_valid(d),
+            ("09", _) => true,
+            ("13", _) => {
+                let scheme = partial(2, 7, 8, &[2, 1], Rule::Mod10Cross);
+                scheme.is_valid(d) || scheme.is_valid(&shift(d, 2))
+            }
+            ("24", _) => {
+                let mut d = *d;
+                match d[0] {
+                    3..=6 => d[0] = 0,
+                    9 => d[..3].copy_from_slice(&[0; 3]),
+                    _ => {}
+                }
+
+                let sum: u32 = d[..9]
+                    .iter()
+                    .skip_while(|d| **d == 0)
+                    .zip([1, 2, 3].iter().cycle())
+                    .map(|(d, w)| (d * w + w) % 11)
+                    .sum();
+
+                sum % 10 == d[9]
+            }
+            ("25", _) => {
+                let scheme = partial(2, 9, 10, &[2, 3, 4, 5, 6, 7, 8, 9], Rule::Mod11(Ten::Zero));
+                scheme.is_valid(d) && (scheme.sum(d) % 11 != 1 || matches!(d[1], 8 | 9))
+            }
+            ("26", _) => {
+                let d = if d[..2] == [0, 0] { shift(d, 2) } else { *d };
+
+                partial(1, 7, 8, &[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Zero)).is_valid(&d)
+            }
+            ("27", _) if number(d) < 1_000_000_000 => MOD10_21.is_valid(d),
+            ("27", _) => method("29", d),
+            ("41", _) if d[3] == 9 => partial(4, 9, 10, &[2, 1], Rule::Mod10Cross).is_valid(d),
+            ("41", _) => MOD10_21.is_valid(d),
+            ("45", _) => d[0] == 0 || d[4] == 1 || MOD10_21.is_valid(d),
+            ("49", _) => MOD10_21.is_valid(d) || MOD10_371.is_valid(d),
+            ("50", _) => {
+                let scheme = partial(1, 6, 7, &[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Zero));
+                scheme.is_valid(d) || (d[..3] == [0, 0, 0] && scheme.is_valid(&shift(d, 3)))
+            }
+            ("51", _) if d[2] == 9 => ledger(d),
+            ("51", _) => {
+                method("32", d)
+                    || method("33", d)
+                    || (!matches!(d[9], 7..=9)
+                        && (method("72", d)
+                            || partial(5, 9, 10, &[2, 3, 4, 5, 6], Rule::Mod7).is_valid(d)))
+            }
+            ("52", _) | ("53", _) | ("C0", _) if d[0] == 9 => method("20", d),
+            ("52", _) => eser(bank_code()?, d),
+            ("53", _) => eser_9(bank_code()?, d),
+            ("54", _) => {
+                let sum = partial(3, 9, 10, &[2, 3, 4, 5, 6, 7, 2], Rule::Mod10).sum(d);
+                d[..2] == [4, 9] && sum % 11 > 1 && 11 - sum % 11 == d[9]
+            }
+            ("56", _) => match (d[0], scheme(&[2, 3, 4, 5, 6, 7], Rule::Mod10).sum(d) % 11) {
+                (9, 0) => d[9] == 8,
+                (9, 1) => d[9] == 7,
+                (_, 0) | (_, 1) => false,
+                (_, r) => 11 - r == d[9],
+            },
+            ("57", _) => match d[0] * 10 + d[1] {
+                0 => false,
+                51 | 55 | 61 | 64..=66 | 70 | 73 | 75..=82 | 88 | 94 | 95 => {
+                    d[..6] == [7; 6] || d[..6] == [8; 6] || method("94", d)
+                }
+                32..=39
+                | 41..=49
+                | 52..=54
+                | 56..=60
+                | 62
+                | 63
+                | 67..=69
+                | 71
+                | 72
+                | 74
+                | 83..=87
+                | 89
+                | 90
+                | 92
+                | 93
+                | 96..=98 => {
+                    let moved = [d[0], d[1], d[3], d[4], d[5], d[6], d[7], d[8], d[9], d[2]];
+                    method("94", &moved)
+                }
+                40 | 50 | 91 | 99 => true,
+                _ => {
+                    number(d) == 185_125_434
+                        || (matches!(d[2] * 10 + d[3], 1..=12)
+                            && d[6] * 100 + d[7] * 10 + d[8] < 500)
+                }
+            },
+            ("59", _) => number(d) < 100_000_000 || MOD10_21.is_valid(d),
+            ("61", _) => extended(d, 8),
+            ("63", _) => {
+                let scheme = partial(2, 7, 8, &[2, 1], Rule::Mod10Cross);
+                d[0] == 0
+                    && (scheme.is_valid(d)
+                        || (d[..3] == [0, 0, 0] && scheme.is_valid(&shift(d, 2))))
+            }
+            ("65", _) => extended(d, 9),
+            ("66", _) => {
+                let sum = partial(2, 9, 10, &[2, 3, 4, 5, 6, 0, 0, 7], Rule::Mod10).sum(d);
+                let check = match sum % 11 {
+                    0 => 1,
+                    1 => 0,
+                    r => 11 - r,
+                };
+
+                d[0] == 0 && (d[1] == 9 || check == d[9])
+            }
+            ("68", _) if d[0] != 0 => {
+                d[3] == 9 && partial(4, 9, 10, &[2, 1], Rule::Mod10Cross).is_valid(d)
+            }
+            ("68", _) => {
+                (400_000_000..=499_999_999).contains(&number(d))
+                    || MOD10_21.is_valid(d)
+                    || scheme(&[2, 1, 2, 1, 2, 0, 0, 1, 2], Rule::Mod10Cross).is_valid(d)
+            }
+            ("69", _) => match number(d) {
+                9_300_000_000..=9_399_999_999 => true,
+                9_700_000_000..=9_799_999_999 => method("29", d),
+                _ => method("28", d) || method("29", d),
+            },
+            ("70", _) if d[3] == 5 || d[3..5] == [6, 9] => method("32", d),
+            ("70", _) => method("06", d),
+            ("73", _) if d[2] == 9 => ledger(d),
+            ("73", _) => {
+                method("72", d)
+                    || partial(5, 9, 10, &[2, 1], Rule::Mod10Cross).is_valid(d)
+                    || partial(5, 9, 10, &[2, 1], Rule::Mod7Cross).is_valid(d)
+            }
+            ("74", _) => {
+                let sum = MOD10_21.sum(d);
+                number(d) >= 10
+                    && (MOD10_21.is_valid(d) || (length(d) == 6 && (5 - sum % 5) % 5 == d[9]))
+            }
+            ("75", _) => match length(d) {
+                6 | 7 => partial(5, 9, 10, &[2, 1], Rule::Mod10Cross).is_valid(d),
+                9 if d[1] == 9 => partial(3, 7, 8, &[2, 1], Rule::Mod10Cross).is_valid(d),
+                9 => partial(2, 6, 7, &[2, 1], Rule::Mod10Cross).is_valid(d),
+                _ => false,
+            },
+            ("76", _) => {
+                let scheme = partial(2, 7, 8, &[2, 3, 4, 5, 6, 7], Rule::Remainder(Ten::Invalid));
+                let valid = |d: &Digits| !matches!(d[0], 1 | 2 | 3 | 5) && scheme.is_valid(d);
+
+                valid(d) || (d[..2] == [0, 0] && valid(&shift(d, 2)))
+            }
+            ("77", _) => {
+                let sum = |weights| partial(6, 10, 10, weights, Rule::Mod10).sum(d);
+                sum(&[1, 2, 3, 4, 5]) % 11 == 0 || sum(&[5, 4, 3, 4, 5]) % 11 == 0
+            }
+            ("78", _) => length(d) == 8 || MOD10_21.is_valid(d),
+            ("79", _) => match d[0] {
+                3..=8 => MOD10_21.is_valid(d),
+                1 | 2 | 9 => partial(1, 8, 9, &[2, 1], Rule::Mod10Cross).is_valid(d),
+                _ => false,
+            },
+            ("80", _) if d[2] == 9 => ledger(d),
+            ("80", _) => {
+                partial(5, 9, 10, &[2, 1], Rule::Mod10Cross).is_valid(d)
+                    || partial(5, 9, 10, &[2, 1], Rule::Mod7Cross).is_valid(d)
+            }
+            ("81", _) if d[2] == 9 => ledger(d),
+            ("81", _) => method("32", d),
+            ("82", _) if d[2..4] == [9, 9] => method("10", d),
+            ("82", _) => method("33", d),
+            ("83", _) | ("85", _) if d[2..4] == [9, 9] => LEDGER.is_valid(d),
+            ("83", _) | ("85", _) => {
+                method("32", d)
+                    || method("33", d)
+                    || partial(5, 9, 10, &[2, 3, 4, 5, 6], Rule::Mod7).is_valid(d)
+            }
+            ("84", _) if d[2] == 9 => ledger(d),
+            ("84", _) => {
+                method("33", d)
+                    || partial(5, 9, 10, &[2, 3, 4, 5, 6], Rule::Mod7).is_valid(d)
+                    || partial(5, 9, 10, &[2, 1], Rule::Mod10Cross).is_valid(d)
+            }
+            ("86", _) if d[2] == 9 => ledger(d),
+            ("86", _) => method("72", d) || method("32", d),
+            ("87", _) if d[2] == 9 => ledger(d),
+            ("87", _) => {
+                method_87a(d)
+                    || method("33", d)
+                    || partial(5, 9, 10, &[2, 3, 4, 5, 6], Rule::Mod7).is_valid(d)
+            }
+            ("88", _) if d[2] == 9 => LEDGER.is_valid(d),
+            ("88", _) => method("32", d),
+            ("89", _) => match length(d) {
+                7 => {
+                    partial(4, 9, 10, &[2, 3, 4, 5, 6, 7], Rule::Mod11Cross(Ten::Zero)).is_valid(d)
+                }
+                8 | 9 => method("10", d),
+                _ => true,
+            },
+            ("90", _) if d[2] == 9 => LEDGER.is_valid(d),
+            ("90", _) => {
+                method("32", d)
+                    || method("33", d)
+                    || partial(5, 9, 10, &[2, 3, 4, 5, 6], Rule::Mod7).is_valid(d)
+                    || partial(5, 9, 10, &[2, 3, 4, 5, 6], Rule::Mod9).is_valid(d)
+                    || partial(5, 9, 10, &[2, 1], Rule::Mod10).is_valid(d)
+                    || partial(4, 9, 10, &[2, 1], Rule::Mod7).is_valid(d)
+            }
+            ("91", _) => {
+                let moved = [d[0], d[1], d[2], d[3], d[4], d[5], d[7], d[8], d[9], d[6]];
+
+                partial(1, 6, 7, &[2, 3, 4, 5, 6, 7], Rule::Mod11(Ten::Zero)).is_valid(d)
+                    || partial(1, 6, 7, &[7, 6, 5, 4, 3, 2], Rule::Mod11(Ten::Zero)).is_valid(d)
+                    || method("10", &moved)
+                    || partial(1, 6, 7, &[2, 4, 8, 5, 10, 9], Rule::Mod11(Ten::Zero)).is_valid(d)
+            }
+            ("93", _) => {
+                let (first, last, check) = if d[..4] == [0; 4] {
+                    (5, 9, 10)
+                } else {
+                    (1, 5, 6)
+                };
+                let weights = &[2, 3, 4, 5, 6];
+
+                partial(first, last, check, weights, Rule::Mod11(Ten::Zero)).is_valid(d)
+                    || partial(first, last, check, weights, Rule::Mod7).is_valid(d)
+            }
+            ("95", _) => match number(d) {
+                1..=1_999_999
+                | 9_000_000..=25_999_999
+                | 396_000_000..=499_999_999
+                | 700_000_000..=799_999_999
+                | 910_000_000..=989_999_999 => true,
+                _ => method("06", d),
+            },
+            ("96", _) => {
+                method("19", d)
+                    || MOD10_21.is_valid(d)
+                    || (1_300_000..=99_399_999).contains(&number(d))
+            }
+            ("97", _) => (number(d) / 10 % 11) as u32 % 10 == d[9],
+            ("98", _) => partial(3, 9, 10, &[3, 1, 7], Rule::Mod10).is_valid(d) || method("32", d),
+            ("99", _) => (396_000_000..=499_999_999).contains(&number(d)) || method("06", d),
+            ("A0", _) => {
+                (100..=999).contains(&number(d))
+                    || partial(5, 9, 10, &[2, 4, 8, 5, 10], Rule::Mod11(Ten::Zero)).is_valid(d)
+            }
+            ("A1", _) => {
+                matches!(length(d), 8 | 10)
+                    && partial(3, 9, 10, &[2, 1], Rule::Mod10Cross).is_valid(d)
+            }
+            ("A2", _) => method("00", d) || method("04", d),
+            ("A3", _) => method("00", d) || method("10", d),
+            ("A4", _) if d[2..4] == [9, 9] => method("33", d) || method("93", d),
+            ("A4", _) => {
+                method("32", d)
+                    || partial(4, 9, 10, &[2, 3, 4, 5, 6, 7], Rule::Mod7).is_valid(d)
+                    || method("93", d)
+            }
+            ("A5", _) => method("00", d) || (d[0] != 9 && method("10", d)),
+            ("A6", _) if d[1] == 8 => method("00", d),
+            ("A6", _) => method("01", d),
+            ("A7", _) => method("00", d) || method("03", d),
+            ("A8", _) if d[2] == 9 => ledger(d),
+            ("A8", _) => method("32", d) || method("72", d),
+            ("A9", _) => method("01", d) || method("06", d),
+            ("B0", _) => match (d[0], d[7]) {
+                (0, _) | (8, _) => false,
+                (_, 1..=3) | (_, 6) => true,
+                _ => method("06", d),
+            },
+            ("B1", _) => method("05", d) || method("01", d) || method("00", d),
+            ("B2", _) if d[0] <= 7 => method("02", d),
+            ("B2", _) => method("00", d),
+            ("B3", _) if d[0] == 9 => method("06", d),
+            ("B3", _) => method("32", d),
+            ("B4", _) if d[0] == 9 => method("00", d),
+            ("B4", _) => method("02", d),
+            ("B5", _) => method("05", d) || (!matches!(d[0], 8 | 9) && method("00", d)),
+            ("B6", _) if d[0] != 0 || (d[1..4] == [2, 6, 9] && d[4] != 0) => method("20", d),
+            ("B6", _) => eser_9(bank_code()?, d),
+            ("B7", _) => match number(d) {
+                1_000_000..=5_999_999 | 700_000_000..=899_999_999 => method("01", d),
+                _ => true,
+            },
+            ("B8", _) => match number(d) {
+                5_100_000_000..=5_999_999_999 | 9_010_000_000..=9_109_999_999 => true,
+                _ => method("20", d) || method("29", d),
+            },
+            ("B9", _) if d[..2] == [0, 0] && d[2] != 0 => {
+                let sum: u32 = (3..=9)
+                    .rev()
+                    .zip([1, 3, 2].iter().cycle())
+                    .map(|(position, w)| (d[position - 1] * w + w) % 11)
+                    .sum();
+
+                sum % 10 == d[9] || (sum + 5) % 10 == d[9]
+            }
+            ("B9", _) if d[..3] == [0, 0, 0] && d[3] != 0 => {
+                let rest = partial(4, 9, 10, &[1, 2, 3, 4, 5, 6], Rule::Mod10).sum(d) % 11;
+                let shifted = if rest + 5 > 10 { rest - 5 } else { rest + 5 };
+
+                rest == d[9] || shifted == d[9]
+            }
+            ("B9", _) => false,
+            ("C0", _) if d[..2] == [0, 0] && d[2] != 0 => eser(bank_code()?, d) || method("20", d),
+            ("C0", _) => method("20", d),
+            ("C1", _) if d[0] == 5 => scheme(&[1, 2], Rule::Decremented).is_valid(d),
+            ("C1", _) => method("17", d),
+            ("C2", _) => method("22", d) || method("00", d),
+            ("C3", _) if d[0] == 9 => method("58", d),
+            ("C3", _) => method("00", d),
+            ("C4", _) if d[0] == 9 => method("58", d),
+            ("C4", _) => method("15", d),
+            ("C5", _) => match (length(d), d[0], d[1]) {
+                (6, _, _) => {
+                    (1..=8).contains(&d[4])
+                        && partial(5, 9, 10, &[2, 1], Rule::Mod10Cross).is_valid(d)
+                }
+                (9, _, _) => (1..=8).contains(&d[1]) && method("75", d),
+                (10, 1, _) | (10, 4..=6, _) | (10, 9, _) => method("29", d),
+                (10, 3, _) => method("00", d),
+                (10, 7, 0) | (10, 8, 5) => true,
+                (8, _, _) => matches!(d[2], 3..=5),
+                _ => false,
+            },
+            ("C6", _) => prefixed(C6_CONSTANTS[d[0] as usize], d, 2),
+            ("C7", _) => method("63", d) || method("06", d),
+            ("C8", _) => method("00", d) || method("04", d) || method("07", d),
+            ("C9", _) => method("00", d) || method("07", d),
+            ("D0", _) => d[..2] == [5, 7] || method("20", d),
+            ("D1", _) => d[0] != 8 && prefixed(436_338, d, 1),
+            ("D2", _) => method("95", d) || method("00", d) || method("68", d),
+            ("D3", _) => method("00", d) || method("27", d),
+            ("D4", _) => d[0] != 0 && prefixed(428_259, d, 1),
+            ("D5", _) if d[2..4] == [9, 9] => LEDGER.is_valid(d),
+            ("D5", _) => {
+                let weights = &[2, 3, 4, 5, 6, 7];
+
+                method("32", d)
+                    || partial(4, 9, 10, weights, Rule::Mod7).is_valid(d)
+                    || partial(4, 9, 10, weights, Rule::Mod10).is_valid(d)
+            }
+            ("D6", _) => method("07", d) || method("03", d) || method("00", d),
+            ("D7", _) => MOD10_21.sum(d) % 10 == d[9],
+            ("D8", _) if d[0] != 0 => method("00", d),
+            ("D8", _) => d[1] == 0 && d[2] != 0,
+            ("D9", _) => method("00", d) || method("10", d) || method("18", d),
+            ("E0", _) => (10 - (MOD10_21.sum(d) + 7) % 10) % 10 == d[9],
+            ("E1", _) => {
+                let ascii = d.map(|d| d + u32::from(b'0'));
+                let scheme = scheme(&[1, 2, 3, 4, 5, 6, 11, 10, 9], Rule::Mod10);
+
+                scheme.sum(&ascii) % 11 == d[9]
+            }
+            ("E2", _) => d[0] <= 5 && prefixed(438_320, d, 1),
+            ("E3", _) => method("00", d) || method("21", d),
+            ("E4", _) => method("02", d) || method("00", d),
+            _ => {
+                return Err(Error::invalid(
+                    "check digit method",
+                    self.as_str(),
+                    Reason::Format("not supported".into()),
+                ))
+            }
+        };
+
+        Ok(valid)
+    }
+}
+
+impl FromStr for CheckMethod {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        CheckMethod::new(code)
+    }
+}
+
+impl From<&str> for CheckMethod {
+    fn from(code: &str) -> Self {
+        CheckMethod::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for CheckMethod {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(self.0.as_str(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for CheckMethod {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{CheckMethod, Result};
+
+    #[test]
+    fn check() -> Result<()> {
+        const VALID_ACCOUNTS: &[(&str, &str)] = &[
+            ("00", "9290701"),
+            ("00", "539290858"),
+            ("00", "1501824"),
+            ("01", "1234567899"),
+            ("06", "94012341"),
+            ("09", "1234567890"),
+            ("13", "0532013000"),
+            ("24", "138301"),
+            ("24", "1306118605"),
+            ("24", "3307118608"),
+            ("24", "9307118603"),
+            ("27", "2847169488"),
+            ("29", "3145863029"),
+            ("31", "1000000524"),
+            ("31", "1000000583"),
+            ("51", "0001156071"),
+            ("51", "0001156136"),
+            ("51", "0000156078"),
+            ("51", "0199100002"),
+            ("51", "0099100010"),
+            ("51", "3199204090"),
+            ("54", "4912345674"),
+            ("56", "0290545005"),
+            ("56", "9718304037"),
+            ("57", "7500021766"),
+            ("57", "9400001734"),
+            ("57", "3251080371"),
+            ("57", "3891234567"),
+            ("57", "3001000000"),
+            ("57", "0185125434"),
+            ("61", "2063099200"),
+            ("61", "0260760481"),
+            ("63", "0123456600"),
+            ("63", "0001234566"),
+            ("65", "1234567400"),
+            ("65", "1234567590"),
+            ("66", "100154508"),
+            ("66", "101154508"),
+            ("66", "100154516"),
+            ("66", "101154516"),
+            ("68", "8889654328"),
+            ("68", "987654324"),
+            ("68", "987654328"),
+            ("68", "400000000"),
+            ("69", "9721134869"),
+            ("69", "1234567900"),
+            ("69", "1234567006"),
+            ("70", "1234567892"),
+            ("70", "0001234560"),
+            ("71", "7101234007"),
+            ("72", "1234567897"),
+            ("73", "0003503398"),
+            ("73", "0003503391"),
+            ("73", "0003503392"),
+            ("73", "0001340966"),
+            ("73", "2599100002"),
+            ("74", "1016"),
+            ("74", "26260"),
+            ("74", "242243"),
+            ("74", "242248"),
+            ("74", "18002113"),
+            ("74", "1821200043"),
+            ("75", "0001234567"),
+            ("75", "0000123455"),
+            ("75", "0000567891"),
+            ("76", "0006543200"),
+            ("76", "9012345600"),
+            ("76", "0001234560"),
+            ("77", "1234567895"),
+            ("78", "12345678"),
+            ("78", "0123456782"),
+            ("79", "4912345677"),
+            ("79", "4901234577"),
+            ("80", "4912345676"),
+            ("80", "1234567891"),
+            ("81", "0001234560"),
+            ("82", "1234567892"),
+            ("82", "0001234567"),
+            ("83", "4901234575"),
+            ("83", "0001234560"),
+            ("84", "4901234575"),
+            ("84", "9123456780"),
+            ("85", "4901234579"),
+            ("86", "9912345676"),
+            ("86", "5000123455"),
+            ("87", "0000000406"),
+            ("87", "0000051768"),
+            ("87", "0010701590"),
+            ("87", "0010720185"),
+            ("87", "0000100005"),
+            ("87", "0000393814"),
+            ("87", "0000950360"),
+            ("88", "1234567897"),
+            ("89", "1098506"),
+            ("89", "32028008"),
+            ("89", "218433000"),
+            ("89", "123456"),
+            ("90", "0001975641"),
+            ("90", "0001988654"),
+            ("90", "0000654321"),
+            ("90", "0000677747"),
+            ("90", "0000840507"),
+            ("90", "0000996663"),
+            ("90", "0000666034"),
+            ("90", "0099100002"),
+            ("90", "0004923250"),
+            ("90", "0003865960"),
+            ("91", "2974118000"),
+            ("91", "2974117000"),
+            ("91", "8840019000"),
+            ("91", "8840012000"),
+            ("92", "1234567893"),
+            ("93", "6714790000"),
+            ("93", "0000671479"),
+            ("93", "1277910000"),
+            ("93", "0000127791"),
+            ("94", "1234567893"),
+            ("95", "1234567892"),
+            ("95", "0000001234"),
+            ("96", "0000254100"),
+            ("96", "9421000009"),
+            ("96", "0000000208"),
+            ("96", "0101115152"),
+            ("96", "0301204301"),
+            ("97", "24010019"),
+            ("98", "9619439213"),
+            ("98", "3009800016"),
+            ("98", "9619509976"),
+            ("98", "5989800173"),
+            ("98", "9619319999"),
+            ("98", "6719430018"),
+            ("99", "1234567892"),
+            ("99", "0400000000"),
+            ("A0", "521003287"),
+            ("A0", "54500"),
+            ("A0", "3287"),
+            ("A0", "18761"),
+            ("A0", "28290"),
+            ("A1", "0010030005"),
+            ("A1", "0010030997"),
+            ("A1", "1010030054"),
+            ("A2", "3456789019"),
+            ("A2", "5678901231"),
+            ("A2", "3456789012"),
+            ("A3", "1234567897"),
+            ("A3", "0123456782"),
+            ("A3", "9876543210"),
+            ("A3", "1234567890"),
+            ("A4", "0004711173"),
+            ("A4", "0007093330"),
+            ("A4", "0004711172"),
+            ("A4", "0001234560"),
+            ("A5", "9941510001"),
+            ("A5", "9961230019"),
+            ("A5", "0000251437"),
+            ("A5", "0007948344"),
+            ("A6", "800048548"),
+            ("A6", "0855000014"),
+            ("A6", "17"),
+            ("A6", "55300030"),
+            ("A6", "150178033"),
+            ("A6", "600003555"),
+            ("A6", "900291823"),
+            ("A7", "19010008"),
+            ("A7", "19010438"),
+            ("A7", "19010660"),
+            ("A7", "19010876"),
+            ("A7", "209010892"),
+            ("A8", "7436661"),
+            ("A8", "7436670"),
+            ("A8", "1359100"),
+            ("A8", "7436660"),
+            ("A8", "7436678"),
+            ("A8", "0199100004"),
+            ("A9", "5043608"),
+            ("A9", "86725"),
+            ("A9", "504360"),
+            ("A9", "822035"),
+            ("A9", "32577083"),
+            ("B0", "1197423162"),
+            ("B0", "1000000606"),
+            ("B0", "1000000406"),
+            ("B0", "1035791538"),
+            ("B0", "1126939724"),
+            ("B0", "1197423460"),
+            ("B1", "1434253150"),
+            ("B1", "2746315471"),
+            ("B1", "7414398260"),
+            ("B1", "8347251693"),
+            ("B2", "0020012357"),
+            ("B2", "0080012345"),
+            ("B2", "0926801910"),
+            ("B2", "8000990054"),
+            ("B2", "9000481805"),
+            ("B3", "1000000060"),
+            ("B3", "0000000140"),
+            ("B3", "0000000019"),
+            ("B3", "1002798417"),
+            ("B3", "8409915001"),
+            ("B3", "9635000101"),
+            ("B3", "9730200100"),
+            ("B4", "9941510001"),
+            ("B4", "9961230019"),
+            ("B4", "9380027210"),
+            ("B4", "9932290910"),
+            ("B4", "0000251437"),
+            ("B4", "0007948344"),
+            ("B5", "0159006955"),
+            ("B5", "2000123451"),
+            ("B5", "1151043216"),
+            ("B5", "9000939033"),
+            ("B5", "0123456782"),
+            ("B5", "0130098767"),
+            ("B5", "1045000252"),
+            ("B6", "9110000000"),
+            ("B6", "0269876545"),
+            ("B7", "0700001529"),
+            ("B7", "0730000019"),
+            ("B7", "0001001008"),
+            ("B7", "0001057887"),
+            ("B7", "0001007222"),
+            ("B7", "0810011825"),
+            ("B7", "0800107653"),
+            ("B7", "0005922372"),
+            ("B8", "0734192657"),
+            ("B8", "6932875274"),
+            ("B8", "3145863029"),
+            ("B8", "2938692523"),
+            ("B8", "5011654366"),
+            ("B8", "9011200140"),
+            ("B9", "87920187"),
+            ("B9", "41203755"),
+            ("B9", "81069577"),
+            ("B9", "61287958"),
+            ("B9", "58467232"),
+            ("B9", "7125633"),
+            ("B9", "1253657"),
+            ("B9", "4353631"),
+            ("C1", "0446786040"),
+            ("C1", "0478046940"),
+            ("C1", "0701625830"),
+            ("C1", "5432112349"),
+            ("C1", "5543223456"),
+            ("C1", "5876556788"),
+            ("C2", "2394871426"),
+            ("C2", "4218461950"),
+            ("C2", "7352569148"),
+            ("C2", "5127485166"),
+            ("C2", "8738142564"),
+            ("C3", "9294182"),
+            ("C3", "4431276"),
+            ("C3", "19919"),
+            ("C3", "9000420530"),
+            ("C3", "9000010006"),
+            ("C3", "9000577650"),
+            ("C4", "0000000019"),
+            ("C4", "0000292932"),
+            ("C4", "0000094455"),
+            ("C4", "9000420530"),
+            ("C4", "9000010006"),
+            ("C4", "9000577650"),
+            ("C5", "0000301168"),
+            ("C5", "0000302554"),
+            ("C5", "0300020050"),
+            ("C5", "0300566000"),
+            ("C5", "1000061378"),
+            ("C5", "4450164064"),
+            ("C5", "5000000028"),
+            ("C5", "6450008149"),
+            ("C5", "9000100012"),
+            ("C5", "3060188103"),
+            ("C5", "3070402023"),
+            ("C5", "0030000000"),
+            ("C5", "7000000000"),
+            ("C5", "8500000000"),
+            ("C6", "0000065516"),
+            ("C6", "0203178249"),
+            ("C6", "1031405209"),
+            ("C6", "2003455189"),
+            ("C6", "3110150986"),
+            ("C6", "4012660028"),
+            ("C6", "5035105948"),
+            ("C6", "6028426119"),
+            ("C6", "7008199027"),
+            ("C6", "8526080015"),
+            ("C6", "9000430223"),
+            ("C7", "3500022"),
+            ("C7", "38150900"),
+            ("C7", "600103660"),
+            ("C7", "39101181"),
+            ("C7", "94012341"),
+            ("C7", "5073321010"),
+            ("C8", "3456789019"),
+            ("C8", "5678901231"),
+            ("C8", "3456789012"),
+            ("C8", "0022007130"),
+            ("C8", "0123456789"),
+            ("C8", "0552071285"),
+            ("C9", "3456789019"),
+            ("C9", "5678901231"),
+            ("C9", "0123456789"),
+            ("D0", "6100272324"),
+            ("D0", "6100273479"),
+            ("D0", "5700000000"),
+            ("D0", "5799999999"),
+            ("D1", "0082012203"),
+            ("D1", "1452683581"),
+            ("D1", "2129642505"),
+            ("D1", "3002000027"),
+            ("D1", "4230001407"),
+            ("D1", "5000065514"),
+            ("D1", "6001526215"),
+            ("D1", "7126502149"),
+            ("D1", "9000430223"),
+            ("D2", "189912137"),
+            ("D2", "235308215"),
+            ("D2", "4455667784"),
+            ("D2", "1234567897"),
+            ("D2", "51181008"),
+            ("D2", "71214205"),
+            ("D3", "1600169591"),
+            ("D3", "1600189151"),
+            ("D3", "1800084079"),
+            ("D3", "6019937007"),
+            ("D3", "6021354007"),
+            ("D3", "6030642006"),
+            ("D4", "1112048219"),
+            ("D4", "2024601814"),
+            ("D4", "3000005012"),
+            ("D4", "4143406984"),
+            ("D4", "5926485111"),
+            ("D4", "6286304975"),
+            ("D4", "7900256617"),
+            ("D4", "8102228628"),
+            ("D4", "9002364588"),
+            ("D5", "5999718138"),
+            ("D5", "1799222116"),
+            ("D5", "0099632004"),
+            ("D5", "0001234560"),
+            ("D6", "3409"),
+            ("D6", "585327"),
+            ("D6", "1650513"),
+            ("D6", "3601671056"),
+            ("D6", "4402001046"),
+            ("D6", "6100268241"),
+            ("D7", "0500018205"),
+            ("D7", "0230103715"),
+            ("D7", "0301000434"),
+            ("D7", "0330035104"),
+            ("D7", "0420001202"),
+            ("D7", "0134637709"),
+            ("D7", "0201005939"),
+            ("D7", "0602006999"),
+            ("D8", "1403414848"),
+            ("D8", "6800000439"),
+            ("D8", "6899999954"),
+            ("D8", "0010000000"),
+            ("D8", "0099999999"),
+            ("D9", "1234567897"),
+            ("D9", "0123456782"),
+            ("D9", "9876543210"),
+            ("D9", "1234567890"),
+            ("D9", "1100132044"),
+            ("D9", "1100669030"),
+            ("E0", "1234568013"),
+            ("E0", "1534568010"),
+            ("E0", "2610015"),
+            ("E0", "8741013011"),
+            ("E1", "0134211909"),
+            ("E1", "0100041104"),
+            ("E1", "0100054106"),
+            ("E1", "0200025107"),
+            ("E2", "0003831745"),
+            ("E2", "0051330335"),
+            ("E2", "1730773556"),
+            ("E3", "9290701"),
+            ("E3", "539290858"),
+            ("E3", "1501824"),
+            ("E3", "1501832"),
+            ("E4", "1234567897"),
+            ("E4", "0123456782"),
+        ];
+        const WRONG_ACCOUNTS: &[(&str, &str)] = &[
+            ("00", "9290702"),
+            ("00", "9290791"),
+            ("00", "12345678901"),
+            ("00", "12A4"),
+            ("01", "1234567890"),
+            ("06", "94012342"),
+            ("63", "1123456600"),
+            ("56", "9718304038"),
+            ("68", "8889654329"),
+            ("68", "987654325"),
+            ("74", "1011"),
+            ("74", "26265"),
+            ("74", "18002118"),
+            ("74", "6160000024"),
+            ("A0", "521003289"),
+            ("A0", "54502"),
+            ("A0", "3289"),
+            ("A0", "18767"),
+            ("A0", "28297"),
+            ("A1", "0110030005"),
+            ("A1", "0010030998"),
+            ("A1", "0000030005"),
+            ("A3", "6543217890"),
+            ("A3", "0543216789"),
+            ("A7", "209010893"),
+            ("B0", "8137423260"),
+            ("B0", "600000606"),
+            ("B0", "51234309"),
+            ("89", "1098507"),
+            ("89", "32028009"),
+            ("B4", "9941510002"),
+            ("B4", "0000251438"),
+            ("B6", "9111000000"),
+            ("B6", "0269456780"),
+            ("E2", "0003831746"),
+            ("E2", "6003831745"),
+            ("D8", "0100000000"),
+            ("79", "0123456789"),
+        ];
+
+        for (method, account) in VALID_ACCOUNTS.iter() {
+            assert!(
+                CheckMethod::new(method)?.check(account).is_ok(),
+                "{} {}",
+                method,
+                account
+            );
+        }
+
+        for (method, account) in WRONG_ACCOUNTS.iter() {
+            assert!(
+                CheckMethod::new(method)?.check(account).is_err(),
+                "{} {}",
+                method,
+                account
+            );
+        }
+
+        assert!(!CheckMethod::new("12")?.is_supported());
+        assert!(CheckMethod::new("12")?.check("1234567890").is_err());
+        assert!(CheckMethod::new("E4")?.is_supported());
+        assert!(!CheckMethod::new("F0")?.is_supported());
+        assert!(!CheckMethod::is_valid("4E"));
+
+        assert_eq!(
+            serde_json::from_str::<CheckMethod>("\"B6\"").ok(),
+            Some(CheckMethod::new("B6")?)
+        );
+        assert!(serde_json::from_str::<CheckMethod>("\"4E\"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_in_bank() -> Result<()> {
+        for method in ["52", "C0"].iter() {
+            let method = CheckMethod::new(method)?;
+
+            assert!(method.check_in_bank("13051172", "43001500").is_ok());
+            assert!(method.check_in_bank("13051172", "48726458").is_ok());
+            assert!(method.check_in_bank("13051172", "48726459").is_err());
+            assert!(method.check("43001500").is_err());
+        }
+
+        for method in ["53", "B6"].iter() {
+            let method = CheckMethod::new(method)?;
+
+            assert!(method.check_in_bank("80053782", "487310018").is_ok());
+            assert!(method.check_in_bank("80053762", "467310018").is_err());
+            assert!(method.check_in_bank("80053772", "477310018").is_err());
+            assert!(method.check("487310018").is_err());
+        }
+
+        let method = CheckMethod::new("53")?;
+        assert!(method.check_in_bank("16052072", "382432256").is_ok());
+        assert!(method.check_in_bank("16052072", "382432257").is_err());
+
+        let method = CheckMethod::new("C0")?;
+        assert!(method.check_in_bank("13051172", "0082335729").is_ok());
+        assert!(method.check("0734192657").is_ok());
+        assert!(method.check("6932875274").is_ok());
+
+        Ok(())
+    }
+}
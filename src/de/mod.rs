@@ -0,0 +1,7 @@
+//! German payment standards.
+
+pub mod blz;
+pub mod method;
+
+pub use blz::*;
+pub use method::*;
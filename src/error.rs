@@ -1,25 +1,232 @@
+use crate::result::Result;
 use std::error;
 use std::fmt;
 
+/// [`Reason`] tells why a code is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// `Length` is a wrong length, in chars: `min` and `max` are the
+    /// expected lengths, equal for fixed length codes.
+    Length {
+        min: usize,
+        max: usize,
+        actual: usize,
+    },
+    /// `Character` is a char not allowed at its 0-based `index`, with the
+    /// kind of chars `expected` there, e.g. "a digit".
+    Character {
+        index: usize,
+        found: char,
+        expected: &'static str,
+    },
+    /// `Checksum` is a check digit mismatch.
+    Checksum { expected: String, actual: String },
+    /// `Unknown` is a well formed code missing from its code list, e.g. an
+    /// unassigned country code.
+    Unknown,
+    /// `Format` is a code not matching its format, described.
+    Format(String),
+}
+
+impl Reason {
+    /// `shift` moves the index of a `Character` reason by `offset` chars.
+    fn shift(self, offset: usize) -> Reason {
+        match self {
+            Reason::Character {
+                index,
+                found,
+                expected,
+            } => Reason::Character {
+                index: index + offset,
+                found,
+                expected,
+            },
+            reason => reason,
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::Length { min, max, actual } if min == max => {
+                write!(f, "wrong length, expected {} chars, found {}", min, actual)
+            }
+            Reason::Length { min, max, actual } => write!(
+                f,
+                "wrong length, expected {} to {} chars, found {}",
+                min, max, actual
+            ),
+            Reason::Character {
+                index,
+                found,
+                expected,
+            } => write!(
+                f,
+                "invalid char {:?} at index {}, expected {}",
+                found, index, expected
+            ),
+            Reason::Checksum { expected, actual } => write!(
+                f,
+                "wrong check digits, expected {}, found {}",
+                expected, actual
+            ),
+            Reason::Unknown => write!(f, "unknown code"),
+            Reason::Format(format) => write!(f, "{}", format),
+        }
+    }
+}
+
 /// `Error` type of the crate.
 #[derive(Debug)]
 pub enum Error {
-    InvalidCode,
-    CodeNotFound,
+    /// `InvalidCode` is a code failing its validation: `kind` names the
+    /// code, e.g. "IBAN", and `reason` tells why `value` is invalid.
+    InvalidCode {
+        kind: &'static str,
+        value: String,
+        reason: Reason,
+    },
+    /// `CodeNotFound` is a valid code missing from its directory.
+    CodeNotFound {
+        kind: &'static str,
+        value: String,
+    },
+    /// `InvalidField` is a field of a SWIFT message failing its format or
+    /// usage rules: `tag` is the field, e.g. "32A", and `reason` tells why
+    /// `value` is invalid.
+    InvalidField {
+        tag: String,
+        value: String,
+        reason: Reason,
+    },
+    /// `InvalidFormat` is a file or message breaking its format: `kind`
+    /// names it, e.g. "Bacs file", `line` is the 1-based line, or record,
+    /// at fault, when known, and `reason` tells why.
+    InvalidFormat {
+        kind: &'static str,
+        line: Option<usize>,
+        reason: Reason,
+    },
+    MissingElement(String),
+    UnknownMessage(String),
+    ParseXML(String),
     ParseCode(arraystring::Error),
     JSONSerialize(serde_json::Error),
     JSONDeserialize(serde_json::Error),
+    /// `Io` is a failure to read a file, e.g. a directory to load.
+    Io(std::io::Error),
+    /// `Random` is a failure of the system random generator, e.g. when
+    /// generating a UETR.
+    Random(getrandom::Error),
     Other(String),
 }
 
+impl Error {
+    /// `invalid` returns an [`Error::InvalidCode`].
+    pub fn invalid(kind: &'static str, value: &str, reason: Reason) -> Error {
+        Error::InvalidCode {
+            kind,
+            value: value.into(),
+            reason,
+        }
+    }
+
+    /// `invalid_field` returns an [`Error::InvalidField`].
+    pub fn invalid_field(tag: &str, value: &str, reason: Reason) -> Error {
+        Error::InvalidField {
+            tag: tag.into(),
+            value: value.into(),
+            reason,
+        }
+    }
+
+    /// `invalid_format` returns an [`Error::InvalidFormat`].
+    pub fn invalid_format(kind: &'static str, line: Option<usize>, reason: Reason) -> Error {
+        Error::InvalidFormat { kind, line, reason }
+    }
+
+    /// `not_found` returns an [`Error::CodeNotFound`].
+    pub fn not_found(kind: &'static str, value: &str) -> Error {
+        Error::CodeNotFound {
+            kind,
+            value: value.into(),
+        }
+    }
+
+    /// `within` returns the error of a part of a code as an error of the
+    /// whole code, the part starting at the char `offset` of the code.
+    pub(crate) fn within(self, kind: &'static str, code: &str, offset: usize) -> Error {
+        match self {
+            Error::InvalidCode { reason, .. } => Error::invalid(kind, code, reason.shift(offset)),
+            err => err,
+        }
+    }
+
+    /// `in_field` returns the error of a code or subfield within a SWIFT
+    /// field as an error of the field, the part starting at the char
+    /// `offset` of the field value.
+    pub(crate) fn in_field(self, tag: &str, value: &str, offset: usize) -> Error {
+        match self {
+            Error::InvalidCode { reason, .. } | Error::InvalidField { reason, .. } => {
+                Error::invalid_field(tag, value, reason.shift(offset))
+            }
+            err => err,
+        }
+    }
+
+    /// `at_line` sets the 1-based line of an [`Error::InvalidFormat`], when
+    /// unknown to the part that failed.
+    pub(crate) fn at_line(self, line: usize) -> Error {
+        match self {
+            Error::InvalidFormat {
+                kind,
+                line: None,
+                reason,
+            } => Error::invalid_format(kind, Some(line), reason),
+            err => err,
+        }
+    }
+
+    /// `reason` returns why a code, field or format is invalid, for an
+    /// [`Error::InvalidCode`], [`Error::InvalidField`] or
+    /// [`Error::InvalidFormat`].
+    pub fn reason(&self) -> Option<&Reason> {
+        match self {
+            Error::InvalidCode { reason, .. }
+            | Error::InvalidField { reason, .. }
+            | Error::InvalidFormat { reason, .. } => Some(reason),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let msg: String = match self {
-            Error::InvalidCode => "invalid code".into(),
-            Error::CodeNotFound => "code not found".into(),
+            Error::InvalidCode {
+                kind,
+                value,
+                reason,
+            } => format!("invalid {} {:?}: {}", kind, value, reason),
+            Error::CodeNotFound { kind, value } => format!("{} not found: {}", kind, value),
+            Error::InvalidField { tag, value, reason } => {
+                format!("invalid field {} {:?}: {}", tag, value, reason)
+            }
+            Error::InvalidFormat {
+                kind,
+                line: Some(line),
+                reason,
+            } => format!("invalid {} at line {}: {}", kind, line, reason),
+            Error::InvalidFormat { kind, reason, .. } => format!("invalid {}: {}", kind, reason),
+            Error::MissingElement(name) => format!("missing element: {}", name),
+            Error::UnknownMessage(name) => format!("unknown message: {}", name),
+            Error::ParseXML(source) => format!("xml parsing error: {}", source),
             Error::ParseCode(source) => format!("code parsing error: {}", source),
             Error::JSONSerialize(source) => format!("json serialization error: {}", source),
             Error::JSONDeserialize(source) => format!("json deserialization error: {}", source),
+            Error::Io(source) => format!("io error: {}", source),
+            Error::Random(source) => format!("random generation error: {}", source),
             Error::Other(source) => source.into(),
         };
 
@@ -33,7 +240,122 @@ impl error::Error for Error {
             Error::ParseCode(ref source) => Some(source),
             Error::JSONSerialize(ref source) => Some(source),
             Error::JSONDeserialize(ref source) => Some(source),
+            Error::Io(ref source) => Some(source),
+            Error::Random(ref source) => Some(source),
             _ => None,
         }
     }
 }
+
+/// `check_length` checks the length of a code, in chars.
+pub(crate) fn check_length(kind: &'static str, code: &str, min: usize, max: usize) -> Result<()> {
+    let actual = code.chars().count();
+
+    if actual < min || actual > max {
+        return Err(Error::invalid(
+            kind,
+            code,
+            Reason::Length { min, max, actual },
+        ));
+    }
+
+    Ok(())
+}
+
+/// `check_chars` checks that all the chars of a code are allowed, returning
+/// the first one that is not.
+pub(crate) fn check_chars<F: Fn(char) -> bool>(
+    kind: &'static str,
+    code: &str,
+    expected: &'static str,
+    allowed: F,
+) -> Result<()> {
+    match code.chars().enumerate().find(|(_, c)| !allowed(*c)) {
+        Some((index, found)) => Err(Error::invalid(
+            kind,
+            code,
+            Reason::Character {
+                index,
+                found,
+                expected,
+            },
+        )),
+        None => Ok(()),
+    }
+}
+
+/// `check_digits` checks that a code is made of `min` to `max` digits.
+pub(crate) fn check_digits(kind: &'static str, code: &str, min: usize, max: usize) -> Result<()> {
+    check_length(kind, code, min, max)?;
+    check_chars(kind, code, "a digit", |c| c.is_ascii_digit())
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{check_digits, Error, Reason};
+    #[allow(unused_imports)] // TODO
+    use std::error::Error as StdError;
+
+    #[test]
+    fn display() {
+        let err = check_digits("sort code", "60-161", 6, 6).unwrap_err();
+        assert_eq!(
+            err.reason(),
+            Some(&Reason::Character {
+                index: 2,
+                found: '-',
+                expected: "a digit"
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "invalid sort code \"60-161\": invalid char '-' at index 2, expected a digit"
+        );
+        assert_eq!(
+            check_digits("sort code", "6016", 6, 6)
+                .unwrap_err()
+                .to_string(),
+            "invalid sort code \"6016\": wrong length, expected 6 chars, found 4"
+        );
+        assert_eq!(
+            Error::invalid(
+                "IBAN",
+                "DE88370400440532013000",
+                Reason::Checksum {
+                    expected: "89".into(),
+                    actual: "88".into()
+                }
+            )
+            .to_string(),
+            "invalid IBAN \"DE88370400440532013000\": wrong check digits, expected 89, found 88"
+        );
+
+        assert_eq!(
+            Error::invalid_format(
+                "Bacs file",
+                Some(12),
+                Reason::Format("totals not matching the records".into())
+            )
+            .to_string(),
+            "invalid Bacs file at line 12: totals not matching the records"
+        );
+
+        let err =
+            check_digits("date", "24O229", 6, 6)
+                .unwrap_err()
+                .in_field("32A", "24O229EUR1,", 0);
+        assert_eq!(
+            err.to_string(),
+            "invalid field 32A \"24O229EUR1,\": invalid char 'O' at index 2, expected a digit"
+        );
+
+        let err = Error::JSONDeserialize(serde_json::from_str::<u8>("x").unwrap_err());
+        assert!(err.source().is_some());
+
+        let err = Error::Io(std::fs::read("/nonexistent/blz.txt").unwrap_err());
+        assert!(err.source().is_some());
+
+        let err = Error::Random(getrandom::Error::UNSUPPORTED);
+        assert!(err.source().is_some());
+    }
+}
@@ -0,0 +1,9 @@
+//! UK payment standards.
+
+pub mod bacs;
+pub mod modulus;
+pub mod sortcode;
+
+pub use bacs::*;
+pub use modulus::*;
+pub use sortcode::*;
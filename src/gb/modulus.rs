@@ -0,0 +1,500 @@
+//! UK sort code and account number modulus checking, as specified by
+//! VocaLink (Pay.UK): the weight table `valacdos.txt` and the sort code
+//! substitution table `scsubtab.txt`.
+
+use crate::error::{check_chars, check_length, Error, Reason};
+use crate::gb::sortcode::{AccountNumber, SortCode};
+use crate::iso13616::{BBAN, IBAN};
+use crate::result::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// `WEIGHTS` is the number of weights of a weighting: six for the sort code
+/// (u to z), eight for the account number (a to h).
+pub const WEIGHTS: usize = 14;
+
+/// `EXCEPTION_9_SORT_CODE` is the sort code substituted by exception 9.
+const EXCEPTION_9_SORT_CODE: &str = "309634";
+
+/// `EXCEPTION_8_SORT_CODE` is the sort code substituted by exception 8.
+const EXCEPTION_8_SORT_CODE: &str = "090126";
+
+/// `EXCEPTION_2_WEIGHTS` are the weights substituted by exception 2, when
+/// `a` is not 0 and `g` is not 9.
+const EXCEPTION_2_WEIGHTS: [i32; WEIGHTS] = [0, 0, 1, 2, 5, 3, 6, 4, 8, 7, 10, 9, 3, 1];
+
+/// `EXCEPTION_2_9_WEIGHTS` are the weights substituted by exception 2, when
+/// `a` is not 0 and `g` is 9.
+const EXCEPTION_2_9_WEIGHTS: [i32; WEIGHTS] = [0, 0, 0, 0, 0, 0, 0, 0, 8, 7, 10, 9, 3, 1];
+
+// NOTE: indices of the digits in the sort code and account number, as
+// named by the specification
+const A: usize = 6;
+const B: usize = 7;
+const C: usize = 8;
+const G: usize = 12;
+const H: usize = 13;
+
+/// [`Algorithm`] is the modulus algorithm of a weighting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Algorithm {
+    /// Standard modulus 10.
+    Mod10,
+    /// Standard modulus 11.
+    Mod11,
+    /// Double alternate: modulus 10 of the sum of the digits of the
+    /// products.
+    DoubleAlternate,
+}
+
+impl Algorithm {
+    /// `as_str` returns the algorithm code of the weight table.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Algorithm::Mod10 => "MOD10",
+            Algorithm::Mod11 => "MOD11",
+            Algorithm::DoubleAlternate => "DBLAL",
+        }
+    }
+
+    /// `modulus` returns the modulus of the algorithm.
+    fn modulus(&self) -> i32 {
+        match self {
+            Algorithm::Mod11 => 11,
+            _ => 10,
+        }
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    fn from_str(code: &str) -> std::result::Result<Self, Self::Err> {
+        match code {
+            "MOD10" => Ok(Algorithm::Mod10),
+            "MOD11" => Ok(Algorithm::Mod11),
+            "DBLAL" => Ok(Algorithm::DoubleAlternate),
+            _ => Err(Error::invalid("modulus algorithm", code, Reason::Unknown)),
+        }
+    }
+}
+
+/// [`Weighting`] is a line of the weight table: a sort code range, its
+/// algorithm, its weights and its exception, if any.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Weighting {
+    pub start: SortCode,
+    pub end: SortCode,
+    pub algorithm: Algorithm,
+    pub weights: [i32; WEIGHTS],
+    pub exception: Option<u8>,
+}
+
+impl Weighting {
+    /// `parse` parses a line of the weight table.
+    pub fn parse(line: &str) -> Result<Weighting> {
+        let invalid = |reason: String| {
+            Error::invalid_format("modulus weight table", None, Reason::Format(reason))
+        };
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        if fields.len() != 3 + WEIGHTS && fields.len() != 4 + WEIGHTS {
+            return Err(invalid(format!(
+                "expected {} or {} columns, found {}",
+                3 + WEIGHTS,
+                4 + WEIGHTS,
+                fields.len()
+            )));
+        }
+
+        let mut weights = [0; WEIGHTS];
+        for (weight, field) in weights.iter_mut().zip(&fields[3..3 + WEIGHTS]) {
+            *weight = field
+                .parse()
+                .map_err(|_| invalid(format!("invalid weight {:?}", field)))?;
+        }
+
+        Ok(Weighting {
+            start: fields[0].parse()?,
+            end: fields[1].parse()?,
+            algorithm: fields[2].parse()?,
+            weights,
+            exception: match fields.get(3 + WEIGHTS) {
+                Some(exception) => Some(
+                    exception
+                        .parse()
+                        .map_err(|_| invalid(format!("invalid exception {:?}", exception)))?,
+                ),
+                None => None,
+            },
+        })
+    }
+
+    /// `contains` returns if the sort code is in the range of the weighting.
+    pub fn contains(&self, sort_code: &SortCode) -> bool {
+        self.start <= *sort_code && *sort_code <= self.end
+    }
+
+    /// `total` returns the sum of the weighted digits.
+    fn total(&self, weights: &[i32; WEIGHTS], digits: &[i32; WEIGHTS]) -> i32 {
+        weights
+            .iter()
+            .zip(digits.iter())
+            .map(|(weight, digit)| match self.algorithm {
+                Algorithm::DoubleAlternate => {
+                    let product = weight * digit;
+                    product / 10 + product % 10
+                }
+                _ => weight * digit,
+            })
+            .sum()
+    }
+}
+
+/// `digits` returns the digits u to h of a sort code and an account number.
+fn digits(sort_code: &str, account: &str) -> [i32; WEIGHTS] {
+    let mut res = [0; WEIGHTS];
+
+    for (digit, c) in res.iter_mut().zip(sort_code.chars().chain(account.chars())) {
+        *digit = c.to_digit(10).unwrap_or_default() as i32;
+    }
+
+    res
+}
+
+/// [`ModulusRules`] are the weight and substitution tables used to check
+/// sort codes and account numbers.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ModulusRules {
+    pub weightings: Vec<Weighting>,
+    /// `substitutions` maps sort codes substituted by exception 5.
+    pub substitutions: BTreeMap<SortCode, SortCode>,
+}
+
+impl ModulusRules {
+    /// `parse` parses the contents of the weight table (`valacdos.txt`) and
+    /// of the sort code substitution table (`scsubtab.txt`).
+    pub fn parse(weightings: &str, substitutions: &str) -> Result<ModulusRules> {
+        let mut res = ModulusRules::default();
+
+        let is_blank = |(_, line): &(usize, &str)| line.trim().is_empty();
+
+        for (i, line) in weightings.lines().enumerate().filter(|l| !is_blank(l)) {
+            res.weightings
+                .push(Weighting::parse(line).map_err(|e| e.at_line(i + 1))?);
+        }
+
+        for (i, line) in substitutions.lines().enumerate().filter(|l| !is_blank(l)) {
+            match line.split_whitespace().collect::<Vec<&str>>().as_slice() {
+                [original, substitute] => {
+                    res.substitutions
+                        .insert(original.parse()?, substitute.parse()?);
+                }
+                columns => {
+                    return Err(Error::invalid_format(
+                        "sort code substitution table",
+                        Some(i + 1),
+                        Reason::Format(format!("expected 2 columns, found {}", columns.len())),
+                    ))
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// `load` loads the weight and sort code substitution tables from local
+    /// paths.
+    pub fn load<P: AsRef<Path>>(weightings: P, substitutions: P) -> Result<ModulusRules> {
+        let read = |path: &Path| std::fs::read_to_string(path).map_err(Error::Io);
+
+        ModulusRules::parse(&read(weightings.as_ref())?, &read(substitutions.as_ref())?)
+    }
+
+    /// `find` returns the weightings of a sort code, none, one or two.
+    pub fn find(&self, sort_code: &SortCode) -> Vec<&Weighting> {
+        self.weightings
+            .iter()
+            .filter(|weighting| weighting.contains(sort_code))
+            .collect()
+    }
+
+    /// `is_valid_with` runs the check of a weighting, with its exception.
+    fn is_valid_with(&self, weighting: &Weighting, sort_code: &SortCode, account: &str) -> bool {
+        let exception = weighting.exception.unwrap_or_default();
+        let sort_code = match exception {
+            5 => self
+                .substitutions
+                .get(sort_code)
+                .unwrap_or(sort_code)
+                .as_str(),
+            8 => EXCEPTION_8_SORT_CODE,
+            9 => EXCEPTION_9_SORT_CODE,
+            _ => sort_code.as_str(),
+        };
+
+        let digits = digits(sort_code, account);
+        let mut weights = weighting.weights;
+
+        match exception {
+            2 if digits[A] != 0 && digits[G] == 9 => weights = EXCEPTION_2_9_WEIGHTS,
+            2 if digits[A] != 0 => weights = EXCEPTION_2_WEIGHTS,
+            7 if digits[G] == 9 => weights[..=B].iter_mut().for_each(|w| *w = 0),
+            10 if (digits[A..=B] == [0, 9] || digits[A..=B] == [9, 9]) && digits[G] == 9 => {
+                weights[..=B].iter_mut().for_each(|w| *w = 0)
+            }
+            _ => {}
+        }
+
+        let mut total = weighting.total(&weights, &digits);
+        if exception == 1 {
+            total += 27;
+        }
+
+        let remainder = total.rem_euclid(weighting.algorithm.modulus());
+
+        match (exception, weighting.algorithm) {
+            (4, _) => remainder == digits[G] * 10 + digits[H],
+            (5, Algorithm::Mod11) => match remainder {
+                0 => digits[G] == 0,
+                1 => false,
+                r => 11 - r == digits[G],
+            },
+            (5, _) => match remainder {
+                0 => digits[H] == 0,
+                r => 10 - r == digits[H],
+            },
+            (14, _) if remainder != 0 => {
+                // NOTE: the last digit may be a currency indicator, the
+                // account number is then shifted right and checked again
+                matches!(digits[H], 0 | 1 | 9) && {
+                    let account = format!("0{}", &account[..7]);
+                    let digits = self::digits(sort_code, &account);
+
+                    weighting.total(&weights, &digits) % 11 == 0
+                }
+            }
+            _ => remainder == 0,
+        }
+    }
+
+    /// `check` checks a sort code and an account number. A sort code out of
+    /// the weight table cannot be checked, and is then considered valid.
+    pub fn check(&self, sort_code: &SortCode, account: &AccountNumber) -> Result<()> {
+        let account = account.as_str();
+        let valid = match self.find(sort_code).as_slice() {
+            [] => true,
+            [first, ..]
+                if first.exception == Some(6)
+                    && (4..=8).contains(&digits(sort_code.as_str(), account)[A])
+                    && account[6..7] == account[7..8] =>
+            {
+                // NOTE: foreign currency accounts cannot be checked
+                true
+            }
+            [weighting] => self.is_valid_with(weighting, sort_code, account),
+            [first, second, ..] => {
+                let either = matches!(
+                    (first.exception, second.exception),
+                    (Some(2), Some(9)) | (Some(10), Some(11)) | (Some(12), Some(13))
+                );
+                let skip_second = second.exception == Some(3)
+                    && matches!(digits(sort_code.as_str(), account)[C], 6 | 9);
+
+                if self.is_valid_with(first, sort_code, account) {
+                    either || skip_second || self.is_valid_with(second, sort_code, account)
+                } else {
+                    either && self.is_valid_with(second, sort_code, account)
+                }
+            }
+        };
+
+        if !valid {
+            return Err(Error::invalid(
+                "account number",
+                account,
+                Reason::Format(format!(
+                    "fails the modulus check of sort code {}",
+                    sort_code
+                )),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `validate_bban` validates a UK BBAN: a four letters bank code, a
+    /// sort code and an account number.
+    pub fn validate_bban(&self, bban: &BBAN) -> Result<()> {
+        let code = bban.as_str();
+
+        check_length("BBAN", code, 18, 18)?;
+        check_chars("BBAN", &code[..4], "a letter", |c| c.is_ascii_alphabetic())
+            .map_err(|e| e.within("BBAN", code, 0))?;
+
+        self.check(&code[4..10].parse()?, &code[10..].parse()?)
+    }
+
+    /// `validate_iban` validates a UK IBAN, its check digits then its BBAN.
+    pub fn validate_iban(&self, iban: &IBAN) -> Result<()> {
+        if iban.country.as_str() != "GB" {
+            return Err(Error::invalid(
+                "IBAN",
+                &iban.to_string(),
+                Reason::Format("expected a GB IBAN".into()),
+            ));
+        }
+
+        iban.validate()?;
+        self.validate_bban(&iban.bban)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Algorithm, ModulusRules, Result, Weighting};
+    #[allow(unused_imports)] // TODO
+    use crate::iso13616::IBAN;
+
+    #[allow(dead_code)]
+    const WEIGHTINGS: &str = "\
+074456 074456 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1    12
+074456 074456 MOD10    0    0    0    0    0    0    7    1    3    7    1    3    7    1    13
+086090 086090 MOD11    2    7    6    5    4    3    2    7    6    5    4    3    2    1    8
+089000 089999 MOD10    0    0    0    0    0    0    7    1    3    7    1    3    7    1
+107999 107999 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1
+118765 118765 DBLAL    0    0    2    1    2    1    2    1    2    1    2    1    2    1    1
+134012 134020 MOD11    0    0    0    7    5    9    8    4    6    3    5    2    0    0    4
+180002 180002 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1    14
+200915 200915 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1    6
+200915 200915 DBLAL    2    1    2    1    2    1    2    1    2    1    2    1    2    1    6
+202900 203099 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1
+202900 203099 DBLAL    2    1    2    1    2    1    2    1    2    1    2    1    2    1
+300000 300099 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1    14
+309070 309070 MOD11    0    0    1    2    5    3    6    4    8    7   10    9    3    1    2
+309070 309070 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1    9
+400000 400099 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1    6
+772798 772798 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1    7
+820000 826099 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1
+820000 826099 DBLAL    2    1    2    1    2    1    2    1    2    1    2    1    2    1    3
+871427 871427 MOD11    0    0    0    0    0    0    8    7    6    5    4    3    2    1    10
+871427 871427 DBLAL    2    1    2    1    2    1    2    1    2    1    2    1    2    1    11
+938000 938696 MOD11    7    6    5    4    3    2    7    6    5    4    3    2    0    0    5
+938000 938696 DBLAL    2    1    2    1    2    1    2    1    2    1    2    1    2    0    5
+";
+
+    #[allow(dead_code)]
+    const SUBSTITUTIONS: &str = "938600 938611\n";
+
+    #[test]
+    fn check() -> Result<()> {
+        let rules = ModulusRules::parse(WEIGHTINGS, SUBSTITUTIONS)?;
+
+        const VALID_ACCOUNTS: &[(&str, &str)] = &[
+            ("089999", "66374958"),
+            ("107999", "88837491"),
+            ("202959", "63748738"),
+            // NOTE: not in the weight table
+            ("999999", "12345678"),
+            // NOTE: exception 14, currency indicator removed
+            ("300000", "10000049"),
+            // NOTE: exception 6, foreign currency account
+            ("400000", "50000011"),
+            ("200915", "41011166"),
+            // NOTE: exception 1, 27 added to the total
+            ("118765", "64371389"),
+            // NOTE: exception 4, remainder equal to the check digits gh
+            ("134020", "63849203"),
+            // NOTE: exception 5, sort code 938600 substituted by 938611
+            ("938611", "07806039"),
+            ("938600", "42368003"),
+            ("938063", "55065200"),
+            // NOTE: exception 14, failing until the last digit is removed
+            ("180002", "00000190"),
+            // NOTE: exception 3, c = 6 so the second check is skipped
+            ("820000", "17603137"),
+            ("820000", "90484495"),
+            // NOTE: exception 7, g = 9 so u to b are ignored
+            ("772798", "01039799"),
+            // NOTE: exception 8, sort code substituted by 090126
+            ("086090", "38288185"),
+            // NOTE: exceptions 2 and 9, with the weights of a, or of a and
+            // g = 9, then with the second check
+            ("309070", "12345677"),
+            ("309070", "99345694"),
+            ("309070", "04723459"),
+            ("309070", "42261864"),
+            // NOTE: exceptions 10 and 11, ab = 09 or 99 and g = 9 so u to b
+            // are ignored, then with the second check
+            ("871427", "09243698"),
+            ("871427", "99190799"),
+            ("871427", "65993528"),
+            // NOTE: exceptions 12 and 13, either check passing
+            ("074456", "69994056"),
+            ("074456", "15804547"),
+        ];
+        const WRONG_ACCOUNTS: &[(&str, &str)] = &[
+            ("089999", "66374959"),
+            ("107999", "88837492"),
+            ("202959", "63748401"),
+            ("300000", "88837495"),
+            ("400000", "50000013"),
+            ("200915", "41011167"),
+            ("118765", "64371388"),
+            ("134020", "63849204"),
+            // NOTE: exception 5, first or second check digit wrong
+            ("938063", "15764273"),
+            ("938063", "15764264"),
+            ("938063", "15763217"),
+            ("820000", "21590109"),
+            ("772798", "58342990"),
+            ("086090", "40924845"),
+            ("309070", "89173789"),
+            ("871427", "86241209"),
+            ("074456", "26013302"),
+        ];
+
+        for (sort_code, account) in VALID_ACCOUNTS.iter() {
+            assert!(
+                rules
+                    .check(&(*sort_code).into(), &(*account).into())
+                    .is_ok(),
+                "{} {}",
+                sort_code,
+                account
+            );
+        }
+
+        for (sort_code, account) in WRONG_ACCOUNTS.iter() {
+            assert!(
+                rules
+                    .check(&(*sort_code).into(), &(*account).into())
+                    .is_err(),
+                "{} {}",
+                sort_code,
+                account
+            );
+        }
+
+        let weighting = Weighting::parse(WEIGHTINGS.lines().nth(12).unwrap_or_default())?;
+        assert_eq!(weighting.algorithm, Algorithm::Mod11);
+        assert_eq!(weighting.exception, Some(14));
+        assert!(Weighting::parse("089000 089999 MOD12 0 0").is_err());
+
+        let rules = ModulusRules::parse(WEIGHTINGS, "089999 107999\n")?;
+        assert_eq!(rules.substitutions.len(), 1);
+        assert!(ModulusRules::parse(WEIGHTINGS, "089999\n").is_err());
+
+        assert!(rules
+            .validate_iban(&IBAN::new("GB54BUKB08999966374958")?)
+            .is_ok());
+        assert!(rules
+            .validate_iban(&IBAN::new("GB27BUKB08999966374959")?)
+            .is_err());
+        assert!(rules.validate_bban(&"BUKB0899996637495".into()).is_err());
+
+        Ok(())
+    }
+}
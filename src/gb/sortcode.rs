@@ -0,0 +1,227 @@
+//! UK sort codes and account numbers.
+
+use crate::code::Code;
+use crate::error::{check_digits, Error};
+use crate::result::Result;
+use arraystring::typenum::{U6, U8};
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// [`SortCode`] is a UK sort code: six digits identifying a bank branch,
+/// printed in pairs, e.g. "60-16-13".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SortCode(Code<U6>);
+
+impl SortCode {
+    /// `new` creates a new `SortCode`, with or without the '-' or ' '
+    /// separators.
+    pub fn new(code: &str) -> Result<SortCode> {
+        let cd: String = code.chars().filter(|c| *c != '-' && *c != ' ').collect();
+
+        SortCode::check(&cd)?;
+
+        Ok(SortCode(Code::<U6>::from(cd.as_str())))
+    }
+
+    /// `as_str` returns the `SortCode` as a string slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_digits("sort code", code, 6, 6)
+    }
+
+    /// `is_valid` returns if a provided code is valid
+    /// by checking length and kind of chars used.
+    pub fn is_valid(code: &str) -> bool {
+        SortCode::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`SortCode`].
+    pub fn validate(&self) -> Result<()> {
+        SortCode::check(self.as_str())
+    }
+
+    /// `print` returns the `SortCode` in print format, e.g. "60-16-13".
+    pub fn print(&self) -> String {
+        let code = self.as_str();
+
+        format!("{}-{}-{}", &code[0..2], &code[2..4], &code[4..6])
+    }
+}
+
+impl fmt::Display for SortCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for SortCode {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        SortCode::new(code)
+    }
+}
+
+impl From<&str> for SortCode {
+    fn from(code: &str) -> Self {
+        SortCode::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for SortCode {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(self.0.as_str(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for SortCode {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// [`AccountNumber`] is a standard UK account number of eight digits.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AccountNumber(Code<U8>);
+
+impl AccountNumber {
+    pub fn new(code: &str) -> Result<AccountNumber> {
+        AccountNumber::check(code)?;
+
+        Ok(AccountNumber(Code::<U8>::from(code)))
+    }
+
+    /// `standardise` converts a non-standard account number, of six to ten
+    /// digits, to a sort code and a standard account number:
+    /// - six and seven digits are left padded with zeros,
+    /// - nine digits (Santander) replace the last digit of the sort code with
+    ///   their first one,
+    /// - ten digits (National Westminster) keep their last eight ones.
+    ///
+    /// NOTE: the ten digits of the Co-operative Bank keep their first eight
+    /// ones instead, and must be standardised by the caller.
+    pub fn standardise(sort_code: &SortCode, code: &str) -> Result<(SortCode, AccountNumber)> {
+        let cd: String = code.chars().filter(|c| *c != '-' && *c != ' ').collect();
+
+        check_digits("account number", &cd, 6, 10)?;
+
+        match cd.len() {
+            6..=8 => Ok((
+                sort_code.clone(),
+                AccountNumber::new(&format!("{:0>8}", cd))?,
+            )),
+            9 => Ok((
+                SortCode::new(&format!("{}{}", &sort_code.as_str()[..5], &cd[..1]))?,
+                AccountNumber::new(&cd[1..])?,
+            )),
+            // NOTE: ten digits, the only length left
+            _ => Ok((sort_code.clone(), AccountNumber::new(&cd[2..])?)),
+        }
+    }
+
+    /// `as_str` returns the `AccountNumber` as a string slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_digits("account number", code, 8, 8)
+    }
+
+    /// `is_valid` returns if a provided code is valid
+    /// by checking length and kind of chars used.
+    pub fn is_valid(code: &str) -> bool {
+        AccountNumber::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`AccountNumber`]. Here only length and
+    /// digits used are checked, see [`crate::gb::ModulusRules`] for the
+    /// modulus check.
+    pub fn validate(&self) -> Result<()> {
+        AccountNumber::check(self.as_str())
+    }
+}
+
+impl fmt::Display for AccountNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for AccountNumber {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        AccountNumber::new(code)
+    }
+}
+
+impl From<&str> for AccountNumber {
+    fn from(code: &str) -> Self {
+        AccountNumber::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for AccountNumber {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(self.0.as_str(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for AccountNumber {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{AccountNumber, Result, SortCode};
+
+    #[test]
+    fn is_valid() -> Result<()> {
+        const WRONG_CODES: &[&str] = &["12345", "1234567", "12-34-5A"];
+        const VALID_CODES: &[&str] = &["601613", "60-16-13", "60 16 13"];
+
+        for code in WRONG_CODES.iter() {
+            assert!(SortCode::new(code).is_err(), "{}", code);
+        }
+
+        for code in VALID_CODES.iter() {
+            assert_eq!(SortCode::new(code)?.print(), "60-16-13");
+        }
+
+        assert!(AccountNumber::is_valid("31926819"));
+        assert!(!AccountNumber::is_valid("3192681"));
+
+        let sort_code = SortCode::new("601613")?;
+        let standardise = |code: &str| -> Result<String> {
+            AccountNumber::standardise(&sort_code, code)
+                .map(|(s, a)| format!("{} {}", s.as_str(), a.as_str()))
+        };
+        assert_eq!(standardise("926819")?, "601613 00926819");
+        assert_eq!(standardise("123456789")?, "601611 23456789");
+        assert_eq!(standardise("0131926819")?, "601613 31926819");
+        assert!(standardise("12345").is_err());
+
+        assert_eq!(
+            serde_json::from_str::<SortCode>("\"601613\"").ok(),
+            Some(sort_code)
+        );
+        assert!(serde_json::from_str::<SortCode>("\"60161A\"").is_err());
+        assert!(serde_json::from_str::<AccountNumber>("\"3192681\"").is_err());
+
+        Ok(())
+    }
+}
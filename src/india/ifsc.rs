@@ -0,0 +1,253 @@
+//! Indian Financial System Codes (IFSC), identifying the bank branches of
+//! NEFT, RTGS and IMPS payments.
+
+use crate::code::Code;
+use crate::csv;
+use crate::error::{check_chars, Error, Reason};
+use crate::result::Result;
+use arraystring::typenum::U11;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// [`Ifsc`] is an IFSC: a four letters bank code, a '0' reserved for
+/// future use and a six chars branch code, e.g. "HDFC0000128".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Ifsc(Code<U11>);
+
+impl Ifsc {
+    pub fn new(code: &str) -> Result<Ifsc> {
+        // NOTE: we only use upper-case codes, but an IFSC
+        // can be lower/mixed-case
+        let cd: &str = &code.trim().to_uppercase();
+
+        Ifsc::check(cd)?;
+
+        Ok(Ifsc(Code::<U11>::from(cd)))
+    }
+
+    /// `as_str` returns the `Ifsc` as a string slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `bank_code` returns the four letters code of the bank.
+    pub fn bank_code(&self) -> &str {
+        &self.as_str()[0..4]
+    }
+
+    /// `branch_code` returns the six chars code of the branch.
+    pub fn branch_code(&self) -> &str {
+        &self.as_str()[5..11]
+    }
+
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        Code::<U11>::check(code).map_err(|e| e.within("IFSC", code, 0))?;
+        check_chars("IFSC", &code[0..4], "an upper-case letter", |c| {
+            c.is_ascii_uppercase()
+        })
+        .map_err(|e| e.within("IFSC", code, 0))?;
+
+        if &code[4..5] != "0" {
+            return Err(Error::invalid(
+                "IFSC",
+                code,
+                Reason::Character {
+                    index: 4,
+                    found: code[4..5].chars().next().unwrap_or_default(),
+                    expected: "'0'",
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `is_valid` returns if a provided code is valid
+    /// by checking length and kind of chars used.
+    pub fn is_valid(code: &str) -> bool {
+        Ifsc::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`Ifsc`]. Here only length and chars
+    /// used are checked, see [`IfscDirectory`] to check that the branch
+    /// exists.
+    pub fn validate(&self) -> Result<()> {
+        Ifsc::check(self.as_str())
+    }
+}
+
+impl FromStr for Ifsc {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        Ifsc::new(code)
+    }
+}
+
+impl From<&str> for Ifsc {
+    fn from(code: &str) -> Self {
+        Ifsc::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for Ifsc {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(self.0.as_str(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for Ifsc {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// [`Branch`] is a bank branch of the IFSC directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Branch {
+    pub ifsc: Ifsc,
+    pub bank: String,
+    pub branch: String,
+    pub address: String,
+    pub city: String,
+    pub state: String,
+    /// `micr` is the nine digits MICR code of the branch, if any.
+    pub micr: Option<String>,
+}
+
+/// [`IfscDirectory`] maps an IFSC with its branch, as listed by the RBI.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IfscDirectory(BTreeMap<Ifsc, Branch>);
+
+impl IfscDirectory {
+    /// `parse` parses a CSV export of the RBI directory, with a header
+    /// naming its columns: "IFSC", "BANK" and "BRANCH" are mandatory,
+    /// "ADDRESS", "CITY", "STATE" and "MICR" are optional.
+    pub fn parse(content: &str) -> Result<IfscDirectory> {
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let header: Vec<String> = csv::fields(lines.next().unwrap_or_default())
+            .into_iter()
+            .map(|name| name.to_uppercase())
+            .collect();
+        let column = |name: &str| header.iter().position(|h| h == name);
+        let required = |name: &str| column(name).ok_or_else(|| Error::MissingElement(name.into()));
+
+        let (ifsc, bank, branch) = (required("IFSC")?, required("BANK")?, required("BRANCH")?);
+        let (address, city, state, micr) = (
+            column("ADDRESS"),
+            column("CITY"),
+            column("STATE"),
+            column("MICR"),
+        );
+
+        let mut res = BTreeMap::new();
+
+        for line in lines {
+            let fields = csv::fields(line);
+            let field =
+                |i: Option<usize>| i.and_then(|i| fields.get(i)).cloned().unwrap_or_default();
+            let code = Ifsc::new(&field(Some(ifsc)))?;
+
+            res.insert(
+                code.clone(),
+                Branch {
+                    ifsc: code,
+                    bank: field(Some(bank)),
+                    branch: field(Some(branch)),
+                    address: field(address),
+                    city: field(city),
+                    state: field(state),
+                    micr: Some(field(micr))
+                        .filter(|m| m.len() == 9 && m.chars().all(|c| c.is_ascii_digit())),
+                },
+            );
+        }
+
+        Ok(IfscDirectory(res))
+    }
+
+    /// `load` loads the directory from a local CSV file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<IfscDirectory> {
+        let content = std::fs::read_to_string(path.as_ref()).map_err(Error::Io)?;
+
+        IfscDirectory::parse(&content)
+    }
+
+    /// `find` finds the branch of an IFSC.
+    pub fn find(&self, code: &Ifsc) -> Option<&Branch> {
+        self.0.get(code)
+    }
+
+    /// `validate` validates that an IFSC is listed in the directory.
+    pub fn validate(&self, code: &str) -> Result<()> {
+        if self.find(&Ifsc::new(code)?).is_none() {
+            return Err(Error::not_found("IFSC", code));
+        }
+
+        Ok(())
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Ifsc, IfscDirectory, Result};
+
+    #[test]
+    fn is_valid() -> Result<()> {
+        const WRONG_CODES: &[&str] = &["HDFC1000128", "HDF00000128", "HDFC000012", "HDFC0000-28"];
+        const VALID_CODES: &[&str] = &["HDFC0000128", "SBIN0005943", "ICIC0ABC123"];
+
+        for code in WRONG_CODES.iter() {
+            assert!(!Ifsc::is_valid(code), "{}", code);
+        }
+
+        for code in VALID_CODES.iter() {
+            assert!(Ifsc::is_valid(code), "{}", code);
+        }
+
+        let ifsc = Ifsc::new("hdfc0000128")?;
+        assert_eq!(ifsc.bank_code(), "HDFC");
+        assert_eq!(ifsc.branch_code(), "000128");
+
+        assert_eq!(
+            serde_json::from_str::<Ifsc>("\"HDFC0000128\"").ok(),
+            Some(ifsc)
+        );
+        assert!(serde_json::from_str::<Ifsc>("\"HDFC1000128\"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn directory() -> Result<()> {
+        let directory = IfscDirectory::parse(
+            "BANK,IFSC,BRANCH,ADDRESS,CITY,STATE,MICR\n\
+             HDFC Bank,HDFC0000128,\"LUDHIANA - FEROZE GANDHI MARKET\",\"SCO 24, FEROZE GANDHI MARKET\",LUDHIANA,PUNJAB,141240002\n\
+             State Bank of India,SBIN0005943,\"\"\"IFB\"\" DELHI\",,NEW DELHI,DELHI,NA\n",
+        )?;
+
+        let branch = directory.find(&"HDFC0000128".into());
+        assert_eq!(
+            branch.map(|b| b.address.as_str()),
+            Some("SCO 24, FEROZE GANDHI MARKET")
+        );
+        assert_eq!(branch.and_then(|b| b.micr.as_deref()), Some("141240002"));
+        assert_eq!(
+            directory
+                .find(&"SBIN0005943".into())
+                .map(|b| b.branch.as_str()),
+            Some("\"IFB\" DELHI")
+        );
+        assert!(directory.validate("SBIN0005943").is_ok());
+        assert!(directory.validate("SBIN0000001").is_err());
+        assert!(IfscDirectory::parse("BANK,BRANCH\n").is_err());
+
+        Ok(())
+    }
+}
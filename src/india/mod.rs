@@ -0,0 +1,8 @@
+//! Indian payment standards. NOTE: the module is not named after the
+//! country code, `in` being a keyword.
+
+pub mod ifsc;
+pub mod upi;
+
+pub use ifsc::*;
+pub use upi::*;
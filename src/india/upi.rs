@@ -0,0 +1,196 @@
+//! UPI (Unified Payments Interface) virtual payment addresses.
+
+use crate::error::{check_chars, check_length, Error, Reason};
+use crate::result::Result;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `MAX_LENGTH` is the max length of a `Vpa`.
+pub const MAX_LENGTH: usize = 255;
+
+/// `KNOWN_HANDLES` are handles of the main PSPs (payment service
+/// providers) and banks, as of 2026.
+pub const KNOWN_HANDLES: &[&str] = &[
+    "abfspay",
+    "airtel",
+    "apl",
+    "axisbank",
+    "axl",
+    "barodampay",
+    "boi",
+    "cnrb",
+    "federal",
+    "fbl",
+    "hdfcbank",
+    "hsbc",
+    "ibl",
+    "icici",
+    "idbi",
+    "idfcbank",
+    "ikwik",
+    "indus",
+    "jio",
+    "kotak",
+    "okaxis",
+    "okhdfcbank",
+    "okicici",
+    "oksbi",
+    "paytm",
+    "pnb",
+    "ptaxis",
+    "pthdfc",
+    "ptsbi",
+    "ptyes",
+    "rbl",
+    "sbi",
+    "sc",
+    "timecosmos",
+    "ubi",
+    "upi",
+    "utbi",
+    "yapl",
+    "ybl",
+    "yesbank",
+];
+
+/// [`Vpa`] is a UPI virtual payment address, "user@handle", e.g.
+/// "ramesh.kumar@okhdfcbank".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vpa {
+    user: String,
+    handle: String,
+}
+
+impl Vpa {
+    /// `new` creates a new `Vpa`, in lower case.
+    pub fn new(vpa: &str) -> Result<Vpa> {
+        let vpa = vpa.trim().to_lowercase();
+
+        Vpa::check(&vpa)?;
+
+        let (user, handle) = vpa.split_once('@').unwrap_or_default();
+
+        Ok(Vpa {
+            user: user.into(),
+            handle: handle.into(),
+        })
+    }
+
+    /// `user` returns the user part of the `Vpa`.
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// `handle` returns the handle of the PSP of the `Vpa`.
+    pub fn handle(&self) -> &str {
+        &self.handle
+    }
+
+    /// `check` checks a provided address, returning why it is invalid.
+    pub fn check(vpa: &str) -> Result<()> {
+        let (user, handle) = vpa.split_once('@').ok_or_else(|| {
+            Error::invalid("VPA", vpa, Reason::Format("expected user@handle".into()))
+        })?;
+
+        check_length("VPA", vpa, 0, MAX_LENGTH)?;
+        check_length("VPA user", user, 2, MAX_LENGTH).map_err(|e| e.within("VPA", vpa, 0))?;
+        check_chars("VPA", user, "a letter, a digit, '.', '-' or '_'", |c| {
+            c.is_ascii_alphanumeric() || ".-_".contains(c)
+        })
+        .map_err(|e| e.within("VPA", vpa, 0))?;
+        check_length("VPA handle", handle, 2, MAX_LENGTH).map_err(|e| e.within("VPA", vpa, 0))?;
+        check_chars("VPA", handle, "a letter", |c| c.is_ascii_alphabetic())
+            .map_err(|e| e.within("VPA", vpa, user.chars().count() + 1))
+    }
+
+    /// `is_valid` returns if a provided address is valid by checking its
+    /// length and format: a user of at least two chars among letters,
+    /// digits, '.', '-' and '_', then '@' and a handle of letters.
+    pub fn is_valid(vpa: &str) -> bool {
+        Vpa::check(vpa).is_ok()
+    }
+
+    /// `validate` validates the [`Vpa`].
+    pub fn validate(&self) -> Result<()> {
+        Vpa::check(&self.to_string())
+    }
+
+    /// `is_known_handle` returns if the handle is one of a known PSP, see
+    /// [`KNOWN_HANDLES`].
+    pub fn is_known_handle(&self) -> bool {
+        KNOWN_HANDLES.contains(&self.handle.as_str())
+    }
+}
+
+impl fmt::Display for Vpa {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.user, self.handle)
+    }
+}
+
+impl FromStr for Vpa {
+    type Err = Error;
+
+    fn from_str(vpa: &str) -> StdResult<Self, Self::Err> {
+        Vpa::new(vpa)
+    }
+}
+
+impl From<&str> for Vpa {
+    fn from(vpa: &str) -> Self {
+        Vpa::from_str(vpa).unwrap()
+    }
+}
+
+impl Serialize for Vpa {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for Vpa {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Result, Vpa};
+
+    #[test]
+    fn is_valid() -> Result<()> {
+        const WRONG_VPAS: &[&str] = &[
+            "ramesh",
+            "r@okhdfcbank",
+            "ramesh@",
+            "ramesh@ok1",
+            "ra mesh@upi",
+            "a@b@upi",
+        ];
+        const VALID_VPAS: &[&str] = &[
+            "ramesh.kumar@okhdfcbank",
+            "9876543210@ybl",
+            "shop_12-a@paytm",
+        ];
+
+        for vpa in WRONG_VPAS.iter() {
+            assert!(!Vpa::is_valid(vpa), "{}", vpa);
+        }
+
+        for vpa in VALID_VPAS.iter() {
+            assert!(Vpa::is_valid(vpa), "{}", vpa);
+        }
+
+        let vpa = Vpa::new("Ramesh.Kumar@OkHdfcBank")?;
+        assert_eq!(vpa.user(), "ramesh.kumar");
+        assert_eq!(vpa.handle(), "okhdfcbank");
+        assert_eq!(vpa.to_string(), "ramesh.kumar@okhdfcbank");
+        assert!(vpa.is_known_handle());
+        assert!(!Vpa::new("ramesh@unknownpsp")?.is_known_handle());
+
+        Ok(())
+    }
+}
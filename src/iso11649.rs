@@ -0,0 +1,227 @@
+//! Structured creditor reference (RF reference) as defined by ISO 11649.
+
+use crate::error::{check_chars, check_length, Error, Reason};
+use crate::iso13616::{mod97, CheckDigits};
+use crate::result::Result;
+use crate::xml::Element;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `REFERENCE_MAX_LENGTH` is the maximum length of the reference, without
+/// the "RF" prefix and the check digits.
+pub const REFERENCE_MAX_LENGTH: usize = 21;
+
+/// [`CreditorReference`] is an RF creditor reference, e.g.
+/// "RF18539007547034": "RF", two ISO 7064 MOD 97-10 check digits and up to
+/// 21 alphanumeric chars.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CreditorReference {
+    pub check: CheckDigits,
+    /// `reference` is the reference, without the "RF" prefix and the check
+    /// digits.
+    pub reference: String,
+}
+
+impl CreditorReference {
+    /// `new` creates a new `CreditorReference` from a string code, either in
+    /// electronic format ("RF18539007547034") or in print format
+    /// ("RF18 5390 0754 7034").
+    pub fn new(code: &str) -> Result<CreditorReference> {
+        let cd = CreditorReference::electronic(code);
+
+        CreditorReference::check(&cd)?;
+
+        Ok(CreditorReference {
+            check: CheckDigits::new(&cd[2..4])?,
+            reference: cd[4..].into(),
+        })
+    }
+
+    /// `generate` creates a new `CreditorReference` from a reference, e.g.
+    /// an invoice number. Spaces and the usual separators ('-', '/', '.')
+    /// are removed, letters are uppercased and the check digits computed.
+    pub fn generate(reference: &str) -> Result<CreditorReference> {
+        let reference: String = reference
+            .chars()
+            .filter(|c| !c.is_whitespace() && !"-/.".contains(*c))
+            .collect::<String>()
+            .to_uppercase();
+
+        check_length("reference", &reference, 1, REFERENCE_MAX_LENGTH)?;
+        check_chars("reference", &reference, "a letter or a digit", |c| {
+            c.is_ascii_alphanumeric()
+        })?;
+
+        Ok(CreditorReference {
+            check: CheckDigits::compute(&format!("{}RF", reference))?,
+            reference,
+        })
+    }
+
+    /// `check` checks a provided code, in electronic or print format,
+    /// returning why it is invalid. Char indexes are those of the
+    /// electronic format.
+    pub fn check(code: &str) -> Result<()> {
+        let cd = CreditorReference::electronic(code);
+
+        check_length("creditor reference", &cd, 5, REFERENCE_MAX_LENGTH + 4)?;
+        check_chars("creditor reference", &cd, "a letter or a digit", |c| {
+            c.is_ascii_alphanumeric()
+        })?;
+
+        if !cd.starts_with("RF") {
+            return Err(Error::invalid(
+                "creditor reference",
+                &cd,
+                Reason::Format("expected the RF prefix".into()),
+            ));
+        }
+
+        CheckDigits::check(&cd[2..4]).map_err(|e| e.within("creditor reference", &cd, 2))?;
+
+        if mod97(&format!("{}{}", &cd[4..], &cd[0..4])) != Some(1) {
+            let expected = CheckDigits::compute(&format!("{}RF", &cd[4..]))?;
+
+            return Err(Error::invalid(
+                "creditor reference",
+                &cd,
+                Reason::Checksum {
+                    expected: expected.as_str().into(),
+                    actual: cd[2..4].into(),
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `is_valid` returns if a provided code is valid by checking the
+    /// prefix, the length, the kind of chars used and the ISO 7064
+    /// MOD 97-10 check digits.
+    pub fn is_valid(code: &str) -> bool {
+        CreditorReference::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`CreditorReference`].
+    pub fn validate(&self) -> Result<()> {
+        CreditorReference::check(&self.to_string())
+    }
+
+    /// `print` returns the `CreditorReference` in print format, in groups of
+    /// four chars.
+    pub fn print(&self) -> String {
+        let code = self.to_string();
+        let chars: Vec<char> = code.chars().collect();
+
+        chars
+            .chunks(4)
+            .map(|c| c.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// `to_element` builds the ISO 20022 `CdtrRefInf` element of the
+    /// reference, typed as an ISO structured creditor reference (SCOR).
+    pub fn to_element(&self) -> Element {
+        Element::new("CdtrRefInf")
+            .with_child(
+                Element::new("Tp")
+                    .with_child(Element::new("CdOrPrtry").with_leaf("Cd", "SCOR"))
+                    .with_leaf("Issr", "ISO"),
+            )
+            .with_leaf("Ref", &self.to_string())
+    }
+
+    fn electronic(code: &str) -> String {
+        code.chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_uppercase()
+    }
+}
+
+impl fmt::Display for CreditorReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RF{}{}", self.check.as_str(), self.reference)
+    }
+}
+
+impl FromStr for CreditorReference {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        CreditorReference::new(code)
+    }
+}
+
+impl From<&str> for CreditorReference {
+    fn from(code: &str) -> Self {
+        CreditorReference::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for CreditorReference {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for CreditorReference {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{CreditorReference, Result};
+
+    #[test]
+    fn is_valid() {
+        const WRONG_CODES: &[&str] = &[
+            "RF19539007547034",
+            "RF18",
+            "RF18 5390 0754 7034!",
+            "XX18539007547034",
+            "RF0412345678901234567890123",
+        ];
+        const VALID_CODES: &[&str] = &[
+            "RF18539007547034",
+            "RF18 5390 0754 7034",
+            "rf18539007547034",
+            "RF712348231",
+        ];
+
+        for code in WRONG_CODES.iter() {
+            assert!(!CreditorReference::is_valid(code), "{}", code);
+        }
+
+        for code in VALID_CODES.iter() {
+            assert!(CreditorReference::is_valid(code), "{}", code);
+        }
+    }
+
+    #[test]
+    fn generate() -> Result<()> {
+        let reference = CreditorReference::generate("5390-0754/7034")?;
+
+        assert_eq!(reference.to_string(), "RF18539007547034");
+        assert_eq!(reference.reference, "539007547034");
+        assert_eq!(reference.print(), "RF18 5390 0754 7034");
+        assert_eq!(CreditorReference::new(&reference.print())?, reference);
+        assert_eq!(
+            CreditorReference::generate("inv 2024 001")?.to_string(),
+            CreditorReference::generate("INV-2024-001")?.to_string()
+        );
+        assert!(CreditorReference::generate("INVOICE_1").is_err());
+        assert!(CreditorReference::generate("1234567890123456789012").is_err());
+
+        let element = reference.to_element();
+        assert_eq!(element.find_text("Tp/CdOrPrtry/Cd"), Some("SCOR"));
+        assert_eq!(element.find_text("Ref"), Some("RF18539007547034"));
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 //! IBAN's BBAN (Basic Bank Account Number), as defined by ISO 13616.
 
-use crate::error::Error;
+use crate::error::{check_chars, check_length, Error};
 use crate::result::Result;
 use serde::{Deserialize, Serialize};
 use std::result::Result as StdResult;
@@ -9,7 +9,7 @@ use std::str::FromStr;
 /// `MAX_LENGTH` is a `BBAN` code maximum length.
 pub const MAX_LENGTH: usize = 30;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct BBAN(String);
 
 impl BBAN {
@@ -18,9 +18,7 @@ impl BBAN {
         // can be lower/mixed-case
         let cd: &str = &code.to_uppercase();
 
-        if !BBAN::is_valid(cd) {
-            return Err(Error::InvalidCode);
-        }
+        BBAN::check(cd)?;
 
         Ok(BBAN(cd.into()))
     }
@@ -30,23 +28,27 @@ impl BBAN {
         self.0.as_str()
     }
 
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        // NOTE: we only use upper-case codes, but a BBAN
+        // can be lower/mixed-case
+        check_length("BBAN", code, 0, MAX_LENGTH)?;
+        check_chars("BBAN", code, "a letter or a digit", |c| {
+            c.is_ascii_alphanumeric()
+        })
+    }
+
     /// `is_valid` returns if a provided code is valid
     /// by checking length and kind of chars used.
     pub fn is_valid(code: &str) -> bool {
-        // NOTE: we only use upper-case codes, but a BBAN
-        // can be lower/mixed-case
-        code.len() <= MAX_LENGTH && code.find(|c: char| !c.is_ascii_alphanumeric()) == None
+        BBAN::check(code).is_ok()
     }
 
     /// `validate` validates the [`BBAN`]. Here only length and digites
     /// used are checked, not if the code is actually representative of a
     /// used code.
     pub fn validate(&self) -> Result<()> {
-        if !BBAN::is_valid(self.as_str()) {
-            return Err(Error::InvalidCode);
-        }
-
-        Ok(())
+        BBAN::check(self.as_str())
     }
 }
 
@@ -58,7 +60,7 @@ impl FromStr for BBAN {
     }
 }
 
-impl<'a> From<&'a str> for BBAN {
+impl From<&str> for BBAN {
     fn from(code: &str) -> Self {
         BBAN::from_str(code).unwrap()
     }
@@ -1,21 +1,19 @@
 //! IBAN's check digits, as defined by ISO 13616.
 
 use crate::code::Code;
-use crate::error::Error;
+use crate::error::{check_chars, check_digits, Error};
 use crate::result::Result;
 use arraystring::typenum::U2;
 use serde::{de::Deserializer, ser::Serializer, Deserialize, Serialize};
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CheckDigits(Code<U2>);
 
 impl CheckDigits {
     pub fn new(code: &str) -> Result<CheckDigits> {
-        if !CheckDigits::is_valid(code) {
-            return Err(Error::InvalidCode);
-        }
+        CheckDigits::check(code)?;
 
         Ok(CheckDigits(Code::<U2>::from(code)))
     }
@@ -25,22 +23,56 @@ impl CheckDigits {
         self.0.as_str()
     }
 
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_digits("check digits", code, 2, 2)
+    }
+
     /// `is_valid` returns if a provided code is valid
     /// by checking length and kind of chars used.
     pub fn is_valid(code: &str) -> bool {
-        Code::<U2>::is_valid(code) && code.find(|c: char| !c.is_ascii_digit()) == None
+        CheckDigits::check(code).is_ok()
+    }
+
+    /// `compute` computes the [`CheckDigits`] of an alphanumeric string
+    /// following ISO 7064 MOD 97-10. The string is the one the check digits
+    /// are going to be prepended to, e.g. the `BBAN` followed by the country
+    /// code.
+    pub fn compute(code: &str) -> Result<CheckDigits> {
+        check_chars("code", code, "a letter or a digit", |c| {
+            c.is_ascii_alphanumeric()
+        })?;
+
+        let rem = mod97(&format!("{}00", code)).unwrap_or_default();
+
+        CheckDigits::new(&format!("{:02}", 98 - rem))
     }
 
     /// `validate` validates the [`CheckDigits`]. Here only length and digites
     /// used are checked, not if the code is actually representative of a
     /// used code.
     pub fn validate(&self) -> Result<()> {
-        if !CheckDigits::is_valid(self.as_str()) {
-            return Err(Error::InvalidCode);
-        }
+        CheckDigits::check(self.as_str())
+    }
+}
 
-        Ok(())
+/// `mod97` returns the ISO 7064 MOD 97-10 remainder of an alphanumeric
+/// string, where letters are converted to numbers (A = 10, ..., Z = 35).
+/// It returns `None` if the string contains any other char.
+pub fn mod97(code: &str) -> Option<u32> {
+    let mut rem: u32 = 0;
+
+    for c in code.chars() {
+        let n = c.to_digit(36)?;
+
+        rem = if n < 10 {
+            (rem * 10 + n) % 97
+        } else {
+            (rem * 100 + n) % 97
+        };
     }
+
+    Some(rem)
 }
 
 impl FromStr for CheckDigits {
@@ -51,7 +83,7 @@ impl FromStr for CheckDigits {
     }
 }
 
-impl<'a> From<&'a str> for CheckDigits {
+impl From<&str> for CheckDigits {
     fn from(code: &str) -> Self {
         CheckDigits::from_str(code).unwrap()
     }
@@ -73,7 +105,7 @@ impl<'a> Deserialize<'a> for CheckDigits {
 
 mod test {
     #[allow(unused_imports)] // TODO
-    use super::{CheckDigits, Result};
+    use super::{mod97, CheckDigits, Result};
 
     #[test]
     fn is_valid() {
@@ -88,4 +120,14 @@ mod test {
             assert!(CheckDigits::is_valid(code));
         }
     }
+
+    #[test]
+    fn compute() -> Result<()> {
+        assert_eq!(CheckDigits::compute("WEST12345698765432GB")?.as_str(), "82");
+        assert_eq!(CheckDigits::compute("370400440532013000DE")?.as_str(), "89");
+        assert_eq!(mod97("WEST12345698765432GB82"), Some(1));
+        assert_eq!(mod97("WEST-1234"), None);
+
+        Ok(())
+    }
 }
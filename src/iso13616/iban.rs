@@ -1,12 +1,197 @@
 //! International Bank Account Number (IBAN) as defined by ISO 13616.
 
-use crate::iso13616::{CheckDigits, BBAN};
+use crate::error::{check_chars, check_length, Error, Reason};
+use crate::iso13616::{mod97, CheckDigits, BBAN};
 use crate::iso3166::CountryCode;
+use crate::result::Result;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+/// `IBAN_MAX_LENGTH` is an `IBAN` code maximum length, in electronic format.
+pub const IBAN_MAX_LENGTH: usize = 34;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct IBAN {
     pub country: CountryCode,
     pub check: CheckDigits,
     pub bban: BBAN,
 }
+
+impl IBAN {
+    /// `new` creates a new `IBAN` from a string code, either in electronic
+    /// format ("DE89370400440532013000") or in print format
+    /// ("DE89 3704 0044 0532 0130 00").
+    pub fn new(code: &str) -> Result<IBAN> {
+        let cd = IBAN::electronic(code);
+
+        IBAN::check(&cd)?;
+
+        Ok(IBAN {
+            country: CountryCode::new(&cd[0..2])?,
+            check: CheckDigits::new(&cd[2..4])?,
+            bban: BBAN::new(&cd[4..])?,
+        })
+    }
+
+    /// `check` checks a provided code, in electronic or print format,
+    /// returning why it is invalid. Char indexes are those of the electronic
+    /// format.
+    pub fn check(code: &str) -> Result<()> {
+        let cd = IBAN::electronic(code);
+
+        check_length("IBAN", &cd, 5, IBAN_MAX_LENGTH)?;
+        check_chars("IBAN", &cd, "a letter or a digit", |c| {
+            c.is_ascii_alphanumeric()
+        })?;
+        CountryCode::check(&cd[0..2]).map_err(|e| e.within("IBAN", &cd, 0))?;
+        CheckDigits::check(&cd[2..4]).map_err(|e| e.within("IBAN", &cd, 2))?;
+        BBAN::check(&cd[4..]).map_err(|e| e.within("IBAN", &cd, 4))?;
+
+        if mod97(&format!("{}{}", &cd[4..], &cd[0..4])) != Some(1) {
+            let expected = CheckDigits::compute(&format!("{}{}", &cd[4..], &cd[0..2]))?;
+
+            return Err(Error::invalid(
+                "IBAN",
+                &cd,
+                Reason::Checksum {
+                    expected: expected.as_str().into(),
+                    actual: cd[2..4].into(),
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `is_valid` returns if a provided code is valid by checking length,
+    /// kind of chars used and the ISO 7064 MOD 97-10 check digits.
+    pub fn is_valid(code: &str) -> bool {
+        IBAN::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`IBAN`].
+    pub fn validate(&self) -> Result<()> {
+        IBAN::check(&self.to_string())
+    }
+
+    /// `print` returns the `IBAN` in print format, in groups of four chars.
+    pub fn print(&self) -> String {
+        let code = self.to_string();
+        let chars: Vec<char> = code.chars().collect();
+
+        chars
+            .chunks(4)
+            .map(|c| c.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn electronic(code: &str) -> String {
+        code.chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_uppercase()
+    }
+}
+
+impl fmt::Display for IBAN {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.country.as_str(),
+            self.check.as_str(),
+            self.bban.as_str()
+        )
+    }
+}
+
+impl FromStr for IBAN {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        IBAN::new(code)
+    }
+}
+
+impl From<&str> for IBAN {
+    fn from(code: &str) -> Self {
+        IBAN::from_str(code).unwrap()
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Result, IBAN};
+    #[allow(unused_imports)] // TODO
+    use crate::error::Reason;
+
+    #[test]
+    fn is_valid() {
+        const WRONG_CODES: &[&str] = &[
+            "DE88370400440532013000",
+            "GB82WEST1234569876543",
+            "XX",
+            "DE89370400440532013000!",
+            "GB82 WEST 1234 5698 7654 3200 0000 0000 0",
+        ];
+        const VALID_CODES: &[&str] = &[
+            "DE89370400440532013000",
+            "GB82 WEST 1234 5698 7654 32",
+            "gb82west12345698765432",
+            "CH9300762011623852957",
+            "NO9386011117947",
+        ];
+
+        for code in WRONG_CODES.iter() {
+            assert!(!IBAN::is_valid(code));
+        }
+
+        for code in VALID_CODES.iter() {
+            assert!(IBAN::is_valid(code));
+        }
+    }
+
+    #[test]
+    fn check() {
+        assert_eq!(
+            IBAN::check("DE88 3704 0044 0532 0130 00")
+                .unwrap_err()
+                .reason(),
+            Some(&Reason::Checksum {
+                expected: "89".into(),
+                actual: "88".into()
+            })
+        );
+        assert_eq!(
+            IBAN::check("DE8937040044053201300!").unwrap_err().reason(),
+            Some(&Reason::Character {
+                index: 21,
+                found: '!',
+                expected: "a letter or a digit"
+            })
+        );
+        assert_eq!(
+            IBAN::check("XX").unwrap_err().reason(),
+            Some(&Reason::Length {
+                min: 5,
+                max: 34,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn print() -> Result<()> {
+        let iban = IBAN::new("DE89370400440532013000")?;
+
+        assert_eq!(iban.country.as_str(), "DE");
+        assert_eq!(iban.bban.as_str(), "370400440532013000");
+        assert_eq!(iban.to_string(), "DE89370400440532013000");
+        assert_eq!(iban.print(), "DE89 3704 0044 0532 0130 00");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,401 @@
+//! Account identification of ISO 20022 messages, unifying the account
+//! schemes of the crate: IBAN, or another identification with its scheme.
+
+use crate::aba::RoutingNumber;
+use crate::error::{check_chars, check_length, Error, Reason};
+use crate::gb::sortcode::{AccountNumber, SortCode};
+use crate::iso13616::IBAN;
+use crate::mx::clabe::Clabe;
+use crate::result::Result;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `ABA_SCHEME` is the proprietary scheme name of US accounts, the code of
+/// the Fedwire clearing system.
+pub const ABA_SCHEME: &str = "USABA";
+
+/// `SORT_CODE_SCHEME` is the proprietary scheme name of UK accounts, the
+/// code of the UK domestic sort code clearing system.
+pub const SORT_CODE_SCHEME: &str = "GBDSC";
+
+/// `CLABE_SCHEME` is the proprietary scheme name of Mexican accounts.
+pub const CLABE_SCHEME: &str = "CLABE";
+
+/// `ID_MAX_LENGTH` is the max length of an other identification.
+pub const ID_MAX_LENGTH: usize = 34;
+
+/// `ABA_ACCOUNT_MAX_LENGTH` is the max length of a US account number.
+const ABA_ACCOUNT_MAX_LENGTH: usize = 17;
+
+/// `is_iban_shaped` returns if a compact code looks like an IBAN: two
+/// letters, two digits and alphanumeric chars.
+fn is_iban_shaped(code: &str) -> bool {
+    let chars: Vec<char> = code.chars().collect();
+
+    chars.len() > 4
+        && chars[..2].iter().all(|c| c.is_ascii_alphabetic())
+        && chars[2..4].iter().all(|c| c.is_ascii_digit())
+        && chars[4..].iter().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// [`SchemeName`] is the scheme of an other identification, `SchmeNm`:
+/// an external code, or a proprietary name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SchemeName {
+    /// `Code` is a code of the ISO 20022 external account identification
+    /// code list, up to 4 chars, e.g. "BBAN".
+    #[serde(rename = "Cd")]
+    Code(String),
+    /// `Proprietary` is a proprietary name, up to 35 chars.
+    #[serde(rename = "Prtry")]
+    Proprietary(String),
+}
+
+impl SchemeName {
+    /// `validate` validates the length of the [`SchemeName`].
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            SchemeName::Code(code) => check_length("scheme name code", code, 1, 4),
+            SchemeName::Proprietary(name) => check_length("proprietary scheme name", name, 1, 35),
+        }
+    }
+}
+
+/// [`AccountIdentifier`] is the identification of an account, in any of
+/// the schemes supported by the crate. Other than IBAN, accounts are
+/// identified in ISO 20022 by an other identification, `Othr`, whose id
+/// is:
+/// - the routing number and the account number for US accounts,
+/// - the sort code and the account number for UK accounts,
+/// - the CLABE for Mexican accounts.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AccountIdentifier {
+    Iban(IBAN),
+    Aba {
+        routing_number: RoutingNumber,
+        /// `account` is the account number, up to 17 chars.
+        account: String,
+    },
+    SortCode {
+        sort_code: SortCode,
+        account: AccountNumber,
+    },
+    Clabe(Clabe),
+    /// `Proprietary` is any other identification, up to 34 chars.
+    Proprietary {
+        id: String,
+        scheme: Option<SchemeName>,
+    },
+}
+
+impl AccountIdentifier {
+    /// `detect` creates a new `AccountIdentifier` from a raw string,
+    /// detecting its scheme, in order:
+    /// - a valid IBAN, in electronic or print format,
+    /// - a valid CLABE, with or without spaces,
+    /// - a valid routing number, a ' ' or '/' separator and an account
+    ///   number,
+    /// - fourteen digits, with or without separators, are a sort code and
+    ///   an account number,
+    /// - any other identification.
+    ///
+    /// An input shaped as an IBAN (two letters, two digits and alphanumeric
+    /// chars) or as a CLABE (eighteen digits) that is not valid returns why,
+    /// rather than becoming another identification.
+    pub fn detect(raw: &str) -> Result<AccountIdentifier> {
+        let raw = raw.trim();
+        let compact: String = raw
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect();
+
+        // NOTE: a routing number and a 5 digits account number are also
+        // fourteen digits, so the separated ABA form goes first.
+        let aba = raw
+            .split_once([' ', '/'])
+            .filter(|(routing, _)| RoutingNumber::is_valid(routing));
+
+        let res = if is_iban_shaped(&compact) {
+            AccountIdentifier::Iban(IBAN::new(&compact)?)
+        } else if Clabe::is_valid(&compact) {
+            AccountIdentifier::Clabe(Clabe::new(&compact)?)
+        } else if let Some((routing, account)) = aba {
+            AccountIdentifier::Aba {
+                routing_number: RoutingNumber::new(routing)?,
+                account: account.trim().into(),
+            }
+        } else if compact.len() == 18 && compact.chars().all(|c| c.is_ascii_digit()) {
+            AccountIdentifier::Clabe(Clabe::new(&compact)?)
+        } else if compact.len() == 14 && compact.chars().all(|c| c.is_ascii_digit()) {
+            AccountIdentifier::SortCode {
+                sort_code: SortCode::new(&compact[..6])?,
+                account: AccountNumber::new(&compact[6..])?,
+            }
+        } else {
+            AccountIdentifier::Proprietary {
+                id: raw.into(),
+                scheme: None,
+            }
+        };
+
+        res.validate()?;
+
+        Ok(res)
+    }
+
+    /// `id` returns the identification, the IBAN in electronic format or
+    /// the id of the other identification.
+    pub fn id(&self) -> String {
+        match self {
+            AccountIdentifier::Iban(iban) => iban.to_string(),
+            AccountIdentifier::Aba {
+                routing_number,
+                account,
+            } => format!("{}{}", routing_number.as_str(), account),
+            AccountIdentifier::SortCode { sort_code, account } => {
+                format!("{}{}", sort_code.as_str(), account.as_str())
+            }
+            AccountIdentifier::Clabe(clabe) => clabe.to_string(),
+            AccountIdentifier::Proprietary { id, .. } => id.clone(),
+        }
+    }
+
+    /// `scheme_name` returns the scheme of the other identification, none
+    /// for an IBAN.
+    pub fn scheme_name(&self) -> Option<SchemeName> {
+        let name = match self {
+            AccountIdentifier::Iban(_) => return None,
+            AccountIdentifier::Aba { .. } => ABA_SCHEME,
+            AccountIdentifier::SortCode { .. } => SORT_CODE_SCHEME,
+            AccountIdentifier::Clabe(_) => CLABE_SCHEME,
+            AccountIdentifier::Proprietary { scheme, .. } => return scheme.clone(),
+        };
+
+        Some(SchemeName::Proprietary(name.into()))
+    }
+
+    /// `validate` validates the [`AccountIdentifier`] with the rules of its
+    /// scheme. The modulus check of UK accounts needs the Vocalink rules,
+    /// see [`crate::gb::ModulusRules`].
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            AccountIdentifier::Iban(iban) => iban.validate(),
+            AccountIdentifier::Aba {
+                routing_number,
+                account,
+            } => {
+                routing_number.validate()?;
+
+                check_length("account number", account, 1, ABA_ACCOUNT_MAX_LENGTH)?;
+                check_chars("account number", account, "a letter or a digit", |c| {
+                    c.is_ascii_alphanumeric()
+                })
+            }
+            AccountIdentifier::SortCode { sort_code, account } => {
+                sort_code.validate()?;
+                account.validate()
+            }
+            AccountIdentifier::Clabe(clabe) => clabe.validate(),
+            AccountIdentifier::Proprietary { id, scheme } => {
+                if id.trim().is_empty() {
+                    return Err(Error::invalid(
+                        "account id",
+                        id,
+                        Reason::Format("blank id".into()),
+                    ));
+                }
+
+                check_length("account id", id, 1, ID_MAX_LENGTH)?;
+
+                scheme.as_ref().map(|s| s.validate()).unwrap_or(Ok(()))
+            }
+        }
+    }
+}
+
+impl fmt::Display for AccountIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl FromStr for AccountIdentifier {
+    type Err = Error;
+
+    fn from_str(raw: &str) -> StdResult<Self, Self::Err> {
+        AccountIdentifier::detect(raw)
+    }
+}
+
+impl From<&str> for AccountIdentifier {
+    fn from(raw: &str) -> Self {
+        AccountIdentifier::from_str(raw).unwrap()
+    }
+}
+
+/// [`Identification`] is the ISO 20022 form of an [`AccountIdentifier`],
+/// used by its serde implementation.
+#[derive(Serialize, Deserialize)]
+enum Identification {
+    #[serde(rename = "IBAN")]
+    Iban(String),
+    #[serde(rename = "Othr")]
+    Other(GenericIdentification),
+}
+
+#[derive(Serialize, Deserialize)]
+struct GenericIdentification {
+    #[serde(rename = "Id")]
+    id: String,
+    #[serde(rename = "SchmeNm", default, skip_serializing_if = "Option::is_none")]
+    scheme: Option<SchemeName>,
+}
+
+impl Identification {
+    fn to_account(&self) -> Result<AccountIdentifier> {
+        let (id, scheme) = match self {
+            Identification::Iban(iban) => return Ok(AccountIdentifier::Iban(IBAN::new(iban)?)),
+            Identification::Other(other) => (other.id.as_str(), &other.scheme),
+        };
+        let split = |at: usize| {
+            id.get(..at).zip(id.get(at..)).ok_or_else(|| {
+                Error::invalid(
+                    "account id",
+                    id,
+                    Reason::Format(format!("expected at least {} chars", at)),
+                )
+            })
+        };
+
+        let res = match scheme {
+            Some(SchemeName::Proprietary(name)) if name == ABA_SCHEME => {
+                let (routing, account) = split(9)?;
+
+                AccountIdentifier::Aba {
+                    routing_number: RoutingNumber::new(routing)?,
+                    account: account.into(),
+                }
+            }
+            Some(SchemeName::Proprietary(name)) if name == SORT_CODE_SCHEME => {
+                let (sort_code, account) = split(6)?;
+
+                AccountIdentifier::SortCode {
+                    sort_code: SortCode::new(sort_code)?,
+                    account: AccountNumber::new(account)?,
+                }
+            }
+            Some(SchemeName::Proprietary(name)) if name == CLABE_SCHEME => {
+                AccountIdentifier::Clabe(Clabe::new(id)?)
+            }
+            _ => AccountIdentifier::Proprietary {
+                id: id.into(),
+                scheme: scheme.clone(),
+            },
+        };
+
+        res.validate()?;
+
+        Ok(res)
+    }
+}
+
+impl Serialize for AccountIdentifier {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        match self {
+            AccountIdentifier::Iban(iban) => Identification::Iban(iban.to_string()),
+            _ => Identification::Other(GenericIdentification {
+                id: self.id(),
+                scheme: self.scheme_name(),
+            }),
+        }
+        .serialize(ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for AccountIdentifier {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        Identification::deserialize(des)?
+            .to_account()
+            .map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{AccountIdentifier, Result, SchemeName};
+
+    #[test]
+    fn detect() -> Result<()> {
+        const RAW_ACCOUNTS: &[(&str, &str)] = &[
+            ("DE89 3704 0044 0532 0130 00", "DE89370400440532013000"),
+            ("032 180 00011835971 9", "032180000118359719"),
+            ("60-16-13 31926819", "60161331926819"),
+            ("021000021/123456789", "021000021123456789"),
+            ("ACME-0001", "ACME-0001"),
+        ];
+
+        for (raw, id) in RAW_ACCOUNTS.iter() {
+            assert_eq!(AccountIdentifier::detect(raw)?.id(), *id);
+        }
+
+        assert!(matches!(
+            AccountIdentifier::detect("021000021 12345")?,
+            AccountIdentifier::Aba { ref account, .. } if account == "12345"
+        ));
+        assert!(matches!(
+            AccountIdentifier::detect("601613 31926819")?,
+            AccountIdentifier::SortCode { .. }
+        ));
+        assert!(AccountIdentifier::detect("021000021/1234-5678").is_err());
+        // NOTE: a typo in an IBAN or a CLABE is not another identification
+        assert!(AccountIdentifier::detect("DE88 3704 0044 0532 0130 00").is_err());
+        assert!(AccountIdentifier::detect("032180000118359710").is_err());
+        assert!(AccountIdentifier::detect("").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn serde() -> Result<()> {
+        const JSON_ACCOUNTS: &[(&str, &str)] = &[
+            (
+                "DE89370400440532013000",
+                r#"{"IBAN":"DE89370400440532013000"}"#,
+            ),
+            (
+                "021000021 123456789",
+                r#"{"Othr":{"Id":"021000021123456789","SchmeNm":{"Prtry":"USABA"}}}"#,
+            ),
+            (
+                "601613 31926819",
+                r#"{"Othr":{"Id":"60161331926819","SchmeNm":{"Prtry":"GBDSC"}}}"#,
+            ),
+            (
+                "032180000118359719",
+                r#"{"Othr":{"Id":"032180000118359719","SchmeNm":{"Prtry":"CLABE"}}}"#,
+            ),
+            ("ACME-0001", r#"{"Othr":{"Id":"ACME-0001"}}"#),
+        ];
+
+        for (raw, json) in JSON_ACCOUNTS.iter() {
+            let account = AccountIdentifier::detect(raw)?;
+            assert_eq!(serde_json::to_string(&account).unwrap_or_default(), *json);
+            assert_eq!(
+                serde_json::from_str::<AccountIdentifier>(json).ok(),
+                Some(account)
+            );
+        }
+
+        let account: AccountIdentifier =
+            serde_json::from_str(r#"{"Othr":{"Id":"12345678","SchmeNm":{"Cd":"BBAN"}}}"#).unwrap();
+        assert_eq!(account.scheme_name(), Some(SchemeName::Code("BBAN".into())));
+        assert!(serde_json::from_str::<AccountIdentifier>(
+            r#"{"Othr":{"Id":"032180000118359710","SchmeNm":{"Prtry":"CLABE"}}}"#
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,718 @@
+//! Postal addresses of ISO 20022 parties, with the structured, hybrid and
+//! unstructured profiles of the CBPR+ and HVPS+ market practices and a best
+//! effort parser of legacy address lines, as found in MT 50a/59a fields,
+//! SEPA `AdrLine` elements or ACH IAT addenda records.
+
+use crate::error::{check_length, Error, Reason};
+use crate::iso3166::{CountryCode, CountryCodes};
+use crate::result::Result;
+use crate::xml::Element;
+use serde::{Deserialize, Serialize};
+
+/// `POST_CODE_FORMATS` are the post code formats of some countries: '9' is
+/// a digit, 'A' a letter, '?' a digit or a letter, any other char is
+/// literal and alternative formats are separated by '|'.
+const POST_CODE_FORMATS: &[(&str, &str)] = &[
+    ("AT", "9999"),
+    ("AU", "9999"),
+    ("BE", "9999"),
+    ("BR", "99999-999|99999999"),
+    ("CA", "A9A 9A9|A9A9A9"),
+    ("CH", "9999"),
+    ("CN", "999999"),
+    ("CZ", "999 99|99999"),
+    ("DE", "99999"),
+    ("DK", "9999"),
+    ("ES", "99999"),
+    ("FI", "99999"),
+    ("FR", "99999"),
+    ("GB", "A9 9AA|A99 9AA|AA9 9AA|AA99 9AA|A9A 9AA|AA9A 9AA"),
+    ("IE", "A99 ????|A9A ????"),
+    ("IN", "999999"),
+    ("IT", "99999"),
+    ("JP", "999-9999|9999999"),
+    ("LU", "9999|L-9999"),
+    ("MX", "99999"),
+    ("NL", "9999 AA|9999AA"),
+    ("NO", "9999"),
+    ("NZ", "9999"),
+    ("PL", "99-999"),
+    ("PT", "9999-999"),
+    ("SE", "999 99|99999"),
+    ("SG", "999999"),
+    ("US", "99999|99999-9999"),
+];
+
+/// `NUMBER_FIRST` are the countries writing the building number before the
+/// street name, e.g. "10 Downing Street".
+const NUMBER_FIRST: &[&str] = &["AU", "CA", "FR", "GB", "IE", "NZ", "US"];
+
+/// `POST_CODE_LAST` are the countries writing the post code after the
+/// town, e.g. "London SW1A 2AA".
+const POST_CODE_LAST: &[&str] = &["AU", "CA", "GB", "IE", "US"];
+
+/// `SUB_DIVISION` are the countries writing the country subdivision between
+/// the town and the post code, e.g. "New York NY 10001".
+const SUB_DIVISION: &[&str] = &["AU", "CA", "US"];
+
+/// `POST_BOXES` are the usual prefixes of post box lines.
+const POST_BOXES: &[&str] = &["PO BOX", "P.O. BOX", "POSTFACH", "BP", "CASE POSTALE"];
+
+/// `ADDRESS_LINES` is the max number of address lines of an ISO 20022
+/// postal address.
+const ADDRESS_LINES: usize = 7;
+
+/// `HYBRID_ADDRESS_LINES` is the max number of address lines of a hybrid
+/// postal address.
+const HYBRID_ADDRESS_LINES: usize = 2;
+
+/// [`AddressProfile`] is a postal address profile of the CBPR+ and HVPS+
+/// market practices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AddressProfile {
+    /// Structured elements only, with at least the town and the country.
+    Structured,
+    /// Structured elements with at least the town and the country, and at
+    /// most two address lines.
+    Hybrid,
+    /// Address lines only, with an optional country.
+    Unstructured,
+}
+
+/// [`PostalAddress`] is an ISO 20022 postal address, with structured
+/// elements and/or free address lines.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub struct PostalAddress {
+    pub department: Option<String>,
+    pub sub_department: Option<String>,
+    pub street_name: Option<String>,
+    pub building_number: Option<String>,
+    pub building_name: Option<String>,
+    pub floor: Option<String>,
+    pub post_box: Option<String>,
+    pub room: Option<String>,
+    pub post_code: Option<String>,
+    pub town_name: Option<String>,
+    pub town_location_name: Option<String>,
+    pub district_name: Option<String>,
+    pub country_sub_division: Option<String>,
+    pub country: Option<CountryCode>,
+    pub address_lines: Vec<String>,
+}
+
+/// `join` joins the present parts with spaces.
+fn join(parts: &[&Option<String>]) -> Option<String> {
+    let parts: Vec<&str> = parts.iter().filter_map(|part| part.as_deref()).collect();
+    Some(parts.join(" ")).filter(|line| !line.is_empty())
+}
+
+/// `matches_format` returns if a post code matches one of the formats of
+/// [`POST_CODE_FORMATS`].
+fn matches_format(format: &str, post_code: &str) -> bool {
+    format.len() == post_code.len()
+        && format.chars().zip(post_code.chars()).all(|(f, c)| match f {
+            '9' => c.is_ascii_digit(),
+            'A' => c.is_ascii_alphabetic(),
+            '?' => c.is_ascii_alphanumeric(),
+            _ => f == c,
+        })
+}
+
+/// `post_code_formats` returns the post code formats of a country, if known.
+fn post_code_formats(country: Option<&CountryCode>) -> Option<&'static str> {
+    let country = country?;
+
+    POST_CODE_FORMATS
+        .iter()
+        .find(|(code, _)| *code == country.as_str())
+        .map(|(_, formats)| *formats)
+}
+
+/// `is_valid_post_code` returns if a post code has the format of the
+/// country. Any post code is valid for the countries without known format.
+pub fn is_valid_post_code(country: &CountryCode, post_code: &str) -> bool {
+    match post_code_formats(Some(country)) {
+        Some(formats) => formats
+            .split('|')
+            .any(|format| matches_format(format, post_code)),
+        None => !post_code.is_empty(),
+    }
+}
+
+/// `is_post_code` returns if a token looks like a post code of the country,
+/// or like any post code if the country or its format is unknown.
+fn is_post_code(country: Option<&CountryCode>, post_code: &str) -> bool {
+    match (country, post_code_formats(country)) {
+        (Some(country), Some(_)) => is_valid_post_code(country, post_code),
+        _ => {
+            post_code.len() <= 16
+                && post_code.chars().any(|c| c.is_ascii_digit())
+                && post_code
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        }
+    }
+}
+
+/// `is_building_number` returns if a token looks like a building number,
+/// e.g. "5", "12a" or "12-14".
+fn is_building_number(number: &str) -> bool {
+    number.len() <= 16
+        && number.starts_with(|c: char| c.is_ascii_digit())
+        && number
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '/')
+}
+
+/// `post_box` returns the number of a post box line, e.g. "PO BOX 123".
+fn post_box(line: &str) -> Option<&str> {
+    POST_BOXES.iter().find_map(|prefix| {
+        line.get(..prefix.len())
+            .filter(|start| start.eq_ignore_ascii_case(prefix))
+            .and_then(|_| line[prefix.len()..].strip_prefix(' '))
+            .map(str::trim)
+            .filter(|number| !number.is_empty())
+    })
+}
+
+impl PostalAddress {
+    /// `elements` returns the structured elements but the country, in
+    /// schema order, with their max length.
+    fn elements(&self) -> [(&'static str, &Option<String>, usize); 13] {
+        [
+            ("Dept", &self.department, 70),
+            ("SubDept", &self.sub_department, 70),
+            ("StrtNm", &self.street_name, 70),
+            ("BldgNb", &self.building_number, 16),
+            ("BldgNm", &self.building_name, 35),
+            ("Flr", &self.floor, 70),
+            ("PstBx", &self.post_box, 16),
+            ("Room", &self.room, 70),
+            ("PstCd", &self.post_code, 16),
+            ("TwnNm", &self.town_name, 35),
+            ("TwnLctnNm", &self.town_location_name, 35),
+            ("DstrctNm", &self.district_name, 35),
+            ("CtrySubDvsn", &self.country_sub_division, 35),
+        ]
+    }
+
+    /// `is_structured` returns if the address has at least a town and a
+    /// country, as required by CBPR+ for structured and hybrid addresses.
+    pub fn is_structured(&self) -> bool {
+        self.town_name.is_some() && self.country.is_some()
+    }
+
+    /// `profile` returns the profile the address is written with. The
+    /// address may still not be valid for it.
+    pub fn profile(&self) -> AddressProfile {
+        if self.elements().iter().all(|(_, value, _)| value.is_none()) {
+            AddressProfile::Unstructured
+        } else if self.address_lines.is_empty() {
+            AddressProfile::Structured
+        } else {
+            AddressProfile::Hybrid
+        }
+    }
+
+    /// `validate` validates the address for a profile: the lengths of the
+    /// elements, the elements required or forbidden by the profile and the
+    /// post code format of the country. Structured addresses are valid
+    /// hybrid addresses.
+    pub fn validate(&self, profile: AddressProfile) -> Result<()> {
+        for (tag, value, length) in self.elements().iter() {
+            if let Some(value) = value {
+                check_length(tag, value, 0, *length)?;
+            }
+        }
+
+        for line in self.address_lines.iter() {
+            check_length("AdrLine", line, 0, 70)?;
+        }
+
+        let max_lines = match profile {
+            AddressProfile::Structured => 0,
+            AddressProfile::Hybrid => HYBRID_ADDRESS_LINES,
+            AddressProfile::Unstructured => ADDRESS_LINES,
+        };
+
+        if self.address_lines.len() > max_lines {
+            return Err(Error::invalid_format(
+                "postal address",
+                None,
+                Reason::Format(format!(
+                    "{} address lines, {} at most for {:?} profile",
+                    self.address_lines.len(),
+                    max_lines,
+                    profile
+                )),
+            ));
+        }
+
+        if profile == AddressProfile::Unstructured {
+            if self.profile() != AddressProfile::Unstructured {
+                return Err(Error::invalid_format(
+                    "postal address",
+                    None,
+                    Reason::Format("structured elements in unstructured profile".into()),
+                ));
+            }
+
+            if self.address_lines.is_empty() {
+                return Err(Error::MissingElement("AdrLine".into()));
+            }
+
+            return Ok(());
+        }
+
+        if self.town_name.is_none() {
+            return Err(Error::MissingElement("TwnNm".into()));
+        }
+
+        let country = self
+            .country
+            .as_ref()
+            .ok_or_else(|| Error::MissingElement("Ctry".into()))?;
+
+        match self.post_code {
+            Some(ref post_code) if !is_valid_post_code(country, post_code) => Err(Error::invalid(
+                "PstCd",
+                post_code,
+                Reason::Format(format!("not a {} post code", country.as_str())),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// `street_line` returns the street name and the building number, in
+    /// the order of the country.
+    pub fn street_line(&self) -> Option<String> {
+        if self.is_country(NUMBER_FIRST) {
+            join(&[&self.building_number, &self.street_name])
+        } else {
+            join(&[&self.street_name, &self.building_number])
+        }
+    }
+
+    /// `town_line` returns the post code and the town, in the order of the
+    /// country, with the country subdivision for the countries writing it.
+    pub fn town_line(&self) -> Option<String> {
+        if !self.is_country(POST_CODE_LAST) {
+            return join(&[&self.post_code, &self.town_name]);
+        }
+
+        let town = match self.country_sub_division {
+            Some(_) if self.is_country(SUB_DIVISION) => {
+                join(&[&self.town_name, &self.country_sub_division])
+            }
+            _ => self.town_name.clone(),
+        };
+
+        join(&[&town, &self.post_code])
+    }
+
+    /// `street_lines` returns the lines of the address before the town
+    /// line, as free text.
+    pub fn street_lines(&self) -> Vec<String> {
+        let mut res: Vec<String> = vec![
+            self.department.clone(),
+            self.sub_department.clone(),
+            join(&[&self.building_name, &self.floor, &self.room]),
+            self.street_line(),
+            self.post_box
+                .as_ref()
+                .map(|number| format!("PO BOX {}", number)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        res.extend(self.address_lines.iter().cloned());
+        res.extend(join(&[&self.town_location_name, &self.district_name]));
+
+        res
+    }
+
+    /// `to_lines` returns the address as free lines, without the country,
+    /// e.g. for an MT 50K or 59 field or the ACH IAT addenda records.
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut res = self.street_lines();
+        res.extend(self.town_line());
+        res
+    }
+
+    /// `from_lines` parses legacy address lines into structured elements,
+    /// on a best effort basis: the town line, with its post code, is looked
+    /// for from the end and the street line before it. The other lines are
+    /// kept as address lines. The country, if not provided, is read from
+    /// the last line, as a code or a name. Without country or town, the
+    /// lines are kept as they are, as an unstructured address.
+    pub fn from_lines(lines: &[String], country: Option<CountryCode>) -> PostalAddress {
+        let mut lines: Vec<&str> = lines
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+        let mut res = PostalAddress {
+            country,
+            ..Default::default()
+        };
+
+        if res.country.is_none() {
+            let codes = CountryCodes::get().ok();
+            let country = lines.last().and_then(|line| {
+                let codes = codes.as_ref()?;
+
+                if line.len() == 2 && line.chars().all(|c| c.is_ascii_uppercase()) {
+                    CountryCode::new(line)
+                        .ok()
+                        .filter(|code| codes.exists(code))
+                } else {
+                    codes.find_exact(line).cloned()
+                }
+            });
+
+            if country.is_some() {
+                res.country = country;
+                lines.pop();
+            }
+        }
+
+        let unstructured = PostalAddress {
+            country: res.country.clone(),
+            address_lines: lines.iter().map(|line| line.to_string()).collect(),
+            ..Default::default()
+        };
+
+        if res.country.is_none() {
+            return unstructured;
+        }
+
+        match lines.iter().rposition(|line| res.parse_town(line)) {
+            Some(i) => {
+                lines.remove(i);
+            }
+            // NOTE: a last line without digits is the town of a country
+            // without post codes, e.g. "3/AE/DUBAI".
+            None => match lines.last() {
+                Some(line) if !line.contains(|c: char| c.is_ascii_digit()) => {
+                    res.town_name = Some(line.to_string());
+                    lines.pop();
+                }
+                _ => return unstructured,
+            },
+        }
+
+        for line in lines {
+            match post_box(line) {
+                Some(number) if res.post_box.is_none() => {
+                    res.post_box = Some(number.into());
+                }
+                _ if res.street_name.is_none() && res.parse_street(line) => {}
+                _ => res.address_lines.push(line.into()),
+            }
+        }
+
+        res
+    }
+
+    /// `is_country` returns if the country of the address is one of
+    /// `countries`.
+    fn is_country(&self, countries: &[&str]) -> bool {
+        self.country
+            .as_ref()
+            .map(|country| countries.contains(&country.as_str()))
+            .unwrap_or(false)
+    }
+
+    /// `parse_town` reads the post code, the town and the country
+    /// subdivision of a town line, returning if it is one.
+    fn parse_town(&mut self, line: &str) -> bool {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let post_code_last = self.is_country(POST_CODE_LAST);
+
+        // NOTE: the order of the country is tried first, and post codes
+        // made of two tokens before single token ones.
+        for last in [post_code_last, !post_code_last].iter() {
+            for n in [2, 1].iter() {
+                if tokens.len() <= *n {
+                    continue;
+                }
+
+                let (post_code, town) = if *last {
+                    let (town, post_code) = tokens.split_at(tokens.len() - n);
+                    (post_code.join(" "), town.to_vec())
+                } else {
+                    let (post_code, town) = tokens.split_at(*n);
+                    (post_code.join(" "), town.to_vec())
+                };
+
+                // NOTE: post codes are often prefixed by the country, as
+                // in "D-60311" or "CH-8001".
+                let post_code = match post_code.split_once('-') {
+                    Some((prefix, code))
+                        if !is_post_code(self.country.as_ref(), &post_code)
+                            && prefix.len() <= 2
+                            && prefix.chars().all(|c| c.is_ascii_alphabetic()) =>
+                    {
+                        code.to_string()
+                    }
+                    _ => post_code,
+                };
+
+                if !is_post_code(self.country.as_ref(), &post_code)
+                    || !town.iter().any(|t| t.contains(char::is_alphabetic))
+                {
+                    continue;
+                }
+
+                let mut town: Vec<&str> = town
+                    .iter()
+                    .map(|t| t.trim_end_matches(','))
+                    .filter(|t| !t.is_empty())
+                    .collect();
+
+                if *last && self.is_country(SUB_DIVISION) && town.len() > 1 {
+                    let division = town[town.len() - 1];
+
+                    if (2..=3).contains(&division.len())
+                        && division.chars().all(|c| c.is_ascii_uppercase())
+                    {
+                        self.country_sub_division = Some(division.into());
+                        town.pop();
+                    }
+                }
+
+                self.post_code = Some(post_code);
+                self.town_name = Some(town.join(" "));
+
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// `parse_street` reads the street name and the building number of a
+    /// street line, returning if it is one.
+    fn parse_street(&mut self, line: &str) -> bool {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        if tokens.len() < 2 {
+            return false;
+        }
+
+        let first = (tokens[0], tokens[1..].join(" "));
+        let last = (
+            tokens[tokens.len() - 1],
+            tokens[..tokens.len() - 1].join(" "),
+        );
+        let candidates = if self.is_country(NUMBER_FIRST) {
+            [first, last]
+        } else {
+            [last, first]
+        };
+
+        for (number, street) in candidates.iter() {
+            let number = number.trim_end_matches(',');
+            let street = street.trim_end_matches(',');
+
+            if is_building_number(number) && street.contains(char::is_alphabetic) {
+                self.street_name = Some(street.into());
+                self.building_number = Some(number.into());
+
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// `from_element` reads an address from its `PstlAdr` element.
+    pub fn from_element(element: &Element) -> Result<PostalAddress> {
+        let text = |path: &str| element.find_text(path).map(String::from);
+
+        Ok(PostalAddress {
+            department: text("Dept"),
+            sub_department: text("SubDept"),
+            street_name: text("StrtNm"),
+            building_number: text("BldgNb"),
+            building_name: text("BldgNm"),
+            floor: text("Flr"),
+            post_box: text("PstBx"),
+            room: text("Room"),
+            post_code: text("PstCd"),
+            town_name: text("TwnNm"),
+            town_location_name: text("TwnLctnNm"),
+            district_name: text("DstrctNm"),
+            country_sub_division: text("CtrySubDvsn"),
+            country: element.find_parsed("Ctry")?,
+            address_lines: element
+                .find_all("AdrLine")
+                .iter()
+                .map(|line| line.text().into())
+                .collect(),
+        })
+    }
+
+    /// `to_element` builds the `PstlAdr` element of the address.
+    pub fn to_element(&self) -> Element {
+        let mut res = Element::new("PstlAdr");
+
+        for (tag, value, _) in self.elements().iter() {
+            if let Some(value) = value {
+                res.push(Element::leaf(tag, value));
+            }
+        }
+
+        if let Some(ref country) = self.country {
+            res.push(Element::leaf("Ctry", country.as_str()));
+        }
+
+        for line in self.address_lines.iter() {
+            res.push(Element::leaf("AdrLine", line));
+        }
+
+        res
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{is_valid_post_code, AddressProfile, PostalAddress};
+    #[allow(unused_imports)] // TODO
+    use crate::iso3166::CountryCode;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+    #[allow(unused_imports)] // TODO
+    use crate::xml::Element;
+
+    #[test]
+    fn validate() -> Result<()> {
+        let mut address = PostalAddress {
+            street_name: Some("Rue de la Loi".into()),
+            building_number: Some("1".into()),
+            post_code: Some("1000".into()),
+            town_name: Some("Bruxelles".into()),
+            country: Some("BE".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(address.profile(), AddressProfile::Structured);
+        assert!(address.validate(AddressProfile::Structured).is_ok());
+        assert!(address.validate(AddressProfile::Hybrid).is_ok());
+        assert!(address.validate(AddressProfile::Unstructured).is_err());
+
+        address.address_lines = vec!["Building B".into(), "Floor 3".into()];
+        assert_eq!(address.profile(), AddressProfile::Hybrid);
+        assert!(address.validate(AddressProfile::Structured).is_err());
+        assert!(address.validate(AddressProfile::Hybrid).is_ok());
+
+        address.address_lines.push("Room 12".into());
+        assert!(address.validate(AddressProfile::Hybrid).is_err());
+
+        address.address_lines.clear();
+        address.post_code = Some("10000".into());
+        assert!(address.validate(AddressProfile::Structured).is_err());
+
+        address.post_code = None;
+        address.town_name = None;
+        assert!(address.validate(AddressProfile::Structured).is_err());
+
+        let address = PostalAddress {
+            country: Some("DE".into()),
+            address_lines: vec!["Hauptstrasse 5".into(), "60311 Frankfurt".into()],
+            ..Default::default()
+        };
+        assert_eq!(address.profile(), AddressProfile::Unstructured);
+        assert!(address.validate(AddressProfile::Unstructured).is_ok());
+        assert!(address.validate(AddressProfile::Hybrid).is_err());
+
+        const POST_CODES: &[(&str, &str, bool)] = &[
+            ("GB", "SW1A 2AA", true),
+            ("GB", "M1 1AE", true),
+            ("GB", "SW1A2AAA", false),
+            ("NL", "1012 AB", true),
+            ("US", "10001-1234", true),
+            ("US", "1001", false),
+            ("CA", "K1A 0B1", true),
+            ("PL", "00-950", true),
+            ("AE", "ANYTHING", true),
+        ];
+
+        for (country, post_code, valid) in POST_CODES.iter() {
+            assert_eq!(
+                is_valid_post_code(&CountryCode::from(*country), post_code),
+                *valid,
+                "{} {}",
+                country,
+                post_code
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_lines() -> Result<()> {
+        let lines = |lines: &[&str]| {
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let address = PostalAddress::from_lines(
+            &lines(&[
+                "Hauptstrasse 5",
+                "c/o Muster GmbH",
+                "D-60311 Frankfurt am Main",
+                "Germany",
+            ]),
+            None,
+        );
+        assert_eq!(address.country, Some("DE".into()));
+        assert_eq!(address.street_name.as_deref(), Some("Hauptstrasse"));
+        assert_eq!(address.building_number.as_deref(), Some("5"));
+        assert_eq!(address.post_code.as_deref(), Some("60311"));
+        assert_eq!(address.town_name.as_deref(), Some("Frankfurt am Main"));
+        assert_eq!(address.address_lines, vec!["c/o Muster GmbH"]);
+        assert!(address.validate(AddressProfile::Hybrid).is_ok());
+
+        let address = PostalAddress::from_lines(
+            &lines(&["350 Fifth Avenue", "PO BOX 42", "New York, NY 10118"]),
+            Some("US".into()),
+        );
+        assert_eq!(address.street_name.as_deref(), Some("Fifth Avenue"));
+        assert_eq!(address.building_number.as_deref(), Some("350"));
+        assert_eq!(address.post_box.as_deref(), Some("42"));
+        assert_eq!(address.town_name.as_deref(), Some("New York"));
+        assert_eq!(address.country_sub_division.as_deref(), Some("NY"));
+        assert_eq!(address.post_code.as_deref(), Some("10118"));
+        assert_eq!(address.profile(), AddressProfile::Structured);
+        assert_eq!(
+            address.to_lines(),
+            vec!["350 Fifth Avenue", "PO BOX 42", "New York NY 10118"]
+        );
+
+        let address = PostalAddress::from_lines(
+            &lines(&["10 Downing Street", "London SW1A 2AA", "GB"]),
+            None,
+        );
+        assert_eq!(address.street_line().as_deref(), Some("10 Downing Street"));
+        assert_eq!(address.town_line().as_deref(), Some("London SW1A 2AA"));
+
+        let address =
+            PostalAddress::from_lines(&lines(&["Sheikh Zayed Road", "Dubai"]), Some("AE".into()));
+        assert_eq!(address.town_name.as_deref(), Some("Dubai"));
+        assert_eq!(address.address_lines, vec!["Sheikh Zayed Road"]);
+
+        // NOTE: without country nor town, the lines are left as they are
+        let address = PostalAddress::from_lines(&lines(&["Hauptstrasse 5", "Frankfurt"]), None);
+        assert_eq!(address.profile(), AddressProfile::Unstructured);
+        assert_eq!(address.address_lines, vec!["Hauptstrasse 5", "Frankfurt"]);
+
+        assert_eq!(PostalAddress::from_element(&address.to_element())?, address);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+//! Business messages: a business application header and the `Document` it
+//! describes, wrapped in an envelope element.
+
+use crate::date::DateTime;
+use crate::error::{Error, Reason};
+use crate::iso20022::{BusinessApplicationHeader, MessageIdentifier};
+use crate::iso9362::BIC;
+use crate::result::Result;
+use crate::xml::Element;
+use serde::{Deserialize, Serialize};
+
+/// `ENVELOPE` is the default name of the element wrapping the header and
+/// the document.
+pub const ENVELOPE: &str = "Envelope";
+
+/// [`FromDocument`] is implemented by the models read from an ISO 20022
+/// `Document` element.
+pub trait FromDocument: Sized {
+    /// `from_document` reads the model from its `Document` element.
+    fn from_document(document: &Element) -> Result<Self>;
+}
+
+/// [`ToDocument`] is implemented by the models written as an ISO 20022
+/// `Document` element.
+pub trait ToDocument {
+    /// `message_identifier` returns the version of the message definition
+    /// the model is written with.
+    fn message_identifier(&self) -> MessageIdentifier;
+
+    /// `to_document` builds the `Document` element of the model, declaring
+    /// the namespace of its message definition.
+    fn to_document(&self) -> Result<Element>;
+}
+
+/// [`Envelope`] is a business message: a [`BusinessApplicationHeader`] and
+/// the document it describes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope<D> {
+    pub header: BusinessApplicationHeader,
+    pub document: D,
+}
+
+impl<D: FromDocument> Envelope<D> {
+    /// `parse` parses a business message XML document.
+    pub fn parse(xml: &str) -> Result<Envelope<D>> {
+        Envelope::from_element(&Element::parse(xml)?)
+    }
+
+    /// `from_element` reads a business message from the element wrapping
+    /// its `AppHdr` and `Document` elements, whatever its name. The version
+    /// of the document must be the one declared by the header.
+    pub fn from_element(element: &Element) -> Result<Envelope<D>> {
+        let header = BusinessApplicationHeader::from_element(element.require("AppHdr")?)?;
+        let document = element.require("Document")?;
+
+        if let Some(identifier) = MessageIdentifier::from_element(document) {
+            if identifier != header.message_definition {
+                return Err(Error::invalid_format(
+                    "business message",
+                    None,
+                    Reason::Format(format!(
+                        "document {} does not match header message definition {}",
+                        identifier, header.message_definition
+                    )),
+                ));
+            }
+        }
+
+        Ok(Envelope {
+            document: D::from_document(document)?,
+            header,
+        })
+    }
+}
+
+impl<D: ToDocument> Envelope<D> {
+    /// `new` creates a new `Envelope`, with a header declaring the message
+    /// definition of the document.
+    pub fn new(
+        from: BIC,
+        to: BIC,
+        business_message_id: &str,
+        created: DateTime,
+        document: D,
+    ) -> Envelope<D> {
+        Envelope {
+            header: BusinessApplicationHeader::new(
+                from,
+                to,
+                business_message_id,
+                document.message_identifier(),
+                created,
+            ),
+            document,
+        }
+    }
+
+    /// `to_element` builds the element named `root` wrapping the header and
+    /// the document.
+    pub fn to_element(&self, root: &str) -> Result<Element> {
+        let document = self.document.to_document()?;
+
+        if MessageIdentifier::from_element(&document).as_ref()
+            != Some(&self.header.message_definition)
+        {
+            return Err(Error::invalid_format(
+                "business message",
+                None,
+                Reason::Format(format!(
+                    "document does not match header message definition {}",
+                    self.header.message_definition
+                )),
+            ));
+        }
+
+        Ok(Element::new(root)
+            .with_child(self.header.to_element())
+            .with_child(document))
+    }
+
+    /// `to_xml` builds the XML document of the business message.
+    pub fn to_xml(&self) -> Result<String> {
+        self.to_element(ENVELOPE)
+            .map(|element| element.to_document())
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::Envelope;
+    #[allow(unused_imports)] // TODO
+    use crate::camt::{AccountReportMessage, MessageKind};
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+    #[allow(unused_imports)] // TODO
+    use crate::swift::Pacs008;
+
+    #[allow(dead_code)]
+    const PACS_008: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<BizMsg>
+  <AppHdr xmlns="urn:iso:std:iso:20022:tech:xsd:head.001.001.02">
+    <Fr><FIId><FinInstnId><BICFI>BANKBEBBXXX</BICFI></FinInstnId></FIId></Fr>
+    <To><FIId><FinInstnId><BICFI>BANKDEFFXXX</BICFI></FinInstnId></FIId></To>
+    <BizMsgIdr>MSG-20240115-1</BizMsgIdr>
+    <MsgDefIdr>pacs.008.001.08</MsgDefIdr>
+    <BizSvc>swift.cbprplus.02</BizSvc>
+    <CreDt>2024-01-15T09:30:00Z</CreDt>
+  </AppHdr>
+  <Document xmlns="urn:iso:std:iso:20022:tech:xsd:pacs.008.001.08">
+    <FIToFICstmrCdtTrf>
+      <GrpHdr>
+        <MsgId>MSG-20240115-1</MsgId>
+        <CreDtTm>2024-01-15T09:30:00</CreDtTm>
+        <NbOfTxs>1</NbOfTxs>
+        <SttlmInf><SttlmMtd>INDA</SttlmMtd></SttlmInf>
+      </GrpHdr>
+      <CdtTrfTxInf>
+        <PmtId>
+          <EndToEndId>E2E-0001</EndToEndId>
+          <UETR>eb6305c9-1f7f-49de-aed0-16487c27b42d</UETR>
+        </PmtId>
+        <IntrBkSttlmAmt Ccy="EUR">1234.56</IntrBkSttlmAmt>
+        <IntrBkSttlmDt>2024-01-15</IntrBkSttlmDt>
+        <ChrgBr>SHAR</ChrgBr>
+        <InstgAgt><FinInstnId><BICFI>BANKBEBBXXX</BICFI></FinInstnId></InstgAgt>
+        <InstdAgt><FinInstnId><BICFI>BANKDEFFXXX</BICFI></FinInstnId></InstdAgt>
+        <Dbtr><Nm>Holding SA</Nm></Dbtr>
+        <DbtrAgt><FinInstnId><BICFI>BANKBEBBXXX</BICFI></FinInstnId></DbtrAgt>
+        <CdtrAgt><FinInstnId><BICFI>BANKDEFFXXX</BICFI></FinInstnId></CdtrAgt>
+        <Cdtr><Nm>Jane Doe</Nm></Cdtr>
+      </CdtTrfTxInf>
+    </FIToFICstmrCdtTrf>
+  </Document>
+</BizMsg>"#;
+
+    #[allow(dead_code)]
+    const CAMT_053: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Envelope>
+  <h:AppHdr xmlns:h="urn:iso:std:iso:20022:tech:xsd:head.001.001.02">
+    <h:Fr><h:FIId><h:FinInstnId><h:BICFI>BANKDEFFXXX</h:BICFI></h:FinInstnId></h:FIId></h:Fr>
+    <h:To><h:FIId><h:FinInstnId><h:BICFI>CUSTDEFFXXX</h:BICFI></h:FinInstnId></h:FIId></h:To>
+    <h:BizMsgIdr>STMT-1</h:BizMsgIdr>
+    <h:MsgDefIdr>camt.053.001.08</h:MsgDefIdr>
+    <h:CreDt>2024-01-15T18:00:00Z</h:CreDt>
+    <h:PssblDplct>true</h:PssblDplct>
+  </h:AppHdr>
+  <Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.08">
+    <BkToCstmrStmt>
+      <GrpHdr><MsgId>STMT-1</MsgId><CreDtTm>2024-01-15T18:00:00</CreDtTm></GrpHdr>
+    </BkToCstmrStmt>
+  </Document>
+</Envelope>"#;
+
+    #[test]
+    fn parse_and_build() -> Result<()> {
+        let envelope = Envelope::<Pacs008>::parse(PACS_008)?;
+
+        assert_eq!(envelope.header.from.to_string(), "BANKBEBBXXX");
+        assert_eq!(envelope.header.to.to_string(), "BANKDEFFXXX");
+        assert_eq!(
+            envelope.header.message_definition.to_string(),
+            "pacs.008.001.08"
+        );
+        assert_eq!(
+            envelope.header.business_service.as_deref(),
+            Some("swift.cbprplus.02")
+        );
+        assert_eq!(envelope.document.end_to_end_id, "E2E-0001");
+
+        let rebuilt = Envelope::new(
+            envelope.header.from.clone(),
+            envelope.header.to.clone(),
+            "MSG-20240115-1",
+            envelope.header.created,
+            envelope.document.clone(),
+        );
+        assert_eq!(
+            rebuilt.header.message_definition,
+            envelope.header.message_definition
+        );
+        assert_eq!(Envelope::<Pacs008>::parse(&rebuilt.to_xml()?)?, rebuilt);
+
+        let statement = Envelope::<AccountReportMessage>::parse(CAMT_053)?;
+        assert_eq!(statement.document.kind, MessageKind::Statement);
+        assert!(statement.header.possible_duplicate);
+
+        let mismatch = CAMT_053.replace(
+            "camt.053.001.08</h:MsgDefIdr>",
+            "camt.053.001.02</h:MsgDefIdr>",
+        );
+        assert!(Envelope::<AccountReportMessage>::parse(&mismatch).is_err());
+
+        Ok(())
+    }
+}
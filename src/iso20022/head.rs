@@ -0,0 +1,149 @@
+//! Business application header (head.001), identifying the sender, the
+//! receiver and the message definition of an ISO 20022 business message.
+
+use crate::date::DateTime;
+use crate::error::{Error, Reason};
+use crate::iso20022::MessageIdentifier;
+use crate::iso9362::BIC;
+use crate::result::Result;
+use crate::xml::Element;
+use serde::{Deserialize, Serialize};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `HEADER_VERSION` is the head.001 version used by CBPR+, T2 and FedNow.
+pub const HEADER_VERSION: &str = "head.001.001.02";
+
+/// [`CopyDuplicate`] tells if a business message is a copy or a duplicate
+/// of a previously sent message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CopyDuplicate {
+    Copy,
+    Duplicate,
+    CopyDuplicate,
+}
+
+impl CopyDuplicate {
+    /// `as_str` returns the ISO 20022 code of the indicator.
+    pub fn as_str(&self) -> &str {
+        match self {
+            CopyDuplicate::Copy => "COPY",
+            CopyDuplicate::Duplicate => "DUPL",
+            CopyDuplicate::CopyDuplicate => "CODU",
+        }
+    }
+}
+
+impl FromStr for CopyDuplicate {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        match code {
+            "COPY" => Ok(CopyDuplicate::Copy),
+            "DUPL" => Ok(CopyDuplicate::Duplicate),
+            "CODU" => Ok(CopyDuplicate::CopyDuplicate),
+            _ => Err(Error::invalid(
+                "copy duplicate indicator",
+                code,
+                Reason::Unknown,
+            )),
+        }
+    }
+}
+
+/// [`BusinessApplicationHeader`] is a head.001 `AppHdr`. Only parties
+/// identified by BIC are supported, as required by the market practices.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BusinessApplicationHeader {
+    /// `version` is the version of the header, e.g. "head.001.001.02".
+    pub version: MessageIdentifier,
+    pub from: BIC,
+    pub to: BIC,
+    /// `business_message_id` is the unique reference of the business message.
+    pub business_message_id: String,
+    /// `message_definition` is the version of the document, e.g.
+    /// "pacs.008.001.08".
+    pub message_definition: MessageIdentifier,
+    /// `business_service` is the service of the market infrastructure, e.g.
+    /// "swift.cbprplus.02".
+    pub business_service: Option<String>,
+    pub created: DateTime,
+    pub copy_duplicate: Option<CopyDuplicate>,
+    pub possible_duplicate: bool,
+    pub priority: Option<String>,
+}
+
+impl BusinessApplicationHeader {
+    /// `new` creates a new `BusinessApplicationHeader` of the current version.
+    pub fn new(
+        from: BIC,
+        to: BIC,
+        business_message_id: &str,
+        message_definition: MessageIdentifier,
+        created: DateTime,
+    ) -> BusinessApplicationHeader {
+        BusinessApplicationHeader {
+            version: HEADER_VERSION.parse().unwrap(),
+            from,
+            to,
+            business_message_id: business_message_id.into(),
+            message_definition,
+            business_service: None,
+            created,
+            copy_duplicate: None,
+            possible_duplicate: false,
+            priority: None,
+        }
+    }
+
+    /// `from_element` reads a header from its `AppHdr` element.
+    pub fn from_element(element: &Element) -> Result<BusinessApplicationHeader> {
+        let version = MessageIdentifier::from_element(element)
+            .unwrap_or_else(|| HEADER_VERSION.parse().unwrap());
+
+        if version.message_name() != "head.001" {
+            return Err(Error::UnknownMessage(version.to_string()));
+        }
+
+        Ok(BusinessApplicationHeader {
+            version,
+            from: element.require_parsed("Fr/FIId/FinInstnId/BICFI")?,
+            to: element.require_parsed("To/FIId/FinInstnId/BICFI")?,
+            business_message_id: element.require_text("BizMsgIdr")?.into(),
+            message_definition: element.require_parsed("MsgDefIdr")?,
+            business_service: element.find_text("BizSvc").map(String::from),
+            created: element.require_parsed("CreDt")?,
+            copy_duplicate: element.find_parsed("CpyDplct")?,
+            possible_duplicate: element.find_text("PssblDplct") == Some("true"),
+            priority: element.find_text("Prty").map(String::from),
+        })
+    }
+
+    /// `to_element` builds the `AppHdr` element of the header.
+    pub fn to_element(&self) -> Element {
+        let party = |name: &str, bic: &BIC| {
+            Element::new(name).with_child(
+                Element::new("FIId")
+                    .with_child(Element::new("FinInstnId").with_leaf("BICFI", &bic.to_string())),
+            )
+        };
+
+        Element::new("AppHdr")
+            .with_attribute("xmlns", &self.version.namespace())
+            .with_child(party("Fr", &self.from))
+            .with_child(party("To", &self.to))
+            .with_leaf("BizMsgIdr", &self.business_message_id)
+            .with_leaf("MsgDefIdr", &self.message_definition.to_string())
+            .with_optional_leaf("BizSvc", self.business_service.as_ref())
+            .with_leaf("CreDt", &self.created.to_string())
+            .with_optional_leaf(
+                "CpyDplct",
+                self.copy_duplicate.map(|c| c.as_str().to_string()),
+            )
+            .with_optional_leaf(
+                "PssblDplct",
+                Some("true").filter(|_| self.possible_duplicate),
+            )
+            .with_optional_leaf("Prty", self.priority.as_ref())
+    }
+}
@@ -0,0 +1,190 @@
+//! ISO 20022 message identifiers, such as "pacs.008.001.08", and their XML
+//! namespaces.
+
+use crate::error::{check_length, Error, Reason};
+use crate::result::Result;
+use crate::xml::Element;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `NAMESPACE_PREFIX` is the prefix of the XML namespaces of the ISO 20022
+/// message schemas.
+pub const NAMESPACE_PREFIX: &str = "urn:iso:std:iso:20022:tech:xsd:";
+
+/// [`MessageIdentifier`] identifies a version of an ISO 20022 message
+/// definition: business area, message number, variant and version.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MessageIdentifier {
+    pub business_area: String,
+    pub message: u16,
+    pub variant: u16,
+    pub version: u16,
+}
+
+impl MessageIdentifier {
+    /// `new` parses a message identifier, e.g. "pacs.008.001.08".
+    pub fn new(identifier: &str) -> Result<MessageIdentifier> {
+        const KIND: &str = "message identifier";
+        // NOTE: 'a' is a lowercase letter and 'n' a digit
+        const PATTERN: &str = "aaaa.nnn.nnn.nn";
+
+        check_length(KIND, identifier, PATTERN.len(), PATTERN.len())?;
+
+        let invalid =
+            identifier
+                .chars()
+                .zip(PATTERN.chars())
+                .enumerate()
+                .find(|(_, (c, p))| match p {
+                    'a' => !c.is_ascii_lowercase(),
+                    'n' => !c.is_ascii_digit(),
+                    _ => c != p,
+                });
+
+        if let Some((index, (found, p))) = invalid {
+            return Err(Error::invalid(
+                KIND,
+                identifier,
+                Reason::Character {
+                    index,
+                    found,
+                    expected: match p {
+                        'a' => "a lowercase letter",
+                        'n' => "a digit",
+                        _ => "'.'",
+                    },
+                },
+            ));
+        }
+
+        let number = |from: usize, to: usize| identifier[from..to].parse().unwrap_or_default();
+
+        Ok(MessageIdentifier {
+            business_area: identifier[0..4].into(),
+            message: number(5, 8),
+            variant: number(9, 12),
+            version: number(13, 15),
+        })
+    }
+
+    /// `from_namespace` parses the message identifier of an XML namespace.
+    pub fn from_namespace(namespace: &str) -> Result<MessageIdentifier> {
+        const KIND: &str = "ISO 20022 namespace";
+
+        namespace
+            .strip_prefix(NAMESPACE_PREFIX)
+            .ok_or_else(|| {
+                Error::invalid(
+                    KIND,
+                    namespace,
+                    Reason::Format(format!("expected a {} prefix", NAMESPACE_PREFIX)),
+                )
+            })
+            .and_then(|identifier| {
+                MessageIdentifier::new(identifier)
+                    .map_err(|e| e.within(KIND, namespace, NAMESPACE_PREFIX.len()))
+            })
+    }
+
+    /// `from_element` returns the message identifier of the default or
+    /// prefixed namespace declared by an element, if any.
+    pub fn from_element(element: &Element) -> Option<MessageIdentifier> {
+        element
+            .attributes
+            .iter()
+            .filter(|(name, _)| name == "xmlns" || name.starts_with("xmlns:"))
+            .find_map(|(_, namespace)| MessageIdentifier::from_namespace(namespace).ok())
+    }
+
+    /// `message_name` returns the identifier without version, e.g.
+    /// "pacs.008", as used by the `MsgNmId` elements of older versions.
+    pub fn message_name(&self) -> String {
+        format!("{}.{:03}", self.business_area, self.message)
+    }
+
+    /// `namespace` returns the XML namespace of the message schema.
+    pub fn namespace(&self) -> String {
+        format!("{}{}", NAMESPACE_PREFIX, self)
+    }
+
+    /// `document` wraps a message element into a `Document` element
+    /// declaring the namespace of the message schema.
+    pub fn document(&self, message: Element) -> Element {
+        Element::new("Document")
+            .with_attribute("xmlns", &self.namespace())
+            .with_child(message)
+    }
+}
+
+impl fmt::Display for MessageIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{:03}.{:03}.{:02}",
+            self.business_area, self.message, self.variant, self.version
+        )
+    }
+}
+
+impl FromStr for MessageIdentifier {
+    type Err = Error;
+
+    fn from_str(identifier: &str) -> StdResult<Self, Self::Err> {
+        MessageIdentifier::new(identifier)
+    }
+}
+
+impl Serialize for MessageIdentifier {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for MessageIdentifier {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::MessageIdentifier;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+    #[allow(unused_imports)] // TODO
+    use crate::xml::Element;
+
+    #[test]
+    fn parse() -> Result<()> {
+        let identifier = MessageIdentifier::new("pacs.008.001.08")?;
+
+        assert_eq!(identifier.business_area, "pacs");
+        assert_eq!(identifier.message, 8);
+        assert_eq!(identifier.version, 8);
+        assert_eq!(identifier.message_name(), "pacs.008");
+        assert_eq!(identifier.to_string(), "pacs.008.001.08");
+        assert_eq!(
+            MessageIdentifier::from_namespace(&identifier.namespace())?,
+            identifier
+        );
+
+        let document = Element::new("Document")
+            .with_attribute("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance")
+            .with_attribute(
+                "xmlns:doc",
+                "urn:iso:std:iso:20022:tech:xsd:camt.053.001.08",
+            );
+        assert_eq!(
+            MessageIdentifier::from_element(&document).map(|id| id.to_string()),
+            Some("camt.053.001.08".into())
+        );
+
+        assert!(MessageIdentifier::new("pacs.8.1.8").is_err());
+        assert!(MessageIdentifier::new("PACS.008.001.08").is_err());
+        assert!(MessageIdentifier::from_namespace("urn:swift:xsd:pacs.008.001.08").is_err());
+
+        Ok(())
+    }
+}
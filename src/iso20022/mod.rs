@@ -0,0 +1,16 @@
+//! ISO 20022 messages infrastructure shared by the pain, pacs and camt
+//! models: message identifiers and their namespaces, the head.001 business
+//! application header, the envelope wrapping a header and a document and
+//! the postal addresses of the parties and their account identifications.
+
+pub mod account;
+pub mod address;
+pub mod envelope;
+pub mod head;
+pub mod identifier;
+
+pub use account::*;
+pub use address::*;
+pub use envelope::*;
+pub use head::*;
+pub use identifier::*;
@@ -1,7 +1,7 @@
 //! Country codes as defined by ISO 3166-1 alpha-2.
 
 use crate::code::Code;
-use crate::error::Error;
+use crate::error::{check_chars, check_length, Error};
 use crate::result::Result;
 use arraystring::typenum::U2;
 use serde::{de::Deserializer, ser::Serializer, Deserialize, Serialize};
@@ -10,14 +10,12 @@ use std::result::Result as StdResult;
 use std::str::FromStr;
 
 /// [`CountryCode`] is an ISO 3166-1 alpha-2 code
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CountryCode(Code<U2>);
 
 impl CountryCode {
     pub fn new(code: &str) -> Result<CountryCode> {
-        if !CountryCode::is_valid(code) {
-            return Err(Error::InvalidCode);
-        }
+        CountryCode::check(code)?;
 
         Ok(CountryCode(Code::<U2>::from(code)))
     }
@@ -27,21 +25,25 @@ impl CountryCode {
         self.0.as_str()
     }
 
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_length("country code", code, 2, 2)?;
+        check_chars("country code", code, "an upper-case letter", |c| {
+            c.is_ascii_uppercase()
+        })
+    }
+
     /// `is_valid` returns if a provided code is valid
     /// by checking length and kind of chars used.
     pub fn is_valid(code: &str) -> bool {
-        Code::<U2>::is_valid(code) && code.find(|c: char| !c.is_ascii_alphabetic()) == None
+        CountryCode::check(code).is_ok()
     }
 
     /// `validate` validates the [`CountryCode`]. Here only length and digites
     /// used are checked, not if the code is actually representative of a
     /// used code.
     pub fn validate(&self) -> Result<()> {
-        if !CountryCode::is_valid(self.as_str()) {
-            return Err(Error::InvalidCode);
-        }
-
-        Ok(())
+        CountryCode::check(self.as_str())
     }
 }
 
@@ -53,7 +55,7 @@ impl FromStr for CountryCode {
     }
 }
 
-impl<'a> From<&'a str> for CountryCode {
+impl From<&str> for CountryCode {
     fn from(code: &str) -> Self {
         CountryCode::from_str(code).unwrap()
     }
@@ -97,7 +99,7 @@ impl CountryCodes {
         let c = CountryCode::new(code)?;
 
         if !self.exists(&c) {
-            Err(Error::CodeNotFound)
+            Err(Error::not_found("country code", code))
         } else {
             Ok(())
         }
@@ -115,6 +117,20 @@ impl CountryCodes {
 
         None
     }
+
+    /// `find_exact` finds a code from a country name, ignoring case and
+    /// the abbreviation between parentheses.
+    pub fn find_exact(&self, country: &str) -> Option<&CountryCode> {
+        let country = country.trim();
+
+        self.0
+            .iter()
+            .find(|(_, name)| {
+                let name = name.split(" (").next().unwrap_or(name);
+                name.eq_ignore_ascii_case(country)
+            })
+            .map(|(code, _)| code)
+    }
 }
 
 mod test {
@@ -162,5 +178,9 @@ mod test {
         for country in VALID_COUNTRIES.iter() {
             assert!(codes.find(country).is_some());
         }
+
+        assert_eq!(codes.find_exact("GERMANY").map(|c| c.as_str()), Some("DE"));
+        assert_eq!(codes.find_exact("belgium").map(|c| c.as_str()), Some("BE"));
+        assert!(codes.find_exact("United States").is_none());
     }
 }
@@ -0,0 +1,133 @@
+//! Currency codes as defined by ISO 4217.
+
+use crate::code::Code;
+use crate::error::{check_chars, check_length, Error};
+use crate::result::Result;
+use arraystring::typenum::U3;
+use serde::{de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `DEFAULT_MINOR_UNITS` is the number of decimals used by most currencies.
+pub const DEFAULT_MINOR_UNITS: u8 = 2;
+
+/// `ZERO_MINOR_UNITS_CODES` are the currencies without decimals.
+pub const ZERO_MINOR_UNITS_CODES: &[&str] = &[
+    "BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "UYI", "VND",
+    "VUV", "XAF", "XOF", "XPF",
+];
+
+/// `THREE_MINOR_UNITS_CODES` are the currencies with three decimals.
+pub const THREE_MINOR_UNITS_CODES: &[&str] = &["BHD", "IQD", "JOD", "KWD", "LYD", "OMR", "TND"];
+
+/// `FOUR_MINOR_UNITS_CODES` are the currencies with four decimals.
+pub const FOUR_MINOR_UNITS_CODES: &[&str] = &["CLF", "UYW"];
+
+/// [`CurrencyCode`] is an ISO 4217 alphabetic code
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CurrencyCode(Code<U3>);
+
+impl CurrencyCode {
+    pub fn new(code: &str) -> Result<CurrencyCode> {
+        CurrencyCode::check(code)?;
+
+        Ok(CurrencyCode(Code::<U3>::from(code)))
+    }
+
+    /// `as_str` returns the `CurrencyCode` as a string slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_length("currency code", code, 3, 3)?;
+        check_chars("currency code", code, "an upper-case letter", |c| {
+            c.is_ascii_uppercase()
+        })
+    }
+
+    /// `is_valid` returns if a provided code is valid
+    /// by checking length and kind of chars used.
+    pub fn is_valid(code: &str) -> bool {
+        CurrencyCode::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`CurrencyCode`]. Here only length and digites
+    /// used are checked, not if the code is actually representative of a
+    /// used code.
+    pub fn validate(&self) -> Result<()> {
+        CurrencyCode::check(self.as_str())
+    }
+
+    /// `minor_units` returns the number of decimals used by the currency.
+    pub fn minor_units(&self) -> u8 {
+        let code = self.as_str();
+
+        if ZERO_MINOR_UNITS_CODES.contains(&code) {
+            0
+        } else if THREE_MINOR_UNITS_CODES.contains(&code) {
+            3
+        } else if FOUR_MINOR_UNITS_CODES.contains(&code) {
+            4
+        } else {
+            DEFAULT_MINOR_UNITS
+        }
+    }
+}
+
+impl FromStr for CurrencyCode {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        CurrencyCode::new(code)
+    }
+}
+
+impl From<&str> for CurrencyCode {
+    fn from(code: &str) -> Self {
+        CurrencyCode::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for CurrencyCode {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(self.0.as_str(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for CurrencyCode {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des).map(Self::from)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{CurrencyCode, Result};
+
+    #[test]
+    fn is_valid() {
+        const WRONG_CODES: &[&str] = &["eur", "EU", "123", "EURO", "E!R"];
+        const VALID_CODES: &[&str] = &["EUR", "USD", "GBP", "JPY", "CHF"];
+
+        for code in WRONG_CODES.iter() {
+            assert!(!CurrencyCode::is_valid(code));
+        }
+
+        for code in VALID_CODES.iter() {
+            assert!(CurrencyCode::is_valid(code));
+        }
+    }
+
+    #[test]
+    fn minor_units() -> Result<()> {
+        assert_eq!(CurrencyCode::new("EUR")?.minor_units(), 2);
+        assert_eq!(CurrencyCode::new("JPY")?.minor_units(), 0);
+        assert_eq!(CurrencyCode::new("KWD")?.minor_units(), 3);
+
+        Ok(())
+    }
+}
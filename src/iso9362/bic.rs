@@ -1,14 +1,180 @@
 //! Business Identifier Code (BIC, or SWIFT BIC or SWIFT code) as defined by ISO 9362.
 
+use crate::error::{check_chars, Error, Reason};
 use crate::iso3166::CountryCode;
 use crate::iso9362::{BranchCode, InstitutionCode, LocationCode};
+use crate::result::Result;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
 
 /// [`BIC`] is an ISO 9362 BIC code.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct BIC {
     pub institution: InstitutionCode,
     pub country: CountryCode,
     pub location: LocationCode,
     pub branch: Option<BranchCode>,
 }
+
+impl BIC {
+    /// `new` creates a new `BIC` from an 8 or 11 chars string code.
+    pub fn new(code: &str) -> Result<BIC> {
+        BIC::check(code)?;
+
+        Ok(BIC {
+            institution: InstitutionCode::new(&code[0..4])?,
+            country: CountryCode::new(&code[4..6])?,
+            location: LocationCode::new(&code[6..8])?,
+            branch: if code.len() == 11 {
+                Some(BranchCode::new(&code[8..11])?)
+            } else {
+                None
+            },
+        })
+    }
+
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        let actual = code.chars().count();
+
+        if actual != 8 && actual != 11 {
+            let length = if actual < 8 { 8 } else { 11 };
+
+            return Err(Error::invalid(
+                "BIC",
+                code,
+                Reason::Length {
+                    min: length,
+                    max: length,
+                    actual,
+                },
+            ));
+        }
+
+        check_chars("BIC", code, "an upper-case letter or a digit", |c| {
+            c.is_ascii_uppercase() || c.is_ascii_digit()
+        })?;
+        InstitutionCode::check(&code[0..4]).map_err(|e| e.within("BIC", code, 0))?;
+        CountryCode::check(&code[4..6]).map_err(|e| e.within("BIC", code, 4))?;
+        LocationCode::check(&code[6..8]).map_err(|e| e.within("BIC", code, 6))?;
+
+        if actual == 11 {
+            BranchCode::check(&code[8..11]).map_err(|e| e.within("BIC", code, 8))?;
+        }
+
+        Ok(())
+    }
+
+    /// `is_valid` returns if a provided code is valid
+    /// by checking length and kind of chars used.
+    pub fn is_valid(code: &str) -> bool {
+        BIC::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`BIC`].
+    pub fn validate(&self) -> Result<()> {
+        BIC::check(&self.to_string())
+    }
+
+    /// `bic8` returns the BIC without branch code.
+    pub fn bic8(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.institution.as_str(),
+            self.country.as_str(),
+            self.location.as_str()
+        )
+    }
+
+    /// `bic11` returns the BIC with branch code, using the primary office
+    /// branch code if none is set.
+    pub fn bic11(&self) -> String {
+        format!(
+            "{}{}",
+            self.bic8(),
+            self.branch
+                .as_ref()
+                .map(|b| b.as_str())
+                .unwrap_or(crate::iso9362::PRIMARY_BRANCH_CODE)
+        )
+    }
+}
+
+impl fmt::Display for BIC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.branch {
+            Some(ref branch) => write!(f, "{}{}", self.bic8(), branch.as_str()),
+            None => write!(f, "{}", self.bic8()),
+        }
+    }
+}
+
+impl FromStr for BIC {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        BIC::new(code)
+    }
+}
+
+impl From<&str> for BIC {
+    fn from(code: &str) -> Self {
+        BIC::from_str(code).unwrap()
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Result, BIC};
+    #[allow(unused_imports)] // TODO
+    use crate::error::Reason;
+
+    #[test]
+    fn is_valid() {
+        const WRONG_CODES: &[&str] = &["DEUTDEF", "deutdeff", "DEUTDEFF50", "1EUTDEFF", "DEUT1EFF"];
+        const VALID_CODES: &[&str] = &["DEUTDEFF", "DEUTDEFF500", "NEDSZAJJXXX", "UBSWCHZH80A"];
+
+        for code in WRONG_CODES.iter() {
+            assert!(!BIC::is_valid(code));
+        }
+
+        for code in VALID_CODES.iter() {
+            assert!(BIC::is_valid(code));
+        }
+    }
+
+    #[test]
+    fn check() {
+        let err = BIC::check("DEUT1EFF").unwrap_err();
+
+        assert_eq!(
+            err.reason(),
+            Some(&Reason::Character {
+                index: 4,
+                found: '1',
+                expected: "an upper-case letter"
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "invalid BIC \"DEUT1EFF\": invalid char '1' at index 4, expected an upper-case letter"
+        );
+    }
+
+    #[test]
+    fn new() -> Result<()> {
+        let bic = BIC::new("DEUTDEFF")?;
+
+        assert_eq!(bic.institution.as_str(), "DEUT");
+        assert_eq!(bic.country.as_str(), "DE");
+        assert_eq!(bic.location.as_str(), "FF");
+        assert!(bic.branch.is_none());
+        assert_eq!(bic.to_string(), "DEUTDEFF");
+        assert_eq!(bic.bic11(), "DEUTDEFFXXX");
+        assert_eq!(BIC::new("DEUTDEFF500")?.to_string(), "DEUTDEFF500");
+
+        Ok(())
+    }
+}
@@ -11,14 +11,12 @@ use std::str::FromStr;
 /// `PRIMARY_BRANCH_CODE` is the branch code of the primary office
 pub const PRIMARY_BRANCH_CODE: &str = "XXX";
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct BranchCode(Code<U3>);
 
 impl BranchCode {
     pub fn new(code: &str) -> Result<BranchCode> {
-        if !BranchCode::is_valid(code) {
-            return Err(Error::InvalidCode);
-        }
+        BranchCode::check(code)?;
 
         Ok(BranchCode(Code::<U3>::from(code)))
     }
@@ -28,21 +26,22 @@ impl BranchCode {
         self.0.as_str()
     }
 
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        Code::<U3>::check(code).map_err(|e| e.within("branch code", code, 0))
+    }
+
     /// `is_valid` returns if a provided code is valid
     /// by checking length and kind of chars used.
     pub fn is_valid(code: &str) -> bool {
-        Code::<U3>::is_valid(code)
+        BranchCode::check(code).is_ok()
     }
 
     /// `validate` validates the [`BranchCode`]. Here only length and digites
     /// used are checked, not if the code is actually representative of a
     /// used code.
     pub fn validate(&self) -> Result<()> {
-        if !BranchCode::is_valid(self.as_str()) {
-            return Err(Error::InvalidCode);
-        }
-
-        Ok(())
+        BranchCode::check(self.as_str())
     }
 
     /// `is_primary` returns if the branch is the primary office.
@@ -59,7 +58,7 @@ impl FromStr for BranchCode {
     }
 }
 
-impl<'a> From<&'a str> for BranchCode {
+impl From<&str> for BranchCode {
     fn from(code: &str) -> Self {
         BranchCode::from_str(code).unwrap()
     }
@@ -1,21 +1,19 @@
 //! BIC's (non-)financial institution code, as defined by ISO 9362.
 
 use crate::code::Code;
-use crate::error::Error;
+use crate::error::{check_chars, check_length, Error};
 use crate::result::Result;
 use arraystring::typenum::U4;
 use serde::{de::Deserializer, ser::Serializer, Deserialize, Serialize};
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct InstitutionCode(Code<U4>);
 
 impl InstitutionCode {
     pub fn new(code: &str) -> Result<InstitutionCode> {
-        if !InstitutionCode::is_valid(code) {
-            return Err(Error::InvalidCode);
-        }
+        InstitutionCode::check(code)?;
 
         Ok(InstitutionCode(Code::<U4>::from(code)))
     }
@@ -25,21 +23,25 @@ impl InstitutionCode {
         self.0.as_str()
     }
 
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_length("institution code", code, 4, 4)?;
+        check_chars("institution code", code, "an upper-case letter", |c| {
+            c.is_ascii_uppercase()
+        })
+    }
+
     /// `is_valid` returns if a provided code is valid
     /// by checking length and kind of chars used.
     pub fn is_valid(code: &str) -> bool {
-        Code::<U4>::is_valid(code) && code.find(|c: char| !c.is_ascii_alphabetic()) == None
+        InstitutionCode::check(code).is_ok()
     }
 
     /// `validate` validates the [`InstitutionCode`]. Here only length and digites
     /// used are checked, not if the code is actually representative of a
     /// used code.
     pub fn validate(&self) -> Result<()> {
-        if !InstitutionCode::is_valid(self.as_str()) {
-            return Err(Error::InvalidCode);
-        }
-
-        Ok(())
+        InstitutionCode::check(self.as_str())
     }
 }
 
@@ -51,7 +53,7 @@ impl FromStr for InstitutionCode {
     }
 }
 
-impl<'a> From<&'a str> for InstitutionCode {
+impl From<&str> for InstitutionCode {
     fn from(code: &str) -> Self {
         InstitutionCode::from_str(code).unwrap()
     }
@@ -12,14 +12,12 @@ use std::str::FromStr;
 /// not connected to the SWIFT network
 pub const PASSIVE_LOCATION_SUFFIX: char = '1';
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct LocationCode(Code<U2>);
 
 impl LocationCode {
     pub fn new(code: &str) -> Result<LocationCode> {
-        if !LocationCode::is_valid(code) {
-            return Err(Error::InvalidCode);
-        }
+        LocationCode::check(code)?;
 
         Ok(LocationCode(Code::<U2>::from(code)))
     }
@@ -29,21 +27,22 @@ impl LocationCode {
         self.0.as_str()
     }
 
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        Code::<U2>::check(code).map_err(|e| e.within("location code", code, 0))
+    }
+
     /// `is_valid` returns if a provided code is valid
     /// by checking length and kind of chars used.
     pub fn is_valid(code: &str) -> bool {
-        Code::<U2>::is_valid(code)
+        LocationCode::check(code).is_ok()
     }
 
     /// `validate` validates the [`LocationCode`]. Here only length and digites
     /// used are checked, not if the code is actually representative of a
     /// used code.
     pub fn validate(&self) -> Result<()> {
-        if !LocationCode::is_valid(self.as_str()) {
-            return Err(Error::InvalidCode);
-        }
-
-        Ok(())
+        LocationCode::check(self.as_str())
     }
 
     /// `is_connected` returns if the location is connected to the
@@ -61,7 +60,7 @@ impl FromStr for LocationCode {
     }
 }
 
-impl<'a> From<&'a str> for LocationCode {
+impl From<&str> for LocationCode {
     fn from(code: &str) -> Self {
         LocationCode::from_str(code).unwrap()
     }
@@ -1,10 +1,28 @@
 pub mod aba;
 pub mod ach;
+pub mod au;
+pub mod br;
+pub mod ca;
+pub mod camt;
+pub mod ch;
 pub mod code;
+pub mod csv;
+pub mod date;
+pub mod de;
 pub mod error;
+pub mod gb;
+pub mod india;
+pub mod iso11649;
 pub mod iso13616;
+pub mod iso20022;
 pub mod iso3166;
+pub mod iso4217;
 pub mod iso9362;
+pub mod money;
+pub mod mx;
+pub mod nz;
+pub mod pain;
 pub mod result;
 pub mod sepa;
 pub mod swift;
+pub mod xml;
@@ -0,0 +1,160 @@
+//! Monetary amounts in a given ISO 4217 currency.
+
+use crate::error::{check_chars, Error, Reason};
+use crate::iso4217::CurrencyCode;
+use crate::result::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// [`Money`] is an amount expressed in the minor units of its currency,
+/// e.g. `Money { amount: 1050, currency: "EUR" }` is 10.50 EUR.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Money {
+    pub amount: i64,
+    pub currency: CurrencyCode,
+}
+
+impl Money {
+    /// `new` creates a new `Money` from an amount in minor units.
+    pub fn new(amount: i64, currency: CurrencyCode) -> Money {
+        Money { amount, currency }
+    }
+
+    /// `parse` parses a decimal amount in the given currency. Both '.'
+    /// and ',' are accepted as decimal separator, and trailing decimals
+    /// are accepted as long as they are zeros beyond the currency minor units.
+    pub fn parse(input: &str, currency: CurrencyCode) -> Result<Money> {
+        let invalid = |reason: &str| Error::invalid("amount", input, Reason::Format(reason.into()));
+        let digit = |c: char| c.is_ascii_digit();
+        let (negative, amount) = match input.strip_prefix('-') {
+            Some(amount) => (true, amount),
+            None => (false, input),
+        };
+
+        let mut parts = amount.splitn(2, ['.', ',']);
+        let units = parts.next().unwrap_or("");
+        let decimals = parts.next().unwrap_or("");
+        let offset = usize::from(negative);
+
+        if units.is_empty() {
+            return Err(invalid("no digit before the decimal separator"));
+        }
+
+        check_chars("amount", units, "a digit", digit)
+            .map_err(|e| e.within("amount", input, offset))?;
+        check_chars("amount", decimals, "a digit", digit)
+            .map_err(|e| e.within("amount", input, offset + units.len() + 1))?;
+
+        let minor_units = currency.minor_units() as usize;
+
+        if decimals.len() > minor_units && decimals[minor_units..].contains(|c| c != '0') {
+            return Err(invalid(&format!(
+                "more than {} decimals for {}",
+                minor_units,
+                currency.as_str()
+            )));
+        }
+
+        let mut digits = String::from(units);
+
+        for i in 0..minor_units {
+            digits.push(decimals.chars().nth(i).unwrap_or('0'));
+        }
+
+        let value: i64 = digits.parse().map_err(|_| invalid("out of range"))?;
+
+        Ok(Money {
+            amount: if negative { -value } else { value },
+            currency,
+        })
+    }
+
+    /// `format` returns the amount as a decimal string, using `separator`
+    /// as decimal separator and as many decimals as the currency minor units.
+    pub fn format(&self, separator: char) -> String {
+        let minor_units = self.currency.minor_units() as u32;
+        let factor = 10i64.pow(minor_units);
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let abs = self.amount.unsigned_abs();
+
+        if minor_units == 0 {
+            return format!("{}{}", sign, abs);
+        }
+
+        format!(
+            "{}{}{}{:0width$}",
+            sign,
+            abs / factor as u64,
+            separator,
+            abs % factor as u64,
+            width = minor_units as usize
+        )
+    }
+
+    /// `is_zero` returns if the amount is zero.
+    pub fn is_zero(&self) -> bool {
+        self.amount == 0
+    }
+
+    /// `checked_add` adds two amounts in the same currency, returning `None`
+    /// on currency mismatch or overflow.
+    pub fn checked_add(&self, other: &Money) -> Option<Money> {
+        if self.currency != other.currency {
+            return None;
+        }
+
+        self.amount
+            .checked_add(other.amount)
+            .map(|amount| Money::new(amount, self.currency.clone()))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.format('.'), self.currency.as_str())
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Money, Result};
+    #[allow(unused_imports)] // TODO
+    use crate::error::Reason;
+
+    #[test]
+    fn parse() -> Result<()> {
+        assert_eq!(Money::parse("10.5", "EUR".into())?.amount, 1050);
+        assert_eq!(Money::parse("10,50", "EUR".into())?.amount, 1050);
+        assert_eq!(Money::parse("10,", "EUR".into())?.amount, 1000);
+        assert_eq!(Money::parse("10.500", "EUR".into())?.amount, 1050);
+        assert_eq!(Money::parse("-0.01", "EUR".into())?.amount, -1);
+        assert_eq!(Money::parse("1000", "JPY".into())?.amount, 1000);
+
+        const WRONG_AMOUNTS: &[&str] = &["", ".5", "10.505", "1e3", "10.5.0", "+1"];
+
+        for amount in WRONG_AMOUNTS.iter() {
+            assert!(Money::parse(amount, "EUR".into()).is_err());
+        }
+
+        assert_eq!(
+            Money::parse("-10.5x", "EUR".into()).unwrap_err().reason(),
+            Some(&Reason::Character {
+                index: 5,
+                found: 'x',
+                expected: "a digit"
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn format() -> Result<()> {
+        assert_eq!(Money::parse("10.5", "EUR".into())?.format('.'), "10.50");
+        assert_eq!(Money::parse("0.05", "EUR".into())?.format(','), "0,05");
+        assert_eq!(Money::parse("-3", "KWD".into())?.to_string(), "-3.000 KWD");
+        assert_eq!(Money::parse("1000", "JPY".into())?.format('.'), "1000");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! Mexican standardized bank account numbers (CLABE), as used by SPEI.
+
+use crate::code::Code;
+use crate::error::{check_digits, Error, Reason};
+use crate::result::Result;
+use arraystring::typenum::U18;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `LENGTH` is the length of a `Clabe`.
+pub const LENGTH: usize = 18;
+
+/// `WEIGHTS` are the weights of the check digit, repeated from the left.
+const WEIGHTS: [u32; 3] = [3, 7, 1];
+
+/// [`Clabe`] is a CLABE: a bank code (3 digits), a plaza code (3 digits),
+/// an account number (11 digits) and a check digit, e.g.
+/// "032180000118359719".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Clabe(Code<U18>);
+
+impl Clabe {
+    /// `new` creates a new `Clabe` from a string code, with or without
+    /// spaces.
+    pub fn new(code: &str) -> Result<Clabe> {
+        let cd: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+
+        Clabe::check(&cd)?;
+
+        Ok(Clabe(Code::<U18>::from(cd.as_str())))
+    }
+
+    /// `compute` creates a new `Clabe` from its bank code, plaza code and
+    /// account number, computing its check digit.
+    pub fn compute(bank: &str, plaza: &str, account: &str) -> Result<Clabe> {
+        let code = format!("{}{}{}", bank, plaza, account);
+
+        check_digits("bank code", bank, 3, 3)?;
+        check_digits("plaza code", plaza, 3, 3)?;
+        check_digits("account number", account, 11, 11)?;
+
+        Ok(Clabe(Code::<U18>::from(
+            format!("{}{}", code, Clabe::check_digit(&code)).as_str(),
+        )))
+    }
+
+    /// `check_digit` returns the check digit of the first 17 digits: the
+    /// last digits of the products by the weights 3, 7 and 1 are summed,
+    /// and the check digit is the complement to 10 of the sum.
+    fn check_digit(code: &str) -> u32 {
+        let sum: u32 = code
+            .chars()
+            .zip(WEIGHTS.iter().cycle())
+            .map(|(c, w)| c.to_digit(10).unwrap_or_default() * w % 10)
+            .sum();
+
+        (10 - sum % 10) % 10
+    }
+
+    /// `as_str` returns the `Clabe` as a string slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `bank_code` returns the code of the bank, e.g. "032".
+    pub fn bank_code(&self) -> &str {
+        &self.as_str()[0..3]
+    }
+
+    /// `plaza_code` returns the code of the plaza (city) of the account.
+    pub fn plaza_code(&self) -> &str {
+        &self.as_str()[3..6]
+    }
+
+    /// `account_number` returns the account number.
+    pub fn account_number(&self) -> &str {
+        &self.as_str()[6..17]
+    }
+
+    /// `check` checks a provided code, returning why it is invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_digits("CLABE", code, LENGTH, LENGTH)?;
+
+        let expected = Clabe::check_digit(&code[..17]);
+
+        if code[17..].parse::<u32>().ok() != Some(expected) {
+            return Err(Error::invalid(
+                "CLABE",
+                code,
+                Reason::Checksum {
+                    expected: expected.to_string(),
+                    actual: code[17..].into(),
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `is_valid` returns if a provided code is valid
+    /// by checking length, kind of chars used and the check digit.
+    pub fn is_valid(code: &str) -> bool {
+        Clabe::check(code).is_ok()
+    }
+
+    /// `validate` validates the [`Clabe`].
+    pub fn validate(&self) -> Result<()> {
+        Clabe::check(self.as_str())
+    }
+}
+
+impl fmt::Display for Clabe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Clabe {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        Clabe::new(code)
+    }
+}
+
+impl From<&str> for Clabe {
+    fn from(code: &str) -> Self {
+        Clabe::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for Clabe {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for Clabe {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Clabe, Result};
+
+    #[test]
+    fn is_valid() -> Result<()> {
+        const WRONG_CODES: &[&str] = &[
+            "032180000118359710",
+            "03218000011835971",
+            "0321800001183597190",
+            "03218000011835971A",
+        ];
+        const VALID_CODES: &[&str] = &["032180000118359719", "002010077777777771"];
+
+        for code in WRONG_CODES.iter() {
+            assert!(!Clabe::is_valid(code), "{}", code);
+        }
+
+        for code in VALID_CODES.iter() {
+            assert!(Clabe::is_valid(code), "{}", code);
+        }
+
+        let clabe = Clabe::compute("032", "180", "00011835971")?;
+        assert_eq!(clabe, Clabe::new("032 180 00011835971 9")?);
+        assert_eq!(clabe.bank_code(), "032");
+        assert_eq!(clabe.plaza_code(), "180");
+        assert_eq!(clabe.account_number(), "00011835971");
+        assert_eq!(
+            serde_json::to_string(&clabe).unwrap_or_default(),
+            "\"032180000118359719\""
+        );
+        assert!(serde_json::from_str::<Clabe>("\"032180000118359710\"").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+//! Mexican payment standards.
+
+pub mod clabe;
+
+pub use clabe::*;
@@ -0,0 +1,307 @@
+//! New Zealand bank account numbers, and their check digit algorithms as
+//! published by Payments NZ and the IRD.
+
+use crate::code::Code;
+use crate::error::{check_digits, Error, Reason};
+use crate::result::Result;
+use arraystring::typenum::U18;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `LENGTH` is the length of a normalised `BankAccount`: bank (2 digits),
+/// branch (4 digits), account base (8 digits) and suffix (4 digits).
+pub const LENGTH: usize = 18;
+
+/// `ALGORITHM_B_BASE` is the account base from which the algorithm B is
+/// used instead of the algorithm A.
+const ALGORITHM_B_BASE: u32 = 990_000;
+
+/// [`Algorithm`] is a check digit algorithm of NZ bank accounts, applied
+/// to the 18 digits of the normalised account number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    /// `X` is used by banks without check digit, any account is valid.
+    X,
+}
+
+impl Algorithm {
+    /// `for_account` returns the algorithm of a bank and an account base:
+    /// some banks have their own algorithm, others use A, or B for account
+    /// bases from 00990000.
+    pub fn for_account(bank: &str, account: &str) -> Algorithm {
+        match bank {
+            "08" => Algorithm::D,
+            "09" => Algorithm::E,
+            "25" | "33" => Algorithm::F,
+            "26" | "28" | "29" => Algorithm::G,
+            "31" => Algorithm::X,
+            _ => match account.parse::<u32>() {
+                Ok(base) if base < ALGORITHM_B_BASE => Algorithm::A,
+                _ => Algorithm::B,
+            },
+        }
+    }
+
+    /// `weights` returns the weights of the 18 digits.
+    fn weights(&self) -> [u32; LENGTH] {
+        match self {
+            Algorithm::A => [0, 0, 6, 3, 7, 9, 0, 0, 10, 5, 8, 4, 2, 1, 0, 0, 0, 0],
+            Algorithm::B => [0, 0, 0, 0, 0, 0, 0, 0, 10, 5, 8, 4, 2, 1, 0, 0, 0, 0],
+            Algorithm::C => [3, 7, 0, 0, 0, 0, 9, 1, 10, 5, 3, 4, 2, 1, 0, 0, 0, 0],
+            Algorithm::D => [0, 0, 0, 0, 0, 0, 0, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0, 0],
+            Algorithm::E => [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5, 4, 3, 2, 0, 0, 0, 1],
+            Algorithm::F => [0, 0, 0, 0, 0, 0, 0, 1, 7, 3, 1, 7, 3, 1, 0, 0, 0, 0],
+            Algorithm::G => [0, 0, 0, 0, 0, 0, 0, 1, 3, 7, 1, 3, 7, 1, 0, 3, 7, 1],
+            Algorithm::X => [0; LENGTH],
+        }
+    }
+
+    /// `modulus` returns the modulus of the weighted sum.
+    fn modulus(&self) -> u32 {
+        match self {
+            Algorithm::F | Algorithm::G => 10,
+            Algorithm::X => 1,
+            _ => 11,
+        }
+    }
+
+    /// `check` checks the 18 digits of an account number. The algorithms E
+    /// and G sum the digits of each product, until a single digit is left.
+    pub fn check(&self, digits: &str) -> bool {
+        let sum: u32 = digits
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .zip(self.weights().iter())
+            .map(|(d, w)| match self {
+                Algorithm::E | Algorithm::G => {
+                    let mut p = d * w;
+                    while p > 9 {
+                        p = p / 10 + p % 10;
+                    }
+                    p
+                }
+                _ => d * w,
+            })
+            .sum();
+
+        digits.len() == LENGTH && sum.is_multiple_of(self.modulus())
+    }
+}
+
+/// [`BankAccount`] is a NZ bank account number: bank, branch, account base
+/// and suffix, printed "01-0902-0068389-000".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BankAccount(Code<U18>);
+
+impl BankAccount {
+    /// `new` creates a new `BankAccount`, from its four parts separated by
+    /// '-' or ' ', the account base having 7 or 8 digits and the suffix 2
+    /// to 4 digits, or from its 15, 16 or 18 digits.
+    pub fn new(code: &str) -> Result<BankAccount> {
+        let code = code.trim();
+        let parts: Vec<&str> = if code.contains(['-', ' ']) {
+            code.split(['-', ' ']).filter(|p| !p.is_empty()).collect()
+        } else {
+            check_digits("bank account", code, 15, LENGTH)?;
+
+            match code.len() {
+                15 | 16 => vec![&code[0..2], &code[2..6], &code[6..13], &code[13..]],
+                LENGTH => vec![&code[0..2], &code[2..6], &code[6..14], &code[14..]],
+                _ => return Err(Error::invalid("bank account", code, format_reason())),
+            }
+        };
+
+        match parts.as_slice() {
+            [bank, branch, account, suffix]
+                if bank.len() == 2
+                    && (1..=4).contains(&branch.len())
+                    && (7..=8).contains(&account.len())
+                    && (2..=4).contains(&suffix.len()) =>
+            {
+                let cd = format!("{}{:0>4}{:0>8}{:0>4}", bank, branch, account, suffix);
+
+                BankAccount::check(&cd)?;
+
+                Ok(BankAccount(Code::<U18>::from(cd.as_str())))
+            }
+            _ => Err(Error::invalid("bank account", code, format_reason())),
+        }
+    }
+
+    /// `as_str` returns the `BankAccount` as a string slice, its 18 digits.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// `bank_code` returns the two digits code of the bank, e.g. "01".
+    pub fn bank_code(&self) -> &str {
+        &self.as_str()[0..2]
+    }
+
+    /// `branch_code` returns the four digits code of the branch.
+    pub fn branch_code(&self) -> &str {
+        &self.as_str()[2..6]
+    }
+
+    /// `account_base` returns the eight digits account base.
+    pub fn account_base(&self) -> &str {
+        &self.as_str()[6..14]
+    }
+
+    /// `suffix` returns the four digits suffix.
+    pub fn suffix(&self) -> &str {
+        &self.as_str()[14..18]
+    }
+
+    /// `algorithm` returns the check digit algorithm of the account.
+    pub fn algorithm(&self) -> Algorithm {
+        Algorithm::for_account(self.bank_code(), self.account_base())
+    }
+
+    /// `is_valid` returns if a provided normalised code is valid by
+    /// checking length, kind of chars used and the check digit. The
+    /// branch is not checked against the ranges of its bank.
+    pub fn is_valid(code: &str) -> bool {
+        BankAccount::check(code).is_ok()
+    }
+
+    /// `check` checks a provided normalised code, returning why it is
+    /// invalid.
+    pub fn check(code: &str) -> Result<()> {
+        check_digits("bank account", code, LENGTH, LENGTH)?;
+
+        let algorithm = Algorithm::for_account(&code[0..2], &code[6..14]);
+
+        if !algorithm.check(code) {
+            return Err(Error::invalid(
+                "bank account",
+                code,
+                Reason::Format(format!("fails the check digit algorithm {:?}", algorithm)),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `validate` validates the [`BankAccount`].
+    pub fn validate(&self) -> Result<()> {
+        BankAccount::check(self.as_str())
+    }
+
+    /// `print` returns the `BankAccount` in print format, with a 7 digits
+    /// account base and a 3 digits suffix when possible, e.g.
+    /// "01-0902-0068389-000".
+    pub fn print(&self) -> String {
+        let account = self.account_base();
+        let suffix = self.suffix();
+
+        format!(
+            "{}-{}-{}-{}",
+            self.bank_code(),
+            self.branch_code(),
+            account.strip_prefix('0').unwrap_or(account),
+            suffix.strip_prefix('0').unwrap_or(suffix)
+        )
+    }
+}
+
+/// `format_reason` describes the accepted formats of a `BankAccount`.
+fn format_reason() -> Reason {
+    Reason::Format("expected bank-branch-account-suffix or 15, 16 or 18 digits".into())
+}
+
+impl fmt::Display for BankAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for BankAccount {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        BankAccount::new(code)
+    }
+}
+
+impl From<&str> for BankAccount {
+    fn from(code: &str) -> Self {
+        BankAccount::from_str(code).unwrap()
+    }
+}
+
+impl Serialize for BankAccount {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        Serialize::serialize(self.0.as_str(), ser)
+    }
+}
+
+impl<'a> Deserialize<'a> for BankAccount {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Algorithm, BankAccount, Result};
+
+    #[test]
+    fn is_valid() -> Result<()> {
+        const WRONG_ACCOUNTS: &[&str] = &[
+            "01-902-0068390-00",
+            "08-6523-1954513-001",
+            "26-2600-0320872-032",
+            "01-0902-68389-00",
+            "01-0902-0068389-00001",
+            "010902006838",
+            "aé345678901234",
+        ];
+        const VALID_ACCOUNTS: &[(&str, Algorithm)] = &[
+            ("01-902-0068389-00", Algorithm::A),
+            ("08-6523-1954512-001", Algorithm::D),
+            ("26-2600-0320871-032", Algorithm::G),
+            ("31 2800 0000001 000", Algorithm::X),
+        ];
+
+        for account in WRONG_ACCOUNTS.iter() {
+            assert!(BankAccount::new(account).is_err(), "{}", account);
+        }
+
+        for (account, algorithm) in VALID_ACCOUNTS.iter() {
+            assert_eq!(BankAccount::new(account)?.algorithm(), *algorithm);
+        }
+
+        let account = BankAccount::new("0865231954512001")?;
+        assert_eq!(account.as_str(), "086523019545120001");
+        assert_eq!(account.algorithm(), Algorithm::D);
+
+        let account = BankAccount::new("01-902-0068389-00")?;
+        assert_eq!(account.as_str(), "010902000683890000");
+        assert_eq!(account.print(), "01-0902-0068389-000");
+        assert_eq!(account.branch_code(), "0902");
+        assert_eq!(account.account_base(), "00068389");
+        assert_eq!(account.suffix(), "0000");
+        assert_eq!(Algorithm::for_account("01", "00990000"), Algorithm::B);
+
+        assert_eq!(
+            serde_json::from_str::<BankAccount>("\"010902000683890000\"").ok(),
+            Some(account)
+        );
+        assert!(serde_json::from_str::<BankAccount>("\"01-902-0068390-00\"").is_err());
+        assert!(serde_json::from_str::<BankAccount>("\"aé345678901234\"").is_err());
+
+        Ok(())
+    }
+}
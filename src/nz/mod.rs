@@ -0,0 +1,5 @@
+//! New Zealand payment standards.
+
+pub mod account;
+
+pub use account::*;
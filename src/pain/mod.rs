@@ -0,0 +1,7 @@
+//! Payments initiation messages, as defined by ISO 20022.
+
+pub mod report;
+pub mod status;
+
+pub use report::*;
+pub use status::*;
@@ -0,0 +1,341 @@
+//! Customer payment status reports (pain.002), sent by the bank in answer
+//! to a pain.001 credit transfer or a pain.008 direct debit initiation.
+
+use crate::date::DateTime;
+use crate::error::{Error, Reason};
+use crate::iso20022::FromDocument;
+use crate::money::Money;
+use crate::pain::{ReasonCode, Status};
+use crate::result::Result;
+use crate::swift::Uetr;
+use crate::xml::Element;
+use serde::{Deserialize, Serialize};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `ROOT` is the name of the pain.002 message element inside the `Document`.
+pub const ROOT: &str = "CstmrPmtStsRpt";
+
+/// [`OriginalIds`] are the identifiers of the original message, payment
+/// information and transaction a status refers to. Each level of the
+/// report inherits the identifiers of the level above.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct OriginalIds {
+    pub message_id: String,
+    pub message_name: String,
+    pub payment_information_id: Option<String>,
+    pub instruction_id: Option<String>,
+    pub end_to_end_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub uetr: Option<Uetr>,
+}
+
+/// [`StatusReason`] explains a status, usually a rejection.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StatusReason {
+    pub code: Option<ReasonCode>,
+    pub proprietary: Option<String>,
+    pub originator: Option<String>,
+    pub additional_information: Vec<String>,
+}
+
+impl StatusReason {
+    pub(crate) fn from_element(element: &Element) -> Result<StatusReason> {
+        Ok(StatusReason {
+            code: element.find_parsed("Rsn/Cd")?,
+            proprietary: element.find_text("Rsn/Prtry").map(String::from),
+            originator: element
+                .find_text("Orgtr/Nm")
+                .or_else(|| element.find_text("Orgtr/Id/OrgId/AnyBIC"))
+                .or_else(|| element.find_text("Orgtr/Id/OrgId/BICOrBEI"))
+                .map(String::from),
+            additional_information: element
+                .find_all("AddtlInf")
+                .iter()
+                .map(|info| info.text().into())
+                .collect(),
+        })
+    }
+
+    pub(crate) fn all_from_element(element: &Element) -> Result<Vec<StatusReason>> {
+        element
+            .find_all("StsRsnInf")
+            .into_iter()
+            .map(StatusReason::from_element)
+            .collect()
+    }
+}
+
+/// [`TransactionStatus`] is the status of a single transaction (`TxInfAndSts`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionStatus {
+    pub original: OriginalIds,
+    pub status_id: Option<String>,
+    pub status: Option<Status>,
+    pub reasons: Vec<StatusReason>,
+    pub original_amount: Option<Money>,
+}
+
+impl TransactionStatus {
+    pub(crate) fn from_element(element: &Element, parent: &OriginalIds) -> Result<Self> {
+        let original_amount = match element.find_money("OrgnlTxRef/Amt/InstdAmt")? {
+            Some(amount) => Some(amount),
+            None => element.find_money("OrgnlTxRef/IntrBkSttlmAmt")?,
+        };
+
+        Ok(TransactionStatus {
+            original: OriginalIds {
+                instruction_id: element.find_text("OrgnlInstrId").map(String::from),
+                end_to_end_id: element.find_text("OrgnlEndToEndId").map(String::from),
+                transaction_id: element.find_text("OrgnlTxId").map(String::from),
+                uetr: element.find_parsed("OrgnlUETR")?,
+                ..parent.clone()
+            },
+            status_id: element.find_text("StsId").map(String::from),
+            status: element.find_parsed("TxSts")?,
+            reasons: StatusReason::all_from_element(element)?,
+            original_amount,
+        })
+    }
+}
+
+/// [`PaymentInformationStatus`] is the status of a payment information
+/// block (`OrgnlPmtInfAndSts`) and of its transactions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentInformationStatus {
+    pub original: OriginalIds,
+    pub status: Option<Status>,
+    pub reasons: Vec<StatusReason>,
+    pub transactions: Vec<TransactionStatus>,
+}
+
+impl PaymentInformationStatus {
+    fn from_element(element: &Element, parent: &OriginalIds) -> Result<Self> {
+        let original = OriginalIds {
+            payment_information_id: Some(element.require_text("OrgnlPmtInfId")?.into()),
+            ..parent.clone()
+        };
+
+        Ok(PaymentInformationStatus {
+            status: element.find_parsed("PmtInfSts")?,
+            reasons: StatusReason::all_from_element(element)?,
+            transactions: element
+                .find_all("TxInfAndSts")
+                .into_iter()
+                .map(|tx| TransactionStatus::from_element(tx, &original))
+                .collect::<Result<_>>()?,
+            original,
+        })
+    }
+}
+
+/// [`GroupStatus`] is the status of the whole original message
+/// (`OrgnlGrpInfAndSts`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupStatus {
+    pub original: OriginalIds,
+    pub original_created: Option<DateTime>,
+    pub original_number_of_transactions: Option<u64>,
+    pub status: Option<Status>,
+    pub reasons: Vec<StatusReason>,
+}
+
+impl GroupStatus {
+    pub(crate) fn from_element(element: &Element) -> Result<GroupStatus> {
+        Ok(GroupStatus {
+            original: OriginalIds {
+                message_id: element.require_text("OrgnlMsgId")?.into(),
+                message_name: element.require_text("OrgnlMsgNmId")?.into(),
+                ..Default::default()
+            },
+            original_created: element.find_parsed("OrgnlCreDtTm")?,
+            original_number_of_transactions: element
+                .find_text("OrgnlNbOfTxs")
+                .map(|n| {
+                    n.parse().map_err(|_| {
+                        Error::invalid(
+                            "number of transactions",
+                            n,
+                            Reason::Format("not a number".into()),
+                        )
+                    })
+                })
+                .transpose()?,
+            status: element.find_parsed("GrpSts")?,
+            reasons: StatusReason::all_from_element(element)?,
+        })
+    }
+}
+
+/// [`PaymentStatusReport`] is a pain.002 message: a tree of statuses, from
+/// the original group down to the single transactions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentStatusReport {
+    pub message_id: String,
+    pub created: DateTime,
+    pub group: GroupStatus,
+    pub payment_informations: Vec<PaymentInformationStatus>,
+}
+
+impl PaymentStatusReport {
+    /// `parse` parses a pain.002 XML document.
+    pub fn parse(xml: &str) -> Result<PaymentStatusReport> {
+        PaymentStatusReport::from_element(&Element::parse(xml)?)
+    }
+
+    /// `from_element` reads a pain.002 message from its `Document` element.
+    pub fn from_element(document: &Element) -> Result<PaymentStatusReport> {
+        let message = document
+            .child(ROOT)
+            .ok_or_else(|| Error::UnknownMessage(document.name.clone()))?;
+
+        let group = GroupStatus::from_element(message.require("OrgnlGrpInfAndSts")?)?;
+
+        Ok(PaymentStatusReport {
+            message_id: message.require_text("GrpHdr/MsgId")?.into(),
+            created: message.require_parsed("GrpHdr/CreDtTm")?,
+            payment_informations: message
+                .find_all("OrgnlPmtInfAndSts")
+                .into_iter()
+                .map(|info| PaymentInformationStatus::from_element(info, &group.original))
+                .collect::<Result<_>>()?,
+            group,
+        })
+    }
+
+    /// `transactions` returns the statuses of all the reported transactions.
+    pub fn transactions(&self) -> impl Iterator<Item = &TransactionStatus> {
+        self.payment_informations
+            .iter()
+            .flat_map(|info| info.transactions.iter())
+    }
+
+    /// `transaction_status` returns the effective status of a transaction:
+    /// its own status if reported, otherwise the one of its payment
+    /// information or of the group.
+    pub fn transaction_status<'a>(
+        &'a self,
+        transaction: &'a TransactionStatus,
+    ) -> Option<&'a Status> {
+        transaction
+            .status
+            .as_ref()
+            .or_else(|| {
+                self.payment_informations
+                    .iter()
+                    .find(|info| {
+                        info.original.payment_information_id
+                            == transaction.original.payment_information_id
+                    })
+                    .and_then(|info| info.status.as_ref())
+            })
+            .or(self.group.status.as_ref())
+    }
+
+    /// `rejected` returns the transactions that have been rejected.
+    pub fn rejected(&self) -> impl Iterator<Item = &TransactionStatus> {
+        self.transactions().filter(move |tx| {
+            self.transaction_status(tx)
+                .map(Status::is_rejected)
+                .unwrap_or(false)
+        })
+    }
+}
+
+impl FromStr for PaymentStatusReport {
+    type Err = Error;
+
+    fn from_str(xml: &str) -> StdResult<Self, Self::Err> {
+        PaymentStatusReport::parse(xml)
+    }
+}
+
+impl FromDocument for PaymentStatusReport {
+    fn from_document(document: &Element) -> Result<PaymentStatusReport> {
+        PaymentStatusReport::from_element(document)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{PaymentStatusReport, ReasonCode, Result, Status};
+
+    #[allow(dead_code)]
+    const PAIN_002: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:pain.002.001.03">
+  <CstmrPmtStsRpt>
+    <GrpHdr>
+      <MsgId>STS-1</MsgId>
+      <CreDtTm>2024-02-01T09:00:00</CreDtTm>
+    </GrpHdr>
+    <OrgnlGrpInfAndSts>
+      <OrgnlMsgId>MSG-1</OrgnlMsgId>
+      <OrgnlMsgNmId>pain.001.001.03</OrgnlMsgNmId>
+      <OrgnlNbOfTxs>3</OrgnlNbOfTxs>
+      <GrpSts>PART</GrpSts>
+    </OrgnlGrpInfAndSts>
+    <OrgnlPmtInfAndSts>
+      <OrgnlPmtInfId>PMT-1</OrgnlPmtInfId>
+      <PmtInfSts>PART</PmtInfSts>
+      <TxInfAndSts>
+        <StsId>1</StsId>
+        <OrgnlEndToEndId>E2E-1</OrgnlEndToEndId>
+        <TxSts>RJCT</TxSts>
+        <StsRsnInf>
+          <Orgtr><Id><OrgId><BICOrBEI>DEUTDEFF</BICOrBEI></OrgId></Id></Orgtr>
+          <Rsn><Cd>AM04</Cd></Rsn>
+          <AddtlInf>Insufficient funds</AddtlInf>
+        </StsRsnInf>
+        <OrgnlTxRef><Amt><InstdAmt Ccy="EUR">100.00</InstdAmt></Amt></OrgnlTxRef>
+      </TxInfAndSts>
+      <TxInfAndSts>
+        <OrgnlEndToEndId>E2E-2</OrgnlEndToEndId>
+        <TxSts>ACSC</TxSts>
+      </TxInfAndSts>
+    </OrgnlPmtInfAndSts>
+    <OrgnlPmtInfAndSts>
+      <OrgnlPmtInfId>PMT-2</OrgnlPmtInfId>
+      <PmtInfSts>RJCT</PmtInfSts>
+      <StsRsnInf><Rsn><Cd>AC01</Cd></Rsn></StsRsnInf>
+      <TxInfAndSts>
+        <OrgnlEndToEndId>E2E-3</OrgnlEndToEndId>
+      </TxInfAndSts>
+    </OrgnlPmtInfAndSts>
+  </CstmrPmtStsRpt>
+</Document>"#;
+
+    #[test]
+    fn parse() -> Result<()> {
+        let report = PaymentStatusReport::parse(PAIN_002)?;
+
+        assert_eq!(report.message_id, "STS-1");
+        assert_eq!(report.group.original.message_id, "MSG-1");
+        assert_eq!(report.group.original.message_name, "pain.001.001.03");
+        assert_eq!(report.group.status, Some(Status::PartiallyAccepted));
+        assert_eq!(report.payment_informations.len(), 2);
+        assert_eq!(report.transactions().count(), 3);
+
+        let rejected: Vec<_> = report.rejected().collect();
+
+        assert_eq!(rejected.len(), 2);
+        assert_eq!(rejected[0].original.message_id, "MSG-1");
+        assert_eq!(
+            rejected[0].original.payment_information_id,
+            Some("PMT-1".into())
+        );
+        assert_eq!(rejected[0].original.end_to_end_id, Some("E2E-1".into()));
+        assert_eq!(
+            rejected[0].reasons[0].code,
+            Some(ReasonCode::InsufficientFunds)
+        );
+        assert_eq!(rejected[0].reasons[0].originator, Some("DEUTDEFF".into()));
+        assert_eq!(
+            rejected[0].original_amount.as_ref().map(|a| a.amount),
+            Some(10_000)
+        );
+        assert_eq!(rejected[1].original.end_to_end_id, Some("E2E-3".into()));
+        assert_eq!(rejected[1].status, None);
+
+        Ok(())
+    }
+}
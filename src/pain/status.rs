@@ -0,0 +1,219 @@
+//! Payment status codes and ISO external status reason codes.
+
+use crate::error::{check_chars, check_length, Error};
+use serde::{de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `external_codes` defines an enum of ISO 20022 external codes, with their
+/// code, name and description. Unknown codes are kept in an `Other` variant,
+/// as the external code sets are updated quarterly.
+macro_rules! external_codes {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($variant:ident => ($code:literal, $description:literal),)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum $name {
+            $($variant,)*
+            Other(String),
+        }
+
+        impl $name {
+            /// `code` returns the four chars ISO code.
+            pub fn code(&self) -> &str {
+                match self {
+                    $($name::$variant => $code,)*
+                    $name::Other(code) => code,
+                }
+            }
+
+            /// `description` returns the ISO description of the code.
+            pub fn description(&self) -> &str {
+                match self {
+                    $($name::$variant => $description,)*
+                    $name::Other(_) => "unknown code",
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+                check_length("external code", code, 1, 4)?;
+                check_chars("external code", code, "a letter or a digit", |c| {
+                    c.is_ascii_alphanumeric()
+                })?;
+
+                Ok(match code {
+                    $($code => $name::$variant,)*
+                    _ => $name::Other(code.into()),
+                })
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.code())
+            }
+        }
+
+        impl Serialize for $name {
+            #[inline]
+            fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+                Serialize::serialize(self.code(), ser)
+            }
+        }
+
+        impl<'a> Deserialize<'a> for $name {
+            #[inline]
+            fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+                String::deserialize(des)
+                    .and_then(|s| s.parse().map_err(serde::de::Error::custom))
+            }
+        }
+    };
+}
+
+external_codes! {
+    /// [`Status`] is the status of a group, a payment information or a
+    /// transaction, as ISO external payment transaction status code.
+    pub enum Status {
+        AcceptedSettlementCompletedCreditorAccount => ("ACCC", "Settlement on the creditor's account has been completed"),
+        AcceptedCustomerProfile => ("ACCP", "Preceding check of technical validation was successful. Customer profile check was also successful"),
+        AcceptedFundsChecked => ("ACFC", "Preceding check of technical validation and customer profile was successful and an automatic funds check was positive"),
+        AcceptedClearingProcessed => ("ACPD", "Status of the payment initiation has been processed by the clearing agent"),
+        AcceptedSettlementCompleted => ("ACSC", "Settlement on the debtor's account has been completed"),
+        AcceptedSettlementInProcess => ("ACSP", "All preceding checks were successful and therefore the payment initiation has been accepted for execution"),
+        AcceptedTechnicalValidation => ("ACTC", "Authentication and syntactical and semantical validation are successful"),
+        AcceptedWithChange => ("ACWC", "Instruction is accepted but a change will be made, such as date or remittance not sent"),
+        AcceptedWithoutPosting => ("ACWP", "Payment instruction included in the credit transfer is accepted without being posted to the creditor customer's account"),
+        Blocked => ("BLCK", "Payment transaction previously reported with status 'ACWP' is blocked"),
+        Cancelled => ("CANC", "Payment initiation has been cancelled before execution"),
+        PartiallyAccepted => ("PART", "A number of transactions have been accepted, whereas another number of transactions have not yet achieved 'accepted' status"),
+        PartiallyAcceptedTechnicalCorrect => ("PATC", "Payment initiation needs multiple authentications, where some but not yet all have been performed"),
+        Pending => ("PDNG", "Payment initiation or individual transaction included in the payment initiation is pending"),
+        Presented => ("PRES", "Request for payment has been presented to the debtor"),
+        Received => ("RCVD", "Payment initiation has been received by the receiving agent"),
+        Rejected => ("RJCT", "Payment initiation or individual transaction included in the payment initiation has been rejected"),
+    }
+}
+
+impl Status {
+    /// `is_rejected` returns if the status is a rejection.
+    pub fn is_rejected(&self) -> bool {
+        *self == Status::Rejected
+    }
+
+    /// `is_final` returns if no further status is expected after this one.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Status::AcceptedSettlementCompletedCreditorAccount
+                | Status::AcceptedSettlementCompleted
+                | Status::Cancelled
+                | Status::Rejected
+        )
+    }
+}
+
+external_codes! {
+    /// [`ReasonCode`] is an ISO external status reason code, telling why a
+    /// payment was rejected, returned or cancelled.
+    pub enum ReasonCode {
+        IncorrectAccountNumber => ("AC01", "Format of the account number specified is not correct"),
+        InvalidDebtorAccountNumber => ("AC02", "Debtor account number invalid or missing"),
+        InvalidCreditorAccountNumber => ("AC03", "Wrong IBAN in SCT"),
+        ClosedAccountNumber => ("AC04", "Account number specified has been closed on the bank of account's books"),
+        ClosedDebtorAccountNumber => ("AC05", "Debtor account number closed"),
+        BlockedAccount => ("AC06", "Account specified is blocked, prohibiting posting of transactions against it"),
+        ClosedCreditorAccountNumber => ("AC07", "Creditor account number closed"),
+        InvalidAccountCurrency => ("AC09", "Account currency is invalid or missing"),
+        InvalidDebtorAccountType => ("AC13", "Debtor account type missing or invalid"),
+        InvalidAgent => ("AC14", "An agent in the payment workflow is invalid"),
+        TransactionForbidden => ("AG01", "Transaction forbidden on this type of account (formerly No Agreement)"),
+        InvalidBankOperationCode => ("AG02", "Bank operation code specified in the message is not valid for receiver"),
+        TransactionNotSupported => ("AG03", "Transaction type not supported/authorized on this account"),
+        IncorrectAgent => ("AGNT", "Agent in the payment workflow is incorrect"),
+        ZeroAmount => ("AM01", "Specified message amount is equal to zero"),
+        NotAllowedAmount => ("AM02", "Specific transaction/message amount is greater than allowed maximum"),
+        NotAllowedCurrency => ("AM03", "Specified message amount is an non processable currency outside of existing agreement"),
+        InsufficientFunds => ("AM04", "Amount of funds available to cover specified message amount is insufficient"),
+        Duplication => ("AM05", "Duplication"),
+        TooLowAmount => ("AM06", "Specified transaction amount is less than agreed minimum"),
+        BlockedAmount => ("AM07", "Amount specified in message has been blocked by regulatory authorities"),
+        WrongAmount => ("AM09", "Amount received is not the amount agreed or expected"),
+        InvalidControlSum => ("AM10", "Sum of instructed amounts does not equal the control sum"),
+        AlreadyReturned => ("ARDT", "Cancellation not accepted as the transaction has already been returned"),
+        InconsistentWithEndCustomer => ("BE01", "Identification of end customer is not consistent with associated account number"),
+        MissingCreditorAddress => ("BE04", "Specification of creditor's address, which is required for payment, is missing/not correct"),
+        UnrecognisedInitiatingParty => ("BE05", "Party who initiated the message is not recognised by the end customer"),
+        UnknownEndCustomer => ("BE06", "End customer specified is not known at associated Sort/National Bank Code or does no longer exist in the books"),
+        MissingDebtorAddress => ("BE07", "Specification of debtor's address, which is required for payment, is missing/not correct"),
+        CreditorBankIsNotRegistered => ("CNOR", "Creditor bank is not registered under this BIC in the CSM"),
+        IncorrectCurrency => ("CURR", "Currency of the payment is incorrect"),
+        RequestedByCustomer => ("CUST", "Cancellation requested by the debtor"),
+        DebtorBankIsNotRegistered => ("DNOR", "Debtor bank is not registered under this BIC in the CSM"),
+        CancelledByUser => ("DS02", "An authorized user has cancelled the order"),
+        InvalidDate => ("DT01", "Invalid date (eg, wrong or missing settlement date)"),
+        DuplicatePayment => ("DUPL", "Payment is a duplicate of another payment"),
+        SettlementFailed => ("ED05", "Settlement of the transaction has failed"),
+        InvalidFileFormat => ("FF01", "File Format incomplete or invalid"),
+        InvalidLocalInstrumentCode => ("FF05", "Local Instrument code is missing or invalid"),
+        FollowingCancellationRequest => ("FOCR", "Return following a cancellation request"),
+        FraudulentOrigin => ("FRAD", "Cancellation requested following a transaction that was originated fraudulently"),
+        LegalDecision => ("LEGL", "Reported when the cancellation cannot be accepted because of regulatory rules"),
+        NoMandate => ("MD01", "No Mandate"),
+        MissingMandatoryInformationInMandate => ("MD02", "Mandate related information data required by the scheme is missing"),
+        RefundRequestByEndCustomer => ("MD06", "Return of funds requested by end customer"),
+        EndCustomerDeceased => ("MD07", "End customer is deceased"),
+        NotSpecifiedReasonCustomerGenerated => ("MS02", "Reason has not been specified by end customer"),
+        NotSpecifiedReasonAgentGenerated => ("MS03", "Reason has not been specified by agent"),
+        Narrative => ("NARR", "Reason is provided as narrative information in the additional reason information"),
+        NoAnswerFromCustomer => ("NOAS", "No response from beneficiary"),
+        NoOriginalTransactionReceived => ("NOOR", "Original transaction (subject to cancellation) never received"),
+        BankIdentifierIncorrect => ("RC01", "Bank identifier code specified in the message has an incorrect format"),
+        MissingDebtorAccountOrIdentification => ("RR01", "Specification of the debtor's account or unique identification needed for reasons of regulatory requirements is insufficient or missing"),
+        MissingDebtorNameOrAddress => ("RR02", "Specification of the debtor's name and/or address needed for regulatory requirements is insufficient or missing"),
+        MissingCreditorNameOrAddress => ("RR03", "Specification of the creditor's name and/or address needed for regulatory requirements is insufficient or missing"),
+        RegulatoryReason => ("RR04", "Regulatory Reason"),
+        SpecificServiceOfferedByDebtorAgent => ("SL01", "Due to specific service offered by the debtor agent"),
+        TechnicalProblem => ("TECH", "Technical problems resulting in erroneous SDDs or SCTs"),
+        InvalidCutOffTime => ("TM01", "Associated message was received after agreed processing cut-off time"),
+        UnduePayment => ("UPAY", "Payment is not justified"),
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{ReasonCode, Status};
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[test]
+    fn parse() -> Result<()> {
+        assert_eq!("RJCT".parse::<Status>()?, Status::Rejected);
+        assert!("RJCT".parse::<Status>()?.is_final());
+        assert_eq!("AM04".parse::<ReasonCode>()?, ReasonCode::InsufficientFunds);
+        assert_eq!(
+            ReasonCode::InsufficientFunds.description(),
+            "Amount of funds available to cover specified message amount is insufficient"
+        );
+        assert_eq!(
+            "ZZ99".parse::<ReasonCode>()?,
+            ReasonCode::Other("ZZ99".into())
+        );
+        assert_eq!(ReasonCode::Other("ZZ99".into()).code(), "ZZ99");
+
+        assert!("".parse::<ReasonCode>().is_err());
+        assert!("AC-1".parse::<ReasonCode>().is_err());
+        assert!("TOOLONG".parse::<Status>().is_err());
+
+        Ok(())
+    }
+}
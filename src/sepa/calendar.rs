@@ -0,0 +1,108 @@
+//! TARGET2 calendar, used to compute the SEPA scheme time limits.
+
+use crate::date::Date;
+use crate::result::Result;
+
+/// `easter_sunday` returns the date of the (gregorian) easter sunday of a year.
+pub fn easter_sunday(year: u16) -> Result<Date> {
+    // Anonymous gregorian algorithm (Meeus/Jones/Butcher)
+    let y = i64::from(year);
+    let a = y % 19;
+    let b = y / 100;
+    let c = y % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    Date::new(year, month as u8, day as u8)
+}
+
+/// `is_target_holiday` returns if the date is a TARGET closing day other
+/// than a weekend: New Year's Day, Good Friday, Easter Monday, Labour Day,
+/// Christmas Day and Boxing Day.
+pub fn is_target_holiday(date: &Date) -> Result<bool> {
+    match (date.month(), date.day()) {
+        (1, 1) | (5, 1) | (12, 25) | (12, 26) => return Ok(true),
+        _ => (),
+    }
+
+    let easter = easter_sunday(date.year())?;
+
+    Ok(*date == easter.add_days(-2)? || *date == easter.add_days(1)?)
+}
+
+/// `is_target_day` returns if the date is a TARGET business day.
+pub fn is_target_day(date: &Date) -> Result<bool> {
+    Ok(!date.is_weekend() && !is_target_holiday(date)?)
+}
+
+/// `add_target_days` returns the date `days` TARGET business days after
+/// the given date.
+pub fn add_target_days(date: &Date, days: u32) -> Result<Date> {
+    let mut res = *date;
+    let mut left = days;
+
+    while left > 0 {
+        res = res.add_days(1)?;
+
+        if is_target_day(&res)? {
+            left -= 1;
+        }
+    }
+
+    Ok(res)
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{add_target_days, easter_sunday, is_target_day};
+    #[allow(unused_imports)] // TODO
+    use crate::date::Date;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[test]
+    fn easter() -> Result<()> {
+        assert_eq!(easter_sunday(2024)?.to_string(), "2024-03-31");
+        assert_eq!(easter_sunday(2025)?.to_string(), "2025-04-20");
+        assert_eq!(easter_sunday(2038)?.to_string(), "2038-04-25");
+
+        Ok(())
+    }
+
+    #[test]
+    fn target_days() -> Result<()> {
+        const CLOSING_DAYS: &[&str] = &[
+            "2024-01-01",
+            "2024-03-29",
+            "2024-04-01",
+            "2024-05-01",
+            "2024-12-25",
+            "2024-12-26",
+            "2024-06-08",
+        ];
+
+        for date in CLOSING_DAYS.iter() {
+            assert!(!is_target_day(&date.parse()?)?);
+        }
+
+        assert!(is_target_day(&"2024-03-28".parse()?)?);
+
+        let date: Date = "2024-03-27".parse()?;
+
+        assert_eq!(add_target_days(&date, 0)?, date);
+        assert_eq!(add_target_days(&date, 1)?.to_string(), "2024-03-28");
+        assert_eq!(add_target_days(&date, 2)?.to_string(), "2024-04-02");
+        assert_eq!(add_target_days(&date, 5)?.to_string(), "2024-04-05");
+
+        Ok(())
+    }
+}
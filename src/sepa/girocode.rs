@@ -0,0 +1,626 @@
+//! EPC069-12 quick response code of SEPA credit transfers, also known as
+//! "GiroCode": the "BCD" payload printed on invoices, to be scanned by the
+//! banking apps of the payers. Versions 001 and 002 are supported, with all
+//! the character sets of the guidelines.
+
+use crate::error::{check_chars, Error, Reason};
+use crate::iso11649::CreditorReference;
+use crate::iso13616::IBAN;
+use crate::iso9362::BIC;
+use crate::money::Money;
+use crate::result::Result;
+use serde::{Deserialize, Serialize};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `SERVICE_TAG` is the first element of the payload.
+pub const SERVICE_TAG: &str = "BCD";
+
+/// `IDENTIFICATION` is the SEPA credit transfer identification code.
+pub const IDENTIFICATION: &str = "SCT";
+
+/// `MAX_PAYLOAD_LENGTH` is the max length of the payload, in bytes.
+pub const MAX_PAYLOAD_LENGTH: usize = 331;
+
+/// `MAX_AMOUNT` is the max amount of the transfer, in euro cents.
+const MAX_AMOUNT: i64 = 99_999_999_999;
+
+/// `LATIN_2` are the chars 0xA0 to 0xFF of ISO 8859-2.
+const LATIN_2: &str = "\u{00A0}Ą˘Ł¤ĽŚ§¨ŠŞŤŹ\u{00AD}ŽŻ°ą˛ł´ľśˇ¸šşťź˝žżŔÁÂĂÄĹĆÇČÉĘËĚÍÎĎĐŃŇÓÔŐÖ×ŘŮÚŰÜÝŢßŕáâăäĺćçčéęëěíîďđńňóôőö÷řůúűüýţ˙";
+
+/// `LATIN_4` are the chars 0xA0 to 0xFF of ISO 8859-4.
+const LATIN_4: &str = "\u{00A0}ĄĸŖ¤ĨĻ§¨ŠĒĢŦ\u{00AD}Ž¯°ą˛ŗ´ĩļˇ¸šēģŧŊžŋĀÁÂÃÄÅÆĮČÉĘËĖÍÎĪĐŅŌĶÔÕÖ×ØŲÚÛÜŨŪßāáâãäåæįčéęëėíîīđņōķôõö÷øųúûüũū˙";
+
+/// `CYRILLIC` are the chars 0xA0 to 0xFF of ISO 8859-5.
+const CYRILLIC: &str = "\u{00A0}ЁЂЃЄЅІЇЈЉЊЋЌ\u{00AD}ЎЏАБВГДЕЖЗИЙКЛМНОПРСТУФХЦЧШЩЪЫЬЭЮЯабвгдежзийклмнопрстуфхцчшщъыьэюя№ёђѓєѕіїјљњћќ§ўџ";
+
+/// `GREEK` are the chars 0xA0 to 0xFF of ISO 8859-7.
+const GREEK: &str = "\u{00A0}‘’£€₯¦§¨©ͺ«¬\u{00AD}\u{FFFD}―°±²³΄΅Ά·ΈΉΊ»Ό½ΎΏΐΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡ\u{FFFD}ΣΤΥΦΧΨΩΪΫάέήίΰαβγδεζηθικλμνξοπρςστυφχψωϊϋόύώ\u{FFFD}";
+
+/// `LATIN_6` are the chars 0xA0 to 0xFF of ISO 8859-10.
+const LATIN_6: &str = "\u{00A0}ĄĒĢĪĨĶ§ĻĐŠŦŽ\u{00AD}ŪŊ°ąēģīĩķ·ļđšŧž―ūŋĀÁÂÃÄÅÆĮČÉĘËĖÍÎÏÐŅŌÓÔÕÖŨØŲÚÛÜÝÞßāáâãäåæįčéęëėíîïðņōóôõöũøųúûüýþĸ";
+
+/// `LATIN_9` are the chars 0xA0 to 0xFF of ISO 8859-15.
+const LATIN_9: &str = "\u{00A0}¡¢£€¥Š§š©ª«¬\u{00AD}®¯°±²³Žµ¶·ž¹º»ŒœŸ¿ÀÁÂÃÄÅÆÇÈÉÊËÌÍÎÏÐÑÒÓÔÕÖ×ØÙÚÛÜÝÞßàáâãäåæçèéêëìíîïðñòóôõö÷øùúûüýþÿ";
+
+/// [`Version`] is the version of the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Version {
+    /// Version 001, with a mandatory BIC.
+    V1,
+    /// Version 002, with an optional BIC within the EEA.
+    V2,
+}
+
+impl Version {
+    /// `as_str` returns the version code of the payload.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Version::V1 => "001",
+            Version::V2 => "002",
+        }
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        match code {
+            "001" => Ok(Version::V1),
+            "002" => Ok(Version::V2),
+            _ => Err(Error::invalid("GiroCode version", code, Reason::Unknown)),
+        }
+    }
+}
+
+/// [`CharacterSet`] is the character set the payload is encoded with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CharacterSet {
+    Utf8,
+    /// ISO 8859-1, western european.
+    Latin1,
+    /// ISO 8859-2, central european.
+    Latin2,
+    /// ISO 8859-4, north european.
+    Latin4,
+    /// ISO 8859-5, cyrillic.
+    Cyrillic,
+    /// ISO 8859-7, greek.
+    Greek,
+    /// ISO 8859-10, nordic.
+    Latin6,
+    /// ISO 8859-15, western european with the euro sign.
+    Latin9,
+}
+
+impl CharacterSet {
+    /// `SETS` are all the character sets, in code order.
+    pub const SETS: [CharacterSet; 8] = [
+        CharacterSet::Utf8,
+        CharacterSet::Latin1,
+        CharacterSet::Latin2,
+        CharacterSet::Latin4,
+        CharacterSet::Cyrillic,
+        CharacterSet::Greek,
+        CharacterSet::Latin6,
+        CharacterSet::Latin9,
+    ];
+
+    /// `code` returns the code of the character set in the payload.
+    pub fn code(&self) -> u8 {
+        CharacterSet::SETS
+            .iter()
+            .position(|set| set == self)
+            .unwrap_or_default() as u8
+            + 1
+    }
+
+    /// `upper_half` returns the chars 0xA0 to 0xFF of ISO 8859 sets but
+    /// Latin 1, whose chars are the first 256 Unicode chars.
+    fn upper_half(&self) -> Option<&'static str> {
+        match self {
+            CharacterSet::Utf8 | CharacterSet::Latin1 => None,
+            CharacterSet::Latin2 => Some(LATIN_2),
+            CharacterSet::Latin4 => Some(LATIN_4),
+            CharacterSet::Cyrillic => Some(CYRILLIC),
+            CharacterSet::Greek => Some(GREEK),
+            CharacterSet::Latin6 => Some(LATIN_6),
+            CharacterSet::Latin9 => Some(LATIN_9),
+        }
+    }
+
+    /// `encode` encodes a text, failing on chars out of the character set.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        if *self == CharacterSet::Utf8 {
+            return Ok(text.as_bytes().to_vec());
+        }
+
+        let mut res = Vec::with_capacity(text.len());
+
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                res.push(b'\n');
+            }
+
+            for (index, c) in line.chars().enumerate() {
+                let byte = match (c as u32, self.upper_half()) {
+                    (n, _) if n < 0xa0 => Some(n as u8),
+                    (n, None) if n <= 0xff => Some(n as u8),
+                    (_, Some(upper)) if c != '\u{FFFD}' => {
+                        upper.chars().position(|u| u == c).map(|i| 0xa0 + i as u8)
+                    }
+                    _ => None,
+                };
+
+                res.push(byte.ok_or_else(|| {
+                    invalid(
+                        Some(i + 1),
+                        Reason::Character {
+                            index,
+                            found: c,
+                            expected: "a char of the character set",
+                        },
+                    )
+                })?);
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// `decode` decodes a text, failing on bytes out of the character set.
+    pub fn decode(&self, bytes: &[u8]) -> Result<String> {
+        let line =
+            |offset: usize| Some(bytes[..offset].iter().filter(|b| **b == b'\n').count() + 1);
+
+        if *self == CharacterSet::Utf8 {
+            return String::from_utf8(bytes.to_vec()).map_err(|e| {
+                let offset = e.utf8_error().valid_up_to();
+                invalid(line(offset), Reason::Format("invalid UTF-8".into()))
+            });
+        }
+
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(offset, b)| match (*b, self.upper_half()) {
+                (b, Some(upper)) if b >= 0xa0 => upper
+                    .chars()
+                    .nth((b - 0xa0) as usize)
+                    .filter(|c| *c != '\u{FFFD}')
+                    .ok_or_else(|| {
+                        invalid(
+                            line(offset),
+                            Reason::Format(format!("byte {:#x} out of the {:?} set", b, self)),
+                        )
+                    }),
+                (b, _) => Ok(b as char),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for CharacterSet {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        match code.parse::<usize>() {
+            Ok(n) if code.len() == 1 && (1..=8).contains(&n) => Ok(CharacterSet::SETS[n - 1]),
+            _ => Err(Error::invalid("character set", code, Reason::Unknown)),
+        }
+    }
+}
+
+/// [`Remittance`] is the remittance information of the transfer: an RF
+/// creditor reference or a text, not both.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Remittance {
+    Reference(CreditorReference),
+    Text(String),
+    None,
+}
+
+/// [`GiroCode`] is an EPC069-12 SEPA credit transfer QR code payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GiroCode {
+    pub version: Version,
+    pub character_set: CharacterSet,
+    /// `bic` is the BIC of the beneficiary bank, mandatory in version 001.
+    pub bic: Option<BIC>,
+    /// `name` is the name of the beneficiary.
+    pub name: String,
+    pub iban: IBAN,
+    /// `amount` is the amount in euro, `None` if left to the payer.
+    pub amount: Option<Money>,
+    /// `purpose` is the ISO 20022 purpose code of the transfer.
+    pub purpose: Option<String>,
+    pub remittance: Remittance,
+    /// `information` is the beneficiary to originator information, shown to
+    /// the payer.
+    pub information: Option<String>,
+}
+
+/// `invalid` returns an error for an invalid payload, at a 1-based line if
+/// known.
+fn invalid(line: Option<usize>, reason: Reason) -> Error {
+    Error::invalid_format("EPC QR code", line, reason)
+}
+
+/// `check_length` checks the length, in chars, of the element at the
+/// 1-based `line`.
+fn check_length(line: usize, value: &str, max: usize) -> Result<()> {
+    let actual = value.chars().count();
+
+    if actual > max {
+        return Err(invalid(
+            Some(line),
+            Reason::Length {
+                min: 0,
+                max,
+                actual,
+            },
+        ));
+    }
+
+    Ok(())
+}
+
+impl GiroCode {
+    /// `new` creates a new version 002 `GiroCode`, encoded in UTF-8, without
+    /// BIC, purpose nor information.
+    pub fn new(name: &str, iban: IBAN, amount: Option<Money>, remittance: Remittance) -> GiroCode {
+        GiroCode {
+            version: Version::V2,
+            character_set: CharacterSet::Utf8,
+            bic: None,
+            name: name.into(),
+            iban,
+            amount,
+            purpose: None,
+            remittance,
+            information: None,
+        }
+    }
+
+    /// `check` checks the elements of the payload, but its length.
+    fn check(&self) -> Result<()> {
+        if self.version == Version::V1 && self.bic.is_none() {
+            return Err(Error::MissingElement("BIC".into()));
+        }
+
+        if self.name.trim().is_empty() {
+            return Err(Error::MissingElement("beneficiary name".into()));
+        }
+        check_length(6, &self.name, 70)?;
+
+        if let Some(ref amount) = self.amount {
+            if amount.currency.as_str() != "EUR" {
+                return Err(invalid(
+                    Some(8),
+                    Reason::Format(format!("expected EUR, found {}", amount.currency.as_str())),
+                ));
+            }
+
+            if amount.amount <= 0 || amount.amount > MAX_AMOUNT {
+                return Err(invalid(
+                    Some(8),
+                    Reason::Format("expected an amount of 0.01 to 999999999.99".into()),
+                ));
+            }
+        }
+
+        if let Some(ref purpose) = self.purpose {
+            crate::error::check_length("purpose", purpose, 4, 4)?;
+            check_chars("purpose", purpose, "an uppercase letter or a digit", |c| {
+                c.is_ascii_uppercase() || c.is_ascii_digit()
+            })?;
+        }
+
+        match self.remittance {
+            Remittance::Reference(ref reference) => reference.validate()?,
+            Remittance::Text(ref text) => check_length(11, text, 140)?,
+            Remittance::None => {}
+        }
+
+        if let Some(ref information) = self.information {
+            check_length(12, information, 70)?;
+        }
+
+        for (i, line) in self.to_lines().iter().enumerate() {
+            if let Some((index, found)) = line
+                .chars()
+                .enumerate()
+                .find(|(_, c)| *c == '\r' || *c == '\n')
+            {
+                return Err(invalid(
+                    Some(i + 1),
+                    Reason::Character {
+                        index,
+                        found,
+                        expected: "a char other than a line break",
+                    },
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `validate` validates the payload: its elements, the chars of its
+    /// character set and its length.
+    pub fn validate(&self) -> Result<()> {
+        self.to_bytes().map(|_| ())
+    }
+
+    /// `to_lines` returns the elements of the payload, without the trailing
+    /// empty ones.
+    fn to_lines(&self) -> Vec<String> {
+        let (reference, text) = match self.remittance {
+            Remittance::Reference(ref reference) => (reference.to_string(), String::new()),
+            Remittance::Text(ref text) => (String::new(), text.clone()),
+            Remittance::None => (String::new(), String::new()),
+        };
+
+        let mut res = vec![
+            SERVICE_TAG.to_string(),
+            self.version.as_str().into(),
+            self.character_set.code().to_string(),
+            IDENTIFICATION.into(),
+            self.bic.as_ref().map(|b| b.to_string()).unwrap_or_default(),
+            self.name.clone(),
+            self.iban.to_string(),
+            self.amount
+                .as_ref()
+                .map(|a| format!("EUR{}", a.format('.')))
+                .unwrap_or_default(),
+            self.purpose.clone().unwrap_or_default(),
+            reference,
+            text,
+            self.information.clone().unwrap_or_default(),
+        ];
+
+        while res.last().map(|line| line.is_empty()).unwrap_or(false) {
+            res.pop();
+        }
+
+        res
+    }
+
+    /// `to_payload` builds the payload as a text, with LF separators.
+    pub fn to_payload(&self) -> Result<String> {
+        self.validate()?;
+
+        Ok(self.to_lines().join("\n"))
+    }
+
+    /// `to_bytes` builds the payload encoded with its character set, as
+    /// put in the QR code.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        self.check()?;
+
+        let res = self.character_set.encode(&self.to_lines().join("\n"))?;
+
+        if res.len() > MAX_PAYLOAD_LENGTH {
+            return Err(too_long(res.len()));
+        }
+
+        Ok(res)
+    }
+
+    /// `parse` parses a payload, with LF or CR LF separators.
+    pub fn parse(payload: &str) -> Result<GiroCode> {
+        let res = GiroCode::from_lines(payload)?;
+
+        // NOTE: the length is the one of the payload as encoded, not the one
+        // of the payload as built again
+        let length = res.character_set.encode(payload)?.len();
+        if length > MAX_PAYLOAD_LENGTH {
+            return Err(too_long(length));
+        }
+
+        Ok(res)
+    }
+
+    /// `from_bytes` parses a payload encoded with the character set it
+    /// declares, as read from the QR code.
+    pub fn from_bytes(bytes: &[u8]) -> Result<GiroCode> {
+        let character_set: CharacterSet = bytes
+            .split(|b| *b == b'\n')
+            .nth(2)
+            .and_then(|code| std::str::from_utf8(code).ok())
+            .map(|code| code.trim_end_matches('\r'))
+            .ok_or_else(|| Error::MissingElement("character set".into()))?
+            .parse()?;
+
+        GiroCode::parse(&character_set.decode(bytes)?)
+    }
+
+    /// `from_lines` reads and checks the elements of a payload.
+    fn from_lines(payload: &str) -> Result<GiroCode> {
+        let lines: Vec<&str> = payload
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+
+        let expected = |line: usize, value: &str| {
+            invalid(
+                Some(line),
+                Reason::Format(format!("expected {}, found {:?}", value, lines[line - 1])),
+            )
+        };
+
+        if lines.len() < 7 || lines.len() > 12 {
+            return Err(invalid(
+                None,
+                Reason::Format(format!("expected 7 to 12 elements, found {}", lines.len())),
+            ));
+        }
+
+        if lines[0] != SERVICE_TAG {
+            return Err(expected(1, SERVICE_TAG));
+        }
+
+        if lines[3] != IDENTIFICATION {
+            return Err(expected(4, IDENTIFICATION));
+        }
+
+        let line = |i: usize| lines.get(i).copied().unwrap_or_default();
+        let text = |i: usize| Some(line(i).to_string()).filter(|line| !line.is_empty());
+
+        let amount = match line(7) {
+            "" => None,
+            amount => {
+                let amount = amount
+                    .strip_prefix("EUR")
+                    .ok_or_else(|| expected(8, "an amount starting with EUR"))?;
+                let decimals = amount.split_once('.').map(|(_, d)| d.len()).unwrap_or(0);
+
+                if decimals > 2 || amount.contains(|c: char| !c.is_ascii_digit() && c != '.') {
+                    return Err(expected(8, "an amount with a '.' and 2 decimals at most"));
+                }
+
+                Some(Money::parse(amount, "EUR".into())?)
+            }
+        };
+
+        let remittance = match (line(9), line(10)) {
+            ("", "") => Remittance::None,
+            (reference, "") => Remittance::Reference(reference.parse()?),
+            ("", text) => Remittance::Text(text.into()),
+            _ => {
+                return Err(invalid(
+                    Some(11),
+                    Reason::Format("both structured and unstructured remittance".into()),
+                ))
+            }
+        };
+
+        let res = GiroCode {
+            version: lines[1].parse()?,
+            character_set: lines[2].parse()?,
+            bic: match lines[4] {
+                "" => None,
+                bic => Some(bic.parse()?),
+            },
+            name: lines[5].into(),
+            iban: lines[6].parse()?,
+            amount,
+            purpose: text(8),
+            remittance,
+            information: text(11),
+        };
+
+        res.check()?;
+
+        Ok(res)
+    }
+}
+
+/// `too_long` returns the error for a payload of `length` bytes, over
+/// [`MAX_PAYLOAD_LENGTH`].
+fn too_long(length: usize) -> Error {
+    invalid(
+        None,
+        Reason::Format(format!("{} bytes, {} at most", length, MAX_PAYLOAD_LENGTH)),
+    )
+}
+
+impl FromStr for GiroCode {
+    type Err = Error;
+
+    fn from_str(payload: &str) -> StdResult<Self, Self::Err> {
+        GiroCode::parse(payload)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{CharacterSet, GiroCode, Remittance, Version};
+    #[allow(unused_imports)] // TODO
+    use crate::iso11649::CreditorReference;
+    #[allow(unused_imports)] // TODO
+    use crate::money::Money;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[allow(dead_code)]
+    const PAYLOAD: &str = "BCD\n001\n1\nSCT\nBPOTBEB1\nRed Cross of Belgium\nBE72000000001616\nEUR1\nCHAR\n\nUrgency fund\nSample EPC QR code";
+
+    #[test]
+    fn parse_and_build() -> Result<()> {
+        let code = GiroCode::parse(PAYLOAD)?;
+
+        assert_eq!(code.version, Version::V1);
+        assert_eq!(
+            code.bic.as_ref().map(|b| b.to_string()),
+            Some("BPOTBEB1".into())
+        );
+        assert_eq!(
+            code.amount.as_ref().map(|a| a.to_string()),
+            Some("1.00 EUR".into())
+        );
+        assert_eq!(code.purpose.as_deref(), Some("CHAR"));
+        assert_eq!(code.remittance, Remittance::Text("Urgency fund".into()));
+        assert_eq!(code.to_payload()?, PAYLOAD.replace("EUR1\n", "EUR1.00\n"));
+        assert_eq!(GiroCode::parse(&PAYLOAD.replace('\n', "\r\n"))?, code);
+
+        let mut code = GiroCode::new(
+            "Łódź Ogród Zoologiczny",
+            "BE72000000001616".into(),
+            Some(Money::new(1050, "EUR".into())),
+            Remittance::Reference(CreditorReference::new("RF18539007547034")?),
+        );
+        assert_eq!(
+            code.to_payload()?,
+            "BCD\n002\n1\nSCT\n\nŁódź Ogród Zoologiczny\nBE72000000001616\nEUR10.50\n\nRF18539007547034"
+        );
+
+        code.character_set = CharacterSet::Latin2;
+        let bytes = code.to_bytes()?;
+        assert_eq!(
+            bytes[bytes.iter().position(|b| *b == 0xa3).unwrap() + 1],
+            0xf3
+        );
+        assert_eq!(GiroCode::from_bytes(&bytes)?, code);
+
+        code.character_set = CharacterSet::Latin1;
+        assert!(code.to_bytes().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject() -> Result<()> {
+        const WRONG_PAYLOADS: &[&str] = &[
+            // NOTE: no BIC in version 001
+            "BCD\n001\n1\nSCT\n\nRed Cross of Belgium\nBE72000000001616\nEUR1",
+            "BCD\n002\n1\nSCT\n\n\nBE72000000001616\nEUR1",
+            "BCD\n002\n1\nSCT\n\nRed Cross\nBE72000000001616\nUSD1",
+            "BCD\n002\n1\nSCT\n\nRed Cross\nBE72000000001616\nEUR1,50",
+            "BCD\n002\n1\nSCT\n\nRed Cross\nBE72000000001616\nEUR0",
+            "BCD\n002\n1\nSCT\n\nRed Cross\nBE72000000001616\nEUR1\n\nRF18539007547034\nFund",
+            "BCD\n002\n1\nSCT\n\nRed Cross\nBE72000000001616\nEUR1\n\nRF19539007547034",
+            "BCD\n003\n1\nSCT\n\nRed Cross\nBE72000000001616",
+            "BCD\n002\n9\nSCT\n\nRed Cross\nBE72000000001616",
+            "BCD\n002\n1\nSCT\n\nRed Cross\nBE72000000001617",
+        ];
+
+        for payload in WRONG_PAYLOADS.iter() {
+            assert!(GiroCode::parse(payload).is_err(), "{}", payload);
+        }
+
+        let mut code = GiroCode::new(
+            &"Я".repeat(70),
+            "BE72000000001616".into(),
+            None,
+            Remittance::Text("Ж".repeat(140)),
+        );
+        assert!(code.to_payload().is_err());
+
+        code.character_set = CharacterSet::Cyrillic;
+        assert!(code.to_payload().is_ok());
+
+        Ok(())
+    }
+}
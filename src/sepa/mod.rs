@@ -0,0 +1,10 @@
+//! Single Euro Payments Area (SEPA) schemes, as defined by the EPC rulebooks.
+
+pub mod calendar;
+pub mod girocode;
+pub mod rmessage;
+pub mod rtransaction;
+
+pub use girocode::*;
+pub use rmessage::*;
+pub use rtransaction::*;
@@ -0,0 +1,763 @@
+//! Messages carrying SEPA R-transactions: pacs.002 rejects, pacs.004
+//! returns and refunds, camt.056 recalls and camt.029 answers to recalls.
+
+use crate::date::{Date, DateTime};
+use crate::error::{Error, Reason};
+use crate::iso20022::{FromDocument, MessageIdentifier, ToDocument};
+use crate::iso9362::BIC;
+use crate::money::Money;
+use crate::pain::{GroupStatus, OriginalIds, Status};
+use crate::result::Result;
+use crate::sepa::{RTransaction, RTransactionKind};
+use crate::xml::Element;
+use serde::{Deserialize, Serialize};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// [`RMessageType`] is the type of a message carrying R-transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RMessageType {
+    /// pacs.002, FI to FI payment status report.
+    PaymentStatusReport,
+    /// pacs.004, payment return.
+    PaymentReturn,
+    /// camt.056, FI to FI payment cancellation request.
+    CancellationRequest,
+    /// camt.029, resolution of investigation.
+    ResolutionOfInvestigation,
+}
+
+impl RMessageType {
+    /// `TYPES` are all the types of messages carrying R-transactions.
+    pub const TYPES: [RMessageType; 4] = [
+        RMessageType::PaymentStatusReport,
+        RMessageType::PaymentReturn,
+        RMessageType::CancellationRequest,
+        RMessageType::ResolutionOfInvestigation,
+    ];
+
+    /// `from_kind` returns the type of message carrying an R-transaction kind.
+    pub fn from_kind(kind: RTransactionKind) -> RMessageType {
+        match kind {
+            RTransactionKind::Reject => RMessageType::PaymentStatusReport,
+            RTransactionKind::Return | RTransactionKind::Refund => RMessageType::PaymentReturn,
+            RTransactionKind::Recall => RMessageType::CancellationRequest,
+            RTransactionKind::RecallAnswer => RMessageType::ResolutionOfInvestigation,
+        }
+    }
+
+    /// `namespace` returns the XML namespace of the version of the message
+    /// used by the EPC rulebooks.
+    pub fn namespace(&self) -> &str {
+        match self {
+            RMessageType::PaymentStatusReport => "urn:iso:std:iso:20022:tech:xsd:pacs.002.001.10",
+            RMessageType::PaymentReturn => "urn:iso:std:iso:20022:tech:xsd:pacs.004.001.09",
+            RMessageType::CancellationRequest => "urn:iso:std:iso:20022:tech:xsd:camt.056.001.08",
+            RMessageType::ResolutionOfInvestigation => {
+                "urn:iso:std:iso:20022:tech:xsd:camt.029.001.09"
+            }
+        }
+    }
+
+    /// `identifier` returns the message identifier of the namespace.
+    pub fn identifier(&self) -> MessageIdentifier {
+        MessageIdentifier::from_namespace(self.namespace()).unwrap()
+    }
+
+    /// `root` returns the name of the message element inside the `Document`.
+    pub fn root(&self) -> &str {
+        match self {
+            RMessageType::PaymentStatusReport => "FIToFIPmtStsRpt",
+            RMessageType::PaymentReturn => "PmtRtr",
+            RMessageType::CancellationRequest => "FIToFIPmtCxlReq",
+            RMessageType::ResolutionOfInvestigation => "RsltnOfInvstgtn",
+        }
+    }
+}
+
+/// [`RTransactionMessage`] is a message carrying SEPA R-transactions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RTransactionMessage {
+    pub message_type: RMessageType,
+    /// `message_id` is the message id, or the assignment id of camt messages.
+    pub message_id: String,
+    pub created: DateTime,
+    /// `instructing_agent` is the instructing agent, or the assigner of
+    /// camt messages.
+    pub instructing_agent: Option<BIC>,
+    /// `instructed_agent` is the instructed agent, or the assignee of
+    /// camt messages.
+    pub instructed_agent: Option<BIC>,
+    /// `settlement_date` is the interbank settlement date of returns.
+    pub settlement_date: Option<Date>,
+    pub transactions: Vec<RTransaction>,
+}
+
+/// `accepted` parses the cancellation status of camt.029 answers.
+fn accepted(code: Option<&str>) -> Option<bool> {
+    match code {
+        Some("ACCR") => Some(true),
+        Some("RJCR") => Some(false),
+        _ => None,
+    }
+}
+
+/// `original_group` reads the original message of the group of camt.056
+/// and camt.029 messages, whose `OrgnlMsgId` and `OrgnlMsgNmId` are direct
+/// children of `OrgnlGrpInfAndCxl` or `OrgnlGrpInfAndSts`.
+fn original_group(group: Option<&Element>) -> OriginalIds {
+    let text = |name: &str| {
+        group
+            .and_then(|g| g.find_text(name))
+            .map(String::from)
+            .unwrap_or_default()
+    };
+
+    OriginalIds {
+        message_id: text("OrgnlMsgId"),
+        message_name: text("OrgnlMsgNmId"),
+        ..Default::default()
+    }
+}
+
+impl RTransactionMessage {
+    /// `new` creates a new `RTransactionMessage` from its R-transactions,
+    /// which have to be carried by the same type of message.
+    pub fn new(
+        message_id: &str,
+        created: DateTime,
+        transactions: Vec<RTransaction>,
+    ) -> Result<RTransactionMessage> {
+        let message_type = transactions
+            .first()
+            .map(|tx| RMessageType::from_kind(tx.kind))
+            .ok_or_else(|| Error::MissingElement("R-transaction".into()))?;
+
+        if transactions
+            .iter()
+            .any(|tx| RMessageType::from_kind(tx.kind) != message_type)
+        {
+            return Err(Error::invalid_format(
+                "R-transaction message",
+                None,
+                Reason::Format("R-transactions of different message types".into()),
+            ));
+        }
+
+        Ok(RTransactionMessage {
+            message_type,
+            message_id: message_id.into(),
+            created,
+            instructing_agent: None,
+            instructed_agent: None,
+            settlement_date: None,
+            transactions,
+        })
+    }
+
+    /// `parse` parses a pacs.002, pacs.004, camt.056 or camt.029 XML document.
+    pub fn parse(xml: &str) -> Result<RTransactionMessage> {
+        RTransactionMessage::from_element(&Element::parse(xml)?)
+    }
+
+    /// `from_element` reads the R-transactions of a message from its
+    /// `Document` element. Only the rejected transactions of a pacs.002
+    /// are read.
+    pub fn from_element(document: &Element) -> Result<RTransactionMessage> {
+        let (message_type, message) = RMessageType::TYPES
+            .iter()
+            .find_map(|t| document.child(t.root()).map(|message| (*t, message)))
+            .ok_or_else(|| {
+                Error::UnknownMessage(
+                    document
+                        .children
+                        .first()
+                        .map(|message| message.name.clone())
+                        .unwrap_or_else(|| document.name.clone()),
+                )
+            })?;
+
+        let (id, created, instructing, instructed) = match message_type {
+            RMessageType::PaymentStatusReport | RMessageType::PaymentReturn => (
+                "GrpHdr/MsgId",
+                "GrpHdr/CreDtTm",
+                "GrpHdr/InstgAgt",
+                "GrpHdr/InstdAgt",
+            ),
+            _ => (
+                "Assgnmt/Id",
+                "Assgnmt/CreDtTm",
+                "Assgnmt/Assgnr/Agt",
+                "Assgnmt/Assgne/Agt",
+            ),
+        };
+        let agent = |path: &str| -> Result<Option<BIC>> {
+            match message.find(path) {
+                Some(agent) => agent
+                    .find("FinInstnId/BICFI")
+                    .or_else(|| agent.find("FinInstnId/BIC"))
+                    .map(|bic| bic.text().parse())
+                    .transpose(),
+                None => Ok(None),
+            }
+        };
+
+        let mut res = RTransactionMessage {
+            message_type,
+            message_id: message.require_text(id)?.into(),
+            created: message.require_parsed(created)?,
+            instructing_agent: agent(instructing)?,
+            instructed_agent: agent(instructed)?,
+            settlement_date: message.find_parsed("GrpHdr/IntrBkSttlmDt")?,
+            transactions: vec![],
+        };
+
+        match message_type {
+            RMessageType::PaymentStatusReport => {
+                let group = match message.find("OrgnlGrpInfAndSts") {
+                    Some(group) => GroupStatus::from_element(group)?,
+                    None => GroupStatus {
+                        original: OriginalIds::default(),
+                        original_created: None,
+                        original_number_of_transactions: None,
+                        status: None,
+                        reasons: vec![],
+                    },
+                };
+
+                for tx in message.find_all("TxInfAndSts") {
+                    let status: Option<Status> = tx.find_parsed("TxSts")?;
+
+                    if status.as_ref().or(group.status.as_ref()) != Some(&Status::Rejected) {
+                        continue;
+                    }
+
+                    res.transactions.push(RTransaction::from_element(
+                        RTransactionKind::Reject,
+                        tx,
+                        &group.original,
+                        "StsId",
+                        "StsRsnInf",
+                    )?);
+                }
+
+                // NOTE: a whole message can be rejected without reporting
+                // its transactions
+                if res.transactions.is_empty() && group.status == Some(Status::Rejected) {
+                    let reason = group.reasons.into_iter().next().unwrap_or_default();
+                    let mut tx = RTransaction::new(
+                        RTransactionKind::Reject,
+                        &res.message_id,
+                        group.original,
+                    );
+
+                    tx.reason = reason.code;
+                    tx.proprietary_reason = reason.proprietary;
+                    tx.originator = reason.originator;
+                    tx.additional_information = reason.additional_information;
+                    res.transactions.push(tx);
+                }
+            }
+            RMessageType::PaymentReturn => {
+                let group = RTransaction::original_from_element(message, &OriginalIds::default())?;
+
+                for tx in message.find_all("TxInf") {
+                    let mut rtransaction = RTransaction::from_element(
+                        RTransactionKind::Return,
+                        tx,
+                        &group,
+                        "RtrId",
+                        "RtrRsnInf",
+                    )?;
+
+                    rtransaction.classify_return(
+                        tx.find_parsed("IntrBkSttlmDt")?.or(res.settlement_date),
+                    )?;
+                    res.transactions.push(rtransaction);
+                }
+            }
+            RMessageType::CancellationRequest => {
+                for underlying in message.find_all("Undrlyg") {
+                    let group = original_group(underlying.child("OrgnlGrpInfAndCxl"));
+
+                    for tx in underlying.find_all("TxInf") {
+                        res.transactions.push(RTransaction::from_element(
+                            RTransactionKind::Recall,
+                            tx,
+                            &group,
+                            "CxlId",
+                            "CxlRsnInf",
+                        )?);
+                    }
+                }
+            }
+            RMessageType::ResolutionOfInvestigation => {
+                let status = message.find_text("Sts/Conf");
+
+                for details in message.find_all("CxlDtls") {
+                    let group = original_group(details.child("OrgnlGrpInfAndSts"));
+
+                    for tx in details.find_all("TxInfAndSts") {
+                        let mut answer = RTransaction::from_element(
+                            RTransactionKind::RecallAnswer,
+                            tx,
+                            &group,
+                            "CxlStsId",
+                            "CxlStsRsnInf",
+                        )?;
+
+                        answer.accepted = accepted(tx.find_text("TxCxlSts").or(status));
+                        res.transactions.push(answer);
+                    }
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// `total_amount` returns the sum of the returned amounts.
+    pub fn total_amount(&self) -> Option<Money> {
+        let mut amounts = self.transactions.iter().map(|tx| tx.amount.as_ref());
+        let first = amounts.next()??.clone();
+
+        amounts.try_fold(first, |total, amount| total.checked_add(amount?))
+    }
+
+    /// `to_document` builds the `Document` element of the message.
+    pub fn to_document(&self) -> Result<Element> {
+        let agent = |name: &str, bic: &Option<BIC>| -> Result<Element> {
+            let bic = bic
+                .as_ref()
+                .ok_or_else(|| Error::MissingElement(name.into()))?;
+
+            Ok(Element::new(name)
+                .with_child(Element::new("FinInstnId").with_leaf("BICFI", &bic.to_string())))
+        };
+
+        let mut message = Element::new(self.message_type.root());
+
+        match self.message_type {
+            RMessageType::PaymentStatusReport | RMessageType::PaymentReturn => {
+                let mut header = Element::new("GrpHdr")
+                    .with_leaf("MsgId", &self.message_id)
+                    .with_leaf("CreDtTm", &self.created.to_string());
+
+                if self.message_type == RMessageType::PaymentReturn {
+                    let total = self
+                        .total_amount()
+                        .ok_or_else(|| Error::MissingElement("RtrdIntrBkSttlmAmt".into()))?;
+                    let date = self
+                        .settlement_date
+                        .ok_or_else(|| Error::MissingElement("IntrBkSttlmDt".into()))?;
+
+                    header = header
+                        .with_leaf("NbOfTxs", &self.transactions.len().to_string())
+                        .with_child(Element::money("TtlRtrdIntrBkSttlmAmt", &total))
+                        .with_leaf("IntrBkSttlmDt", &date.to_string())
+                        .with_child(Element::new("SttlmInf").with_leaf("SttlmMtd", "CLRG"));
+                }
+
+                if self.instructing_agent.is_some() {
+                    header.push(agent("InstgAgt", &self.instructing_agent)?);
+                }
+
+                if self.instructed_agent.is_some() {
+                    header.push(agent("InstdAgt", &self.instructed_agent)?);
+                }
+
+                message.push(header);
+            }
+            RMessageType::CancellationRequest | RMessageType::ResolutionOfInvestigation => {
+                message.push(
+                    Element::new("Assgnmt")
+                        .with_leaf("Id", &self.message_id)
+                        .with_child(
+                            Element::new("Assgnr")
+                                .with_child(agent("Agt", &self.instructing_agent)?),
+                        )
+                        .with_child(
+                            Element::new("Assgne")
+                                .with_child(agent("Agt", &self.instructed_agent)?),
+                        )
+                        .with_leaf("CreDtTm", &self.created.to_string()),
+                );
+            }
+        }
+
+        match self.message_type {
+            RMessageType::PaymentStatusReport => {
+                for tx in self.transactions.iter() {
+                    let mut element = Element::new("TxInfAndSts").with_leaf("StsId", &tx.id);
+
+                    element.children.extend(tx.original_to_elements());
+                    element.push(Element::leaf("TxSts", Status::Rejected.code()));
+                    element.push(tx.reason_to_element("StsRsnInf")?);
+                    element.push(original_transaction_reference(tx));
+                    message.push(element);
+                }
+            }
+            RMessageType::PaymentReturn => {
+                for tx in self.transactions.iter() {
+                    let mut element = Element::new("TxInf").with_leaf("RtrId", &tx.id);
+                    let amount = tx
+                        .amount
+                        .as_ref()
+                        .ok_or_else(|| Error::MissingElement("RtrdIntrBkSttlmAmt".into()))?;
+
+                    element.children.extend(tx.original_to_elements());
+
+                    if let Some(ref original) = tx.original_amount {
+                        element.push(Element::money("OrgnlIntrBkSttlmAmt", original));
+                    }
+
+                    element.push(Element::money("RtrdIntrBkSttlmAmt", amount));
+                    element.push(tx.reason_to_element("RtrRsnInf")?);
+                    element.push(original_transaction_reference(tx));
+                    message.push(element);
+                }
+            }
+            RMessageType::CancellationRequest => {
+                let mut underlying = Element::new("Undrlyg");
+
+                for tx in self.transactions.iter() {
+                    let mut element = Element::new("TxInf").with_leaf("CxlId", &tx.id);
+
+                    element.children.extend(tx.original_to_elements());
+
+                    if let Some(ref original) = tx.original_amount {
+                        element.push(Element::money("OrgnlIntrBkSttlmAmt", original));
+                    }
+
+                    if let Some(date) = tx.original_settlement_date {
+                        element.push(Element::leaf("OrgnlIntrBkSttlmDt", &date.to_string()));
+                    }
+
+                    element.push(tx.reason_to_element("CxlRsnInf")?);
+                    underlying.push(element);
+                }
+
+                message.push(underlying);
+            }
+            RMessageType::ResolutionOfInvestigation => {
+                let status = |accepted: Option<bool>| match accepted {
+                    Some(true) => "ACCR",
+                    Some(false) => "RJCR",
+                    None => "PDCR",
+                };
+                let mut details = Element::new("CxlDtls");
+
+                message.push(Element::new("Sts").with_leaf(
+                    "Conf",
+                    status(self.transactions.first().and_then(|tx| tx.accepted)),
+                ));
+
+                for tx in self.transactions.iter() {
+                    let mut element = Element::new("TxInfAndSts").with_leaf("CxlStsId", &tx.id);
+
+                    element.children.extend(tx.original_to_elements());
+                    element.push(Element::leaf("TxCxlSts", status(tx.accepted)));
+
+                    if tx.reason.is_some() || tx.proprietary_reason.is_some() {
+                        element.push(tx.reason_to_element("CxlStsRsnInf")?);
+                    }
+
+                    element.push(original_transaction_reference(tx));
+                    details.push(element);
+                }
+
+                message.push(details);
+            }
+        }
+
+        Ok(self.message_type.identifier().document(message))
+    }
+
+    /// `to_xml` builds the XML document of the message.
+    pub fn to_xml(&self) -> Result<String> {
+        self.to_document().map(|document| document.to_document())
+    }
+}
+
+/// `original_transaction_reference` returns the `OrgnlTxRef` element of an
+/// R-transaction, with the original amount and settlement date.
+fn original_transaction_reference(tx: &RTransaction) -> Element {
+    let mut res = Element::new("OrgnlTxRef");
+
+    if let Some(ref amount) = tx.original_amount {
+        res.push(Element::money("IntrBkSttlmAmt", amount));
+    }
+
+    if let Some(date) = tx.original_settlement_date {
+        res.push(Element::leaf("IntrBkSttlmDt", &date.to_string()));
+    }
+
+    res
+}
+
+impl FromStr for RTransactionMessage {
+    type Err = Error;
+
+    fn from_str(xml: &str) -> StdResult<Self, Self::Err> {
+        RTransactionMessage::parse(xml)
+    }
+}
+
+impl FromDocument for RTransactionMessage {
+    fn from_document(document: &Element) -> Result<RTransactionMessage> {
+        RTransactionMessage::from_element(document)
+    }
+}
+
+impl ToDocument for RTransactionMessage {
+    fn message_identifier(&self) -> MessageIdentifier {
+        self.message_type.identifier()
+    }
+
+    fn to_document(&self) -> Result<Element> {
+        RTransactionMessage::to_document(self)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{RMessageType, RTransactionMessage};
+    #[allow(unused_imports)] // TODO
+    use crate::money::Money;
+    #[allow(unused_imports)] // TODO
+    use crate::pain::{OriginalIds, ReasonCode};
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+    #[allow(unused_imports)] // TODO
+    use crate::sepa::{RTransaction, RTransactionKind};
+
+    #[allow(dead_code)]
+    const PACS_004: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:pacs.004.001.02">
+  <PmtRtr>
+    <GrpHdr>
+      <MsgId>RTR-1</MsgId>
+      <CreDtTm>2024-04-02T10:00:00</CreDtTm>
+      <NbOfTxs>3</NbOfTxs>
+      <IntrBkSttlmDt>2024-04-02</IntrBkSttlmDt>
+      <InstgAgt><FinInstnId><BIC>DEUTDEFF</BIC></FinInstnId></InstgAgt>
+    </GrpHdr>
+    <OrgnlGrpInf>
+      <OrgnlMsgId>MSG-1</OrgnlMsgId>
+      <OrgnlMsgNmId>pacs.003.001.02</OrgnlMsgNmId>
+    </OrgnlGrpInf>
+    <TxInf>
+      <RtrId>R-1</RtrId>
+      <OrgnlEndToEndId>E2E-1</OrgnlEndToEndId>
+      <OrgnlTxId>TX-1</OrgnlTxId>
+      <OrgnlIntrBkSttlmAmt Ccy="EUR">50.00</OrgnlIntrBkSttlmAmt>
+      <RtrdIntrBkSttlmAmt Ccy="EUR">50.00</RtrdIntrBkSttlmAmt>
+      <RtrRsnInf>
+        <Orgtr><Id><OrgId><BICOrBEI>DEUTDEFF</BICOrBEI></OrgId></Id></Orgtr>
+        <Rsn><Cd>MD06</Cd></Rsn>
+      </RtrRsnInf>
+      <OrgnlTxRef><IntrBkSttlmDt>2024-03-01</IntrBkSttlmDt></OrgnlTxRef>
+    </TxInf>
+    <TxInf>
+      <RtrId>R-2</RtrId>
+      <OrgnlEndToEndId>E2E-2</OrgnlEndToEndId>
+      <RtrdIntrBkSttlmAmt Ccy="EUR">20.00</RtrdIntrBkSttlmAmt>
+      <RtrRsnInf><Rsn><Cd>AC04</Cd></Rsn></RtrRsnInf>
+    </TxInf>
+    <TxInf>
+      <RtrId>R-3</RtrId>
+      <OrgnlEndToEndId>E2E-3</OrgnlEndToEndId>
+      <RtrdIntrBkSttlmAmt Ccy="EUR">10.00</RtrdIntrBkSttlmAmt>
+      <RtrRsnInf><Rsn><Cd>MD01</Cd></Rsn></RtrRsnInf>
+      <OrgnlTxRef><IntrBkSttlmDt>2024-03-28</IntrBkSttlmDt></OrgnlTxRef>
+    </TxInf>
+  </PmtRtr>
+</Document>"#;
+
+    #[allow(dead_code)]
+    const CAMT_029: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.029.001.09">
+  <RsltnOfInvstgtn>
+    <Assgnmt>
+      <Id>ANS-1</Id>
+      <Assgnr><Agt><FinInstnId><BICFI>DEUTDEFF</BICFI></FinInstnId></Agt></Assgnr>
+      <Assgne><Agt><FinInstnId><BICFI>COBADEFF</BICFI></FinInstnId></Agt></Assgne>
+      <CreDtTm>2024-04-03T10:00:00</CreDtTm>
+    </Assgnmt>
+    <Sts><Conf>RJCR</Conf></Sts>
+    <CxlDtls>
+      <TxInfAndSts>
+        <CxlStsId>C-1</CxlStsId>
+        <OrgnlGrpInf>
+          <OrgnlMsgId>MSG-2</OrgnlMsgId>
+          <OrgnlMsgNmId>pacs.008.001.08</OrgnlMsgNmId>
+        </OrgnlGrpInf>
+        <OrgnlEndToEndId>E2E-3</OrgnlEndToEndId>
+        <TxCxlSts>RJCR</TxCxlSts>
+        <CxlStsRsnInf><Rsn><Cd>NOAS</Cd></Rsn></CxlStsRsnInf>
+      </TxInfAndSts>
+    </CxlDtls>
+  </RsltnOfInvstgtn>
+</Document>"#;
+
+    #[allow(dead_code)]
+    const CAMT_056: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.056.001.08">
+  <FIToFIPmtCxlReq>
+    <Assgnmt>
+      <Id>REQ-1</Id>
+      <Assgnr><Agt><FinInstnId><BICFI>COBADEFF</BICFI></FinInstnId></Agt></Assgnr>
+      <Assgne><Agt><FinInstnId><BICFI>DEUTDEFF</BICFI></FinInstnId></Agt></Assgne>
+      <CreDtTm>2024-04-03T10:00:00</CreDtTm>
+    </Assgnmt>
+    <Undrlyg>
+      <OrgnlGrpInfAndCxl>
+        <OrgnlMsgId>MSG-2</OrgnlMsgId>
+        <OrgnlMsgNmId>pacs.008.001.08</OrgnlMsgNmId>
+      </OrgnlGrpInfAndCxl>
+      <TxInf>
+        <CxlId>C-1</CxlId>
+        <OrgnlEndToEndId>E2E-3</OrgnlEndToEndId>
+        <OrgnlIntrBkSttlmAmt Ccy="EUR">10.00</OrgnlIntrBkSttlmAmt>
+        <CxlRsnInf><Rsn><Cd>DUPL</Cd></Rsn></CxlRsnInf>
+      </TxInf>
+    </Undrlyg>
+  </FIToFIPmtCxlReq>
+</Document>"#;
+
+    #[test]
+    fn parse_return() -> Result<()> {
+        let msg = RTransactionMessage::parse(PACS_004)?;
+
+        assert_eq!(msg.message_type, RMessageType::PaymentReturn);
+        assert_eq!(msg.message_id, "RTR-1");
+        assert_eq!(msg.instructing_agent, Some("DEUTDEFF".parse()?));
+        assert_eq!(msg.transactions.len(), 3);
+
+        let refund = &msg.transactions[0];
+
+        assert_eq!(refund.kind, RTransactionKind::Refund);
+        assert_eq!(refund.original.message_id, "MSG-1");
+        assert_eq!(refund.original.transaction_id, Some("TX-1".into()));
+        assert_eq!(refund.reason, Some(ReasonCode::RefundRequestByEndCustomer));
+        assert_eq!(refund.originator, Some("DEUTDEFF".into()));
+        assert_eq!(refund.deadline()?.unwrap().to_string(), "2024-04-26");
+
+        assert_eq!(msg.transactions[1].kind, RTransactionKind::Return);
+
+        // NOTE: settled within the time limit of returns, an unauthorised
+        // transaction is returned by the debtor bank, not refunded
+        let md01 = &msg.transactions[2];
+
+        assert_eq!(md01.reason, Some(ReasonCode::NoMandate));
+        assert_eq!(md01.kind, RTransactionKind::Return);
+        assert_eq!(msg.total_amount().map(|a| a.amount), Some(8_000));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_recall() -> Result<()> {
+        let msg = RTransactionMessage::parse(CAMT_056)?;
+
+        assert_eq!(msg.message_type, RMessageType::CancellationRequest);
+        assert_eq!(msg.message_id, "REQ-1");
+
+        let recall = &msg.transactions[0];
+
+        assert_eq!(recall.kind, RTransactionKind::Recall);
+        assert_eq!(recall.original.message_id, "MSG-2");
+        assert_eq!(recall.original.message_name, "pacs.008.001.08");
+        assert_eq!(recall.original.end_to_end_id, Some("E2E-3".into()));
+        assert_eq!(recall.reason, Some(ReasonCode::DuplicatePayment));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_recall_answer() -> Result<()> {
+        let msg = RTransactionMessage::parse(CAMT_029)?;
+
+        assert_eq!(msg.message_type, RMessageType::ResolutionOfInvestigation);
+        assert_eq!(msg.message_id, "ANS-1");
+        assert_eq!(msg.instructed_agent, Some("COBADEFF".parse()?));
+
+        let answer = &msg.transactions[0];
+
+        assert_eq!(answer.kind, RTransactionKind::RecallAnswer);
+        assert_eq!(answer.accepted, Some(false));
+        assert_eq!(answer.original.end_to_end_id, Some("E2E-3".into()));
+        assert_eq!(answer.reason, Some(ReasonCode::NoAnswerFromCustomer));
+
+        // NOTE: the original message id may only be given for the group
+        let group = "<OrgnlGrpInfAndSts><OrgnlMsgId>MSG-3</OrgnlMsgId></OrgnlGrpInfAndSts>";
+        let xml = CAMT_029
+            .replace("<CxlDtls>", &format!("<CxlDtls>{}", group))
+            .replace("<OrgnlMsgId>MSG-2</OrgnlMsgId>", "");
+        let msg = RTransactionMessage::parse(&xml)?;
+        assert_eq!(msg.transactions[0].original.message_id, "MSG-3");
+
+        Ok(())
+    }
+
+    #[test]
+    fn build() -> Result<()> {
+        let original = OriginalIds {
+            message_id: "MSG-2".into(),
+            message_name: "pacs.008.001.08".into(),
+            end_to_end_id: Some("E2E-3".into()),
+            transaction_id: Some("TX-3".into()),
+            ..Default::default()
+        };
+
+        let mut recall = RTransaction::new(RTransactionKind::Recall, "C-1", original.clone());
+        recall.reason = Some(ReasonCode::DuplicatePayment);
+        recall.original_amount = Some(Money::new(1_000, "EUR".into()));
+        recall.original_settlement_date = Some("2024-04-02".parse()?);
+
+        let mut msg =
+            RTransactionMessage::new("REQ-1", "2024-04-03T10:00:00".parse()?, vec![recall])?;
+
+        assert!(msg.to_xml().is_err());
+
+        msg.instructing_agent = Some("COBADEFF".parse()?);
+        msg.instructed_agent = Some("DEUTDEFF".parse()?);
+
+        let parsed = RTransactionMessage::parse(&msg.to_xml()?)?;
+
+        assert_eq!(parsed, msg);
+
+        let mut ret = RTransaction::new(RTransactionKind::Return, "R-3", original.clone());
+        ret.reason = Some(ReasonCode::FollowingCancellationRequest);
+        ret.amount = Some(Money::new(1_000, "EUR".into()));
+
+        let mut msg = RTransactionMessage::new("RTR-2", "2024-04-04T10:00:00".parse()?, vec![ret])?;
+
+        assert!(msg.to_xml().is_err());
+
+        msg.settlement_date = Some("2024-04-04".parse()?);
+
+        let parsed = RTransactionMessage::parse(&msg.to_xml()?)?;
+
+        assert_eq!(parsed, msg);
+
+        let mut reject = RTransaction::new(RTransactionKind::Reject, "S-1", original);
+        reject.reason = Some(ReasonCode::ClosedAccountNumber);
+
+        let msg = RTransactionMessage::new("STS-1", "2024-04-04T10:00:00".parse()?, vec![reject])?;
+        let parsed = RTransactionMessage::parse(&msg.to_xml()?)?;
+
+        assert_eq!(parsed, msg);
+
+        let ret = parsed.transactions[0].clone();
+        let mut answer = ret.clone();
+        answer.kind = RTransactionKind::RecallAnswer;
+
+        assert!(
+            RTransactionMessage::new("X", "2024-04-04T10:00:00".parse()?, vec![ret, answer])
+                .is_err()
+        );
+
+        Ok(())
+    }
+}
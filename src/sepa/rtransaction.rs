@@ -0,0 +1,378 @@
+//! SEPA R-transactions: rejects, returns, refunds, recalls and answers to
+//! recalls, with the EPC rulebooks time limits.
+
+use crate::date::Date;
+use crate::error::Error;
+use crate::money::Money;
+use crate::pain::{OriginalIds, ReasonCode, StatusReason};
+use crate::result::Result;
+use crate::sepa::calendar::add_target_days;
+use crate::xml::Element;
+use serde::{Deserialize, Serialize};
+
+/// [`Scheme`] is the SEPA scheme of the original transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Scheme {
+    CreditTransfer,
+    DirectDebit,
+}
+
+impl Scheme {
+    /// `from_message_name` returns the scheme of an original message name,
+    /// e.g. "pacs.008.001.08" for a credit transfer.
+    pub fn from_message_name(name: &str) -> Option<Scheme> {
+        if name.starts_with("pacs.008") || name.starts_with("pain.001") {
+            Some(Scheme::CreditTransfer)
+        } else if name.starts_with("pacs.003") || name.starts_with("pain.008") {
+            Some(Scheme::DirectDebit)
+        } else {
+            None
+        }
+    }
+}
+
+/// [`RTransactionKind`] is the kind of an [`RTransaction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RTransactionKind {
+    /// Transaction refused before settlement (pacs.002).
+    Reject,
+    /// Funds sent back after settlement (pacs.004).
+    Return,
+    /// Direct debit refunded to the debtor after settlement (pacs.004).
+    Refund,
+    /// Request to cancel a credit transfer (camt.056).
+    Recall,
+    /// Answer to a recall (camt.029). A positive answer to a recall
+    /// comes as a return with reason "FOCR".
+    RecallAnswer,
+}
+
+/// [`TimeLimit`] is the time limit of an R-transaction, counted from the
+/// settlement date of the original transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum TimeLimit {
+    TargetDays(u32),
+    Weeks(u32),
+    Months(u32),
+}
+
+impl TimeLimit {
+    /// `deadline` returns the last date allowed by the time limit.
+    pub fn deadline(&self, from: &Date) -> Result<Date> {
+        match *self {
+            TimeLimit::TargetDays(days) => add_target_days(from, days),
+            TimeLimit::Weeks(weeks) => from.add_days(7 * i64::from(weeks)),
+            TimeLimit::Months(months) => from.add_months(months),
+        }
+    }
+}
+
+/// [`RTransaction`] is an exception to a SEPA credit transfer or direct
+/// debit, as carried by pacs.002, pacs.004, camt.056 and camt.029 messages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RTransaction {
+    pub kind: RTransactionKind,
+    /// `id` is the return, status, cancellation or cancellation status id.
+    pub id: String,
+    pub original: OriginalIds,
+    pub reason: Option<ReasonCode>,
+    pub proprietary_reason: Option<String>,
+    pub originator: Option<String>,
+    pub additional_information: Vec<String>,
+    pub original_amount: Option<Money>,
+    /// `amount` is the returned or refunded amount.
+    pub amount: Option<Money>,
+    pub original_settlement_date: Option<Date>,
+    /// `accepted` is the outcome of a recall answer, `None` if pending.
+    pub accepted: Option<bool>,
+}
+
+impl RTransaction {
+    /// `new` creates a new `RTransaction` of the given kind.
+    pub fn new(kind: RTransactionKind, id: &str, original: OriginalIds) -> RTransaction {
+        RTransaction {
+            kind,
+            id: id.into(),
+            original,
+            reason: None,
+            proprietary_reason: None,
+            originator: None,
+            additional_information: vec![],
+            original_amount: None,
+            amount: None,
+            original_settlement_date: None,
+            accepted: None,
+        }
+    }
+
+    /// `scheme` returns the scheme of the original transaction, if known.
+    pub fn scheme(&self) -> Option<Scheme> {
+        Scheme::from_message_name(&self.original.message_name)
+    }
+
+    /// `time_limit` returns the EPC time limit of the R-transaction:
+    ///
+    /// - rejects: 5 TARGET days,
+    /// - returns: 5 TARGET days for direct debits, 3 for credit transfers,
+    /// - refunds: 8 weeks, or 13 months for unauthorised transactions (MD01),
+    /// - recalls: 10 TARGET days for duplicates, technical problems and
+    ///   frauds, 13 months for the other reasons (request for recall by
+    ///   the originator).
+    ///
+    /// Answers to recalls are timed from the recall, not from the original
+    /// settlement, so no time limit is returned for them.
+    pub fn time_limit(&self) -> Option<TimeLimit> {
+        match self.kind {
+            RTransactionKind::Reject => Some(TimeLimit::TargetDays(5)),
+            RTransactionKind::Return => match self.scheme()? {
+                Scheme::DirectDebit => Some(TimeLimit::TargetDays(5)),
+                Scheme::CreditTransfer => Some(TimeLimit::TargetDays(3)),
+            },
+            RTransactionKind::Refund => match self.reason {
+                Some(ReasonCode::NoMandate) => Some(TimeLimit::Months(13)),
+                _ => Some(TimeLimit::Weeks(8)),
+            },
+            RTransactionKind::Recall => match self.reason {
+                Some(ReasonCode::DuplicatePayment)
+                | Some(ReasonCode::TechnicalProblem)
+                | Some(ReasonCode::FraudulentOrigin) => Some(TimeLimit::TargetDays(10)),
+                _ => Some(TimeLimit::Months(13)),
+            },
+            RTransactionKind::RecallAnswer => None,
+        }
+    }
+
+    /// `deadline` returns the last date the R-transaction can be initiated,
+    /// if the time limit and the original settlement date are known.
+    pub fn deadline(&self) -> Result<Option<Date>> {
+        match (self.time_limit(), self.original_settlement_date) {
+            (Some(limit), Some(date)) => limit.deadline(&date).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// `is_timely` returns if the R-transaction initiated at `date` respects
+    /// its time limit. R-transactions without a known deadline are timely.
+    pub fn is_timely(&self, date: &Date) -> Result<bool> {
+        Ok(self.deadline()?.map(|d| *date <= d).unwrap_or(true))
+    }
+
+    /// `original_from_element` reads the original identifiers of a
+    /// transaction element, falling back to the group ones.
+    pub(crate) fn original_from_element(
+        element: &Element,
+        group: &OriginalIds,
+    ) -> Result<OriginalIds> {
+        let text = |path: &str| element.find_text(path).map(String::from);
+
+        Ok(OriginalIds {
+            message_id: text("OrgnlGrpInf/OrgnlMsgId").unwrap_or_else(|| group.message_id.clone()),
+            message_name: text("OrgnlGrpInf/OrgnlMsgNmId")
+                .unwrap_or_else(|| group.message_name.clone()),
+            payment_information_id: None,
+            instruction_id: text("OrgnlInstrId"),
+            end_to_end_id: text("OrgnlEndToEndId"),
+            transaction_id: text("OrgnlTxId"),
+            uetr: element.find_parsed("OrgnlUETR")?,
+        })
+    }
+
+    /// `from_element` reads an R-transaction from a transaction element.
+    /// `id` and `reason` are the names of the element holding the id and
+    /// the reasons, which differ from message to message.
+    pub(crate) fn from_element(
+        kind: RTransactionKind,
+        element: &Element,
+        group: &OriginalIds,
+        id: &str,
+        reason: &str,
+    ) -> Result<RTransaction> {
+        let original = RTransaction::original_from_element(element, group)?;
+        let reason = element
+            .find(reason)
+            .map(StatusReason::from_element)
+            .transpose()?
+            .unwrap_or_default();
+
+        let original_amount = match element.find_money("OrgnlIntrBkSttlmAmt")? {
+            Some(amount) => Some(amount),
+            None => element.find_money("OrgnlTxRef/IntrBkSttlmAmt")?,
+        };
+
+        let original_settlement_date = match element.find_parsed("OrgnlIntrBkSttlmDt")? {
+            Some(date) => Some(date),
+            None => element.find_parsed("OrgnlTxRef/IntrBkSttlmDt")?,
+        };
+
+        let mut res = RTransaction::new(kind, element.require_text(id)?, original);
+
+        res.reason = reason.code;
+        res.proprietary_reason = reason.proprietary;
+        res.originator = reason.originator;
+        res.additional_information = reason.additional_information;
+        res.original_amount = original_amount;
+        res.amount = element.find_money("RtrdIntrBkSttlmAmt")?;
+        res.original_settlement_date = original_settlement_date;
+
+        Ok(res)
+    }
+
+    /// `classify_return` turns a direct debit return settled at
+    /// `settlement_date` into a refund when the debtor requested it (MD06).
+    /// A return for an unauthorised transaction (MD01) is only a refund when
+    /// settled after the time limit of returns, as the debtor bank returns
+    /// it before and the debtor claims it after.
+    pub(crate) fn classify_return(&mut self, settlement_date: Option<Date>) -> Result<()> {
+        if self.kind != RTransactionKind::Return || self.scheme() != Some(Scheme::DirectDebit) {
+            return Ok(());
+        }
+
+        let refund = match self.reason {
+            Some(ReasonCode::RefundRequestByEndCustomer) => true,
+            Some(ReasonCode::NoMandate) => match (settlement_date, self.deadline()?) {
+                (Some(date), Some(deadline)) => date > deadline,
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if refund {
+            self.kind = RTransactionKind::Refund;
+        }
+
+        Ok(())
+    }
+
+    /// `original_to_elements` returns the elements of the original
+    /// identifiers, in the order of the ISO 20022 schemas.
+    pub(crate) fn original_to_elements(&self) -> Vec<Element> {
+        let mut res = vec![Element::new("OrgnlGrpInf")
+            .with_leaf("OrgnlMsgId", &self.original.message_id)
+            .with_leaf("OrgnlMsgNmId", &self.original.message_name)];
+
+        let ids = [
+            ("OrgnlInstrId", self.original.instruction_id.clone()),
+            ("OrgnlEndToEndId", self.original.end_to_end_id.clone()),
+            ("OrgnlTxId", self.original.transaction_id.clone()),
+            ("OrgnlUETR", self.original.uetr.map(|uetr| uetr.to_string())),
+        ];
+
+        for (name, id) in ids.iter() {
+            if let Some(id) = id {
+                res.push(Element::leaf(name, id));
+            }
+        }
+
+        res
+    }
+
+    /// `reason_to_element` returns the reason element with the given name.
+    pub(crate) fn reason_to_element(&self, name: &str) -> Result<Element> {
+        let mut res = Element::new(name);
+
+        if let Some(ref originator) = self.originator {
+            res.push(Element::new("Orgtr").with_leaf("Nm", originator));
+        }
+
+        res.push(match (&self.reason, &self.proprietary_reason) {
+            (Some(code), _) => Element::new("Rsn").with_leaf("Cd", code.code()),
+            (None, Some(proprietary)) => Element::new("Rsn").with_leaf("Prtry", proprietary),
+            (None, None) => return Err(Error::MissingElement(format!("{}/Rsn", name))),
+        });
+
+        for info in self.additional_information.iter() {
+            res.push(Element::leaf("AddtlInf", info));
+        }
+
+        Ok(res)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{RTransaction, RTransactionKind, TimeLimit};
+    #[allow(unused_imports)] // TODO
+    use crate::pain::{OriginalIds, ReasonCode};
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[allow(dead_code)]
+    fn rtransaction(kind: RTransactionKind, message_name: &str) -> RTransaction {
+        let mut res = RTransaction::new(
+            kind,
+            "R-1",
+            OriginalIds {
+                message_id: "MSG-1".into(),
+                message_name: message_name.into(),
+                ..Default::default()
+            },
+        );
+
+        res.original_settlement_date = Some("2024-03-27".parse().unwrap());
+        res
+    }
+
+    #[test]
+    fn time_limit() -> Result<()> {
+        let reject = rtransaction(RTransactionKind::Reject, "pacs.003.001.08");
+
+        assert_eq!(reject.time_limit(), Some(TimeLimit::TargetDays(5)));
+        assert_eq!(reject.deadline()?.unwrap().to_string(), "2024-04-05");
+        assert!(reject.is_timely(&"2024-04-05".parse()?)?);
+        assert!(!reject.is_timely(&"2024-04-06".parse()?)?);
+
+        let ct_return = rtransaction(RTransactionKind::Return, "pacs.008.001.08");
+
+        assert_eq!(ct_return.time_limit(), Some(TimeLimit::TargetDays(3)));
+
+        let mut refund = rtransaction(RTransactionKind::Refund, "pacs.003.001.08");
+
+        refund.reason = Some(ReasonCode::RefundRequestByEndCustomer);
+        assert_eq!(refund.deadline()?.unwrap().to_string(), "2024-05-22");
+
+        refund.reason = Some(ReasonCode::NoMandate);
+        assert_eq!(refund.deadline()?.unwrap().to_string(), "2025-04-27");
+
+        let mut recall = rtransaction(RTransactionKind::Recall, "pacs.008.001.08");
+
+        recall.reason = Some(ReasonCode::DuplicatePayment);
+        assert_eq!(recall.time_limit(), Some(TimeLimit::TargetDays(10)));
+
+        recall.reason = Some(ReasonCode::RequestedByCustomer);
+        assert_eq!(recall.time_limit(), Some(TimeLimit::Months(13)));
+
+        let answer = rtransaction(RTransactionKind::RecallAnswer, "pacs.008.001.08");
+
+        assert_eq!(answer.deadline()?, None);
+        assert!(answer.is_timely(&"2030-01-01".parse()?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn classify_return() -> Result<()> {
+        let mut dd_return = rtransaction(RTransactionKind::Return, "pacs.003.001.08");
+
+        dd_return.reason = Some(ReasonCode::NoMandate);
+        dd_return.classify_return(Some("2024-04-05".parse()?))?;
+        assert_eq!(dd_return.kind, RTransactionKind::Return);
+        dd_return.classify_return(None)?;
+        assert_eq!(dd_return.kind, RTransactionKind::Return);
+        dd_return.classify_return(Some("2024-06-03".parse()?))?;
+        assert_eq!(dd_return.kind, RTransactionKind::Refund);
+
+        let mut dd_return = rtransaction(RTransactionKind::Return, "pacs.003.001.08");
+
+        dd_return.reason = Some(ReasonCode::RefundRequestByEndCustomer);
+        dd_return.classify_return(None)?;
+        assert_eq!(dd_return.kind, RTransactionKind::Refund);
+
+        let mut ct_return = rtransaction(RTransactionKind::Return, "pacs.008.001.08");
+
+        ct_return.reason = Some(ReasonCode::NoMandate);
+        ct_return.classify_return(Some("2024-06-03".parse()?))?;
+        assert_eq!(ct_return.kind, RTransactionKind::Return);
+
+        Ok(())
+    }
+}
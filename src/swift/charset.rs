@@ -0,0 +1,155 @@
+//! SWIFT X character set validation and transliteration.
+//!
+//! The X character set is made of the latin letters, digits, space and
+//! `/ - ? : ( ) . , ' +`. Lines must not start with ':' or '-', which are
+//! reserved as field and text block delimiters.
+
+use crate::error::{Error, Reason};
+use crate::result::Result;
+
+/// `X_SPECIAL_CHARS` are the non alphanumeric chars of the X character set.
+pub const X_SPECIAL_CHARS: &str = "/-?:().,'+ ";
+
+/// `is_x_char` returns if a char is part of the X character set, line
+/// breaks excluded.
+pub fn is_x_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || X_SPECIAL_CHARS.contains(c)
+}
+
+/// `is_valid` returns if a, possibly multi-lines, text only uses the X
+/// character set and has no line starting with ':' or '-'.
+pub fn is_valid(text: &str) -> bool {
+    text.split('\n')
+        .all(|line| line.chars().all(is_x_char) && !line.starts_with(':') && !line.starts_with('-'))
+}
+
+/// `validate` validates a text against the X character set.
+pub fn validate(text: &str) -> Result<()> {
+    let mut line_start = true;
+
+    for (index, found) in text.chars().enumerate() {
+        let expected = match found {
+            '\n' => None,
+            ':' | '-' if line_start => Some("a char other than ':' or '-' at line start"),
+            c if !is_x_char(c) => Some("an X character set char"),
+            _ => None,
+        };
+
+        if let Some(expected) = expected {
+            return Err(Error::invalid(
+                "X text",
+                text,
+                Reason::Character {
+                    index,
+                    found,
+                    expected,
+                },
+            ));
+        }
+
+        line_start = found == '\n';
+    }
+
+    Ok(())
+}
+
+/// `transliterate` rewrites a text to the X character set, following the
+/// SWIFT and PMPG guidelines: accented letters lose their accents, German
+/// umlauts and ligatures are expanded, a few symbols are replaced by their
+/// closest X equivalent and any other char is replaced by a '.'. A ':' or
+/// '-' at the start of a line is replaced by a '.'.
+pub fn transliterate(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            result.push('\n');
+        }
+
+        let start = result.len();
+        for c in line.chars() {
+            if is_x_char(c) {
+                result.push(c);
+            } else {
+                result.push_str(transliterate_char(c));
+            }
+        }
+
+        if result[start..].starts_with(':') || result[start..].starts_with('-') {
+            result.replace_range(start..start + 1, ".");
+        }
+    }
+
+    result
+}
+
+/// `transliterate_char` returns the X replacement of a non X char.
+fn transliterate_char(c: char) -> &'static str {
+    match c {
+        'À' | 'Á' | 'Â' | 'Ã' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à' | 'á' | 'â' | 'ã' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'Ä' | 'Æ' => "AE",
+        'ä' | 'æ' => "ae",
+        'Ç' | 'Ć' | 'Č' => "C",
+        'ç' | 'ć' | 'č' => "c",
+        'Ď' | 'Đ' => "D",
+        'ď' | 'đ' => "d",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'Ğ' => "G",
+        'ğ' => "g",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ī' | 'İ' => "I",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+        'Ł' => "L",
+        'ł' => "l",
+        'Ñ' | 'Ń' | 'Ň' => "N",
+        'ñ' | 'ń' | 'ň' => "n",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ø' | 'Ō' | 'Ő' => "O",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ø' | 'ō' | 'ő' => "o",
+        'Ö' | 'Œ' => "OE",
+        'ö' | 'œ' => "oe",
+        'Ř' => "R",
+        'ř' => "r",
+        'Ś' | 'Š' | 'Ş' => "S",
+        'ś' | 'š' | 'ş' => "s",
+        'ß' => "ss",
+        'Ť' | 'Ţ' => "T",
+        'ť' | 'ţ' => "t",
+        'Ù' | 'Ú' | 'Û' | 'Ū' | 'Ů' | 'Ű' => "U",
+        'ù' | 'ú' | 'û' | 'ū' | 'ů' | 'ű' => "u",
+        'Ü' => "UE",
+        'ü' => "ue",
+        'Ý' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        '&' => "+",
+        '_' => "-",
+        '"' | '`' | '´' | '‘' | '’' => "'",
+        '[' | '{' | '<' => "(",
+        ']' | '}' | '>' => ")",
+        '\t' | '\r' | '\u{a0}' => " ",
+        _ => ".",
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{is_valid, transliterate, validate};
+
+    #[test]
+    fn transliterate_text() {
+        assert!(is_valid("JOHN DOE, 1 RUE (BAT. A)\n/REF/12-34"));
+        assert!(!is_valid("JOHN & DOE"));
+        assert!(!is_valid("NAME\n-LINE"));
+        assert!(validate("Müller").is_err());
+
+        assert_eq!(
+            transliterate("Müller & Söhne GmbH"),
+            "Mueller + Soehne GmbH"
+        );
+        assert_eq!(transliterate("Crème brûlée_café"), "Creme brulee-cafe");
+        assert_eq!(transliterate("Łódź\n:Straße #1"), "Lodz\n.Strasse .1");
+        assert!(is_valid(&transliterate("–emoji 😀 test")));
+    }
+}
@@ -0,0 +1,738 @@
+//! Common group messages: MTn92 Request for Cancellation, MTn96 Answers and
+//! MTn99 Free Format Message, where n is the category of the message they
+//! are about (1 for customer payments, 2 for financial institution
+//! transfers).
+//!
+//! They keep the reference (field 20) of the message they relate to in
+//! their field 21, and its UETR in their user header (field 121), so that
+//! the messages of an investigation can be threaded together.
+
+use crate::date::Date;
+use crate::error::{Error, Reason};
+use crate::result::Result;
+use crate::swift::fields::*;
+use crate::swift::{format, ApplicationHeader, Field, LogicalTerminal, Message, TextBlock, Uetr};
+
+/// [`OriginalMessage`] identifies the message a cancellation request or an
+/// answer refers to (fields 11R and 11S).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginalMessage {
+    pub message_type: String,
+    pub date: Date,
+    pub session_number: Option<String>,
+    pub sequence_number: Option<String>,
+}
+
+impl OriginalMessage {
+    /// `from_message` identifies a message with the given sending date. The
+    /// session and sequence numbers are taken from the message input
+    /// reference of an output message, or from the basic header of an
+    /// input message.
+    pub fn from_message(message: &Message, date: Date) -> Result<OriginalMessage> {
+        let (session_number, sequence_number) = match message.application_header {
+            Some(ApplicationHeader::Output {
+                ref session_number,
+                ref sequence_number,
+                ..
+            }) => (session_number, sequence_number),
+            _ => (
+                &message.basic_header.session_number,
+                &message.basic_header.sequence_number,
+            ),
+        };
+
+        Ok(OriginalMessage {
+            message_type: message
+                .message_type()
+                .ok_or_else(|| Error::MissingElement("application header".into()))?
+                .into(),
+            date,
+            session_number: Some(session_number.clone()),
+            sequence_number: Some(sequence_number.clone()),
+        })
+    }
+
+    fn parse(tag: &str, value: &str) -> Result<OriginalMessage> {
+        format::check(tag, value, "3!n$6!n[$4!n6!n]")?;
+
+        let lines: Vec<&str> = value.split('\n').collect();
+
+        Ok(OriginalMessage {
+            message_type: lines[0].into(),
+            date: Date::from_yymmdd(lines[1]).map_err(|e| e.in_field(tag, value, 4))?,
+            session_number: lines.get(2).map(|l| l[0..4].to_string()),
+            sequence_number: lines.get(2).map(|l| l[4..].to_string()),
+        })
+    }
+
+    fn value(&self) -> String {
+        let mut value = format!("{}\n{}", self.message_type, self.date.to_yymmdd());
+
+        if let (Some(session), Some(sequence)) = (&self.session_number, &self.sequence_number) {
+            value.push_str(&format!("\n{}{}", session, sequence));
+        }
+
+        value
+    }
+}
+
+/// [`CancellationStatus`] is the answer to a cancellation request, from
+/// the first line of field 76.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CancellationStatus {
+    /// "/CNCL/": the payment is cancelled.
+    Cancelled,
+    /// "/PDCR/": the cancellation request is pending.
+    Pending,
+    /// "/RJCR/": the cancellation request is rejected.
+    Rejected,
+    Other(String),
+}
+
+impl CancellationStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CancellationStatus::Cancelled => "CNCL",
+            CancellationStatus::Pending => "PDCR",
+            CancellationStatus::Rejected => "RJCR",
+            CancellationStatus::Other(code) => code,
+        }
+    }
+
+    fn from_code(code: &str) -> CancellationStatus {
+        match code {
+            "CNCL" => CancellationStatus::Cancelled,
+            "PDCR" => CancellationStatus::Pending,
+            "RJCR" => CancellationStatus::Rejected,
+            _ => CancellationStatus::Other(code.into()),
+        }
+    }
+}
+
+/// [`MTn92`] is a request for cancellation of a previously sent message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MTn92 {
+    /// `category` is the category of the original message, 1 to 9.
+    pub category: u8,
+    /// Field 20.
+    pub transaction_reference: String,
+    /// Field 21, the field 20 of the original message.
+    pub related_reference: String,
+    /// Field 11S.
+    pub original: OriginalMessage,
+    /// Field 79.
+    pub narrative: Vec<String>,
+    /// Copy of the fields of the original message.
+    pub copy_of_fields: Vec<Field>,
+    /// UETR of the original message.
+    pub uetr: Option<Uetr>,
+}
+
+impl MTn92 {
+    /// `for_original` creates a cancellation request of `original`, sent
+    /// on `date`, copying its fields.
+    pub fn for_original(
+        transaction_reference: &str,
+        original: &Message,
+        date: Date,
+    ) -> Result<MTn92> {
+        Ok(MTn92 {
+            category: category(original)?,
+            transaction_reference: transaction_reference.into(),
+            related_reference: original_reference(original)?,
+            original: OriginalMessage::from_message(original, date)?,
+            narrative: vec![],
+            copy_of_fields: original.fields().to_vec(),
+            uetr: original.uetr(),
+        })
+    }
+
+    /// `parse` parses a FIN MTn92 message.
+    pub fn parse(message: &str) -> Result<MTn92> {
+        MTn92::from_message(&Message::parse(message)?)
+    }
+
+    /// `from_message` reads an MTn92 from a parsed FIN message.
+    pub fn from_message(message: &Message) -> Result<MTn92> {
+        let category = common_category(message, "92")?;
+        let mut fields = Fields::new(message.fields());
+        let request = MTn92 {
+            category,
+            transaction_reference: fields.required("20")?,
+            related_reference: fields.required("21")?,
+            original: OriginalMessage::parse("11S", &fields.required("11S")?)?,
+            narrative: fields.lines("79"),
+            copy_of_fields: fields.rest(),
+            uetr: message.uetr(),
+        };
+
+        request.validate()?;
+
+        Ok(request)
+    }
+
+    /// `validate` checks the fields of the request.
+    pub fn validate(&self) -> Result<()> {
+        check_reference("20", &self.transaction_reference)?;
+        check_reference("21", &self.related_reference)?;
+        check_category(self.category)?;
+
+        if !self
+            .original
+            .message_type
+            .starts_with(&self.category.to_string())
+        {
+            return Err(invalid_field(
+                "11S",
+                &self.original.value(),
+                &format!("not a category {} message type", self.category),
+            ));
+        }
+
+        // C1: field 79, a copy of the original fields or both are present.
+        if self.narrative.is_empty() && self.copy_of_fields.is_empty() {
+            return Err(Error::invalid_format(
+                "MTn92",
+                None,
+                Reason::Format("rule C1: field 79 or a copy of fields must be present".into()),
+            ));
+        }
+
+        if !self.narrative.is_empty() {
+            check_lines("79", &self.narrative, 35, 50)?;
+        }
+
+        Ok(())
+    }
+
+    /// `to_text_block` returns the fields of the request in network order.
+    pub fn to_text_block(&self) -> TextBlock {
+        let mut fields = vec![
+            Field::new("20", &self.transaction_reference),
+            Field::new("21", &self.related_reference),
+            Field::new("11S", &self.original.value()),
+        ];
+
+        if !self.narrative.is_empty() {
+            fields.push(Field::new("79", &self.narrative.join("\n")));
+        }
+
+        fields.extend(self.copy_of_fields.iter().cloned());
+
+        TextBlock { fields }
+    }
+
+    /// `to_message` validates the request and builds the FIN input message
+    /// from `sender` to `receiver`.
+    pub fn to_message(
+        &self,
+        sender: LogicalTerminal,
+        receiver: LogicalTerminal,
+    ) -> Result<Message> {
+        self.validate()?;
+
+        Ok(with_uetr(
+            Message::input(
+                sender,
+                receiver,
+                &format!("{}92", self.category),
+                self.to_text_block(),
+            ),
+            self.uetr,
+        ))
+    }
+}
+
+/// [`MTn96`] is an answer to a request, such as an MTn92 cancellation
+/// request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MTn96 {
+    /// `category` is the category of the original message, 1 to 9.
+    pub category: u8,
+    /// Field 20.
+    pub transaction_reference: String,
+    /// Field 21, the field 20 of the request.
+    pub related_reference: String,
+    /// Field 76.
+    pub answers: Vec<String>,
+    /// Field 77A.
+    pub narrative: Vec<String>,
+    /// Field 11R or 11S, the request being answered.
+    pub original: Option<OriginalMessage>,
+    /// `original_received` tells if `original` was received (11R) rather
+    /// than sent (11S).
+    pub original_received: bool,
+    /// Copy of the fields of the request, whose field 21 is the field 20 of
+    /// the original message.
+    pub copy_of_fields: Vec<Field>,
+    /// UETR of the payment.
+    pub uetr: Option<Uetr>,
+}
+
+impl MTn96 {
+    /// `answer` creates the answer to a received cancellation `request`,
+    /// received on `date`, with the given status and optional reason code.
+    /// The mandatory fields of the request are copied, so the answer still
+    /// carries the reference of the original message.
+    pub fn answer(
+        transaction_reference: &str,
+        request: &MTn92,
+        date: Date,
+        status: CancellationStatus,
+        reason: Option<&str>,
+    ) -> MTn96 {
+        MTn96 {
+            category: request.category,
+            transaction_reference: transaction_reference.into(),
+            related_reference: request.transaction_reference.clone(),
+            answers: vec![format!("/{}/{}", status.as_str(), reason.unwrap_or(""))],
+            narrative: vec![],
+            original: Some(OriginalMessage {
+                message_type: format!("{}92", request.category),
+                date,
+                session_number: None,
+                sequence_number: None,
+            }),
+            original_received: true,
+            copy_of_fields: vec![
+                Field::new("20", &request.transaction_reference),
+                Field::new("21", &request.related_reference),
+                Field::new("11S", &request.original.value()),
+            ],
+            uetr: request.uetr,
+        }
+    }
+
+    /// `status` returns the cancellation status of the first answer.
+    pub fn status(&self) -> Option<CancellationStatus> {
+        let answer = self.answers.first()?.strip_prefix('/')?;
+
+        answer
+            .split('/')
+            .next()
+            .filter(|code| !code.is_empty())
+            .map(CancellationStatus::from_code)
+    }
+
+    /// `reason` returns the reason code following the status of the first
+    /// answer, such as "AC04" in "/RJCR/AC04".
+    pub fn reason(&self) -> Option<&str> {
+        let answer = self.answers.first()?.strip_prefix('/')?;
+
+        answer
+            .split_once('/')
+            .map(|(_, reason)| reason.split('/').next().unwrap_or(""))
+            .filter(|reason| !reason.is_empty())
+    }
+
+    /// `original_reference` returns the field 20 of the original message,
+    /// when the field 21 of the request is among the copied fields.
+    pub fn original_reference(&self) -> Option<&str> {
+        self.copy_of_fields
+            .iter()
+            .find(|f| f.tag == "21")
+            .map(|f| f.value.as_str())
+    }
+
+    /// `parse` parses a FIN MTn96 message.
+    pub fn parse(message: &str) -> Result<MTn96> {
+        MTn96::from_message(&Message::parse(message)?)
+    }
+
+    /// `from_message` reads an MTn96 from a parsed FIN message.
+    pub fn from_message(message: &Message) -> Result<MTn96> {
+        let category = common_category(message, "96")?;
+        let mut fields = Fields::new(message.fields());
+        let transaction_reference = fields.required("20")?;
+        let related_reference = fields.required("21")?;
+        let answers = fields.lines("76");
+        let narrative = fields.lines("77A");
+        // NOTE: a copied request may have its own 11S after an 11R
+        let (original, original_received) = match fields.take("11R") {
+            Some(value) => (Some(OriginalMessage::parse("11R", &value)?), true),
+            None => match fields.take("11S") {
+                Some(value) => (Some(OriginalMessage::parse("11S", &value)?), false),
+                None => (None, false),
+            },
+        };
+
+        let answer = MTn96 {
+            category,
+            transaction_reference,
+            related_reference,
+            answers,
+            narrative,
+            original,
+            original_received,
+            copy_of_fields: fields.rest(),
+            uetr: message.uetr(),
+        };
+
+        answer.validate()?;
+
+        Ok(answer)
+    }
+
+    /// `validate` checks the fields of the answer.
+    pub fn validate(&self) -> Result<()> {
+        check_reference("20", &self.transaction_reference)?;
+        check_reference("21", &self.related_reference)?;
+        check_category(self.category)?;
+
+        if self.answers.is_empty() {
+            return Err(Error::MissingElement("MTn96 field 76".into()));
+        }
+
+        check_lines("76", &self.answers, 6, 35)?;
+
+        if !self.narrative.is_empty() {
+            check_lines("77A", &self.narrative, 20, 35)?;
+        }
+
+        Ok(())
+    }
+
+    /// `to_text_block` returns the fields of the answer in network order.
+    pub fn to_text_block(&self) -> TextBlock {
+        let mut fields = vec![
+            Field::new("20", &self.transaction_reference),
+            Field::new("21", &self.related_reference),
+            Field::new("76", &self.answers.join("\n")),
+        ];
+
+        if !self.narrative.is_empty() {
+            fields.push(Field::new("77A", &self.narrative.join("\n")));
+        }
+
+        if let Some(ref original) = self.original {
+            let tag = if self.original_received { "11R" } else { "11S" };
+            fields.push(Field::new(tag, &original.value()));
+        }
+
+        fields.extend(self.copy_of_fields.iter().cloned());
+
+        TextBlock { fields }
+    }
+
+    /// `to_message` validates the answer and builds the FIN input message
+    /// from `sender` to `receiver`.
+    pub fn to_message(
+        &self,
+        sender: LogicalTerminal,
+        receiver: LogicalTerminal,
+    ) -> Result<Message> {
+        self.validate()?;
+
+        Ok(with_uetr(
+            Message::input(
+                sender,
+                receiver,
+                &format!("{}96", self.category),
+                self.to_text_block(),
+            ),
+            self.uetr,
+        ))
+    }
+}
+
+/// [`MTn99`] is a free format message, such as an MT199 or MT299.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MTn99 {
+    /// `category` is the category of the message, 1 to 9.
+    pub category: u8,
+    /// Field 20.
+    pub transaction_reference: String,
+    /// Field 21, the field 20 of the related message.
+    pub related_reference: Option<String>,
+    /// Field 79.
+    pub narrative: Vec<String>,
+    /// UETR of the related payment.
+    pub uetr: Option<Uetr>,
+}
+
+impl MTn99 {
+    /// `new` creates a free format message of the given category.
+    pub fn new(category: u8, transaction_reference: &str, narrative: &[&str]) -> MTn99 {
+        MTn99 {
+            category,
+            transaction_reference: transaction_reference.into(),
+            related_reference: None,
+            narrative: narrative.iter().map(|line| line.to_string()).collect(),
+            uetr: None,
+        }
+    }
+
+    /// `about` creates a free format message about `original`, keeping its
+    /// reference and UETR.
+    pub fn about(
+        transaction_reference: &str,
+        original: &Message,
+        narrative: &[&str],
+    ) -> Result<MTn99> {
+        let mut message = MTn99::new(category(original)?, transaction_reference, narrative);
+
+        message.related_reference = Some(original_reference(original)?);
+        message.uetr = original.uetr();
+
+        Ok(message)
+    }
+
+    /// `parse` parses a FIN MTn99 message.
+    pub fn parse(message: &str) -> Result<MTn99> {
+        MTn99::from_message(&Message::parse(message)?)
+    }
+
+    /// `from_message` reads an MTn99 from a parsed FIN message.
+    pub fn from_message(message: &Message) -> Result<MTn99> {
+        let category = common_category(message, "99")?;
+        let mut fields = Fields::new(message.fields());
+        let free_format = MTn99 {
+            category,
+            transaction_reference: fields.required("20")?,
+            related_reference: fields.take("21"),
+            narrative: fields.lines("79"),
+            uetr: message.uetr(),
+        };
+
+        if let Some(field) = fields.rest().first() {
+            return Err(Error::invalid_field(
+                &field.tag,
+                &field.value,
+                Reason::Format("unexpected in MTn99".into()),
+            ));
+        }
+
+        free_format.validate()?;
+
+        Ok(free_format)
+    }
+
+    /// `validate` checks the fields of the free format message.
+    pub fn validate(&self) -> Result<()> {
+        check_reference("20", &self.transaction_reference)?;
+        check_category(self.category)?;
+
+        if let Some(ref reference) = self.related_reference {
+            check_reference("21", reference)?;
+        }
+
+        if self.narrative.is_empty() {
+            return Err(Error::MissingElement("MTn99 field 79".into()));
+        }
+
+        check_lines("79", &self.narrative, 35, 50)
+    }
+
+    /// `to_text_block` returns the fields of the message in network order.
+    pub fn to_text_block(&self) -> TextBlock {
+        let mut fields = vec![Field::new("20", &self.transaction_reference)];
+
+        if let Some(ref reference) = self.related_reference {
+            fields.push(Field::new("21", reference));
+        }
+
+        fields.push(Field::new("79", &self.narrative.join("\n")));
+
+        TextBlock { fields }
+    }
+
+    /// `to_message` validates the message and builds the FIN input message
+    /// from `sender` to `receiver`.
+    pub fn to_message(
+        &self,
+        sender: LogicalTerminal,
+        receiver: LogicalTerminal,
+    ) -> Result<Message> {
+        self.validate()?;
+
+        Ok(with_uetr(
+            Message::input(
+                sender,
+                receiver,
+                &format!("{}99", self.category),
+                self.to_text_block(),
+            ),
+            self.uetr,
+        ))
+    }
+}
+
+/// [`Fields`] takes the fields of a common group message one at a time.
+struct Fields {
+    fields: Vec<Field>,
+}
+
+impl Fields {
+    fn new(fields: &[Field]) -> Fields {
+        Fields {
+            fields: fields.to_vec(),
+        }
+    }
+
+    fn take(&mut self, tag: &str) -> Option<String> {
+        let pos = self.fields.iter().position(|f| f.tag == tag)?;
+
+        Some(self.fields.remove(pos).value)
+    }
+
+    fn required(&mut self, tag: &str) -> Result<String> {
+        self.take(tag)
+            .ok_or_else(|| Error::MissingElement(format!("field {}", tag)))
+    }
+
+    fn lines(&mut self, tag: &str) -> Vec<String> {
+        self.take(tag)
+            .map(|value| value.split('\n').map(String::from).collect())
+            .unwrap_or_default()
+    }
+
+    fn rest(self) -> Vec<Field> {
+        self.fields
+    }
+}
+
+fn check_category(category: u8) -> Result<()> {
+    if !(1..=9).contains(&category) {
+        return Err(Error::invalid(
+            "message category",
+            &category.to_string(),
+            Reason::Format("expected 1 to 9".into()),
+        ));
+    }
+
+    Ok(())
+}
+
+/// `category` returns the category of a message, from its message type.
+fn category(message: &Message) -> Result<u8> {
+    message
+        .message_type()
+        .and_then(|t| t.chars().next())
+        .and_then(|c| c.to_digit(10))
+        .map(|c| c as u8)
+        .ok_or_else(|| Error::MissingElement("application header".into()))
+}
+
+/// `common_category` returns the category of a common group message of
+/// the given type, such as "92".
+fn common_category(message: &Message, message_type: &str) -> Result<u8> {
+    match message.message_type() {
+        Some(t) if t.ends_with(message_type) => category(message),
+        other => Err(Error::UnknownMessage(format!("MT{}", other.unwrap_or("")))),
+    }
+}
+
+/// `original_reference` returns the field 20 of the original message.
+fn original_reference(original: &Message) -> Result<String> {
+    original
+        .field("20")
+        .map(|f| f.value.clone())
+        .ok_or_else(|| Error::MissingElement("original field 20".into()))
+}
+
+/// `with_uetr` sets the UETR (field 121) in the user header of a message.
+fn with_uetr(mut message: Message, uetr: Option<Uetr>) -> Message {
+    if let Some(uetr) = uetr {
+        message.set_uetr(uetr);
+    }
+
+    message
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{CancellationStatus, MTn92, MTn96, MTn99};
+    #[allow(unused_imports)] // TODO
+    use crate::date::Date;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+    #[allow(unused_imports)] // TODO
+    use crate::swift::{LogicalTerminal, Message};
+
+    #[allow(dead_code)]
+    const ORIGINAL: &str = "{1:F01BANKBEBBAXXX1234567890}{2:I103BANKDEFFXXXXN}\
+        {3:{121:e7b4e7c5-3b1a-4d3c-9f1e-0a5e1c2a3b4d}}\
+        {4:\r\n:20:REF12345\r\n:23B:CRED\r\n:32A:240115EUR1234,56\r\n\
+        :50K:JOHN DOE\r\n:59:/DE89370400440532013000\r\nJANE DOE\r\n:71A:SHA\r\n-}";
+
+    #[test]
+    fn cancellation() -> Result<()> {
+        let original = Message::parse(ORIGINAL)?;
+        let bebb = LogicalTerminal::new("BANKBEBBAXXX")?;
+        let deff = LogicalTerminal::new("BANKDEFFXXXX")?;
+
+        let request = MTn92::for_original("CANCEL1", &original, Date::new(2024, 1, 15)?)?;
+        assert_eq!(request.related_reference, "REF12345");
+        assert_eq!(request.copy_of_fields.len(), 6);
+
+        let message = request.to_message(bebb.clone(), deff.clone())?;
+        assert_eq!(message.message_type(), Some("192"));
+        assert_eq!(message.uetr(), original.uetr());
+        assert_eq!(
+            message.field("11S").map(|f| f.value.as_str()),
+            Some("103\n240115\n1234567890")
+        );
+
+        let request = MTn92::parse(&message.to_string())?;
+        assert_eq!(request.original.sequence_number.as_deref(), Some("567890"));
+
+        let answer = MTn96::answer(
+            "ANSWER1",
+            &request,
+            Date::new(2024, 1, 16)?,
+            CancellationStatus::Rejected,
+            Some("AC04"),
+        );
+        let answer = MTn96::parse(&answer.to_message(deff, bebb)?.to_string())?;
+        assert_eq!(answer.related_reference, "CANCEL1");
+        assert_eq!(answer.original_reference(), Some("REF12345"));
+        assert_eq!(answer.copy_of_fields.len(), 3);
+        assert_eq!(answer.status(), Some(CancellationStatus::Rejected));
+        assert_eq!(answer.reason(), Some("AC04"));
+        assert_eq!(
+            answer.uetr.map(|u| u.to_string()),
+            Some("e7b4e7c5-3b1a-4d3c-9f1e-0a5e1c2a3b4d".into())
+        );
+
+        let mut request = request;
+        request.narrative = vec!["DUPLICATE PAYMENT".into()];
+        request.validate()?;
+        request.copy_of_fields.clear();
+        request.validate()?;
+        request.narrative.clear();
+        assert!(request.validate().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn free_format() -> Result<()> {
+        let original = Message::parse(ORIGINAL)?;
+        let message = MTn99::about(
+            "QUERY1",
+            &original,
+            &["PLEASE CONFIRM THE BENEFICIARY", "ACCOUNT"],
+        )?
+        .to_message(
+            LogicalTerminal::new("BANKBEBBAXXX")?,
+            LogicalTerminal::new("BANKDEFFXXXX")?,
+        )?;
+
+        assert_eq!(message.message_type(), Some("199"));
+
+        let free_format = MTn99::parse(&message.to_string())?;
+        assert_eq!(free_format.category, 1);
+        assert_eq!(free_format.related_reference.as_deref(), Some("REF12345"));
+        assert_eq!(
+            free_format.uetr.map(|u| u.to_string()),
+            Some("e7b4e7c5-3b1a-4d3c-9f1e-0a5e1c2a3b4d".into())
+        );
+        assert_eq!(free_format.narrative.len(), 2);
+
+        assert!(MTn99::new(2, "REF", &[]).validate().is_err());
+        assert!(MTn99::new(2, "REF", &["TOO {BRACES}"]).validate().is_err());
+        assert!(MTn99::parse(ORIGINAL).is_err());
+
+        Ok(())
+    }
+}
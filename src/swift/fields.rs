@@ -0,0 +1,425 @@
+//! Field formats shared by the typed MT messages: amounts, texts and
+//! party identification options.
+
+use crate::date::Date;
+use crate::error::{Error, Reason};
+use crate::iso4217::CurrencyCode;
+use crate::iso9362::BIC;
+use crate::money::Money;
+use crate::result::Result;
+use crate::swift::{format, Field};
+
+/// `invalid_field` returns the error for a field breaking a format or
+/// usage rule, described by `reason`.
+pub(crate) fn invalid_field(tag: &str, value: &str, reason: &str) -> Error {
+    Error::invalid_field(tag, value, Reason::Format(reason.into()))
+}
+
+/// `check_text` checks a `lines*columns x` text value.
+pub(crate) fn check_text(tag: &str, value: &str, lines: usize, columns: usize) -> Result<()> {
+    format::check(tag, value, &format!("{}*{}x", lines, columns))
+}
+
+/// `check_lines` checks a list of `columns x` lines, at most `lines` long.
+pub(crate) fn check_lines(
+    tag: &str,
+    values: &[String],
+    lines: usize,
+    columns: usize,
+) -> Result<()> {
+    check_text(tag, &values.join("\n"), lines, columns)
+}
+
+/// `parse_amount` parses a `15d` amount, where the decimal comma is
+/// mandatory.
+pub(crate) fn parse_amount(tag: &str, amount: &str, currency: CurrencyCode) -> Result<Money> {
+    format::check(tag, amount, "15d")?;
+
+    Money::parse(amount, currency).map_err(|e| e.in_field(tag, amount, 0))
+}
+
+/// `format_amount` formats a `15d` amount.
+pub(crate) fn format_amount(amount: &Money) -> String {
+    let amount = amount.format(',');
+
+    if amount.contains(',') {
+        amount
+    } else {
+        format!("{},", amount)
+    }
+}
+
+/// `format_currency_amount` formats a `3!a15d` currency and amount.
+pub(crate) fn format_currency_amount(amount: &Money) -> String {
+    format!("{}{}", amount.currency.as_str(), format_amount(amount))
+}
+
+/// `format_date_currency_amount` formats a `6!n3!a15d` date, currency and
+/// amount.
+pub(crate) fn format_date_currency_amount(date: &Date, amount: &Money) -> String {
+    format!("{}{}", date.to_yymmdd(), format_currency_amount(amount))
+}
+
+/// `check_reference` checks a `16x` reference, which must not start or end
+/// with a '/' nor contain "//".
+pub(crate) fn check_reference(tag: &str, reference: &str) -> Result<()> {
+    format::check(tag, reference, "16x")?;
+
+    if reference.starts_with('/') || reference.ends_with('/') || reference.contains("//") {
+        return Err(invalid_field(
+            tag,
+            reference,
+            "starting or ending with '/' or containing \"//\"",
+        ));
+    }
+
+    Ok(())
+}
+
+/// `check_party` checks that an optional party field uses one of the
+/// allowed `options`, "" standing for the field without option letter.
+pub(crate) fn check_party(tag: &str, party: Option<&Party>, options: &[&str]) -> Result<()> {
+    let party = match party {
+        Some(party) => party,
+        None => return Ok(()),
+    };
+    let tag = format!("{}{}", tag, party.option());
+
+    if !options.contains(&party.option()) {
+        return Err(invalid_field(
+            &tag,
+            &party.value(),
+            &format!("option {:?} not allowed", party.option()),
+        ));
+    }
+
+    party.validate(&tag)
+}
+
+/// `parse_currency_amount` parses a `3!a15d` currency and amount.
+pub(crate) fn parse_currency_amount(tag: &str, value: &str) -> Result<Money> {
+    format::check(tag, value, "3!a15d")?;
+
+    let currency = CurrencyCode::new(&value[0..3]).map_err(|e| e.in_field(tag, value, 0))?;
+
+    parse_amount(tag, &value[3..], currency)
+}
+
+/// `parse_date_currency_amount` parses a `6!n3!a15d` date, currency and
+/// amount.
+pub(crate) fn parse_date_currency_amount(tag: &str, value: &str) -> Result<(Date, Money)> {
+    format::check(tag, value, "6!n3!a15d")?;
+
+    let date = Date::from_yymmdd(&value[0..6]).map_err(|e| e.in_field(tag, value, 0))?;
+
+    Ok((date, parse_currency_amount(tag, &value[6..])?))
+}
+
+/// [`Party`] is a party identification field (50a, 52a to 57a, 59a), with
+/// its option letter as variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Party {
+    /// Option A: optional party identifier and BIC.
+    A {
+        party_identifier: Option<String>,
+        bic: BIC,
+    },
+    /// Option B: optional party identifier and location.
+    B {
+        party_identifier: Option<String>,
+        location: Option<String>,
+    },
+    /// Option C: party identifier only.
+    C { party_identifier: String },
+    /// Option D: optional party identifier, name and address.
+    D {
+        party_identifier: Option<String>,
+        name_and_address: Vec<String>,
+    },
+    /// Option F: party identifier and numbered name and address lines.
+    F {
+        party_identifier: String,
+        name_and_address: Vec<String>,
+    },
+    /// Option K: optional account, name and address.
+    K {
+        account: Option<String>,
+        name_and_address: Vec<String>,
+    },
+    /// No option letter: optional account, name and address (field 59).
+    NoOption {
+        account: Option<String>,
+        name_and_address: Vec<String>,
+    },
+}
+
+impl Party {
+    /// `option` returns the option letter of the party field.
+    pub fn option(&self) -> &str {
+        match self {
+            Party::A { .. } => "A",
+            Party::B { .. } => "B",
+            Party::C { .. } => "C",
+            Party::D { .. } => "D",
+            Party::F { .. } => "F",
+            Party::K { .. } => "K",
+            Party::NoOption { .. } => "",
+        }
+    }
+
+    /// `bic` returns the BIC of an option A party.
+    pub fn bic(&self) -> Option<&BIC> {
+        match self {
+            Party::A { bic, .. } => Some(bic),
+            _ => None,
+        }
+    }
+
+    /// `account` returns the account or party identifier, if any.
+    pub fn account(&self) -> Option<&str> {
+        match self {
+            Party::A {
+                party_identifier, ..
+            }
+            | Party::B {
+                party_identifier, ..
+            }
+            | Party::D {
+                party_identifier, ..
+            } => party_identifier.as_deref(),
+            Party::C { party_identifier }
+            | Party::F {
+                party_identifier, ..
+            } => Some(party_identifier),
+            Party::K { account, .. } | Party::NoOption { account, .. } => account.as_deref(),
+        }
+    }
+
+    /// `name_and_address` returns the name and address lines, if any.
+    pub fn name_and_address(&self) -> &[String] {
+        match self {
+            Party::D {
+                name_and_address, ..
+            }
+            | Party::F {
+                name_and_address, ..
+            }
+            | Party::K {
+                name_and_address, ..
+            }
+            | Party::NoOption {
+                name_and_address, ..
+            } => name_and_address,
+            _ => &[],
+        }
+    }
+
+    /// `parse` parses the value of a party field with the given tag, such
+    /// as "50K" or "59".
+    pub fn parse(tag: &str, value: &str) -> Result<Party> {
+        let invalid = || invalid_field(tag, value, "wrong lines for the option");
+        let mut lines: Vec<&str> = value.split('\n').collect();
+        let identifier = match lines.first() {
+            Some(line) if line.starts_with('/') => Some(lines.remove(0)[1..].to_string()),
+            _ => None,
+        };
+        let rest: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+
+        let party = match tag.get(2..).unwrap_or("") {
+            "A" => match rest.as_slice() {
+                [bic] => Party::A {
+                    party_identifier: identifier,
+                    bic: BIC::new(bic).map_err(|e| {
+                        e.in_field(tag, value, value.chars().count() - bic.chars().count())
+                    })?,
+                },
+                _ => return Err(invalid()),
+            },
+            "B" if rest.len() <= 1 => Party::B {
+                party_identifier: identifier,
+                location: rest.into_iter().next(),
+            },
+            "C" if rest.is_empty() => Party::C {
+                party_identifier: identifier.ok_or_else(invalid)?,
+            },
+            "D" => Party::D {
+                party_identifier: identifier,
+                name_and_address: rest,
+            },
+            "F" => {
+                let mut lines = value.split('\n').map(String::from);
+                Party::F {
+                    party_identifier: lines.next().unwrap_or_default(),
+                    name_and_address: lines.collect(),
+                }
+            }
+            "K" => Party::K {
+                account: identifier,
+                name_and_address: rest,
+            },
+            "" => Party::NoOption {
+                account: identifier,
+                name_and_address: rest,
+            },
+            _ => return Err(invalid_field(tag, value, "unknown option")),
+        };
+
+        party.validate(tag)?;
+
+        Ok(party)
+    }
+
+    /// `validate` checks the line formats of the party field.
+    pub fn validate(&self, tag: &str) -> Result<()> {
+        if let Some(account) = self.account() {
+            let pattern = if let Party::F { .. } = self {
+                "35x"
+            } else {
+                "34x"
+            };
+            format::check(tag, account, pattern)?;
+        }
+
+        match self {
+            Party::B {
+                location: Some(location),
+                ..
+            } => check_text(tag, location, 1, 35),
+            Party::D {
+                name_and_address, ..
+            }
+            | Party::K {
+                name_and_address, ..
+            }
+            | Party::NoOption {
+                name_and_address, ..
+            } => check_lines(tag, name_and_address, 4, 35),
+            Party::F {
+                name_and_address, ..
+            } => {
+                check_lines(tag, name_and_address, 4, 35)?;
+                let numbered = name_and_address.iter().all(|line| {
+                    let bytes = line.as_bytes();
+                    bytes.len() > 2 && (b'1'..=b'8').contains(&bytes[0]) && bytes[1] == b'/'
+                });
+                if numbered {
+                    Ok(())
+                } else {
+                    Err(invalid_field(
+                        tag,
+                        &self.value(),
+                        "name and address lines not numbered 1/ to 8/",
+                    ))
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `to_field` returns the party as a field, the option letter being
+    /// appended to `tag`.
+    pub fn to_field(&self, tag: &str) -> Field {
+        Field::new(&format!("{}{}", tag, self.option()), &self.value())
+    }
+
+    /// `value` returns the field value of the party.
+    pub fn value(&self) -> String {
+        let mut lines = vec![];
+        let identifier = |id: &Option<String>| id.as_ref().map(|id| format!("/{}", id));
+
+        match self {
+            Party::A {
+                party_identifier,
+                bic,
+            } => {
+                lines.extend(identifier(party_identifier));
+                lines.push(bic.to_string());
+            }
+            Party::B {
+                party_identifier,
+                location,
+            } => {
+                lines.extend(identifier(party_identifier));
+                lines.extend(location.clone());
+            }
+            Party::C { party_identifier } => lines.push(format!("/{}", party_identifier)),
+            Party::D {
+                party_identifier: account,
+                name_and_address,
+            }
+            | Party::K {
+                account,
+                name_and_address,
+            }
+            | Party::NoOption {
+                account,
+                name_and_address,
+            } => {
+                lines.extend(identifier(account));
+                lines.extend(name_and_address.iter().cloned());
+            }
+            Party::F {
+                party_identifier,
+                name_and_address,
+            } => {
+                lines.push(party_identifier.clone());
+                lines.extend(name_and_address.iter().cloned());
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{format_amount, parse_date_currency_amount, Party};
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[test]
+    fn amounts() -> Result<()> {
+        let (date, amount) = parse_date_currency_amount("32A", "240115EUR1234,5")?;
+
+        assert_eq!(date.to_string(), "2024-01-15");
+        assert_eq!(amount.amount, 123_450);
+        assert_eq!(format_amount(&amount), "1234,50");
+
+        let (_, amount) = parse_date_currency_amount("32A", "240115JPY1000,")?;
+        assert_eq!(format_amount(&amount), "1000,");
+
+        assert!(parse_date_currency_amount("32A", "240115EUR1234.50").is_err());
+        assert!(parse_date_currency_amount("32A", "240115EUR1234").is_err());
+        assert!(parse_date_currency_amount("32A", "240115EUR1234567890123,45").is_err());
+        assert!(parse_date_currency_amount("32A", "241315EUR1,").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parties() -> Result<()> {
+        let party = Party::parse("50K", "/BE71096123456769\nJOHN DOE\nRUE DE LA LOI 1")?;
+
+        assert_eq!(party.option(), "K");
+        assert_eq!(party.account(), Some("BE71096123456769"));
+        assert_eq!(party.name_and_address(), ["JOHN DOE", "RUE DE LA LOI 1"]);
+        assert_eq!(
+            party.value(),
+            "/BE71096123456769\nJOHN DOE\nRUE DE LA LOI 1"
+        );
+
+        let party = Party::parse("57A", "/C/123\nBANKDEFF")?;
+        assert_eq!(party.bic().map(|b| b.to_string()), Some("BANKDEFF".into()));
+        assert_eq!(party.account(), Some("C/123"));
+
+        let party = Party::parse("50F", "/12345678\n1/JOHN DOE\n3/BE/BRUSSELS")?;
+        assert_eq!(party.account(), Some("/12345678"));
+
+        assert!(Party::parse("50F", "/12345678\nJOHN DOE").is_err());
+        assert!(Party::parse("59", "/123\nNAME\nL2\nL3\nL4\nL5").is_err());
+        assert!(Party::parse("52A", "NOT A BIC").is_err());
+        assert!(Party::parse("59", "/123\nJOHN DOE {").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+//! SWIFT field format patterns, such as `16x`, `4*35x` or `3!a15d`.
+//!
+//! A pattern is a sequence of components:
+//! - `n!c` exactly `n` chars of class `c`,
+//! - `nc` up to `n` chars of class `c`,
+//! - `l*nc` up to `l` lines of up to `n` chars of class `c`,
+//! - `[...]` an optional part,
+//! - `$` a line break, and any other char a literal.
+//!
+//! The char classes are `n` digits, `a` uppercase letters, `c` uppercase
+//! letters and digits, `h` uppercase hexadecimal digits, `d` decimal with a
+//! mandatory comma, `e` space, `x` X character set, `y` EDIFACT level A and
+//! `z` the Z character set.
+
+use crate::error::{Error, Reason};
+use crate::result::Result;
+use crate::swift::charset::is_x_char;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Component {
+        lines: usize,
+        length: usize,
+        fixed: bool,
+        class: char,
+    },
+    Literal(char),
+    Optional(Vec<Token>),
+}
+
+/// [`Format`] is a compiled field format pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    pattern: String,
+    tokens: Vec<Token>,
+}
+
+impl Format {
+    /// `new` compiles a format pattern.
+    pub fn new(pattern: &str) -> Result<Format> {
+        let mut chars = pattern.chars().peekable();
+        let tokens = parse_tokens(&mut chars, pattern)?;
+
+        if chars.next().is_some() {
+            return Err(invalid_pattern(pattern));
+        }
+
+        Ok(Format {
+            pattern: pattern.into(),
+            tokens,
+        })
+    }
+
+    /// `pattern` returns the source pattern.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// `matches` returns if a value, with `\n` line breaks, matches the
+    /// format.
+    pub fn matches(&self, value: &str) -> bool {
+        let chars: Vec<char> = value.chars().collect();
+
+        match_tokens(&self.tokens, &chars, 0, &mut |end| end == chars.len())
+    }
+
+    /// `validate` checks the value of the field `tag` against the format.
+    pub fn validate(&self, tag: &str, value: &str) -> Result<()> {
+        if !self.matches(value) {
+            return Err(Error::invalid_field(
+                tag,
+                value,
+                Reason::Format(format!("expected {}", self.pattern)),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(pattern: &str) -> StdResult<Self, Self::Err> {
+        Format::new(pattern)
+    }
+}
+
+/// `check` validates the value of the field `tag` against a format pattern.
+pub fn check(tag: &str, value: &str, pattern: &str) -> Result<()> {
+    Format::new(pattern)?.validate(tag, value)
+}
+
+fn invalid_pattern(pattern: &str) -> Error {
+    Error::invalid(
+        "format pattern",
+        pattern,
+        Reason::Format("malformed component".into()),
+    )
+}
+
+fn parse_tokens(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    pattern: &str,
+) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            ']' => break,
+            '[' => {
+                chars.next();
+                tokens.push(Token::Optional(parse_tokens(chars, pattern)?));
+                if chars.next() != Some(']') {
+                    return Err(invalid_pattern(pattern));
+                }
+            }
+            '$' => {
+                chars.next();
+                tokens.push(Token::Literal('\n'));
+            }
+            '0'..='9' => {
+                let mut length = number(chars);
+                let mut lines = 1;
+
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    lines = length;
+                    length = number(chars);
+                }
+
+                let fixed = chars.peek() == Some(&'!');
+                if fixed {
+                    chars.next();
+                }
+
+                let class = chars.next().ok_or_else(|| invalid_pattern(pattern))?;
+                if length == 0 || lines == 0 || !"nachdexyz".contains(class) {
+                    return Err(invalid_pattern(pattern));
+                }
+
+                tokens.push(Token::Component {
+                    lines,
+                    length,
+                    fixed,
+                    class,
+                });
+            }
+            _ => {
+                chars.next();
+                tokens.push(Token::Literal(c));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn number(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> usize {
+    let mut number = 0;
+
+    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+        chars.next();
+        number = number * 10 + digit as usize;
+    }
+
+    number
+}
+
+fn is_class_char(class: char, c: char) -> bool {
+    match class {
+        'n' => c.is_ascii_digit(),
+        'a' => c.is_ascii_uppercase(),
+        'c' => c.is_ascii_uppercase() || c.is_ascii_digit(),
+        'h' => c.is_ascii_digit() || ('A'..='F').contains(&c),
+        'd' => c.is_ascii_digit() || c == ',',
+        'e' => c == ' ',
+        'x' => is_x_char(c),
+        'y' => c.is_ascii_uppercase() || c.is_ascii_digit() || " .,-()/='+:?!\"%&*<>;".contains(c),
+        'z' => is_x_char(c) || "=!\"%&*<>;{@#_".contains(c),
+        _ => false,
+    }
+}
+
+/// `match_tokens` matches `tokens` from `pos`, calling `next` with every
+/// possible end position until it accepts one.
+fn match_tokens(
+    tokens: &[Token],
+    chars: &[char],
+    pos: usize,
+    next: &mut dyn FnMut(usize) -> bool,
+) -> bool {
+    let (token, rest) = match tokens.split_first() {
+        Some(split) => split,
+        None => return next(pos),
+    };
+
+    match token {
+        Token::Literal(c) => chars.get(pos) == Some(c) && match_tokens(rest, chars, pos + 1, next),
+        Token::Optional(inner) => {
+            match_tokens(inner, chars, pos, &mut |end| {
+                match_tokens(rest, chars, end, next)
+            }) || match_tokens(rest, chars, pos, next)
+        }
+        Token::Component {
+            lines,
+            length,
+            fixed,
+            class,
+        } => match_lines(*lines, *length, *fixed, *class, chars, pos, &mut |end| {
+            match_tokens(rest, chars, end, next)
+        }),
+    }
+}
+
+/// `match_lines` matches up to `lines` lines of a component, longest first.
+fn match_lines(
+    lines: usize,
+    length: usize,
+    fixed: bool,
+    class: char,
+    chars: &[char],
+    pos: usize,
+    next: &mut dyn FnMut(usize) -> bool,
+) -> bool {
+    let available = chars[pos..]
+        .iter()
+        .take(length)
+        .take_while(|c| is_class_char(class, **c))
+        .count();
+    let min = if fixed { length } else { 1 };
+
+    (min..=available).rev().any(|len| {
+        let end = pos + len;
+
+        if class == 'd' && !is_decimal(&chars[pos..end]) {
+            return false;
+        }
+
+        (lines > 1
+            && chars.get(end) == Some(&'\n')
+            && match_lines(lines - 1, length, fixed, class, chars, end + 1, next))
+            || next(end)
+    })
+}
+
+/// `is_decimal` checks a decimal number with a mandatory comma.
+fn is_decimal(chars: &[char]) -> bool {
+    chars.first().map(|c| c.is_ascii_digit()).unwrap_or(false)
+        && chars.iter().filter(|c| **c == ',').count() == 1
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::Format;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[test]
+    fn matches() -> Result<()> {
+        const CASES: &[(&str, &str, bool)] = &[
+            ("16x", "REF-12345", true),
+            ("16x", "", false),
+            ("16x", "REF-12345-67890-12", false),
+            ("16x", "REF&1", false),
+            ("4!c", "CRED", true),
+            ("4!c", "CRE", false),
+            ("4*35x", "LINE 1\nLINE 2\nLINE 3\nLINE 4", true),
+            ("4*35x", "LINE 1\nLINE 2\nLINE 3\nLINE 4\nLINE 5", false),
+            ("4*35x", "LINE 1\n\nLINE 3", false),
+            ("6!n3!a15d", "240115EUR1234,56", true),
+            ("6!n3!a15d", "240115EUR1234", false),
+            ("6!n3!a15d", "240115EUR12,34,56", false),
+            ("6!n3!a15d", "240115EUR,56", false),
+            ("3!a15d", "EUR123456789012345,", false),
+            ("5n[/5n]", "1/2", true),
+            ("5n[/5n]", "12345", true),
+            ("5n[/5n]", "1/", false),
+            ("[/1!a][/34x]$4!a2!a2!c[3!c]", "/C/123\nBANKDEFF", true),
+            ("[/1!a][/34x]$4!a2!a2!c[3!c]", "/123\nBANKDEFFXXX", true),
+            (
+                "6!n[4!n]2a[1!a]15d1!a3!c16x[//16x][$34x]",
+                "2401020102DR150,00NDDTNONREF//B1\nDETAILS",
+                true,
+            ),
+            (
+                "6!n[4!n]2a[1!a]15d1!a3!c16x[//16x][$34x]",
+                "240102C150,00NTRFREF",
+                true,
+            ),
+            (
+                "6!n[4!n]2a[1!a]15d1!a3!c16x[//16x][$34x]",
+                "240102150,00NTRFREF",
+                false,
+            ),
+        ];
+
+        for (pattern, value, result) in CASES.iter() {
+            assert_eq!(
+                Format::new(pattern)?.matches(value),
+                *result,
+                "{} {:?}",
+                pattern,
+                value
+            );
+        }
+
+        assert!(Format::new("[16x").is_err());
+        assert!(Format::new("16q").is_err());
+        assert!(Format::new("0x").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,773 @@
+//! SWIFT FIN message envelope: basic header, application header, user
+//! header, text and trailer blocks.
+
+use crate::date::Date;
+use crate::error::{check_chars, check_digits, check_length, Error, Reason};
+use crate::iso9362::BIC;
+use crate::result::Result;
+use crate::swift::Uetr;
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// [`LogicalTerminal`] is a 12 chars logical terminal address: the BIC8 of
+/// the institution, the logical terminal letter and the branch code.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LogicalTerminal {
+    pub bic: BIC,
+    pub terminal: char,
+}
+
+impl LogicalTerminal {
+    /// `new` creates a new `LogicalTerminal` from its 12 chars address.
+    pub fn new(address: &str) -> Result<LogicalTerminal> {
+        const KIND: &str = "logical terminal address";
+
+        check_length(KIND, address, 12, 12)?;
+        check_chars(KIND, address, "a letter or a digit", |c| {
+            c.is_ascii_alphanumeric()
+        })?;
+
+        let terminal = address[8..9].chars().next().unwrap_or(' ');
+        if !terminal.is_ascii_uppercase() {
+            return Err(Error::invalid(
+                KIND,
+                address,
+                Reason::Character {
+                    index: 8,
+                    found: terminal,
+                    expected: "an uppercase letter",
+                },
+            ));
+        }
+
+        Ok(LogicalTerminal {
+            bic: BIC::new(&format!("{}{}", &address[0..8], &address[9..12]))?,
+            terminal,
+        })
+    }
+}
+
+impl fmt::Display for LogicalTerminal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bic11 = self.bic.bic11();
+        write!(f, "{}{}{}", &bic11[0..8], self.terminal, &bic11[8..11])
+    }
+}
+
+/// [`BasicHeader`] is the basic header block `{1:}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicHeader {
+    pub application_id: char,
+    pub service_id: String,
+    pub logical_terminal: LogicalTerminal,
+    pub session_number: String,
+    pub sequence_number: String,
+}
+
+impl BasicHeader {
+    fn parse(block: &str) -> Result<BasicHeader> {
+        const KIND: &str = "basic header";
+
+        check_length(KIND, block, 25, 25)?;
+        check_chars(KIND, block, "an ASCII char", |c| c.is_ascii())?;
+        check_numeric(KIND, block, 1, 3)?;
+        check_numeric(KIND, block, 15, 25)?;
+
+        Ok(BasicHeader {
+            application_id: block.chars().next().unwrap_or(' '),
+            service_id: block[1..3].into(),
+            logical_terminal: LogicalTerminal::new(&block[3..15])?,
+            session_number: block[15..19].into(),
+            sequence_number: block[19..25].into(),
+        })
+    }
+}
+
+impl fmt::Display for BasicHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}",
+            self.application_id,
+            self.service_id,
+            self.logical_terminal,
+            self.session_number,
+            self.sequence_number
+        )
+    }
+}
+
+/// [`ApplicationHeader`] is the application header block `{2:}`, whose
+/// layout depends on the direction of the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplicationHeader {
+    /// A message sent to SWIFT.
+    Input {
+        message_type: String,
+        receiver: LogicalTerminal,
+        priority: Option<char>,
+        delivery_monitoring: Option<char>,
+        obsolescence_period: Option<String>,
+    },
+    /// A message delivered by SWIFT. The sender, session and sequence
+    /// numbers are part of the message input reference (MIR).
+    Output {
+        message_type: String,
+        input_time: String,
+        input_date: Date,
+        sender: LogicalTerminal,
+        session_number: String,
+        sequence_number: String,
+        output_date: Date,
+        output_time: String,
+        priority: Option<char>,
+    },
+}
+
+impl ApplicationHeader {
+    /// `message_type` returns the three digits message type, such as "103".
+    pub fn message_type(&self) -> &str {
+        match self {
+            ApplicationHeader::Input { message_type, .. }
+            | ApplicationHeader::Output { message_type, .. } => message_type,
+        }
+    }
+
+    fn parse(block: &str) -> Result<ApplicationHeader> {
+        const KIND: &str = "application header";
+
+        check_length(KIND, block, 16, 47)?;
+        check_chars(KIND, block, "an ASCII char", |c| c.is_ascii())?;
+        check_numeric(KIND, block, 1, 4)?;
+
+        let message_type = block[1..4].to_string();
+        let rest = &block[4..];
+
+        match &block[0..1] {
+            "I" => {
+                check_length(KIND, block, 16, 21)?;
+
+                let mut options = rest[12..].chars();
+                let priority = options.next();
+                let delivery_monitoring = options.next();
+                let obsolescence_period = options.as_str();
+
+                if !obsolescence_period.is_empty() {
+                    check_length(KIND, block, 21, 21)?;
+                    check_numeric(KIND, block, 18, 21)?;
+                }
+
+                Ok(ApplicationHeader::Input {
+                    message_type,
+                    receiver: LogicalTerminal::new(&rest[0..12])?,
+                    priority,
+                    delivery_monitoring,
+                    obsolescence_period: Some(obsolescence_period.to_string())
+                        .filter(|p| !p.is_empty()),
+                })
+            }
+            "O" => {
+                check_length(KIND, block, 46, 47)?;
+                check_numeric(KIND, block, 4, 14)?;
+                check_numeric(KIND, block, 26, 46)?;
+
+                Ok(ApplicationHeader::Output {
+                    message_type,
+                    input_time: rest[0..4].into(),
+                    input_date: Date::from_yymmdd(&rest[4..10])?,
+                    sender: LogicalTerminal::new(&rest[10..22])?,
+                    session_number: rest[22..26].into(),
+                    sequence_number: rest[26..32].into(),
+                    output_date: Date::from_yymmdd(&rest[32..38])?,
+                    output_time: rest[38..42].into(),
+                    priority: rest[42..].chars().next(),
+                })
+            }
+            direction => Err(Error::invalid(
+                KIND,
+                block,
+                Reason::Character {
+                    index: 0,
+                    found: direction.chars().next().unwrap_or(' '),
+                    expected: "'I' or 'O'",
+                },
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ApplicationHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApplicationHeader::Input {
+                message_type,
+                receiver,
+                priority,
+                delivery_monitoring,
+                obsolescence_period,
+            } => {
+                write!(f, "I{}{}", message_type, receiver)?;
+                for option in [priority, delivery_monitoring]
+                    .iter()
+                    .filter_map(|o| o.as_ref())
+                {
+                    write!(f, "{}", option)?;
+                }
+                write!(f, "{}", obsolescence_period.as_deref().unwrap_or(""))
+            }
+            ApplicationHeader::Output {
+                message_type,
+                input_time,
+                input_date,
+                sender,
+                session_number,
+                sequence_number,
+                output_date,
+                output_time,
+                priority,
+            } => {
+                write!(
+                    f,
+                    "O{}{}{}{}{}{}{}{}",
+                    message_type,
+                    input_time,
+                    input_date.to_yymmdd(),
+                    sender,
+                    session_number,
+                    sequence_number,
+                    output_date.to_yymmdd(),
+                    output_time
+                )?;
+                if let Some(priority) = priority {
+                    write!(f, "{}", priority)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// [`TagBlock`] is a block made of `{tag:value}` sub-blocks, used by the
+/// user header block `{3:}` and the trailer block `{5:}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagBlock {
+    pub fields: Vec<(String, String)>,
+}
+
+impl TagBlock {
+    /// `get` returns the value of the first sub-block with the given tag.
+    pub fn get(&self, tag: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(t, _)| t == tag)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn parse(block: &str) -> Result<TagBlock> {
+        let mut fields = vec![];
+        let mut rest = block;
+
+        while !rest.is_empty() {
+            let invalid = |expected| {
+                Error::invalid(
+                    "tag block",
+                    block,
+                    Reason::Character {
+                        index: block[..block.len() - rest.len()].chars().count(),
+                        found: rest.chars().next().unwrap_or(' '),
+                        expected,
+                    },
+                )
+            };
+            let end = match (rest.starts_with('{'), rest.find('}')) {
+                (true, Some(end)) => end,
+                _ => return Err(invalid("a terminated '{' sub-block")),
+            };
+            let (tag, value) = match rest[1..end].find(':') {
+                Some(pos) => (&rest[1..pos + 1], &rest[pos + 2..end]),
+                None => return Err(invalid("a '{tag:' sub-block")),
+            };
+
+            fields.push((tag.to_string(), value.to_string()));
+            rest = &rest[end + 1..];
+        }
+
+        Ok(TagBlock { fields })
+    }
+}
+
+impl fmt::Display for TagBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (tag, value) in self.fields.iter() {
+            write!(f, "{{{}:{}}}", tag, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// [`UserHeader`] is the optional user header block `{3:}`.
+pub type UserHeader = TagBlock;
+
+/// [`Trailer`] is the trailer block `{5:}`.
+pub type Trailer = TagBlock;
+
+impl UserHeader {
+    /// `uetr` returns the unique end-to-end transaction reference (field 121).
+    pub fn uetr(&self) -> Option<Uetr> {
+        self.get("121").and_then(|uetr| uetr.parse().ok())
+    }
+
+    /// `set_uetr` sets the unique end-to-end transaction reference (field
+    /// 121), replacing any previous one.
+    pub fn set_uetr(&mut self, uetr: Uetr) {
+        let value = uetr.to_string();
+
+        match self.fields.iter_mut().find(|(tag, _)| tag == "121") {
+            Some((_, previous)) => *previous = value,
+            None => self.fields.push(("121".into(), value)),
+        }
+    }
+
+    /// `validation_flag` returns the validation flag (field 119), such as
+    /// "STP" or "COV".
+    pub fn validation_flag(&self) -> Option<&str> {
+        self.get("119")
+    }
+
+    /// `service_type_identifier` returns the service type identifier
+    /// (field 111).
+    pub fn service_type_identifier(&self) -> Option<&str> {
+        self.get("111")
+    }
+}
+
+/// [`Field`] is a field of the text block `{4:}`. Multi-lines values are
+/// kept with `\n` separators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub tag: String,
+    pub value: String,
+}
+
+impl Field {
+    pub fn new(tag: &str, value: &str) -> Field {
+        Field {
+            tag: tag.into(),
+            value: value.into(),
+        }
+    }
+
+    /// `lines` returns the lines of the field value.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.value.split('\n')
+    }
+}
+
+/// [`TextBlock`] is the text block `{4:}`, an ordered list of fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextBlock {
+    pub fields: Vec<Field>,
+}
+
+impl TextBlock {
+    /// `get` returns the first field with the given tag.
+    pub fn get(&self, tag: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.tag == tag)
+    }
+
+    /// `get_all` returns all the fields with the given tag.
+    pub fn get_all<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a Field> {
+        self.fields.iter().filter(move |f| f.tag == tag)
+    }
+
+    fn parse(block: &str) -> Result<TextBlock> {
+        let block = block.replace("\r\n", "\n");
+        let body = block
+            .strip_prefix('\n')
+            .and_then(|b| b.strip_suffix("-"))
+            .ok_or_else(|| {
+                Error::invalid_format(
+                    "text block",
+                    None,
+                    Reason::Format("expected a line break then fields ended by \"-\"".into()),
+                )
+            })?;
+
+        TextBlock::parse_fields(body)
+    }
+
+    /// `parse_fields` parses the `:tag:value` lines of a text block, without
+    /// its leading line break and trailing "-".
+    pub fn parse_fields(body: &str) -> Result<TextBlock> {
+        let mut fields: Vec<Field> = vec![];
+
+        for line in body.lines() {
+            match parse_tag(line) {
+                Some((tag, value)) => fields.push(Field::new(tag, value)),
+                None => {
+                    let field = fields.last_mut().ok_or_else(|| {
+                        Error::invalid_format(
+                            "text block",
+                            Some(1),
+                            Reason::Format("expected a \":tag:\" line".into()),
+                        )
+                    })?;
+                    field.value.push('\n');
+                    field.value.push_str(line);
+                }
+            }
+        }
+
+        Ok(TextBlock { fields })
+    }
+}
+
+impl fmt::Display for TextBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\r\n")?;
+        for field in self.fields.iter() {
+            write!(
+                f,
+                ":{}:{}\r\n",
+                field.tag,
+                field.value.replace('\n', "\r\n")
+            )?;
+        }
+        write!(f, "-")
+    }
+}
+
+/// [`Message`] is a SWIFT FIN message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub basic_header: BasicHeader,
+    pub application_header: Option<ApplicationHeader>,
+    pub user_header: Option<UserHeader>,
+    pub text: Option<TextBlock>,
+    pub trailer: Option<Trailer>,
+}
+
+impl Message {
+    /// `input` creates a normal priority input message of `message_type`
+    /// from `sender` to `receiver`.
+    pub fn input(
+        sender: LogicalTerminal,
+        receiver: LogicalTerminal,
+        message_type: &str,
+        text: TextBlock,
+    ) -> Message {
+        Message {
+            basic_header: BasicHeader {
+                application_id: 'F',
+                service_id: "01".into(),
+                logical_terminal: sender,
+                session_number: "0000".into(),
+                sequence_number: "000000".into(),
+            },
+            application_header: Some(ApplicationHeader::Input {
+                message_type: message_type.into(),
+                receiver,
+                priority: Some('N'),
+                delivery_monitoring: None,
+                obsolescence_period: None,
+            }),
+            user_header: None,
+            text: Some(text),
+            trailer: None,
+        }
+    }
+
+    /// `parse` parses a FIN message from its blocks.
+    pub fn parse(message: &str) -> Result<Message> {
+        let mut basic_header = None;
+        let mut application_header = None;
+        let mut user_header = None;
+        let mut text = None;
+        let mut trailer = None;
+
+        for (line, id, content) in split_blocks(message.trim())? {
+            match id {
+                "1" => basic_header = Some(BasicHeader::parse(content)?),
+                "2" => application_header = Some(ApplicationHeader::parse(content)?),
+                "3" => {
+                    let header = UserHeader::parse(content)?;
+
+                    // NOTE: gpi trackers reject malformed UETRs
+                    if let Some(uetr) = header.get("121") {
+                        Uetr::check(uetr).map_err(|e| e.in_field("121", uetr, 0))?;
+                    }
+
+                    user_header = Some(header);
+                }
+                "4" => text = Some(TextBlock::parse(content)?),
+                "5" => trailer = Some(Trailer::parse(content)?),
+                _ => {
+                    return Err(Error::invalid_format(
+                        "SWIFT message",
+                        Some(line),
+                        Reason::Format(format!("unknown block {}", id)),
+                    ))
+                }
+            }
+        }
+
+        Ok(Message {
+            basic_header: basic_header
+                .ok_or_else(|| Error::MissingElement("basic header".into()))?,
+            application_header,
+            user_header,
+            text,
+            trailer,
+        })
+    }
+
+    /// `message_type` returns the message type, such as "103".
+    pub fn message_type(&self) -> Option<&str> {
+        self.application_header.as_ref().map(|h| h.message_type())
+    }
+
+    /// `sender` returns the BIC of the sender of the message.
+    pub fn sender(&self) -> &BIC {
+        match self.application_header {
+            Some(ApplicationHeader::Output { ref sender, .. }) => &sender.bic,
+            _ => &self.basic_header.logical_terminal.bic,
+        }
+    }
+
+    /// `receiver` returns the BIC of the receiver of the message.
+    pub fn receiver(&self) -> Option<&BIC> {
+        match self.application_header {
+            Some(ApplicationHeader::Input { ref receiver, .. }) => Some(&receiver.bic),
+            Some(ApplicationHeader::Output { .. }) => Some(&self.basic_header.logical_terminal.bic),
+            None => None,
+        }
+    }
+
+    /// `uetr` returns the unique end-to-end transaction reference of the
+    /// user header, if any.
+    pub fn uetr(&self) -> Option<Uetr> {
+        self.user_header.as_ref().and_then(|h| h.uetr())
+    }
+
+    /// `set_uetr` sets the unique end-to-end transaction reference of the
+    /// user header.
+    pub fn set_uetr(&mut self, uetr: Uetr) {
+        self.user_header
+            .get_or_insert_with(UserHeader::default)
+            .set_uetr(uetr);
+    }
+
+    /// `fields` returns the fields of the text block.
+    pub fn fields(&self) -> &[Field] {
+        self.text
+            .as_ref()
+            .map(|t| t.fields.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// `field` returns the first field of the text block with the given tag.
+    pub fn field(&self, tag: &str) -> Option<&Field> {
+        self.text.as_ref().and_then(|t| t.get(tag))
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{1:{}}}", self.basic_header)?;
+        if let Some(ref header) = self.application_header {
+            write!(f, "{{2:{}}}", header)?;
+        }
+        if let Some(ref header) = self.user_header {
+            write!(f, "{{3:{}}}", header)?;
+        }
+        if let Some(ref text) = self.text {
+            write!(f, "{{4:{}}}", text)?;
+        }
+        if let Some(ref trailer) = self.trailer {
+            write!(f, "{{5:{}}}", trailer)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Message {
+    type Err = Error;
+
+    fn from_str(message: &str) -> StdResult<Self, Self::Err> {
+        Message::parse(message)
+    }
+}
+
+/// `split_blocks` splits a message into its top level `{id:content}` blocks,
+/// with the 1-based line where each starts.
+fn split_blocks(message: &str) -> Result<Vec<(usize, &str, &str)>> {
+    let mut blocks = vec![];
+    let mut rest = message;
+
+    while !rest.is_empty() {
+        let start = &message[..message.len() - rest.len()];
+        let line = start.matches('\n').count() + 1;
+        let invalid = |expected| {
+            Error::invalid_format(
+                "SWIFT message",
+                Some(line),
+                Reason::Character {
+                    index: start.chars().count(),
+                    found: rest.chars().next().unwrap_or(' '),
+                    expected,
+                },
+            )
+        };
+
+        if !rest.starts_with('{') {
+            return Err(invalid("'{'"));
+        }
+
+        let mut depth = 0;
+        let mut end = None;
+        for (pos, c) in rest.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(pos);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let end = end.ok_or_else(|| invalid("a block terminated by '}'"))?;
+        let colon = rest[..end]
+            .find(':')
+            .ok_or_else(|| invalid("a \"{id:\" block"))?;
+
+        blocks.push((line, &rest[1..colon], &rest[colon + 1..end]));
+        rest = rest[end + 1..].trim_start();
+    }
+
+    Ok(blocks)
+}
+
+/// `parse_tag` splits a text block line starting with a `:NNa:` tag.
+fn parse_tag(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix(':')?;
+    let end = rest.find(':')?;
+    let tag = &rest[..end];
+    let bytes = tag.as_bytes();
+
+    let valid = (tag.len() == 2 || tag.len() == 3)
+        && bytes[0].is_ascii_digit()
+        && bytes[1].is_ascii_digit()
+        && (tag.len() == 2 || bytes[2].is_ascii_uppercase());
+
+    if valid {
+        Some((tag, &rest[end + 1..]))
+    } else {
+        None
+    }
+}
+
+/// `check_numeric` checks that the ASCII header `block` has digits at
+/// `from..to`.
+fn check_numeric(kind: &'static str, block: &str, from: usize, to: usize) -> Result<()> {
+    check_digits(kind, &block[from..to], to - from, to - from)
+        .map_err(|e| e.within(kind, block, from))
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{ApplicationHeader, Message};
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[allow(dead_code)]
+    const INPUT_MESSAGE: &str = "{1:F01BANKBEBBAXXX0000000000}{2:I103BANKDEFFXXXXN}\
+        {3:{108:MUR12345}{121:e7b4e7c5-3b1a-4d3c-9f1e-0a5e1c2a3b4d}}\
+        {4:\r\n:20:REF12345\r\n:23B:CRED\r\n:32A:240115EUR1234,56\r\n\
+        :50K:/BE71096123456769\r\nJOHN DOE\r\nRUE DE LA LOI 1\r\n\
+        :59:/DE89370400440532013000\r\nJANE DOE\r\n:71A:SHA\r\n-}\
+        {5:{CHK:123456789ABC}}";
+
+    #[test]
+    fn parse_input() -> Result<()> {
+        let message = Message::parse(INPUT_MESSAGE)?;
+
+        assert_eq!(message.basic_header.application_id, 'F');
+        assert_eq!(message.basic_header.service_id, "01");
+        assert_eq!(message.basic_header.logical_terminal.terminal, 'A');
+        assert_eq!(message.sender().to_string(), "BANKBEBBXXX");
+        assert_eq!(
+            message.receiver().map(|b| b.bic8()),
+            Some("BANKDEFF".into())
+        );
+        assert_eq!(message.message_type(), Some("103"));
+        assert_eq!(
+            message.uetr().map(|u| u.to_string()),
+            Some("e7b4e7c5-3b1a-4d3c-9f1e-0a5e1c2a3b4d".into())
+        );
+        assert!(Message::parse(&INPUT_MESSAGE.replace("-4d3c-", "-1d3c-")).is_err());
+        assert_eq!(
+            message.user_header.as_ref().and_then(|h| h.get("108")),
+            Some("MUR12345")
+        );
+
+        let tags: Vec<_> = message.fields().iter().map(|f| f.tag.as_str()).collect();
+        assert_eq!(tags, ["20", "23B", "32A", "50K", "59", "71A"]);
+        assert_eq!(
+            message.field("50K").map(|f| f.lines().collect::<Vec<_>>()),
+            Some(vec!["/BE71096123456769", "JOHN DOE", "RUE DE LA LOI 1"])
+        );
+        assert_eq!(
+            message.trailer.as_ref().and_then(|t| t.get("CHK")),
+            Some("123456789ABC")
+        );
+
+        assert_eq!(message.to_string(), INPUT_MESSAGE);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_output() -> Result<()> {
+        let message: Message = "{1:F01BANKDEFFAXXX1234567890}\
+            {2:O1031200240115BANKBEBBAXXX22221234562401151201N}{4:\r\n:20:REF\r\n-}"
+            .parse()?;
+
+        match message.application_header {
+            Some(ApplicationHeader::Output {
+                ref input_date,
+                ref session_number,
+                ref output_time,
+                priority,
+                ..
+            }) => {
+                assert_eq!(input_date.to_string(), "2024-01-15");
+                assert_eq!(session_number, "2222");
+                assert_eq!(output_time, "1201");
+                assert_eq!(priority, Some('N'));
+            }
+            _ => panic!("expected an output header"),
+        }
+
+        assert_eq!(message.sender().bic8(), "BANKBEBB");
+        assert_eq!(
+            message.receiver().map(|b| b.bic8()),
+            Some("BANKDEFF".into())
+        );
+        assert_eq!(
+            message.to_string(),
+            "{1:F01BANKDEFFAXXX1234567890}\
+             {2:O1031200240115BANKBEBBAXXX22221234562401151201N}{4:\r\n:20:REF\r\n-}"
+        );
+
+        assert!(Message::parse("{2:I103BANKDEFFXXXXN}").is_err());
+        assert!(Message::parse("{1:F01BANKBEBBAXXX0000000000}{4:\r\n:20:REF\r\n}").is_err());
+        assert!(Message::parse("{1:F01BANKBEBBAXXX0000000000}{2:X103BANKDEFFXXXXN}").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+//! SWIFT FIN messages (MT), as exchanged on the SWIFT network.
+
+pub mod charset;
+pub mod common;
+pub mod fields;
+pub mod format;
+pub mod message;
+pub mod mt103;
+pub mod mt202;
+pub mod mt940;
+pub mod pacs008;
+pub mod translate;
+pub mod uetr;
+
+pub use common::*;
+pub use fields::Party;
+pub use format::Format;
+pub use message::*;
+pub use mt103::*;
+pub use mt202::*;
+pub use mt940::*;
+pub use pacs008::Pacs008;
+pub use translate::*;
+pub use uetr::Uetr;
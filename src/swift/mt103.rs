@@ -0,0 +1,764 @@
+//! MT103 Single Customer Credit Transfer, including the STP (MT103+)
+//! subset.
+
+use crate::date::Date;
+use crate::error::{Error, Reason};
+use crate::money::Money;
+use crate::result::Result;
+use crate::swift::fields::*;
+use crate::swift::format;
+use crate::swift::{Field, LogicalTerminal, Message, TagBlock, TextBlock, Uetr};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `BANK_OPERATION_CODES` are the allowed codes of field 23B.
+pub const BANK_OPERATION_CODES: &[&str] = &["CRED", "CRTS", "SPAY", "SPRI", "SSTD"];
+
+/// `INSTRUCTION_CODES` are the allowed codes of field 23E.
+pub const INSTRUCTION_CODES: &[&str] = &[
+    "CHQB", "CORT", "HOLD", "INTC", "PHOB", "PHOI", "PHON", "REPA", "SDVA", "TELB", "TELE", "TELI",
+];
+
+/// `INFORMATION_INSTRUCTION_CODES` are the codes of field 23E which may be
+/// followed by additional information.
+pub const INFORMATION_INSTRUCTION_CODES: &[&str] = &[
+    "HOLD", "PHOB", "PHOI", "PHON", "REPA", "TELB", "TELE", "TELI",
+];
+
+/// `SPRI_INSTRUCTION_CODES` are the codes of field 23E allowed with the
+/// "SPRI" bank operation code.
+const SPRI_INSTRUCTION_CODES: &[&str] = &["INTC", "PHOB", "SDVA", "TELB"];
+
+/// `STP_INSTRUCTION_CODES` are the codes of field 23E allowed in an MT103+.
+pub const STP_INSTRUCTION_CODES: &[&str] = &["CORT", "INTC", "REPA", "SDVA"];
+
+/// `PARTY_TAGS` are the party fields of an MT103, in network order.
+const PARTY_TAGS: &[&str] = &["50", "52", "53", "54", "55", "56", "57", "59"];
+
+/// [`DetailsOfCharges`] tells which party bears the charges (field 71A).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DetailsOfCharges {
+    Ours,
+    Shared,
+    Beneficiary,
+}
+
+impl DetailsOfCharges {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DetailsOfCharges::Ours => "OUR",
+            DetailsOfCharges::Shared => "SHA",
+            DetailsOfCharges::Beneficiary => "BEN",
+        }
+    }
+}
+
+impl FromStr for DetailsOfCharges {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        match code {
+            "OUR" => Ok(DetailsOfCharges::Ours),
+            "SHA" => Ok(DetailsOfCharges::Shared),
+            "BEN" => Ok(DetailsOfCharges::Beneficiary),
+            _ => Err(Error::invalid_field("71A", code, Reason::Unknown)),
+        }
+    }
+}
+
+/// [`MT103`] is a typed MT103 Single Customer Credit Transfer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MT103 {
+    /// Field 20.
+    pub senders_reference: String,
+    /// Field 13C, repeatable.
+    pub time_indications: Vec<String>,
+    /// Field 23B.
+    pub bank_operation_code: String,
+    /// Field 23E, repeatable.
+    pub instruction_codes: Vec<String>,
+    /// Field 26T.
+    pub transaction_type_code: Option<String>,
+    /// Field 32A.
+    pub value_date: Date,
+    /// Field 32A, the interbank settled amount.
+    pub amount: Money,
+    /// Field 33B.
+    pub instructed_amount: Option<Money>,
+    /// Field 36.
+    pub exchange_rate: Option<String>,
+    /// Field 50a, with option A, F or K.
+    pub ordering_customer: Party,
+    /// Field 52a.
+    pub ordering_institution: Option<Party>,
+    /// Field 53a.
+    pub senders_correspondent: Option<Party>,
+    /// Field 54a.
+    pub receivers_correspondent: Option<Party>,
+    /// Field 55a.
+    pub third_reimbursement_institution: Option<Party>,
+    /// Field 56a.
+    pub intermediary_institution: Option<Party>,
+    /// Field 57a.
+    pub account_with_institution: Option<Party>,
+    /// Field 59a, with no option letter, option A or F.
+    pub beneficiary_customer: Party,
+    /// Field 70.
+    pub remittance_information: Vec<String>,
+    /// Field 71A.
+    pub details_of_charges: DetailsOfCharges,
+    /// Field 71F, repeatable.
+    pub senders_charges: Vec<Money>,
+    /// Field 71G.
+    pub receivers_charges: Option<Money>,
+    /// Field 72.
+    pub sender_to_receiver_information: Vec<String>,
+    /// Field 77B.
+    pub regulatory_reporting: Vec<String>,
+    /// Field 77T, the envelope contents of an MT103 REMIT.
+    pub envelope_contents: Option<String>,
+}
+
+impl MT103 {
+    /// `new` creates a new `MT103` with the mandatory fields, using the
+    /// "CRED" bank operation code and shared charges.
+    pub fn new(
+        senders_reference: &str,
+        value_date: Date,
+        amount: Money,
+        ordering_customer: Party,
+        beneficiary_customer: Party,
+    ) -> MT103 {
+        MT103 {
+            senders_reference: senders_reference.into(),
+            time_indications: vec![],
+            bank_operation_code: "CRED".into(),
+            instruction_codes: vec![],
+            transaction_type_code: None,
+            value_date,
+            amount,
+            instructed_amount: None,
+            exchange_rate: None,
+            ordering_customer,
+            ordering_institution: None,
+            senders_correspondent: None,
+            receivers_correspondent: None,
+            third_reimbursement_institution: None,
+            intermediary_institution: None,
+            account_with_institution: None,
+            beneficiary_customer,
+            remittance_information: vec![],
+            details_of_charges: DetailsOfCharges::Shared,
+            senders_charges: vec![],
+            receivers_charges: None,
+            sender_to_receiver_information: vec![],
+            regulatory_reporting: vec![],
+            envelope_contents: None,
+        }
+    }
+
+    /// `parse` parses a FIN MT103 message.
+    pub fn parse(message: &str) -> Result<MT103> {
+        MT103::from_message(&Message::parse(message)?)
+    }
+
+    /// `from_message` reads an MT103 from a parsed FIN message.
+    pub fn from_message(message: &Message) -> Result<MT103> {
+        if message.message_type() != Some("103") {
+            return Err(Error::UnknownMessage(format!(
+                "MT{}",
+                message.message_type().unwrap_or("")
+            )));
+        }
+
+        MT103::from_fields(message.fields())
+    }
+
+    /// `from_fields` reads an MT103 from the fields of its text block.
+    pub fn from_fields(fields: &[Field]) -> Result<MT103> {
+        let mut senders_reference = None;
+        let mut time_indications = vec![];
+        let mut bank_operation_code = None;
+        let mut instruction_codes = vec![];
+        let mut transaction_type_code = None;
+        let mut value_date_amount = None;
+        let mut instructed_amount = None;
+        let mut exchange_rate = None;
+        let mut parties: [Option<Party>; 8] = Default::default();
+        let mut remittance_information = vec![];
+        let mut details_of_charges = None;
+        let mut senders_charges = vec![];
+        let mut receivers_charges = None;
+        let mut sender_to_receiver_information = vec![];
+        let mut regulatory_reporting = vec![];
+        let mut envelope_contents = None;
+
+        for field in fields {
+            let (tag, value) = (field.tag.as_str(), field.value.as_str());
+            let lines = || value.split('\n').map(String::from).collect::<Vec<_>>();
+
+            match tag {
+                "20" => senders_reference = Some(value.to_string()),
+                "13C" => time_indications.push(value.into()),
+                "23B" => bank_operation_code = Some(value.to_string()),
+                "23E" => instruction_codes.push(value.into()),
+                "26T" => transaction_type_code = Some(value.into()),
+                "32A" => value_date_amount = Some(parse_date_currency_amount(tag, value)?),
+                "33B" => instructed_amount = Some(parse_currency_amount(tag, value)?),
+                "36" => exchange_rate = Some(value.into()),
+                "70" => remittance_information = lines(),
+                "71A" => details_of_charges = Some(value.parse()?),
+                "71F" => senders_charges.push(parse_currency_amount(tag, value)?),
+                "71G" => receivers_charges = Some(parse_currency_amount(tag, value)?),
+                "72" => sender_to_receiver_information = lines(),
+                "77B" => regulatory_reporting = lines(),
+                "77T" => envelope_contents = Some(value.into()),
+                _ => match PARTY_TAGS.iter().position(|t| tag.starts_with(t)) {
+                    Some(i) => parties[i] = Some(Party::parse(tag, value)?),
+                    None => {
+                        return Err(Error::invalid_field(
+                            tag,
+                            value,
+                            Reason::Format("unexpected in MT103".into()),
+                        ))
+                    }
+                },
+            }
+        }
+
+        let missing = |tag: &str| Error::MissingElement(format!("MT103 field {}", tag));
+        let (value_date, amount) = value_date_amount.ok_or_else(|| missing("32A"))?;
+        let [ordering_customer, ordering_institution, senders_correspondent, receivers_correspondent, third_reimbursement_institution, intermediary_institution, account_with_institution, beneficiary_customer] =
+            parties;
+
+        let mt = MT103 {
+            senders_reference: senders_reference.ok_or_else(|| missing("20"))?,
+            time_indications,
+            bank_operation_code: bank_operation_code.ok_or_else(|| missing("23B"))?,
+            instruction_codes,
+            transaction_type_code,
+            value_date,
+            amount,
+            instructed_amount,
+            exchange_rate,
+            ordering_customer: ordering_customer.ok_or_else(|| missing("50a"))?,
+            ordering_institution,
+            senders_correspondent,
+            receivers_correspondent,
+            third_reimbursement_institution,
+            intermediary_institution,
+            account_with_institution,
+            beneficiary_customer: beneficiary_customer.ok_or_else(|| missing("59a"))?,
+            remittance_information,
+            details_of_charges: details_of_charges.ok_or_else(|| missing("71A"))?,
+            senders_charges,
+            receivers_charges,
+            sender_to_receiver_information,
+            regulatory_reporting,
+            envelope_contents,
+        };
+
+        mt.validate()?;
+
+        Ok(mt)
+    }
+
+    /// `validate` checks the field formats and the network validated rules
+    /// of the MT103.
+    pub fn validate(&self) -> Result<()> {
+        check_reference("20", &self.senders_reference)?;
+
+        for indication in self.time_indications.iter() {
+            format::check("13C", indication, "/8c/4!n1!x4!n")?;
+        }
+
+        format::check("23B", &self.bank_operation_code, "4!c")?;
+        if !BANK_OPERATION_CODES.contains(&self.bank_operation_code.as_str()) {
+            return Err(Error::invalid_field(
+                "23B",
+                &self.bank_operation_code,
+                Reason::Unknown,
+            ));
+        }
+
+        for code in self.instruction_codes.iter() {
+            format::check("23E", code, "4!c[/30x]")?;
+
+            if !INSTRUCTION_CODES.contains(&&code[0..4]) {
+                return Err(Error::invalid_field("23E", code, Reason::Unknown));
+            }
+
+            if code.len() > 4 && !INFORMATION_INSTRUCTION_CODES.contains(&&code[0..4]) {
+                return Err(invalid_field(
+                    "23E",
+                    code,
+                    "additional information not allowed for the code",
+                ));
+            }
+        }
+
+        if let Some(ref code) = self.transaction_type_code {
+            format::check("26T", code, "3!c")?;
+        }
+
+        if let Some(ref rate) = self.exchange_rate {
+            format::check("36", rate, "12d")?;
+        }
+
+        check_party("50", Some(&self.ordering_customer), &["A", "F", "K"])?;
+        check_party("52", self.ordering_institution.as_ref(), &["A", "D"])?;
+        check_party("53", self.senders_correspondent.as_ref(), &["A", "B", "D"])?;
+        check_party(
+            "54",
+            self.receivers_correspondent.as_ref(),
+            &["A", "B", "D"],
+        )?;
+        check_party(
+            "55",
+            self.third_reimbursement_institution.as_ref(),
+            &["A", "B", "D"],
+        )?;
+        check_party(
+            "56",
+            self.intermediary_institution.as_ref(),
+            &["A", "C", "D"],
+        )?;
+        check_party(
+            "57",
+            self.account_with_institution.as_ref(),
+            &["A", "B", "C", "D"],
+        )?;
+        check_party("59", Some(&self.beneficiary_customer), &["", "A", "F"])?;
+
+        if !self.remittance_information.is_empty() {
+            check_lines("70", &self.remittance_information, 4, 35)?;
+        }
+
+        if !self.sender_to_receiver_information.is_empty() {
+            check_lines("72", &self.sender_to_receiver_information, 6, 35)?;
+        }
+
+        if !self.regulatory_reporting.is_empty() {
+            check_lines("77B", &self.regulatory_reporting, 3, 35)?;
+        }
+
+        if let Some(ref contents) = self.envelope_contents {
+            for line in contents.split('\n') {
+                format::check("77T", line, "9000z")?;
+            }
+        }
+
+        let rule = |rule: &str| {
+            Err(Error::invalid_format(
+                "MT103",
+                None,
+                Reason::Format(format!("rule {}", rule)),
+            ))
+        };
+
+        // C1: an exchange rate is required when the currencies differ.
+        match self.instructed_amount {
+            Some(ref instructed) if instructed.currency != self.amount.currency => {
+                if self.exchange_rate.is_none() {
+                    return rule("C1: field 36 is required when 33B and 32A currencies differ");
+                }
+            }
+            _ => {
+                if self.exchange_rate.is_some() {
+                    return rule("C1: field 36 is only allowed when 33B and 32A currencies differ");
+                }
+            }
+        }
+
+        // C3: the instruction codes depend on the bank operation code.
+        match self.bank_operation_code.as_str() {
+            "SPRI" => {
+                if let Some(code) = self
+                    .instruction_codes
+                    .iter()
+                    .find(|code| !SPRI_INSTRUCTION_CODES.contains(&&code[0..4]))
+                {
+                    return rule(&format!(
+                        "C3: field 23E code {} is not allowed with SPRI",
+                        code
+                    ));
+                }
+            }
+            "SSTD" | "SPAY" if !self.instruction_codes.is_empty() => {
+                return rule("C3: field 23E is not allowed with SSTD and SPAY")
+            }
+            _ => {}
+        }
+
+        // C4: a third reimbursement institution needs both correspondents.
+        if self.third_reimbursement_institution.is_some()
+            && (self.senders_correspondent.is_none() || self.receivers_correspondent.is_none())
+        {
+            return rule("C4: field 55a requires fields 53a and 54a");
+        }
+
+        // C5: an intermediary needs an account with institution.
+        if self.intermediary_institution.is_some() && self.account_with_institution.is_none() {
+            return rule("C5: field 56a requires field 57a");
+        }
+
+        // C6: SPRI payments do not go through an intermediary, SSTD and
+        // SPAY ones only through an identified one.
+        match (
+            self.bank_operation_code.as_str(),
+            self.intermediary_institution.as_ref(),
+        ) {
+            ("SPRI", Some(_)) => return rule("C6: field 56a is not allowed with SPRI"),
+            ("SSTD", Some(party)) | ("SPAY", Some(party)) if !"AC".contains(party.option()) => {
+                return rule("C6: field 56a option must be A or C with SSTD and SPAY")
+            }
+            _ => {}
+        }
+
+        // C7: charges fields depend on field 71A.
+        match self.details_of_charges {
+            DetailsOfCharges::Ours if !self.senders_charges.is_empty() => {
+                return rule("C7: field 71F is not allowed with OUR")
+            }
+            DetailsOfCharges::Shared | DetailsOfCharges::Beneficiary
+                if self.receivers_charges.is_some() =>
+            {
+                return rule("C7: field 71G is only allowed with OUR")
+            }
+            DetailsOfCharges::Beneficiary if self.senders_charges.is_empty() => {
+                return rule("C7: field 71F is required with BEN")
+            }
+            _ => {}
+        }
+
+        // C8: charges fields require the instructed amount.
+        if (!self.senders_charges.is_empty() || self.receivers_charges.is_some())
+            && self.instructed_amount.is_none()
+        {
+            return rule("C8: fields 71F and 71G require field 33B");
+        }
+
+        // C9: the receiver's charges are in the settlement currency.
+        match self.receivers_charges {
+            Some(ref charges) if charges.currency != self.amount.currency => {
+                return rule("C9: field 71G and 32A currencies must be the same")
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// `validate_stp` checks the MT103 against the STP (MT103+) subset
+    /// rules, on top of the standard ones.
+    pub fn validate_stp(&self) -> Result<()> {
+        self.validate()?;
+
+        let rule = |rule: &str| {
+            Err(Error::invalid_format(
+                "MT103+",
+                None,
+                Reason::Format(rule.into()),
+            ))
+        };
+
+        if self.bank_operation_code == "CRTS" {
+            return rule("field 23B CRTS is not allowed");
+        }
+
+        if let Some(code) = self
+            .instruction_codes
+            .iter()
+            .find(|code| !STP_INSTRUCTION_CODES.contains(&code.get(0..4).unwrap_or("")))
+        {
+            return rule(&format!("field 23E code {} is not allowed", code));
+        }
+
+        let parties = [
+            ("52a", self.ordering_institution.as_ref(), "A"),
+            ("53a", self.senders_correspondent.as_ref(), "AB"),
+            ("54a", self.receivers_correspondent.as_ref(), "A"),
+            ("55a", self.third_reimbursement_institution.as_ref(), "A"),
+            ("56a", self.intermediary_institution.as_ref(), "A"),
+            ("57a", self.account_with_institution.as_ref(), "A"),
+        ];
+
+        for (tag, party, options) in parties.iter() {
+            if let Some(party) = party {
+                if !options.contains(party.option()) {
+                    return rule(&format!(
+                        "field {} option {} is not allowed",
+                        tag,
+                        party.option()
+                    ));
+                }
+            }
+        }
+
+        if self.beneficiary_customer.account().is_none() {
+            return rule("field 59a account is mandatory");
+        }
+
+        if self
+            .sender_to_receiver_information
+            .iter()
+            .any(|line| line.starts_with("/REJT/") || line.starts_with("/RETN/"))
+        {
+            return rule("field 72 codes REJT and RETN are not allowed");
+        }
+
+        Ok(())
+    }
+
+    /// `to_text_block` returns the fields of the MT103 in network order.
+    pub fn to_text_block(&self) -> TextBlock {
+        let mut fields = vec![Field::new("20", &self.senders_reference)];
+        fields.extend(
+            self.time_indications
+                .iter()
+                .map(|indication| Field::new("13C", indication)),
+        );
+        fields.push(Field::new("23B", &self.bank_operation_code));
+        fields.extend(
+            self.instruction_codes
+                .iter()
+                .map(|code| Field::new("23E", code)),
+        );
+
+        if let Some(ref code) = self.transaction_type_code {
+            fields.push(Field::new("26T", code));
+        }
+        fields.push(Field::new(
+            "32A",
+            &format_date_currency_amount(&self.value_date, &self.amount),
+        ));
+
+        if let Some(ref amount) = self.instructed_amount {
+            fields.push(Field::new("33B", &format_currency_amount(amount)));
+        }
+
+        if let Some(ref rate) = self.exchange_rate {
+            fields.push(Field::new("36", rate));
+        }
+
+        for (tag, party) in [
+            ("50", Some(&self.ordering_customer)),
+            ("52", self.ordering_institution.as_ref()),
+            ("53", self.senders_correspondent.as_ref()),
+            ("54", self.receivers_correspondent.as_ref()),
+            ("55", self.third_reimbursement_institution.as_ref()),
+            ("56", self.intermediary_institution.as_ref()),
+            ("57", self.account_with_institution.as_ref()),
+            ("59", Some(&self.beneficiary_customer)),
+        ]
+        .iter()
+        {
+            fields.extend(party.map(|party| party.to_field(tag)));
+        }
+
+        if !self.remittance_information.is_empty() {
+            fields.push(Field::new("70", &self.remittance_information.join("\n")));
+        }
+
+        fields.push(Field::new("71A", self.details_of_charges.as_str()));
+        fields.extend(
+            self.senders_charges
+                .iter()
+                .map(|amount| Field::new("71F", &format_currency_amount(amount))),
+        );
+
+        if let Some(ref amount) = self.receivers_charges {
+            fields.push(Field::new("71G", &format_currency_amount(amount)));
+        }
+
+        if !self.sender_to_receiver_information.is_empty() {
+            fields.push(Field::new(
+                "72",
+                &self.sender_to_receiver_information.join("\n"),
+            ));
+        }
+
+        if !self.regulatory_reporting.is_empty() {
+            fields.push(Field::new("77B", &self.regulatory_reporting.join("\n")));
+        }
+
+        if let Some(ref contents) = self.envelope_contents {
+            fields.push(Field::new("77T", contents));
+        }
+
+        TextBlock { fields }
+    }
+
+    /// `to_message` validates the MT103 and builds the FIN input message
+    /// from `sender` to `receiver`, tracked by `uetr` (field 121). STP
+    /// messages are flagged with the "STP" validation flag (field 119) in
+    /// the user header.
+    pub fn to_message(
+        &self,
+        sender: LogicalTerminal,
+        receiver: LogicalTerminal,
+        uetr: Uetr,
+        stp: bool,
+    ) -> Result<Message> {
+        let mut user_header = TagBlock::default();
+
+        if stp {
+            self.validate_stp()?;
+            user_header.fields.push(("119".into(), "STP".into()));
+        } else {
+            self.validate()?;
+        }
+
+        let mut message = Message::input(sender, receiver, "103", self.to_text_block());
+        message.user_header = Some(user_header);
+        message.set_uetr(uetr);
+
+        Ok(message)
+    }
+}
+
+impl fmt::Display for MT103 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_text_block())
+    }
+}
+
+impl FromStr for MT103 {
+    type Err = Error;
+
+    fn from_str(message: &str) -> StdResult<Self, Self::Err> {
+        MT103::parse(message)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{DetailsOfCharges, MT103};
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+    #[allow(unused_imports)] // TODO
+    use crate::swift::fields::parse_currency_amount;
+    #[allow(unused_imports)] // TODO
+    use crate::swift::{LogicalTerminal, Message, Party};
+
+    #[allow(dead_code)]
+    const MESSAGE: &str = "{1:F01BANKBEBBAXXX0000000000}{2:I103BANKDEFFXXXXN}{3:{119:STP}{121:eb6305c9-1f7f-49de-aed0-16487c27b42d}}\
+        {4:\r\n:20:REF12345\r\n:23B:CRED\r\n:32A:240115EUR1234,56\r\n:33B:USD1350,00\r\n\
+        :36:0,9145\r\n:50K:/BE71096123456769\r\nJOHN DOE\r\nRUE DE LA LOI 1\r\n\
+        :52A:BANKBEBB\r\n:57A:BANKDEFF\r\n:59:/DE89370400440532013000\r\nJANE DOE\r\n\
+        :70:INVOICE 2024-001\r\n:71A:SHA\r\n:71F:EUR2,50\r\n-}";
+
+    #[test]
+    fn parse() -> Result<()> {
+        let mt = MT103::parse(MESSAGE)?;
+
+        assert_eq!(mt.senders_reference, "REF12345");
+        assert_eq!(mt.value_date.to_string(), "2024-01-15");
+        assert_eq!(mt.amount.to_string(), "1234.56 EUR");
+        assert_eq!(
+            mt.instructed_amount.as_ref().map(|a| a.amount),
+            Some(135_000)
+        );
+        assert_eq!(mt.ordering_customer.account(), Some("BE71096123456769"));
+        assert_eq!(mt.beneficiary_customer.name_and_address(), ["JANE DOE"]);
+        assert_eq!(mt.details_of_charges, DetailsOfCharges::Shared);
+        assert_eq!(mt.senders_charges.len(), 1);
+        mt.validate_stp()?;
+
+        let message = mt.to_message(
+            LogicalTerminal::new("BANKBEBBAXXX")?,
+            LogicalTerminal::new("BANKDEFFXXXX")?,
+            "eb6305c9-1f7f-49de-aed0-16487c27b42d".parse()?,
+            true,
+        )?;
+        assert_eq!(message.to_string(), MESSAGE);
+
+        Ok(())
+    }
+
+    #[test]
+    fn optional_fields() -> Result<()> {
+        let message = MESSAGE
+            .replace(
+                ":23B:CRED\r\n",
+                ":13C:/SNDTIME/1249+0100\r\n:23B:CRED\r\n:26T:K90\r\n",
+            )
+            .replace(
+                ":71F:EUR2,50\r\n",
+                ":71F:EUR2,50\r\n:77B:/ORDERRES/BE//MEILAAN 1, 9000 GENT\r\n",
+            );
+        let mt = MT103::parse(&message)?;
+
+        assert_eq!(mt.time_indications, ["/SNDTIME/1249+0100"]);
+        assert_eq!(mt.transaction_type_code.as_deref(), Some("K90"));
+        assert_eq!(
+            mt.regulatory_reporting,
+            ["/ORDERRES/BE//MEILAAN 1, 9000 GENT"]
+        );
+        assert_eq!(
+            Message::parse(&message)?.fields(),
+            mt.to_text_block().fields.as_slice()
+        );
+
+        let mt = MT103::parse(&MESSAGE.replace(
+            ":71F:EUR2,50\r\n",
+            ":71F:EUR2,50\r\n:77T:/NARR/REMITTANCE\r\n",
+        ))?;
+        assert_eq!(mt.envelope_contents.as_deref(), Some("/NARR/REMITTANCE"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rules() -> Result<()> {
+        let mut mt = MT103::parse(MESSAGE)?;
+
+        mt.exchange_rate = None;
+        assert!(mt.validate().is_err());
+
+        let mut mt = MT103::parse(MESSAGE)?;
+        mt.details_of_charges = DetailsOfCharges::Ours;
+        assert!(mt.validate().is_err());
+
+        let mut mt = MT103::parse(MESSAGE)?;
+        mt.ordering_institution = Some(Party::D {
+            party_identifier: None,
+            name_and_address: vec!["SOME BANK".into()],
+        });
+        mt.validate()?;
+        assert!(mt.validate_stp().is_err());
+
+        let mut mt = MT103::parse(MESSAGE)?;
+        mt.senders_reference = "REF//1".into();
+        assert!(mt.validate().is_err());
+
+        let mut mt = MT103::parse(MESSAGE)?;
+        mt.instruction_codes = vec!["ABCD".into()];
+        assert!(mt.validate().is_err());
+        mt.instruction_codes = vec!["SDVA/NOW".into()];
+        assert!(mt.validate().is_err());
+        mt.instruction_codes = vec!["CORT".into()];
+        mt.validate()?;
+        mt.bank_operation_code = "SPRI".into();
+        assert!(mt.validate().is_err());
+        mt.instruction_codes = vec!["SDVA".into()];
+        mt.validate()?;
+        mt.bank_operation_code = "SSTD".into();
+        assert!(mt.validate().is_err());
+
+        let mut mt = MT103::parse(MESSAGE)?;
+        mt.details_of_charges = DetailsOfCharges::Ours;
+        mt.senders_charges = vec![];
+        mt.receivers_charges = Some(parse_currency_amount("71G", "EUR2,50")?);
+        mt.validate()?;
+        mt.receivers_charges = Some(parse_currency_amount("71G", "USD2,50")?);
+        assert!(mt.validate().is_err());
+
+        assert!(MT103::parse(&MESSAGE.replace(":23B:CRED\r\n", "")).is_err());
+        assert!(MT103::parse(&MESSAGE.replace("EUR1234,56", "EUR1234.56")).is_err());
+        assert!(MT103::from_message(&Message::parse(&MESSAGE.replace("I103", "I202"))?).is_err());
+
+        Ok(())
+    }
+}
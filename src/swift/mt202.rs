@@ -0,0 +1,545 @@
+//! MT202 General Financial Institution Transfer and MT202 COV, the cover
+//! payment of an underlying customer credit transfer.
+
+use crate::date::Date;
+use crate::error::{Error, Reason};
+use crate::money::Money;
+use crate::result::Result;
+use crate::swift::fields::*;
+use crate::swift::{format, Field, LogicalTerminal, Message, TagBlock, TextBlock, Uetr};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `COVER_VALIDATION_FLAG` is the validation flag (field 119) of an
+/// MT202 COV.
+pub const COVER_VALIDATION_FLAG: &str = "COV";
+
+/// [`UnderlyingCustomerCreditTransfer`] is the sequence B of an MT202 COV,
+/// copied from the underlying MT103.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnderlyingCustomerCreditTransfer {
+    /// Field 50a, with option A, F or K.
+    pub ordering_customer: Party,
+    /// Field 52a.
+    pub ordering_institution: Option<Party>,
+    /// Field 56a.
+    pub intermediary_institution: Option<Party>,
+    /// Field 57a.
+    pub account_with_institution: Option<Party>,
+    /// Field 59a, with no option letter, option A or F.
+    pub beneficiary_customer: Party,
+    /// Field 70.
+    pub remittance_information: Vec<String>,
+    /// Field 72.
+    pub sender_to_receiver_information: Vec<String>,
+    /// Field 33B.
+    pub instructed_amount: Option<Money>,
+}
+
+impl UnderlyingCustomerCreditTransfer {
+    /// `new` creates a new sequence B with its mandatory fields.
+    pub fn new(
+        ordering_customer: Party,
+        beneficiary_customer: Party,
+    ) -> UnderlyingCustomerCreditTransfer {
+        UnderlyingCustomerCreditTransfer {
+            ordering_customer,
+            ordering_institution: None,
+            intermediary_institution: None,
+            account_with_institution: None,
+            beneficiary_customer,
+            remittance_information: vec![],
+            sender_to_receiver_information: vec![],
+            instructed_amount: None,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        check_party("50", Some(&self.ordering_customer), &["A", "F", "K"])?;
+        check_party("52", self.ordering_institution.as_ref(), &["A", "D"])?;
+        check_party(
+            "56",
+            self.intermediary_institution.as_ref(),
+            &["A", "C", "D"],
+        )?;
+        check_party(
+            "57",
+            self.account_with_institution.as_ref(),
+            &["A", "B", "C", "D"],
+        )?;
+        check_party("59", Some(&self.beneficiary_customer), &["", "A", "F"])?;
+
+        if !self.remittance_information.is_empty() {
+            check_lines("70", &self.remittance_information, 4, 35)?;
+        }
+
+        if !self.sender_to_receiver_information.is_empty() {
+            check_lines("72", &self.sender_to_receiver_information, 6, 35)?;
+        }
+
+        // C2: an intermediary needs an account with institution.
+        if self.intermediary_institution.is_some() && self.account_with_institution.is_none() {
+            return Err(Error::invalid_format(
+                "MT202 COV",
+                None,
+                Reason::Format("rule C2: field 56a requires field 57a in sequence B".into()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn fields(&self) -> Vec<Field> {
+        let mut fields = vec![self.ordering_customer.to_field("50")];
+
+        for (tag, party) in [
+            ("52", self.ordering_institution.as_ref()),
+            ("56", self.intermediary_institution.as_ref()),
+            ("57", self.account_with_institution.as_ref()),
+        ]
+        .iter()
+        {
+            fields.extend(party.map(|party| party.to_field(tag)));
+        }
+
+        fields.push(self.beneficiary_customer.to_field("59"));
+
+        if !self.remittance_information.is_empty() {
+            fields.push(Field::new("70", &self.remittance_information.join("\n")));
+        }
+
+        if !self.sender_to_receiver_information.is_empty() {
+            fields.push(Field::new(
+                "72",
+                &self.sender_to_receiver_information.join("\n"),
+            ));
+        }
+
+        if let Some(ref amount) = self.instructed_amount {
+            fields.push(Field::new("33B", &format_currency_amount(amount)));
+        }
+
+        fields
+    }
+}
+
+/// [`MT202`] is a typed MT202 General Financial Institution Transfer, or
+/// an MT202 COV when the underlying customer credit transfer is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MT202 {
+    /// Field 20.
+    pub transaction_reference: String,
+    /// Field 21.
+    pub related_reference: String,
+    /// Field 13C, repeatable.
+    pub time_indications: Vec<String>,
+    /// Field 32A.
+    pub value_date: Date,
+    /// Field 32A.
+    pub amount: Money,
+    /// Field 52a.
+    pub ordering_institution: Option<Party>,
+    /// Field 53a.
+    pub senders_correspondent: Option<Party>,
+    /// Field 54a.
+    pub receivers_correspondent: Option<Party>,
+    /// Field 56a.
+    pub intermediary: Option<Party>,
+    /// Field 57a.
+    pub account_with_institution: Option<Party>,
+    /// Field 58a.
+    pub beneficiary_institution: Party,
+    /// Field 72.
+    pub sender_to_receiver_information: Vec<String>,
+    /// Sequence B, for an MT202 COV only.
+    pub underlying: Option<UnderlyingCustomerCreditTransfer>,
+}
+
+impl MT202 {
+    /// `new` creates a new `MT202` with the mandatory fields.
+    pub fn new(
+        transaction_reference: &str,
+        related_reference: &str,
+        value_date: Date,
+        amount: Money,
+        beneficiary_institution: Party,
+    ) -> MT202 {
+        MT202 {
+            transaction_reference: transaction_reference.into(),
+            related_reference: related_reference.into(),
+            time_indications: vec![],
+            value_date,
+            amount,
+            ordering_institution: None,
+            senders_correspondent: None,
+            receivers_correspondent: None,
+            intermediary: None,
+            account_with_institution: None,
+            beneficiary_institution,
+            sender_to_receiver_information: vec![],
+            underlying: None,
+        }
+    }
+
+    /// `is_cover` returns if the message is an MT202 COV.
+    pub fn is_cover(&self) -> bool {
+        self.underlying.is_some()
+    }
+
+    /// `parse` parses a FIN MT202 or MT202 COV message.
+    pub fn parse(message: &str) -> Result<MT202> {
+        MT202::from_message(&Message::parse(message)?)
+    }
+
+    /// `from_message` reads an MT202 from a parsed FIN message. Messages
+    /// flagged as COV must have a sequence B.
+    pub fn from_message(message: &Message) -> Result<MT202> {
+        if message.message_type() != Some("202") {
+            return Err(Error::UnknownMessage(format!(
+                "MT{}",
+                message.message_type().unwrap_or("")
+            )));
+        }
+
+        let mt = MT202::from_fields(message.fields())?;
+        let flag = message
+            .user_header
+            .as_ref()
+            .and_then(|h| h.validation_flag());
+
+        if flag == Some(COVER_VALIDATION_FLAG) && !mt.is_cover() {
+            return Err(Error::MissingElement("MT202 COV sequence B".into()));
+        }
+
+        Ok(mt)
+    }
+
+    /// `from_fields` reads an MT202 from the fields of its text block. The
+    /// sequence B starts with the ordering customer field (50a).
+    pub fn from_fields(fields: &[Field]) -> Result<MT202> {
+        let missing = |tag: &str| Error::MissingElement(format!("MT202 field {}", tag));
+        let unexpected = |field: &Field| {
+            Error::invalid_field(
+                &field.tag,
+                &field.value,
+                Reason::Format("unexpected in MT202".into()),
+            )
+        };
+        let split = fields
+            .iter()
+            .position(|f| f.tag.starts_with("50"))
+            .unwrap_or(fields.len());
+        let (sequence_a, sequence_b) = fields.split_at(split);
+
+        let mut transaction_reference = None;
+        let mut related_reference = None;
+        let mut time_indications = vec![];
+        let mut value_date_amount = None;
+        let mut parties: [Option<Party>; 6] = Default::default();
+        let mut sender_to_receiver_information = vec![];
+
+        for field in sequence_a {
+            let (tag, value) = (field.tag.as_str(), field.value.as_str());
+
+            match tag {
+                "20" => transaction_reference = Some(value.to_string()),
+                "21" => related_reference = Some(value.to_string()),
+                "13C" => time_indications.push(value.into()),
+                "32A" => value_date_amount = Some(parse_date_currency_amount(tag, value)?),
+                "72" => {
+                    sender_to_receiver_information = value.split('\n').map(String::from).collect()
+                }
+                _ => match ["52", "53", "54", "56", "57", "58"]
+                    .iter()
+                    .position(|t| tag.starts_with(t))
+                {
+                    Some(i) => parties[i] = Some(Party::parse(tag, value)?),
+                    None => return Err(unexpected(field)),
+                },
+            }
+        }
+
+        let underlying = if sequence_b.is_empty() {
+            None
+        } else {
+            let mut parties: [Option<Party>; 5] = Default::default();
+            let mut remittance_information = vec![];
+            let mut sender_to_receiver_information = vec![];
+            let mut instructed_amount = None;
+
+            for field in sequence_b {
+                let (tag, value) = (field.tag.as_str(), field.value.as_str());
+
+                match tag {
+                    "70" => remittance_information = value.split('\n').map(String::from).collect(),
+                    "72" => {
+                        sender_to_receiver_information =
+                            value.split('\n').map(String::from).collect()
+                    }
+                    "33B" => instructed_amount = Some(parse_currency_amount(tag, value)?),
+                    _ => match ["50", "52", "56", "57", "59"]
+                        .iter()
+                        .position(|t| tag.starts_with(t))
+                    {
+                        Some(i) => parties[i] = Some(Party::parse(tag, value)?),
+                        None => return Err(unexpected(field)),
+                    },
+                }
+            }
+
+            let [ordering_customer, ordering_institution, intermediary_institution, account_with_institution, beneficiary_customer] =
+                parties;
+
+            Some(UnderlyingCustomerCreditTransfer {
+                ordering_customer: ordering_customer.ok_or_else(|| missing("50a"))?,
+                ordering_institution,
+                intermediary_institution,
+                account_with_institution,
+                beneficiary_customer: beneficiary_customer.ok_or_else(|| missing("59a"))?,
+                remittance_information,
+                sender_to_receiver_information,
+                instructed_amount,
+            })
+        };
+
+        let (value_date, amount) = value_date_amount.ok_or_else(|| missing("32A"))?;
+        let [ordering_institution, senders_correspondent, receivers_correspondent, intermediary, account_with_institution, beneficiary_institution] =
+            parties;
+
+        let mt = MT202 {
+            transaction_reference: transaction_reference.ok_or_else(|| missing("20"))?,
+            related_reference: related_reference.ok_or_else(|| missing("21"))?,
+            time_indications,
+            value_date,
+            amount,
+            ordering_institution,
+            senders_correspondent,
+            receivers_correspondent,
+            intermediary,
+            account_with_institution,
+            beneficiary_institution: beneficiary_institution.ok_or_else(|| missing("58a"))?,
+            sender_to_receiver_information,
+            underlying,
+        };
+
+        mt.validate()?;
+
+        Ok(mt)
+    }
+
+    /// `validate` checks the field formats and the network validated rules
+    /// of the MT202, and of the cover payment for an MT202 COV.
+    pub fn validate(&self) -> Result<()> {
+        check_reference("20", &self.transaction_reference)?;
+        check_reference("21", &self.related_reference)?;
+
+        for indication in self.time_indications.iter() {
+            format::check("13C", indication, "/8c/4!n1!x4!n")?;
+        }
+
+        check_party("52", self.ordering_institution.as_ref(), &["A", "D"])?;
+        check_party("53", self.senders_correspondent.as_ref(), &["A", "B", "D"])?;
+        check_party(
+            "54",
+            self.receivers_correspondent.as_ref(),
+            &["A", "B", "D"],
+        )?;
+        check_party("56", self.intermediary.as_ref(), &["A", "D"])?;
+        check_party(
+            "57",
+            self.account_with_institution.as_ref(),
+            &["A", "B", "D"],
+        )?;
+        check_party("58", Some(&self.beneficiary_institution), &["A", "D"])?;
+
+        if !self.sender_to_receiver_information.is_empty() {
+            check_lines("72", &self.sender_to_receiver_information, 6, 35)?;
+        }
+
+        // C1: an intermediary needs an account with institution.
+        if self.intermediary.is_some() && self.account_with_institution.is_none() {
+            return Err(Error::invalid_format(
+                "MT202",
+                None,
+                Reason::Format("rule C1: field 56a requires field 57a".into()),
+            ));
+        }
+
+        if let Some(ref underlying) = self.underlying {
+            underlying.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// `to_text_block` returns the fields of the MT202 in network order.
+    pub fn to_text_block(&self) -> TextBlock {
+        let mut fields = vec![
+            Field::new("20", &self.transaction_reference),
+            Field::new("21", &self.related_reference),
+        ];
+
+        fields.extend(self.time_indications.iter().map(|t| Field::new("13C", t)));
+        fields.push(Field::new(
+            "32A",
+            &format_date_currency_amount(&self.value_date, &self.amount),
+        ));
+
+        for (tag, party) in [
+            ("52", self.ordering_institution.as_ref()),
+            ("53", self.senders_correspondent.as_ref()),
+            ("54", self.receivers_correspondent.as_ref()),
+            ("56", self.intermediary.as_ref()),
+            ("57", self.account_with_institution.as_ref()),
+            ("58", Some(&self.beneficiary_institution)),
+        ]
+        .iter()
+        {
+            fields.extend(party.map(|party| party.to_field(tag)));
+        }
+
+        if !self.sender_to_receiver_information.is_empty() {
+            fields.push(Field::new(
+                "72",
+                &self.sender_to_receiver_information.join("\n"),
+            ));
+        }
+
+        if let Some(ref underlying) = self.underlying {
+            fields.extend(underlying.fields());
+        }
+
+        TextBlock { fields }
+    }
+
+    /// `to_message` validates the MT202 and builds the FIN input message
+    /// from `sender` to `receiver`, tracked by `uetr` (field 121). An MT202
+    /// COV is flagged with the "COV" validation flag (field 119) in the
+    /// user header.
+    pub fn to_message(
+        &self,
+        sender: LogicalTerminal,
+        receiver: LogicalTerminal,
+        uetr: Uetr,
+    ) -> Result<Message> {
+        self.validate()?;
+
+        let mut message = Message::input(sender, receiver, "202", self.to_text_block());
+
+        if self.is_cover() {
+            message.user_header = Some(TagBlock {
+                fields: vec![("119".into(), COVER_VALIDATION_FLAG.into())],
+            });
+        }
+
+        message.set_uetr(uetr);
+
+        Ok(message)
+    }
+}
+
+impl fmt::Display for MT202 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_text_block())
+    }
+}
+
+impl FromStr for MT202 {
+    type Err = Error;
+
+    fn from_str(message: &str) -> StdResult<Self, Self::Err> {
+        MT202::parse(message)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::MT202;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+    #[allow(unused_imports)] // TODO
+    use crate::swift::{LogicalTerminal, Message, Party, Uetr};
+
+    #[allow(dead_code)]
+    const COVER: &str = "{1:F01BANKBEBBAXXX0000000000}{2:I202BANKUS33XXXXN}{3:{119:COV}{121:eb6305c9-1f7f-49de-aed0-16487c27b42d}}\
+        {4:\r\n:20:COV12345\r\n:21:REF12345\r\n:32A:240115USD1350,00\r\n:57A:BANKUS44\r\n\
+        :58A:/123456789\r\nBANKDEFF\r\n:50K:/BE71096123456769\r\nJOHN DOE\r\n\
+        :59:/DE89370400440532013000\r\nJANE DOE\r\n:70:INVOICE 2024-001\r\n:33B:USD1350,00\r\n-}";
+
+    #[test]
+    fn parse_cover() -> Result<()> {
+        let mt = MT202::parse(COVER)?;
+
+        assert!(mt.is_cover());
+        assert_eq!(mt.related_reference, "REF12345");
+        assert_eq!(
+            mt.beneficiary_institution.bic().map(|b| b.to_string()),
+            Some("BANKDEFF".into())
+        );
+        assert_eq!(mt.beneficiary_institution.account(), Some("123456789"));
+
+        let underlying = mt.underlying.as_ref().unwrap();
+        assert_eq!(
+            underlying.ordering_customer.name_and_address(),
+            ["JOHN DOE"]
+        );
+        assert_eq!(
+            underlying.beneficiary_customer.account(),
+            Some("DE89370400440532013000")
+        );
+        assert_eq!(
+            underlying.instructed_amount.as_ref().map(|a| a.amount),
+            Some(135_000)
+        );
+
+        let message = mt.to_message(
+            LogicalTerminal::new("BANKBEBBAXXX")?,
+            LogicalTerminal::new("BANKUS33XXXX")?,
+            "eb6305c9-1f7f-49de-aed0-16487c27b42d".parse()?,
+        )?;
+        assert_eq!(message.to_string(), COVER);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rules() -> Result<()> {
+        let mut mt = MT202::parse(COVER)?;
+        mt.underlying = None;
+        let message = mt.to_message(
+            LogicalTerminal::new("BANKBEBBAXXX")?,
+            LogicalTerminal::new("BANKUS33XXXX")?,
+            Uetr::generate()?,
+        )?;
+        assert_eq!(
+            message
+                .user_header
+                .as_ref()
+                .and_then(|h| h.validation_flag()),
+            None
+        );
+        assert!(message.uetr().is_some());
+        assert!(!MT202::from_message(&message)?.is_cover());
+
+        assert!(
+            MT202::parse(&COVER.replace(":50K:/BE71096123456769\r\nJOHN DOE\r\n", "")).is_err()
+        );
+        assert!(
+            MT202::parse(&COVER.replace(":59:/DE89370400440532013000\r\nJANE DOE\r\n", ""))
+                .is_err()
+        );
+        assert!(MT202::parse(&COVER.replace(":58A:/123456789\r\nBANKDEFF\r\n", "")).is_err());
+        assert!(MT202::parse(&COVER.replace(":57A:BANKUS44\r\n", ":56A:BANKUS55\r\n")).is_err());
+        assert!(MT202::parse(&COVER.replace(":58A:/123456789\r\nBANKDEFF", ":58K:JOHN")).is_err());
+
+        let mut mt = MT202::parse(COVER)?;
+        mt.beneficiary_institution = Party::D {
+            party_identifier: None,
+            name_and_address: vec!["SOME BANK".into(), "FRANKFURT".into()],
+        };
+        mt.validate()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,709 @@
+//! MT940 Customer Statement and MT942 Interim Transaction Report, with
+//! pluggable decoders for the field 86 narratives.
+
+use crate::camt::CreditDebit;
+use crate::date::{Date, DateTime};
+use crate::error::{check_digits, Error, Reason};
+use crate::iso13616::IBAN;
+use crate::iso4217::CurrencyCode;
+use crate::money::Money;
+use crate::result::Result;
+use crate::swift::fields::*;
+use crate::swift::format;
+use crate::swift::{Field, Message, TextBlock};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `STATEMENT_LINE_FORMAT` is the format of field 61.
+const STATEMENT_LINE_FORMAT: &str = "6!n[4!n]2a[1!a]15d1!a3!c16x[//16x][$34x]";
+
+/// [`Balance`] is a booked or available balance (fields 60a, 62a, 64, 65).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Balance {
+    /// `intermediate` is set for the M option of fields 60a and 62a, when a
+    /// statement is split over several messages.
+    pub intermediate: bool,
+    pub credit_debit: CreditDebit,
+    pub date: Date,
+    pub amount: Money,
+}
+
+impl Balance {
+    /// `parse` parses a `1!a6!n3!a15d` balance.
+    pub fn parse(tag: &str, value: &str) -> Result<Balance> {
+        let credit_debit = match value.get(0..1) {
+            Some("C") => CreditDebit::Credit,
+            Some("D") => CreditDebit::Debit,
+            _ => return Err(credit_debit_mark(tag, value, 0)),
+        };
+        let (date, amount) =
+            parse_date_currency_amount(tag, &value[1..]).map_err(|e| e.in_field(tag, value, 1))?;
+
+        Ok(Balance {
+            intermediate: tag.ends_with('M'),
+            credit_debit,
+            date,
+            amount,
+        })
+    }
+
+    /// `signed_amount` returns the balance amount, negative for a debit
+    /// balance.
+    pub fn signed_amount(&self) -> i64 {
+        match self.credit_debit {
+            CreditDebit::Credit => self.amount.amount,
+            CreditDebit::Debit => -self.amount.amount,
+        }
+    }
+}
+
+/// [`StatementLine`] is a statement line (field 61) with its optional
+/// narrative (field 86).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementLine {
+    pub value_date: Date,
+    pub entry_date: Option<Date>,
+    /// `credit_debit` is the credit or debit mark, before any reversal.
+    pub credit_debit: CreditDebit,
+    /// `reversal` is set for the "RC" and "RD" marks.
+    pub reversal: bool,
+    /// `funds_code` is the third letter of the currency code, if any.
+    pub funds_code: Option<char>,
+    pub amount: Money,
+    /// `transaction_type` is the type and identification code, such as
+    /// "NTRF" or "NCHK".
+    pub transaction_type: String,
+    pub customer_reference: String,
+    pub bank_reference: Option<String>,
+    pub supplementary_details: Option<String>,
+    pub information: Option<String>,
+}
+
+impl StatementLine {
+    /// `parse` parses a statement line, whose amount is in `currency`.
+    pub fn parse(value: &str, currency: CurrencyCode) -> Result<StatementLine> {
+        format::check("61", value, STATEMENT_LINE_FORMAT)?;
+
+        let mut lines = value.splitn(2, '\n');
+        let line = lines.next().unwrap_or("");
+        let supplementary_details = lines.next().map(String::from);
+
+        let value_date = Date::from_yymmdd(&line[0..6]).map_err(|e| e.in_field("61", value, 0))?;
+        let mut rest = &line[6..];
+
+        let entry_date = if rest.len() > 4 && rest[0..4].chars().all(|c| c.is_ascii_digit()) {
+            let date =
+                entry_date(value_date, &rest[0..4]).map_err(|e| e.in_field("61", value, 6))?;
+            rest = &rest[4..];
+            Some(date)
+        } else {
+            None
+        };
+
+        let reversal = rest.starts_with('R');
+        if reversal {
+            rest = &rest[1..];
+        }
+
+        let credit_debit = match rest.get(0..1) {
+            Some("C") => CreditDebit::Credit,
+            Some("D") => CreditDebit::Debit,
+            _ => return Err(credit_debit_mark("61", value, line.len() - rest.len())),
+        };
+        rest = &rest[1..];
+
+        let funds_code = rest.chars().next().filter(|c| c.is_ascii_uppercase());
+        if funds_code.is_some() {
+            rest = &rest[1..];
+        }
+
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != ',')
+            .ok_or_else(|| invalid_field("61", value, "missing transaction type"))?;
+        let amount = parse_amount("61", &rest[..end], currency)?;
+        rest = &rest[end..];
+
+        if !"SNF".contains(&rest[0..1]) {
+            return Err(Error::invalid_field(
+                "61",
+                value,
+                Reason::Character {
+                    index: line.len() - rest.len(),
+                    found: rest.chars().next().unwrap_or(' '),
+                    expected: "'S', 'N' or 'F'",
+                },
+            ));
+        }
+
+        let transaction_type = rest[0..4].to_string();
+        let (customer_reference, bank_reference) = match rest[4..].find("//") {
+            Some(pos) => (&rest[4..4 + pos], Some(rest[4 + pos + 2..].to_string())),
+            None => (&rest[4..], None),
+        };
+
+        Ok(StatementLine {
+            value_date,
+            entry_date,
+            credit_debit,
+            reversal,
+            funds_code,
+            amount,
+            transaction_type,
+            customer_reference: customer_reference.into(),
+            bank_reference,
+            supplementary_details,
+            information: None,
+        })
+    }
+
+    /// `signed_amount` returns the amount as booked on the account: a
+    /// reversal of credit ("RC") is a debit, and a reversal of debit ("RD")
+    /// is a credit.
+    pub fn signed_amount(&self) -> i64 {
+        match (self.credit_debit, self.reversal) {
+            (CreditDebit::Credit, false) | (CreditDebit::Debit, true) => self.amount.amount,
+            _ => -self.amount.amount,
+        }
+    }
+
+    /// `decode_information` decodes the narrative of the line with the
+    /// given dialect decoder.
+    pub fn decode_information(&self, decoder: &dyn InformationDecoder) -> Option<Information> {
+        self.information.as_ref().and_then(|i| decoder.decode(i))
+    }
+}
+
+/// [`FloorLimit`] is the floor limit indicator of an MT942 (field 34F).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FloorLimit {
+    /// `credit_debit` is `None` when the limit applies to both directions.
+    pub credit_debit: Option<CreditDebit>,
+    pub amount: Money,
+}
+
+/// [`EntriesSummary`] is the number and sum of debit or credit entries of
+/// an MT942 (fields 90D and 90C).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntriesSummary {
+    pub count: u32,
+    pub amount: Money,
+}
+
+/// [`Statement`] is an MT940 customer statement or an MT942 interim
+/// transaction report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    /// `message_type` is "940" or "942".
+    pub message_type: String,
+    /// Field 20.
+    pub transaction_reference: String,
+    /// Field 21.
+    pub related_reference: Option<String>,
+    /// Field 25, often an IBAN, sometimes followed by the currency.
+    pub account: String,
+    /// Field 28C, statement number.
+    pub statement_number: String,
+    /// Field 28C, sequence number.
+    pub sequence_number: Option<String>,
+    /// Field 34F, MT942 only.
+    pub floor_limits: Vec<FloorLimit>,
+    /// Field 13D, MT942 only.
+    pub created: Option<DateTime>,
+    /// Field 60a, MT940 only.
+    pub opening_balance: Option<Balance>,
+    pub lines: Vec<StatementLine>,
+    /// Field 90D, MT942 only.
+    pub debit_entries: Option<EntriesSummary>,
+    /// Field 90C, MT942 only.
+    pub credit_entries: Option<EntriesSummary>,
+    /// Field 62a, MT940 only.
+    pub closing_balance: Option<Balance>,
+    /// Field 64.
+    pub closing_available_balance: Option<Balance>,
+    /// Field 65.
+    pub forward_available_balances: Vec<Balance>,
+    /// Field 86 after the last statement line, for the account owner.
+    pub information: Option<String>,
+}
+
+impl Statement {
+    /// `parse` parses an MT940 or MT942 FIN message.
+    pub fn parse(message: &str) -> Result<Statement> {
+        Statement::from_message(&Message::parse(message)?)
+    }
+
+    /// `parse_file` parses the statements of a bank export file, made of
+    /// text blocks separated by "-" lines, without FIN envelope.
+    pub fn parse_file(file: &str, message_type: &str) -> Result<Vec<Statement>> {
+        let file = file.replace("\r\n", "\n");
+        let mut statements = vec![];
+
+        for body in file.split("\n-") {
+            if body.trim().is_empty() {
+                continue;
+            }
+
+            let text = TextBlock::parse_fields(body.trim_start_matches('\n'))?;
+            statements.push(Statement::from_fields(message_type, &text.fields)?);
+        }
+
+        Ok(statements)
+    }
+
+    /// `from_message` reads a statement from a parsed FIN message.
+    pub fn from_message(message: &Message) -> Result<Statement> {
+        match message.message_type() {
+            Some(message_type @ "940") | Some(message_type @ "942") => {
+                Statement::from_fields(message_type, message.fields())
+            }
+            other => Err(Error::UnknownMessage(format!("MT{}", other.unwrap_or("")))),
+        }
+    }
+
+    /// `from_fields` reads a statement of the given message type from the
+    /// fields of its text block.
+    pub fn from_fields(message_type: &str, fields: &[Field]) -> Result<Statement> {
+        let mut statement = Statement {
+            message_type: message_type.into(),
+            transaction_reference: String::new(),
+            related_reference: None,
+            account: String::new(),
+            statement_number: String::new(),
+            sequence_number: None,
+            floor_limits: vec![],
+            created: None,
+            opening_balance: None,
+            lines: vec![],
+            debit_entries: None,
+            credit_entries: None,
+            closing_balance: None,
+            closing_available_balance: None,
+            forward_available_balances: vec![],
+            information: None,
+        };
+        let mut currency = None;
+
+        for field in fields {
+            let (tag, value) = (field.tag.as_str(), field.value.as_str());
+
+            match tag {
+                "20" => {
+                    format::check(tag, value, "16x")?;
+                    statement.transaction_reference = value.into();
+                }
+                "21" => {
+                    format::check(tag, value, "16x")?;
+                    statement.related_reference = Some(value.into());
+                }
+                "25" | "25P" => {
+                    let account = value.split('\n').next().unwrap_or("");
+                    format::check(tag, account, "35x")?;
+                    statement.account = account.into();
+                }
+                "28C" => {
+                    format::check(tag, value, "5n[/5n]")?;
+
+                    let mut parts = value.splitn(2, '/');
+                    statement.statement_number = parts.next().unwrap_or("").into();
+                    statement.sequence_number = parts.next().map(String::from);
+                }
+                "34F" => {
+                    let limit = floor_limit(value)?;
+                    currency = Some(limit.amount.currency.clone());
+                    statement.floor_limits.push(limit);
+                }
+                "13D" => statement.created = Some(date_time_indication(value)?),
+                "60F" | "60M" => {
+                    let balance = Balance::parse(tag, value)?;
+                    currency = Some(balance.amount.currency.clone());
+                    statement.opening_balance = Some(balance);
+                }
+                "61" => {
+                    let currency = currency
+                        .clone()
+                        .ok_or_else(|| Error::MissingElement("statement currency".into()))?;
+                    statement.lines.push(StatementLine::parse(value, currency)?);
+                }
+                "86" => {
+                    // Narratives are not always restricted to the X
+                    // character set, only their size is checked.
+                    if value.split('\n').count() > 6
+                        || value.split('\n').any(|l| l.chars().count() > 65)
+                    {
+                        return Err(invalid_field(
+                            tag,
+                            value,
+                            "more than 6 lines or a line longer than 65 chars",
+                        ));
+                    }
+                    match statement.lines.last_mut() {
+                        Some(line)
+                            if line.information.is_none()
+                                && statement.closing_balance.is_none() =>
+                        {
+                            line.information = Some(value.into())
+                        }
+                        _ => statement.information = Some(value.into()),
+                    }
+                }
+                "90D" => statement.debit_entries = Some(entries_summary(tag, value)?),
+                "90C" => statement.credit_entries = Some(entries_summary(tag, value)?),
+                "62F" | "62M" => statement.closing_balance = Some(Balance::parse(tag, value)?),
+                "64" => statement.closing_available_balance = Some(Balance::parse(tag, value)?),
+                "65" => statement
+                    .forward_available_balances
+                    .push(Balance::parse(tag, value)?),
+                _ => {
+                    return Err(Error::invalid_field(
+                        tag,
+                        value,
+                        Reason::Format(format!("unexpected in MT{}", message_type)),
+                    ))
+                }
+            }
+        }
+
+        let missing =
+            |tag: &str| Error::MissingElement(format!("MT{} field {}", message_type, tag));
+
+        if statement.transaction_reference.is_empty() {
+            return Err(missing("20"));
+        }
+        if statement.account.is_empty() {
+            return Err(missing("25"));
+        }
+        if statement.statement_number.is_empty() {
+            return Err(missing("28C"));
+        }
+        if message_type == "940" && statement.opening_balance.is_none() {
+            return Err(missing("60a"));
+        }
+        if message_type == "940" && statement.closing_balance.is_none() {
+            return Err(missing("62a"));
+        }
+        if message_type == "942" && statement.floor_limits.is_empty() {
+            return Err(missing("34F"));
+        }
+
+        Ok(statement)
+    }
+
+    /// `iban` returns the account as IBAN, if it is one.
+    pub fn iban(&self) -> Option<IBAN> {
+        IBAN::new(&self.account)
+            .ok()
+            .filter(|iban| iban.validate().is_ok())
+    }
+}
+
+impl FromStr for Statement {
+    type Err = Error;
+
+    fn from_str(message: &str) -> StdResult<Self, Self::Err> {
+        Statement::parse(message)
+    }
+}
+
+/// [`Information`] is a field 86 narrative decoded by a dialect decoder.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Information {
+    /// `transaction_code` is the bank specific business transaction code.
+    pub transaction_code: Option<String>,
+    pub posting_text: Option<String>,
+    pub remittance_information: Vec<String>,
+    pub counterparty_bank: Option<String>,
+    pub counterparty_account: Option<String>,
+    pub counterparty_name: Option<String>,
+    /// `subfields` are all the decoded subfields, by code.
+    pub subfields: Vec<(String, String)>,
+}
+
+/// [`InformationDecoder`] decodes the field 86 narratives of a bank or
+/// country dialect.
+pub trait InformationDecoder {
+    /// `decode` returns the decoded narrative, or `None` if the narrative
+    /// doesn't follow the dialect.
+    fn decode(&self, information: &str) -> Option<Information>;
+}
+
+/// [`GermanDecoder`] decodes the structured narratives of German banks
+/// (DFÜ-Abkommen), made of a three digits transaction code followed by
+/// "?nn" subfields: ?00 posting text, ?20 to ?29 and ?60 to ?63 remittance
+/// information, ?30 counterparty bank, ?31 account and ?32 ?33 name.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GermanDecoder;
+
+impl InformationDecoder for GermanDecoder {
+    fn decode(&self, information: &str) -> Option<Information> {
+        let information: String = information.split('\n').collect();
+        let (position, separator) = information.char_indices().nth(3)?;
+
+        if position != 3
+            || !information[0..3].chars().all(|c| c.is_ascii_digit())
+            || separator.is_alphanumeric()
+        {
+            return None;
+        }
+
+        let mut decoded = Information {
+            transaction_code: Some(information[0..3].into()),
+            ..Default::default()
+        };
+        let mut name = vec![];
+
+        for subfield in information[position + separator.len_utf8()..].split(separator) {
+            let code = subfield
+                .get(0..2)
+                .filter(|c| c.chars().all(|c| c.is_ascii_digit()))?;
+            let value = subfield[2..].to_string();
+
+            match code {
+                "00" => decoded.posting_text = Some(value.clone()),
+                "20" | "21" | "22" | "23" | "24" | "25" | "26" | "27" | "28" | "29" | "60"
+                | "61" | "62" | "63" => decoded.remittance_information.push(value.clone()),
+                "30" => decoded.counterparty_bank = Some(value.clone()),
+                "31" => decoded.counterparty_account = Some(value.clone()),
+                "32" | "33" => name.push(value.clone()),
+                _ => {}
+            }
+
+            decoded.subfields.push((code.into(), value));
+        }
+
+        if !name.is_empty() {
+            decoded.counterparty_name = Some(name.concat());
+        }
+
+        Some(decoded)
+    }
+}
+
+/// `entry_date` returns the "MMDD" entry date closest to the value date,
+/// as entries can be booked around the end of the year.
+fn entry_date(value_date: Date, entry: &str) -> Result<Date> {
+    check_digits("entry date", entry, 4, 4)?;
+
+    let month: u8 = entry[0..2].parse().unwrap_or_default();
+    let day: u8 = entry[2..4].parse().unwrap_or_default();
+    let year = match i16::from(month) - i16::from(value_date.month()) {
+        diff if diff > 6 => value_date.year() - 1,
+        diff if diff < -6 => value_date.year() + 1,
+        _ => value_date.year(),
+    };
+
+    Date::new(year, month, day)
+}
+
+/// `floor_limit` parses a floor limit indicator.
+fn floor_limit(value: &str) -> Result<FloorLimit> {
+    format::check("34F", value, "3!a[1!a]15d")?;
+
+    let currency = CurrencyCode::new(&value[0..3]).map_err(|e| e.in_field("34F", value, 0))?;
+    let (credit_debit, amount) = match &value[3..4] {
+        "C" => (Some(CreditDebit::Credit), &value[4..]),
+        "D" => (Some(CreditDebit::Debit), &value[4..]),
+        _ => (None, &value[3..]),
+    };
+
+    Ok(FloorLimit {
+        credit_debit,
+        amount: parse_amount("34F", amount, currency)?,
+    })
+}
+
+/// `date_time_indication` parses a date, time and UTC offset.
+fn date_time_indication(value: &str) -> Result<DateTime> {
+    format::check("13D", value, "6!n4!n1!x4!n")?;
+
+    // NOTE: the format guarantees the digits, so parsing cannot fail
+    let number = |from: usize, to: usize| value[from..to].parse::<u8>().unwrap_or_default();
+    let offset = i16::from(number(11, 13)) * 60 + i16::from(number(13, 15));
+    let mut datetime = DateTime::new(
+        Date::from_yymmdd(&value[0..6]).map_err(|e| e.in_field("13D", value, 0))?,
+        number(6, 8),
+        number(8, 10),
+        0,
+    )
+    .map_err(|e| e.in_field("13D", value, 0))?;
+
+    datetime.offset = match &value[10..11] {
+        "+" => Some(offset),
+        "-" => Some(-offset),
+        sign => {
+            return Err(Error::invalid_field(
+                "13D",
+                value,
+                Reason::Character {
+                    index: 10,
+                    found: sign.chars().next().unwrap_or(' '),
+                    expected: "'+' or '-'",
+                },
+            ))
+        }
+    };
+
+    Ok(datetime)
+}
+
+/// `entries_summary` parses a number and sum of entries.
+fn entries_summary(tag: &str, value: &str) -> Result<EntriesSummary> {
+    format::check(tag, value, "5n3!a15d")?;
+
+    // NOTE: the format guarantees at most 5 digits then a currency
+    let end = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+
+    Ok(EntriesSummary {
+        count: value[..end].parse().unwrap_or_default(),
+        amount: parse_currency_amount(tag, &value[end..])
+            .map_err(|e| e.in_field(tag, value, end))?,
+    })
+}
+
+/// `credit_debit_mark` returns the error for a missing credit or debit mark
+/// at the char `index` of a field.
+fn credit_debit_mark(tag: &str, value: &str, index: usize) -> Error {
+    Error::invalid_field(
+        tag,
+        value,
+        Reason::Character {
+            index,
+            found: value[index..].chars().next().unwrap_or(' '),
+            expected: "'C' or 'D'",
+        },
+    )
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{GermanDecoder, InformationDecoder, Statement};
+    #[allow(unused_imports)] // TODO
+    use crate::camt::CreditDebit;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[test]
+    fn parse_mt940() -> Result<()> {
+        let statement = Statement::parse(
+            "{1:F01BANKDEFFAXXX0000000000}{2:O9401600240102BANKDEFFAXXX00000000002401021600N}{4:\r\n\
+             :20:STARTUMS\r\n\
+             :25:DE89370400440532013000\r\n\
+             :28C:1/1\r\n\
+             :60F:C231229EUR1000,00\r\n\
+             :61:2401020102DR150,00NDDTNONREF//B2401020001\r\n\
+             SEPA DIRECT DEBIT\r\n\
+             :86:105?00SEPA-BASISLASTSCHRIFT?20EREF+INV-2024-001?21SVWZ+Stro\r\n\
+             m Januar?30COBADEFFXXX?31DE02200400600123456789\r\n\
+             ?32STADTWERKE?33 BEISPIEL\r\n\
+             :61:2401021229CR25,50NTRFREF-1\r\n\
+             :61:240102RC10,00NTRFREF-2\r\n\
+             :62F:C240102EUR865,50\r\n\
+             :64:C240102EUR865,50\r\n\
+             :65:C240103EUR865,50\r\n\
+             :86:END OF STATEMENT\r\n\
+             -}",
+        )?;
+
+        assert_eq!(statement.transaction_reference, "STARTUMS");
+        assert_eq!(
+            statement.iban().map(|i| i.to_string()),
+            Some("DE89370400440532013000".into())
+        );
+        assert_eq!(statement.sequence_number.as_deref(), Some("1"));
+        assert_eq!(
+            statement
+                .opening_balance
+                .as_ref()
+                .map(|b| b.signed_amount()),
+            Some(100_000)
+        );
+        assert_eq!(statement.lines.len(), 3);
+        assert_eq!(statement.information.as_deref(), Some("END OF STATEMENT"));
+
+        let line = &statement.lines[0];
+        assert_eq!(line.credit_debit, CreditDebit::Debit);
+        assert_eq!(line.funds_code, Some('R'));
+        assert_eq!(line.signed_amount(), -15_000);
+        assert_eq!(line.transaction_type, "NDDT");
+        assert_eq!(line.customer_reference, "NONREF");
+        assert_eq!(line.bank_reference.as_deref(), Some("B2401020001"));
+        assert_eq!(
+            line.supplementary_details.as_deref(),
+            Some("SEPA DIRECT DEBIT")
+        );
+
+        let information = line.decode_information(&GermanDecoder).unwrap();
+        assert_eq!(information.transaction_code.as_deref(), Some("105"));
+        assert_eq!(
+            information.posting_text.as_deref(),
+            Some("SEPA-BASISLASTSCHRIFT")
+        );
+        assert_eq!(
+            information.remittance_information,
+            ["EREF+INV-2024-001", "SVWZ+Strom Januar"]
+        );
+        assert_eq!(
+            information.counterparty_bank.as_deref(),
+            Some("COBADEFFXXX")
+        );
+        assert_eq!(
+            information.counterparty_name.as_deref(),
+            Some("STADTWERKE BEISPIEL")
+        );
+
+        let information = GermanDecoder
+            .decode("166§00GUTSCHRIFT§20RECHNUNG 42")
+            .unwrap();
+        assert_eq!(information.transaction_code.as_deref(), Some("166"));
+        assert_eq!(information.posting_text.as_deref(), Some("GUTSCHRIFT"));
+        assert_eq!(information.remittance_information, ["RECHNUNG 42"]);
+        assert!(GermanDecoder.decode("1é6?00GUTSCHRIFT").is_none());
+        assert!(GermanDecoder.decode("166").is_none());
+
+        assert_eq!(
+            statement.lines[1].entry_date.map(|d| d.to_string()),
+            Some("2023-12-29".into())
+        );
+        assert_eq!(statement.lines[1].signed_amount(), 2_550);
+        assert!(statement.lines[2].reversal);
+        assert_eq!(statement.lines[2].signed_amount(), -1_000);
+
+        assert!(Statement::parse(
+            "{1:F01BANKDEFFAXXX0000000000}{2:I940BANKDEFFXXXXN}{4:\r\n:20:X\r\n-}"
+        )
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_mt942_file() -> Result<()> {
+        let statements = Statement::parse_file(
+            ":20:INTRADAY1\n:25:12345678/0532013000\n:28C:5\n:34F:EURD100,\n:34F:EURC200,\n\
+             :13D:2401151030+0100\n:61:240115C250,00NTRFNONREF\n:86:PAYMENT\n\
+             :90D:0EUR0,\n:90C:1EUR250,\n-\n\
+             :20:INTRADAY2\n:25:12345678/0532013000\n:28C:6\n:34F:EUR0,\n-\n",
+            "942",
+        )?;
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0].floor_limits.len(), 2);
+        assert_eq!(
+            statements[0].created.map(|d| d.to_string()),
+            Some("2024-01-15T10:30:00+01:00".into())
+        );
+        assert_eq!(
+            statements[0].lines[0].information.as_deref(),
+            Some("PAYMENT")
+        );
+        assert_eq!(
+            statements[0].credit_entries.as_ref().map(|e| e.count),
+            Some(1)
+        );
+        assert!(statements[0].iban().is_none());
+        assert_eq!(statements[1].transaction_reference, "INTRADAY2");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,909 @@
+//! pacs.008 FI to FI customer credit transfer, as used by CBPR+ for
+//! cross-border payments replacing the MT103. CBPR+ restricts the message
+//! to a single transaction, with the settlement information in the group
+//! header.
+
+use crate::camt::Account;
+use crate::date::{Date, DateTime};
+use crate::error::{Error, Reason};
+use crate::iso11649::CreditorReference;
+use crate::iso20022::{FromDocument, MessageIdentifier, PostalAddress, ToDocument};
+use crate::iso3166::CountryCode;
+use crate::iso9362::BIC;
+use crate::money::Money;
+use crate::result::Result;
+use crate::swift::Uetr;
+use crate::xml::Element;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// `NAMESPACE` is the XML namespace of the pacs.008 version used by CBPR+.
+pub const NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:pacs.008.001.08";
+
+/// `ROOT` is the name of the pacs.008 message element inside the `Document`.
+pub const ROOT: &str = "FIToFICstmrCdtTrf";
+
+/// `NOT_PROVIDED` is the end to end id used when the debtor gave none.
+pub const NOT_PROVIDED: &str = "NOTPROVIDED";
+
+/// `TRANSACTION_ELEMENTS` are the elements of `CdtTrfTxInf` read by
+/// [`Pacs008`], with the paths of the elements read below some of them.
+const TRANSACTION_ELEMENTS: &[(&str, &[&str])] = &[
+    ("PmtId", &["InstrId", "EndToEndId", "UETR"]),
+    ("PmtTpInf", &["SvcLvl", "CtgyPurp"]),
+    ("IntrBkSttlmAmt", &[]),
+    ("IntrBkSttlmDt", &[]),
+    ("SttlmTmIndctn", &[]),
+    ("SttlmTmReq", &[]),
+    ("InstdAmt", &[]),
+    ("XchgRate", &[]),
+    ("ChrgBr", &[]),
+    ("ChrgsInf", &[]),
+    ("PrvsInstgAgt1", &[]),
+    ("InstgAgt", &[]),
+    ("InstdAgt", &[]),
+    ("IntrmyAgt1", &[]),
+    ("IntrmyAgt1Acct", &[]),
+    ("UltmtDbtr", &[]),
+    ("Dbtr", &[]),
+    ("DbtrAcct", &[]),
+    ("DbtrAgt", &[]),
+    ("DbtrAgtAcct", &[]),
+    ("CdtrAgt", &[]),
+    ("CdtrAgtAcct", &[]),
+    ("Cdtr", &[]),
+    ("CdtrAcct", &[]),
+    ("UltmtCdtr", &[]),
+    ("InstrForCdtrAgt", &[]),
+    ("InstrForNxtAgt", &[]),
+    ("Purp", &[]),
+    ("RgltryRptg", &[]),
+    ("RmtInf", &["Ustrd", "Strd"]),
+];
+
+/// `SETTLEMENT_ELEMENTS` are the elements of `GrpHdr/SttlmInf` read by
+/// [`Pacs008`].
+const SETTLEMENT_ELEMENTS: &[&str] = &[
+    "SttlmMtd",
+    "SttlmAcct",
+    "InstgRmbrsmntAgt",
+    "InstgRmbrsmntAgtAcct",
+    "InstdRmbrsmntAgt",
+    "InstdRmbrsmntAgtAcct",
+    "ThrdRmbrsmntAgt",
+    "ThrdRmbrsmntAgtAcct",
+];
+
+/// `unmodelled` returns the path and the text of the children of `element`
+/// that are not `known`, the path starting with `prefix`.
+fn unmodelled<'a>(
+    element: &'a Element,
+    prefix: &'a str,
+    known: &'a [&str],
+) -> impl Iterator<Item = (String, String)> + 'a {
+    element
+        .children
+        .iter()
+        .filter(move |child| !known.contains(&child.name.as_str()))
+        .map(move |child| (format!("{}{}", prefix, child.name), texts(child)))
+}
+
+/// `texts` returns the texts of an element and its descendants.
+fn texts(element: &Element) -> String {
+    if element.children.is_empty() {
+        return element.text.clone();
+    }
+
+    element
+        .children
+        .iter()
+        .map(texts)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// [`ChargeBearer`] tells which party bears the charges (`ChrgBr`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChargeBearer {
+    Debtor,
+    Creditor,
+    Shared,
+}
+
+impl ChargeBearer {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ChargeBearer::Debtor => "DEBT",
+            ChargeBearer::Creditor => "CRED",
+            ChargeBearer::Shared => "SHAR",
+        }
+    }
+}
+
+impl FromStr for ChargeBearer {
+    type Err = Error;
+
+    fn from_str(code: &str) -> StdResult<Self, Self::Err> {
+        match code {
+            "DEBT" => Ok(ChargeBearer::Debtor),
+            "CRED" => Ok(ChargeBearer::Creditor),
+            "SHAR" => Ok(ChargeBearer::Shared),
+            _ => Err(Error::invalid("charge bearer", code, Reason::Unknown)),
+        }
+    }
+}
+
+/// [`PartyIdentification`] is a debtor, a creditor or an ultimate party.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PartyIdentification {
+    pub name: Option<String>,
+    pub address: Option<PostalAddress>,
+    /// `bic` is the `AnyBIC` organisation identification.
+    pub bic: Option<BIC>,
+    /// `other_id` is any other organisation or private identification.
+    pub other_id: Option<String>,
+    /// `private` is set when `other_id` identifies a person (`PrvtId`)
+    /// rather than an organisation (`OrgId`).
+    pub private: bool,
+}
+
+impl PartyIdentification {
+    pub(crate) fn from_element(element: &Element) -> Result<PartyIdentification> {
+        Ok(PartyIdentification {
+            name: element.find_text("Nm").map(String::from),
+            address: element
+                .find("PstlAdr")
+                .map(PostalAddress::from_element)
+                .transpose()?,
+            bic: element.find_parsed("Id/OrgId/AnyBIC")?,
+            other_id: element
+                .find_text("Id/OrgId/Othr/Id")
+                .or_else(|| element.find_text("Id/PrvtId/Othr/Id"))
+                .map(String::from),
+            private: element.find("Id/PrvtId").is_some(),
+        })
+    }
+
+    pub(crate) fn to_element(&self, name: &str) -> Element {
+        let mut res = Element::new(name).with_optional_leaf("Nm", self.name.as_ref());
+
+        if let Some(ref address) = self.address {
+            res.push(address.to_element());
+        }
+
+        // NOTE: the identification is either an organisation or a person,
+        // and only an organisation has an AnyBIC.
+        match (self.private, self.other_id.as_ref()) {
+            (true, Some(other)) => res.push(Element::new("Id").with_child(
+                Element::new("PrvtId").with_child(Element::new("Othr").with_leaf("Id", other)),
+            )),
+            (_, other) if self.bic.is_some() || other.is_some() => {
+                let mut id = Element::new("OrgId").with_optional_leaf("AnyBIC", self.bic.as_ref());
+
+                if let Some(other) = other {
+                    id.push(Element::new("Othr").with_leaf("Id", other));
+                }
+
+                res.push(Element::new("Id").with_child(id));
+            }
+            _ => {}
+        }
+
+        res
+    }
+}
+
+/// [`FinancialInstitution`] is an agent of the payment chain, with the
+/// account it is serviced on, if any.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FinancialInstitution {
+    pub bic: Option<BIC>,
+    /// `clearing_member_id` is the member id in a clearing system, such as
+    /// a Fedwire routing number.
+    pub clearing_member_id: Option<String>,
+    pub name: Option<String>,
+    pub address: Option<PostalAddress>,
+    /// `account` is the `...AgtAcct` account of the agent.
+    pub account: Option<Account>,
+}
+
+impl FinancialInstitution {
+    /// `new` creates a new `FinancialInstitution` identified by its BIC.
+    pub fn new(bic: BIC) -> FinancialInstitution {
+        FinancialInstitution {
+            bic: Some(bic),
+            ..Default::default()
+        }
+    }
+
+    /// `from_element` reads the agent `name` of `parent`, with its
+    /// `{name}Acct` account.
+    pub(crate) fn from_element(
+        parent: &Element,
+        name: &str,
+    ) -> Result<Option<FinancialInstitution>> {
+        let element = match parent.find(&format!("{}/FinInstnId", name)) {
+            Some(element) => element,
+            None => return Ok(None),
+        };
+
+        Ok(Some(FinancialInstitution {
+            bic: element
+                .find_text("BICFI")
+                .or_else(|| element.find_text("BIC"))
+                .map(str::parse)
+                .transpose()?,
+            clearing_member_id: element.find_text("ClrSysMmbId/MmbId").map(String::from),
+            name: element.find_text("Nm").map(String::from),
+            address: element
+                .find("PstlAdr")
+                .map(PostalAddress::from_element)
+                .transpose()?,
+            account: parent
+                .child(&format!("{}Acct", name))
+                .map(Account::from_element)
+                .transpose()?,
+        }))
+    }
+
+    /// `to_elements` returns the agent `name` element, followed by its
+    /// account element if any.
+    pub(crate) fn to_elements(&self, name: &str) -> Vec<Element> {
+        let mut id = Element::new("FinInstnId").with_optional_leaf("BICFI", self.bic.as_ref());
+
+        if let Some(ref member) = self.clearing_member_id {
+            id.push(Element::new("ClrSysMmbId").with_leaf("MmbId", member));
+        }
+
+        id = id.with_optional_leaf("Nm", self.name.as_ref());
+
+        if let Some(ref address) = self.address {
+            id.push(address.to_element());
+        }
+
+        let mut res = vec![Element::new(name).with_child(id)];
+        res.extend(
+            self.account
+                .as_ref()
+                .map(|account| account.to_element(&format!("{}Acct", name))),
+        );
+
+        res
+    }
+}
+
+/// [`Charges`] are the charges taken by an agent (`ChrgsInf`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Charges {
+    pub amount: Money,
+    pub agent: Option<BIC>,
+}
+
+/// [`Instruction`] is an instruction for the creditor agent or the next
+/// agent, as a code, a free text or both.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Instruction {
+    pub code: Option<String>,
+    pub information: Option<String>,
+}
+
+impl Instruction {
+    fn from_element(element: &Element) -> Instruction {
+        Instruction {
+            code: element.find_text("Cd").map(String::from),
+            information: element.find_text("InstrInf").map(String::from),
+        }
+    }
+
+    fn to_element(&self, name: &str) -> Element {
+        Element::new(name)
+            .with_optional_leaf("Cd", self.code.as_ref())
+            .with_optional_leaf("InstrInf", self.information.as_ref())
+    }
+}
+
+/// [`SettlementTimes`] are the settlement time indication (`SttlmTmIndctn`)
+/// and request (`SttlmTmReq`) of the transaction. Requested times are ISO
+/// times with a UTC offset, e.g. "09:15:00+01:00".
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SettlementTimes {
+    /// `debit` is when the debtor agent was debited (`DbtDtTm`).
+    pub debit: Option<DateTime>,
+    /// `credit` is when the creditor agent was credited (`CdtDtTm`).
+    pub credit: Option<DateTime>,
+    /// `cls` is the time of the CLS settlement (`CLSTm`).
+    pub cls: Option<String>,
+    /// `till` is the time until which the transaction can be settled
+    /// (`TillTm`).
+    pub till: Option<String>,
+    /// `from` is the time from which the transaction can be settled
+    /// (`FrTm`).
+    pub from: Option<String>,
+    /// `reject` is the time after which the transaction is rejected
+    /// (`RjctTm`).
+    pub reject: Option<String>,
+}
+
+impl SettlementTimes {
+    /// `is_empty` returns if no settlement time is set.
+    pub fn is_empty(&self) -> bool {
+        *self == SettlementTimes::default()
+    }
+
+    fn from_element(tx: &Element) -> Result<SettlementTimes> {
+        let time = |path: &str| tx.find_text(path).map(String::from);
+
+        Ok(SettlementTimes {
+            debit: tx.find_parsed("SttlmTmIndctn/DbtDtTm")?,
+            credit: tx.find_parsed("SttlmTmIndctn/CdtDtTm")?,
+            cls: time("SttlmTmReq/CLSTm"),
+            till: time("SttlmTmReq/TillTm"),
+            from: time("SttlmTmReq/FrTm"),
+            reject: time("SttlmTmReq/RjctTm"),
+        })
+    }
+
+    fn to_elements(&self) -> Vec<Element> {
+        let mut res = vec![];
+
+        if self.debit.is_some() || self.credit.is_some() {
+            res.push(
+                Element::new("SttlmTmIndctn")
+                    .with_optional_leaf("DbtDtTm", self.debit.as_ref())
+                    .with_optional_leaf("CdtDtTm", self.credit.as_ref()),
+            );
+        }
+
+        if self.cls.is_some() || self.till.is_some() || self.from.is_some() || self.reject.is_some()
+        {
+            res.push(
+                Element::new("SttlmTmReq")
+                    .with_optional_leaf("CLSTm", self.cls.as_ref())
+                    .with_optional_leaf("TillTm", self.till.as_ref())
+                    .with_optional_leaf("FrTm", self.from.as_ref())
+                    .with_optional_leaf("RjctTm", self.reject.as_ref()),
+            );
+        }
+
+        res
+    }
+}
+
+/// [`RegulatoryReporting`] is a regulatory reporting of the transaction
+/// (`RgltryRptg`), with its details as free text.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RegulatoryReporting {
+    /// `indicator` is the side the reporting applies to, "DEBT", "CRED" or
+    /// "BOTH" (`DbtCdtRptgInd`).
+    pub indicator: Option<String>,
+    /// `country` is the country of the authority (`Authrty/Ctry`).
+    pub country: Option<CountryCode>,
+    /// `information` are the lines of the details (`Dtls/Inf`).
+    pub information: Vec<String>,
+}
+
+impl RegulatoryReporting {
+    fn from_element(element: &Element) -> Result<RegulatoryReporting> {
+        Ok(RegulatoryReporting {
+            indicator: element.find_text("DbtCdtRptgInd").map(String::from),
+            country: element.find_parsed("Authrty/Ctry")?,
+            information: element
+                .find_all("Dtls/Inf")
+                .iter()
+                .map(|line| line.text().into())
+                .collect(),
+        })
+    }
+
+    fn to_element(&self) -> Element {
+        let mut res =
+            Element::new("RgltryRptg").with_optional_leaf("DbtCdtRptgInd", self.indicator.as_ref());
+
+        if let Some(ref country) = self.country {
+            res.push(Element::new("Authrty").with_leaf("Ctry", country.as_str()));
+        }
+
+        if !self.information.is_empty() {
+            let mut details = Element::new("Dtls");
+
+            for line in self.information.iter() {
+                details.push(Element::leaf("Inf", line));
+            }
+
+            res.push(details);
+        }
+
+        res
+    }
+}
+
+/// [`Pacs008`] is a CBPR+ pacs.008 FI to FI customer credit transfer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pacs008 {
+    pub message_id: String,
+    pub created: DateTime,
+    /// `settlement_method` is "INDA", "INGA", "COVE" or "CLRG".
+    pub settlement_method: String,
+    pub settlement_account: Option<Account>,
+    pub instructing_reimbursement_agent: Option<FinancialInstitution>,
+    pub instructed_reimbursement_agent: Option<FinancialInstitution>,
+    pub third_reimbursement_agent: Option<FinancialInstitution>,
+    pub instruction_id: Option<String>,
+    pub end_to_end_id: String,
+    pub uetr: Option<Uetr>,
+    pub service_level: Option<String>,
+    pub category_purpose: Option<String>,
+    pub settlement_amount: Money,
+    pub settlement_date: Date,
+    pub settlement_times: SettlementTimes,
+    pub instructed_amount: Option<Money>,
+    /// `exchange_rate` is the decimal exchange rate, with a '.' separator.
+    pub exchange_rate: Option<String>,
+    pub charge_bearer: ChargeBearer,
+    pub charges: Vec<Charges>,
+    pub previous_instructing_agent: Option<BIC>,
+    pub instructing_agent: BIC,
+    pub instructed_agent: BIC,
+    pub intermediary_agent: Option<FinancialInstitution>,
+    pub ultimate_debtor: Option<PartyIdentification>,
+    pub debtor: PartyIdentification,
+    pub debtor_account: Option<Account>,
+    pub debtor_agent: FinancialInstitution,
+    pub creditor_agent: FinancialInstitution,
+    pub creditor: PartyIdentification,
+    pub creditor_account: Option<Account>,
+    pub ultimate_creditor: Option<PartyIdentification>,
+    pub instructions_for_creditor_agent: Vec<Instruction>,
+    pub instructions_for_next_agent: Vec<Instruction>,
+    pub purpose: Option<String>,
+    pub regulatory_reporting: Vec<RegulatoryReporting>,
+    /// `remittance_information` are the unstructured remittance lines.
+    pub remittance_information: Vec<String>,
+    /// `creditor_references` are the references of the structured
+    /// remittance information.
+    pub creditor_references: Vec<String>,
+    /// `unmodelled` are the path and the text of the elements of the
+    /// message that are not read, e.g. "IntrmyAgt2". They are not written
+    /// back.
+    pub unmodelled: Vec<(String, String)>,
+}
+
+impl Pacs008 {
+    /// `parse` parses a pacs.008 XML document.
+    pub fn parse(xml: &str) -> Result<Pacs008> {
+        Pacs008::from_element(&Element::parse(xml)?)
+    }
+
+    /// `from_element` reads a pacs.008 from its `Document` element. Only
+    /// single transaction messages are supported, as in CBPR+.
+    pub fn from_element(document: &Element) -> Result<Pacs008> {
+        let message = document
+            .child(ROOT)
+            .ok_or_else(|| Error::UnknownMessage(document.name.clone()))?;
+        let header = message.require("GrpHdr")?;
+        let settlement = header.require("SttlmInf")?;
+        let tx = match message.find_all("CdtTrfTxInf").as_slice() {
+            [tx] => *tx,
+            [] => return Err(Error::MissingElement("CdtTrfTxInf".into())),
+            _ => {
+                return Err(Error::invalid_format(
+                    "pacs.008",
+                    None,
+                    Reason::Format("several transactions are not supported".into()),
+                ))
+            }
+        };
+        let agent_bic = |name: &str| -> Result<BIC> {
+            FinancialInstitution::from_element(tx, name)?
+                .and_then(|agent| agent.bic)
+                .ok_or_else(|| Error::MissingElement(format!("{}/FinInstnId/BICFI", name)))
+        };
+        let account = |name: &str| tx.child(name).map(Account::from_element).transpose();
+        let party = |name: &str| {
+            tx.child(name)
+                .map(PartyIdentification::from_element)
+                .transpose()
+        };
+
+        let known: Vec<&str> = TRANSACTION_ELEMENTS.iter().map(|(name, _)| *name).collect();
+        let mut unmodelled_elements: Vec<(String, String)> =
+            unmodelled(settlement, "GrpHdr/SttlmInf/", SETTLEMENT_ELEMENTS)
+                .chain(unmodelled(tx, "", &known))
+                .collect();
+
+        for (name, known) in TRANSACTION_ELEMENTS.iter().filter(|(_, k)| !k.is_empty()) {
+            let prefix = format!("{}/", name);
+
+            for element in tx.children(name) {
+                unmodelled_elements.extend(unmodelled(element, &prefix, known));
+            }
+        }
+
+        for structured in tx.find_all("RmtInf/Strd") {
+            unmodelled_elements.extend(unmodelled(structured, "RmtInf/Strd/", &["CdtrRefInf"]));
+        }
+
+        Ok(Pacs008 {
+            message_id: header.require_text("MsgId")?.into(),
+            created: header.require_parsed("CreDtTm")?,
+            settlement_method: settlement.require_text("SttlmMtd")?.into(),
+            settlement_account: settlement
+                .child("SttlmAcct")
+                .map(Account::from_element)
+                .transpose()?,
+            instructing_reimbursement_agent: FinancialInstitution::from_element(
+                settlement,
+                "InstgRmbrsmntAgt",
+            )?,
+            instructed_reimbursement_agent: FinancialInstitution::from_element(
+                settlement,
+                "InstdRmbrsmntAgt",
+            )?,
+            third_reimbursement_agent: FinancialInstitution::from_element(
+                settlement,
+                "ThrdRmbrsmntAgt",
+            )?,
+            instruction_id: tx.find_text("PmtId/InstrId").map(String::from),
+            end_to_end_id: tx.require_text("PmtId/EndToEndId")?.into(),
+            uetr: tx.find_parsed("PmtId/UETR")?,
+            service_level: tx.find_text("PmtTpInf/SvcLvl/Cd").map(String::from),
+            category_purpose: tx.find_text("PmtTpInf/CtgyPurp/Cd").map(String::from),
+            settlement_amount: tx.require_money("IntrBkSttlmAmt")?,
+            settlement_date: tx.require_parsed("IntrBkSttlmDt")?,
+            settlement_times: SettlementTimes::from_element(tx)?,
+            instructed_amount: tx.find_money("InstdAmt")?,
+            exchange_rate: tx.find_text("XchgRate").map(String::from),
+            charge_bearer: tx.require_parsed("ChrgBr")?,
+            charges: tx
+                .find_all("ChrgsInf")
+                .iter()
+                .map(|charges| {
+                    Ok(Charges {
+                        amount: charges.require_money("Amt")?,
+                        agent: charges.find_parsed("Agt/FinInstnId/BICFI")?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            previous_instructing_agent: tx.find_parsed("PrvsInstgAgt1/FinInstnId/BICFI")?,
+            instructing_agent: agent_bic("InstgAgt")?,
+            instructed_agent: agent_bic("InstdAgt")?,
+            intermediary_agent: FinancialInstitution::from_element(tx, "IntrmyAgt1")?,
+            ultimate_debtor: party("UltmtDbtr")?,
+            debtor: party("Dbtr")?.ok_or_else(|| Error::MissingElement("Dbtr".into()))?,
+            debtor_account: account("DbtrAcct")?,
+            debtor_agent: FinancialInstitution::from_element(tx, "DbtrAgt")?
+                .ok_or_else(|| Error::MissingElement("DbtrAgt".into()))?,
+            creditor_agent: FinancialInstitution::from_element(tx, "CdtrAgt")?
+                .ok_or_else(|| Error::MissingElement("CdtrAgt".into()))?,
+            creditor: party("Cdtr")?.ok_or_else(|| Error::MissingElement("Cdtr".into()))?,
+            creditor_account: account("CdtrAcct")?,
+            ultimate_creditor: party("UltmtCdtr")?,
+            instructions_for_creditor_agent: tx
+                .find_all("InstrForCdtrAgt")
+                .into_iter()
+                .map(Instruction::from_element)
+                .collect(),
+            instructions_for_next_agent: tx
+                .find_all("InstrForNxtAgt")
+                .into_iter()
+                .map(Instruction::from_element)
+                .collect(),
+            purpose: tx.find_text("Purp/Cd").map(String::from),
+            regulatory_reporting: tx
+                .find_all("RgltryRptg")
+                .into_iter()
+                .map(RegulatoryReporting::from_element)
+                .collect::<Result<_>>()?,
+            remittance_information: tx
+                .find_all("RmtInf/Ustrd")
+                .iter()
+                .map(|line| line.text().into())
+                .collect(),
+            creditor_references: tx
+                .find_all("RmtInf/Strd/CdtrRefInf/Ref")
+                .iter()
+                .map(|reference| reference.text().into())
+                .collect(),
+            unmodelled: unmodelled_elements,
+        })
+    }
+
+    /// `to_document` builds the `Document` element of the message.
+    pub fn to_document(&self) -> Element {
+        let bic_agent = |name: &str, bic: &BIC| {
+            Element::new(name)
+                .with_child(Element::new("FinInstnId").with_leaf("BICFI", &bic.to_string()))
+        };
+
+        let mut settlement =
+            Element::new("SttlmInf").with_leaf("SttlmMtd", &self.settlement_method);
+
+        if let Some(ref account) = self.settlement_account {
+            settlement.push(account.to_element("SttlmAcct"));
+        }
+
+        for (name, agent) in [
+            ("InstgRmbrsmntAgt", &self.instructing_reimbursement_agent),
+            ("InstdRmbrsmntAgt", &self.instructed_reimbursement_agent),
+            ("ThrdRmbrsmntAgt", &self.third_reimbursement_agent),
+        ] {
+            if let Some(agent) = agent {
+                settlement.children.extend(agent.to_elements(name));
+            }
+        }
+
+        let header = Element::new("GrpHdr")
+            .with_leaf("MsgId", &self.message_id)
+            .with_leaf("CreDtTm", &self.created.to_string())
+            .with_leaf("NbOfTxs", "1")
+            .with_child(settlement);
+
+        let mut tx = Element::new("CdtTrfTxInf").with_child(
+            Element::new("PmtId")
+                .with_optional_leaf("InstrId", self.instruction_id.as_ref())
+                .with_leaf("EndToEndId", &self.end_to_end_id)
+                .with_optional_leaf("UETR", self.uetr.as_ref()),
+        );
+
+        if self.service_level.is_some() || self.category_purpose.is_some() {
+            let mut payment_type = Element::new("PmtTpInf");
+
+            if let Some(ref level) = self.service_level {
+                payment_type.push(Element::new("SvcLvl").with_leaf("Cd", level));
+            }
+
+            if let Some(ref purpose) = self.category_purpose {
+                payment_type.push(Element::new("CtgyPurp").with_leaf("Cd", purpose));
+            }
+
+            tx.push(payment_type);
+        }
+
+        tx.push(Element::money("IntrBkSttlmAmt", &self.settlement_amount));
+        tx.push(Element::leaf(
+            "IntrBkSttlmDt",
+            &self.settlement_date.to_string(),
+        ));
+        tx.children.extend(self.settlement_times.to_elements());
+
+        if let Some(ref amount) = self.instructed_amount {
+            tx.push(Element::money("InstdAmt", amount));
+        }
+
+        tx = tx
+            .with_optional_leaf("XchgRate", self.exchange_rate.as_ref())
+            .with_leaf("ChrgBr", self.charge_bearer.as_str());
+
+        for charges in self.charges.iter() {
+            let mut element =
+                Element::new("ChrgsInf").with_child(Element::money("Amt", &charges.amount));
+
+            if let Some(ref agent) = charges.agent {
+                element.push(bic_agent("Agt", agent));
+            }
+
+            tx.push(element);
+        }
+
+        if let Some(ref agent) = self.previous_instructing_agent {
+            tx.push(bic_agent("PrvsInstgAgt1", agent));
+        }
+
+        tx.push(bic_agent("InstgAgt", &self.instructing_agent));
+        tx.push(bic_agent("InstdAgt", &self.instructed_agent));
+
+        if let Some(ref agent) = self.intermediary_agent {
+            tx.children.extend(agent.to_elements("IntrmyAgt1"));
+        }
+
+        if let Some(ref party) = self.ultimate_debtor {
+            tx.push(party.to_element("UltmtDbtr"));
+        }
+
+        tx.push(self.debtor.to_element("Dbtr"));
+        tx.children.extend(
+            self.debtor_account
+                .as_ref()
+                .map(|account| account.to_element("DbtrAcct")),
+        );
+        tx.children.extend(self.debtor_agent.to_elements("DbtrAgt"));
+        tx.children
+            .extend(self.creditor_agent.to_elements("CdtrAgt"));
+        tx.push(self.creditor.to_element("Cdtr"));
+        tx.children.extend(
+            self.creditor_account
+                .as_ref()
+                .map(|account| account.to_element("CdtrAcct")),
+        );
+
+        if let Some(ref party) = self.ultimate_creditor {
+            tx.push(party.to_element("UltmtCdtr"));
+        }
+
+        for instruction in self.instructions_for_creditor_agent.iter() {
+            tx.push(instruction.to_element("InstrForCdtrAgt"));
+        }
+
+        for instruction in self.instructions_for_next_agent.iter() {
+            tx.push(instruction.to_element("InstrForNxtAgt"));
+        }
+
+        if let Some(ref purpose) = self.purpose {
+            tx.push(Element::new("Purp").with_leaf("Cd", purpose));
+        }
+
+        for reporting in self.regulatory_reporting.iter() {
+            tx.push(reporting.to_element());
+        }
+
+        if !self.remittance_information.is_empty() || !self.creditor_references.is_empty() {
+            let mut remittance = Element::new("RmtInf");
+
+            for line in self.remittance_information.iter() {
+                remittance.push(Element::leaf("Ustrd", line));
+            }
+
+            for reference in self.creditor_references.iter() {
+                remittance.push(Element::new("Strd").with_child(
+                    match CreditorReference::new(reference) {
+                        Ok(reference) => reference.to_element(),
+                        Err(_) => Element::new("CdtrRefInf").with_leaf("Ref", reference),
+                    },
+                ));
+            }
+
+            tx.push(remittance);
+        }
+
+        self.message_identifier()
+            .document(Element::new(ROOT).with_child(header).with_child(tx))
+    }
+
+    /// `to_xml` builds the XML document of the message.
+    pub fn to_xml(&self) -> String {
+        self.to_document().to_document()
+    }
+}
+
+impl FromStr for Pacs008 {
+    type Err = Error;
+
+    fn from_str(xml: &str) -> StdResult<Self, Self::Err> {
+        Pacs008::parse(xml)
+    }
+}
+
+impl FromDocument for Pacs008 {
+    fn from_document(document: &Element) -> Result<Pacs008> {
+        Pacs008::from_element(document)
+    }
+}
+
+impl ToDocument for Pacs008 {
+    fn message_identifier(&self) -> MessageIdentifier {
+        MessageIdentifier::from_namespace(NAMESPACE).unwrap()
+    }
+
+    fn to_document(&self) -> Result<Element> {
+        Ok(Pacs008::to_document(self))
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{ChargeBearer, Pacs008};
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[allow(dead_code)]
+    const PACS_008: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:pacs.008.001.08">
+  <FIToFICstmrCdtTrf>
+    <GrpHdr>
+      <MsgId>MSG-20240115-1</MsgId>
+      <CreDtTm>2024-01-15T09:30:00</CreDtTm>
+      <NbOfTxs>1</NbOfTxs>
+      <SttlmInf><SttlmMtd>INDA</SttlmMtd></SttlmInf>
+    </GrpHdr>
+    <CdtTrfTxInf>
+      <PmtId>
+        <InstrId>INSTRUCTION-ID-2024-0001</InstrId>
+        <EndToEndId>E2E-0001</EndToEndId>
+        <UETR>eb6305c9-1f7f-49de-aed0-16487c27b42d</UETR>
+      </PmtId>
+      <IntrBkSttlmAmt Ccy="EUR">1234.56</IntrBkSttlmAmt>
+      <IntrBkSttlmDt>2024-01-15</IntrBkSttlmDt>
+      <ChrgBr>SHAR</ChrgBr>
+      <InstgAgt><FinInstnId><BICFI>BANKBEBBXXX</BICFI></FinInstnId></InstgAgt>
+      <InstdAgt><FinInstnId><BICFI>BANKDEFFXXX</BICFI></FinInstnId></InstdAgt>
+      <UltmtDbtr><Nm>Holding SA</Nm></UltmtDbtr>
+      <Dbtr>
+        <Nm>Société Générale des Transports Européens et Internationaux</Nm>
+        <PstlAdr>
+          <StrtNm>Rue de la Loi</StrtNm>
+          <BldgNb>1</BldgNb>
+          <PstCd>1000</PstCd>
+          <TwnNm>Bruxelles</TwnNm>
+          <Ctry>BE</Ctry>
+        </PstlAdr>
+      </Dbtr>
+      <DbtrAcct><Id><IBAN>BE71096123456769</IBAN></Id></DbtrAcct>
+      <DbtrAgt><FinInstnId><BICFI>BANKBEBBXXX</BICFI></FinInstnId></DbtrAgt>
+      <CdtrAgt><FinInstnId><BICFI>BANKDEFFXXX</BICFI></FinInstnId></CdtrAgt>
+      <Cdtr>
+        <Nm>Jane Doe</Nm>
+        <PstlAdr><AdrLine>Hauptstrasse 5</AdrLine><AdrLine>Frankfurt</AdrLine></PstlAdr>
+      </Cdtr>
+      <CdtrAcct><Id><IBAN>DE89370400440532013000</IBAN></Id></CdtrAcct>
+      <RmtInf>
+        <Ustrd>INVOICE 2024-001</Ustrd>
+        <Strd><CdtrRefInf><Ref>RF18539007547034</Ref></CdtrRefInf></Strd>
+      </RmtInf>
+    </CdtTrfTxInf>
+  </FIToFICstmrCdtTrf>
+</Document>"#;
+
+    #[test]
+    fn parse_and_build() -> Result<()> {
+        let pacs = Pacs008::parse(PACS_008)?;
+
+        assert_eq!(pacs.message_id, "MSG-20240115-1");
+        assert_eq!(pacs.end_to_end_id, "E2E-0001");
+        assert_eq!(pacs.settlement_amount.to_string(), "1234.56 EUR");
+        assert_eq!(pacs.charge_bearer, ChargeBearer::Shared);
+        assert_eq!(pacs.instructed_agent.to_string(), "BANKDEFFXXX");
+        assert_eq!(
+            pacs.debtor.address.as_ref().map(|a| a.is_structured()),
+            Some(true)
+        );
+        assert_eq!(
+            pacs.creditor.address.as_ref().map(|a| a.is_structured()),
+            Some(false)
+        );
+        assert_eq!(
+            pacs.creditor_account
+                .as_ref()
+                .and_then(|a| a.iban.as_ref())
+                .map(|i| i.to_string()),
+            Some("DE89370400440532013000".into())
+        );
+        assert_eq!(pacs.creditor_references, vec!["RF18539007547034"]);
+
+        assert_eq!(Pacs008::parse(&pacs.to_xml())?, pacs);
+        assert!(pacs.unmodelled.is_empty());
+
+        let unmodelled = Pacs008::parse(
+            &PACS_008
+                .replace(
+                    "<EndToEndId>",
+                    "<TxId>TX-1</TxId>\n        <EndToEndId>",
+                )
+                .replace(
+                    "</CdtrAgt>",
+                    "</CdtrAgt>\n      <CdtrAgtAcct><Id><IBAN>DE89370400440532013000</IBAN></Id></CdtrAgtAcct>",
+                )
+                .replace(
+                    "<Strd>",
+                    "<Strd><RfrdDocInf><Nb>INV-1</Nb></RfrdDocInf></Strd>\n        <Strd>",
+                ),
+        )?
+        .unmodelled;
+        assert_eq!(
+            unmodelled,
+            vec![
+                ("PmtId/TxId".to_string(), "TX-1".to_string()),
+                ("RmtInf/Strd/RfrdDocInf".to_string(), "INV-1".to_string()),
+            ]
+        );
+
+        let mut pacs = pacs;
+        pacs.creditor.other_id = Some("NIDN/DE/123456789".into());
+        pacs.creditor.private = true;
+        assert_eq!(Pacs008::parse(&pacs.to_xml())?, pacs);
+        pacs.creditor.private = false;
+        assert!(!Pacs008::parse(&pacs.to_xml())?.creditor.private);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1557 @@
+//! Translation between the MT103 and the CBPR+ pacs.008, following the
+//! SWIFT CBPR+ translation rules.
+//!
+//! Texts going to MT are transliterated to the X character set, and texts
+//! too long for their MT field are cut with a '+' as last char, the CBPR+
+//! truncation indicator. Structured addresses map to option F of fields
+//! 50a and 59a, with numbered "1/" name, "2/" address and "3/" country and
+//! town lines. Whatever cannot be carried over is listed in a
+//! [`LossReport`] returned along the translated message.
+
+use crate::camt::Account;
+use crate::date::{Date, DateTime};
+use crate::error::Error;
+use crate::iso20022::PostalAddress;
+use crate::iso9362::BIC;
+use crate::money::Money;
+use crate::result::Result;
+use crate::swift::charset;
+use crate::swift::pacs008::*;
+use crate::swift::{
+    DetailsOfCharges, LogicalTerminal, Message, Party, Uetr, INFORMATION_INSTRUCTION_CODES, MT103,
+};
+use std::fmt;
+
+/// `CREDITOR_AGENT_CODES` are the 23E codes carried by `InstrForCdtrAgt`.
+const CREDITOR_AGENT_CODES: &[&str] = &["CHQB", "HOLD", "PHOB", "TELB"];
+
+/// `NEXT_AGENT_CODES` are the 23E codes carried by `InstrForNxtAgt`, with
+/// their ISO 20022 code.
+const NEXT_AGENT_CODES: &[(&str, &str)] = &[("PHON", "PHOA"), ("TELE", "TELA")];
+
+/// `CATEGORY_PURPOSE_CODES` are the 23E codes carried by `CtgyPurp`.
+const CATEGORY_PURPOSE_CODES: &[&str] = &["CORT", "INTC"];
+
+/// [`LossKind`] is the kind of alteration of a translated value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LossKind {
+    /// The value has no place in the target message.
+    Dropped,
+    /// The value was cut to fit its target field.
+    Truncated,
+    /// The value was rewritten to the X character set.
+    Transliterated,
+}
+
+/// [`Loss`] is a value that was not carried over as is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loss {
+    pub kind: LossKind,
+    /// `source` is the source field tag or element path, e.g. "50F" or
+    /// "Dbtr/Nm".
+    pub source: String,
+    /// `value` is the original value.
+    pub value: String,
+}
+
+impl fmt::Display for Loss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            LossKind::Dropped => "dropped",
+            LossKind::Truncated => "truncated",
+            LossKind::Transliterated => "transliterated",
+        };
+
+        write!(f, "{} {}: {:?}", self.source, kind, self.value)
+    }
+}
+
+/// [`LossReport`] lists the values altered by a translation.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LossReport {
+    pub losses: Vec<Loss>,
+}
+
+impl LossReport {
+    /// `is_empty` returns if the translation was lossless.
+    pub fn is_empty(&self) -> bool {
+        self.losses.is_empty()
+    }
+
+    /// `push` records a loss.
+    pub fn push(&mut self, kind: LossKind, source: &str, value: &str) {
+        self.losses.push(Loss {
+            kind,
+            source: source.into(),
+            value: value.into(),
+        });
+    }
+
+    /// `find` returns the losses of a source field or element.
+    pub fn find<'a>(&'a self, source: &'a str) -> impl Iterator<Item = &'a Loss> + 'a {
+        self.losses.iter().filter(move |loss| loss.source == source)
+    }
+
+    /// `text` transliterates a text to the X character set.
+    fn text(&mut self, source: &str, text: &str) -> String {
+        let res = charset::transliterate(text);
+
+        if res != text {
+            self.push(LossKind::Transliterated, source, text);
+        }
+
+        res
+    }
+
+    /// `truncate` cuts a text to `length` chars, ending with the '+'
+    /// truncation indicator when it is too long.
+    fn truncate(&mut self, source: &str, text: &str, length: usize) -> String {
+        if text.chars().count() <= length {
+            return text.into();
+        }
+
+        self.push(LossKind::Truncated, source, text);
+        text.chars().take(length - 1).chain(Some('+')).collect()
+    }
+
+    /// `wrap` splits a text into at most `lines` lines of `length` chars,
+    /// the last one ending with the truncation indicator when it is too long.
+    fn wrap(&mut self, source: &str, text: &str, lines: usize, length: usize) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut res: Vec<String> = chars
+            .chunks(length)
+            .map(|chunk| chunk.iter().collect())
+            .collect();
+
+        if res.len() > lines {
+            self.push(LossKind::Truncated, source, text);
+            res.truncate(lines);
+            if let Some(last) = res.last_mut() {
+                last.pop();
+                last.push('+');
+            }
+        }
+
+        res
+    }
+}
+
+impl fmt::Display for LossReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for loss in self.losses.iter() {
+            writeln!(f, "{}", loss)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// [`Translation`] is a translated message with its loss report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translation<T> {
+    pub message: T,
+    pub losses: LossReport,
+}
+
+/// `account` parses an MT account as an IBAN, or as another account id.
+fn account(id: Option<&str>) -> Option<Account> {
+    id.map(|id| match id.parse() {
+        Ok(iban) => Account {
+            iban: Some(iban),
+            ..Default::default()
+        },
+        Err(_) => Account {
+            other: Some(id.into()),
+            ..Default::default()
+        },
+    })
+}
+
+/// `account_id` returns the IBAN or other id of an account.
+fn account_id(account: Option<&Account>) -> Option<String> {
+    account.and_then(|account| match account.iban {
+        Some(ref iban) => Some(iban.to_string()),
+        None => account.other.clone(),
+    })
+}
+
+/// `mt103_to_pacs008` translates an MT103 FIN message to a pacs.008
+/// created at `created`. The sender and receiver of the message become the
+/// instructing and instructed agents, and its UETR is carried over or
+/// generated when missing.
+pub fn mt103_to_pacs008(message: &Message, created: DateTime) -> Result<Translation<Pacs008>> {
+    let mt = MT103::from_message(message)?;
+    let mut losses = LossReport::default();
+    let sender = message.sender().clone();
+    let receiver = message
+        .receiver()
+        .cloned()
+        .ok_or_else(|| Error::MissingElement("receiver".into()))?;
+
+    if mt.bank_operation_code != "CRED" {
+        losses.push(LossKind::Dropped, "23B", &mt.bank_operation_code);
+    }
+
+    let mut pacs = Pacs008 {
+        message_id: mt.senders_reference.clone(),
+        created,
+        settlement_method: "INDA".into(),
+        settlement_account: None,
+        instructing_reimbursement_agent: None,
+        instructed_reimbursement_agent: None,
+        third_reimbursement_agent: None,
+        instruction_id: Some(mt.senders_reference.clone()),
+        end_to_end_id: NOT_PROVIDED.into(),
+        uetr: Some(match message.uetr() {
+            Some(uetr) => uetr,
+            None => Uetr::generate()?,
+        }),
+        service_level: None,
+        category_purpose: None,
+        settlement_amount: mt.amount.clone(),
+        settlement_date: mt.value_date,
+        settlement_times: SettlementTimes::default(),
+        instructed_amount: mt.instructed_amount.clone(),
+        exchange_rate: mt
+            .exchange_rate
+            .as_ref()
+            .map(|rate| rate.trim_end_matches(',').replace(',', ".")),
+        charge_bearer: match mt.details_of_charges {
+            DetailsOfCharges::Ours => ChargeBearer::Debtor,
+            DetailsOfCharges::Shared => ChargeBearer::Shared,
+            DetailsOfCharges::Beneficiary => ChargeBearer::Creditor,
+        },
+        charges: mt
+            .senders_charges
+            .iter()
+            .map(|amount| Charges {
+                amount: amount.clone(),
+                agent: Some(sender.clone()),
+            })
+            .chain(mt.receivers_charges.iter().map(|amount| Charges {
+                amount: amount.clone(),
+                agent: Some(receiver.clone()),
+            }))
+            .collect(),
+        previous_instructing_agent: None,
+        instructing_agent: sender.clone(),
+        instructed_agent: receiver.clone(),
+        intermediary_agent: mt
+            .intermediary_institution
+            .as_ref()
+            .map(|party| party_to_agent(&mut losses, "56", party)),
+        ultimate_debtor: None,
+        debtor: PartyIdentification::default(),
+        debtor_account: None,
+        debtor_agent: match mt.ordering_institution {
+            Some(ref party) => party_to_agent(&mut losses, "52", party),
+            None => FinancialInstitution::new(sender),
+        },
+        creditor_agent: match mt.account_with_institution {
+            Some(ref party) => party_to_agent(&mut losses, "57", party),
+            None => FinancialInstitution::new(receiver),
+        },
+        creditor: PartyIdentification::default(),
+        creditor_account: None,
+        ultimate_creditor: None,
+        instructions_for_creditor_agent: vec![],
+        instructions_for_next_agent: vec![],
+        purpose: None,
+        regulatory_reporting: vec![],
+        remittance_information: vec![],
+        creditor_references: vec![],
+        unmodelled: vec![],
+    };
+
+    for indication in mt.time_indications.iter() {
+        if !set_settlement_time(&mut pacs.settlement_times, mt.value_date, indication) {
+            losses.push(LossKind::Dropped, "13C", indication);
+        }
+    }
+
+    if let Some(ref code) = mt.transaction_type_code {
+        losses.push(LossKind::Dropped, "26T", code);
+    }
+
+    if !mt.regulatory_reporting.is_empty() {
+        pacs.regulatory_reporting
+            .push(regulatory_reporting(&mt.regulatory_reporting));
+    }
+
+    if let Some(ref contents) = mt.envelope_contents {
+        losses.push(LossKind::Dropped, "77T", contents);
+    }
+
+    let (debtor, debtor_account) = party_to_customer(&mut losses, "50", &mt.ordering_customer);
+    pacs.debtor = debtor;
+    pacs.debtor_account = debtor_account;
+
+    let (creditor, creditor_account) =
+        party_to_customer(&mut losses, "59", &mt.beneficiary_customer);
+    pacs.creditor = creditor;
+    pacs.creditor_account = creditor_account;
+
+    // NOTE: an account only 53B is the account of the sender at the
+    // receiver, any other correspondent is a reimbursement agent.
+    match mt.senders_correspondent {
+        Some(Party::B {
+            ref party_identifier,
+            location: None,
+        }) => pacs.settlement_account = account(party_identifier.as_deref()),
+        Some(ref party) => {
+            pacs.instructing_reimbursement_agent = Some(party_to_agent(&mut losses, "53", party))
+        }
+        None => {}
+    }
+
+    pacs.instructed_reimbursement_agent = mt
+        .receivers_correspondent
+        .as_ref()
+        .map(|party| party_to_agent(&mut losses, "54", party));
+    pacs.third_reimbursement_agent = mt
+        .third_reimbursement_institution
+        .as_ref()
+        .map(|party| party_to_agent(&mut losses, "55", party));
+
+    if pacs.instructing_reimbursement_agent.is_some()
+        || pacs.instructed_reimbursement_agent.is_some()
+    {
+        pacs.settlement_method = "COVE".into();
+    }
+
+    for code in mt.instruction_codes.iter() {
+        let (name, information) = match code.split_once('/') {
+            Some((name, information)) => (name, Some(information.to_string())),
+            None => (code.as_str(), None),
+        };
+
+        if name == "SDVA" && pacs.service_level.is_none() {
+            pacs.service_level = Some(name.into());
+        } else if CATEGORY_PURPOSE_CODES.contains(&name) && pacs.category_purpose.is_none() {
+            pacs.category_purpose = Some(name.into());
+        } else if CREDITOR_AGENT_CODES.contains(&name) {
+            pacs.instructions_for_creditor_agent.push(Instruction {
+                code: Some(name.into()),
+                information,
+            });
+        } else if let Some((_, iso)) = NEXT_AGENT_CODES.iter().find(|(mt, _)| *mt == name) {
+            pacs.instructions_for_next_agent.push(Instruction {
+                code: Some(iso.to_string()),
+                information,
+            });
+        } else {
+            losses.push(LossKind::Dropped, "23E", code);
+        }
+    }
+
+    let mut remittance = mt.remittance_information.as_slice();
+    if let Some(reference) = remittance.first().and_then(|l| l.strip_prefix("/ROC/")) {
+        pacs.end_to_end_id = reference.into();
+        remittance = &remittance[1..];
+    }
+    if !remittance.is_empty() {
+        pacs.remittance_information.push(remittance.concat());
+    }
+
+    for (code, text) in narrative_codes(&mt.sender_to_receiver_information) {
+        match code.as_str() {
+            "ACC" => pacs.instructions_for_creditor_agent.push(Instruction {
+                code: None,
+                information: Some(text),
+            }),
+            "INT" => pacs.instructions_for_next_agent.push(Instruction {
+                code: None,
+                information: Some(text),
+            }),
+            "INS" if pacs.previous_instructing_agent.is_none() && BIC::is_valid(&text) => {
+                pacs.previous_instructing_agent = Some(BIC::new(&text)?)
+            }
+            _ => losses.push(LossKind::Dropped, "72", &format!("/{}/{}", code, text)),
+        }
+    }
+
+    Ok(Translation {
+        message: pacs,
+        losses,
+    })
+}
+
+/// `pacs008_to_mt103` translates a pacs.008 to an MT103 FIN message from
+/// the instructing agent to the instructed agent, carrying the UETR in
+/// the user header or generating one when missing.
+pub fn pacs008_to_mt103(pacs: &Pacs008) -> Result<Translation<Message>> {
+    let mut losses = LossReport::default();
+    let reference = pacs.instruction_id.as_ref().unwrap_or(&pacs.message_id);
+    let reference = losses.text("InstrId", reference);
+    let reference = losses.truncate("InstrId", &reference, 16);
+
+    let mut mt = MT103::new(
+        &reference,
+        pacs.settlement_date,
+        pacs.settlement_amount.clone(),
+        customer_to_party(
+            &mut losses,
+            "Dbtr",
+            &pacs.debtor,
+            pacs.debtor_account.as_ref(),
+            "K",
+        ),
+        customer_to_party(
+            &mut losses,
+            "Cdtr",
+            &pacs.creditor,
+            pacs.creditor_account.as_ref(),
+            "",
+        ),
+    );
+
+    mt.instructed_amount = pacs.instructed_amount.clone();
+    mt.exchange_rate = pacs.exchange_rate.as_ref().map(|rate| {
+        if rate.contains('.') {
+            rate.replace('.', ",")
+        } else {
+            format!("{},", rate)
+        }
+    });
+
+    if let Some(ref level) = pacs.service_level {
+        if level == "SDVA" {
+            mt.instruction_codes.push(level.clone());
+        } else {
+            losses.push(LossKind::Dropped, "PmtTpInf/SvcLvl/Cd", level);
+        }
+    }
+
+    if let Some(ref purpose) = pacs.category_purpose {
+        if CATEGORY_PURPOSE_CODES.contains(&purpose.as_str()) {
+            mt.instruction_codes.push(purpose.clone());
+        } else {
+            losses.push(LossKind::Dropped, "PmtTpInf/CtgyPurp/Cd", purpose);
+        }
+    }
+
+    let mut narrative = vec![];
+
+    if let Some(ref agent) = pacs.previous_instructing_agent {
+        narrative.push(("INS", agent.to_string()));
+    }
+
+    let instructions = pacs
+        .instructions_for_creditor_agent
+        .iter()
+        .map(|instruction| {
+            let code = instruction
+                .code
+                .as_deref()
+                .filter(|code| CREDITOR_AGENT_CODES.contains(code));
+            ("InstrForCdtrAgt", "ACC", instruction, code)
+        })
+        .chain(pacs.instructions_for_next_agent.iter().map(|instruction| {
+            let code = NEXT_AGENT_CODES
+                .iter()
+                .find(|(_, iso)| instruction.code.as_deref() == Some(*iso))
+                .map(|(mt, _)| *mt);
+            ("InstrForNxtAgt", "INT", instruction, code)
+        }));
+
+    for (source, narrative_code, instruction, code) in instructions {
+        let information = instruction
+            .information
+            .as_ref()
+            .map(|information| losses.text(source, information));
+
+        match (code, information) {
+            (Some(code), Some(information)) if INFORMATION_INSTRUCTION_CODES.contains(&code) => {
+                mt.instruction_codes.push(format!(
+                    "{}/{}",
+                    code,
+                    losses.truncate(source, &information, 30)
+                ))
+            }
+            (Some(code), information) => {
+                mt.instruction_codes.push(code.into());
+                narrative.extend(information.map(|information| (narrative_code, information)));
+            }
+            (None, information) => {
+                if let Some(ref code) = instruction.code {
+                    losses.push(LossKind::Dropped, &format!("{}/Cd", source), code);
+                }
+                narrative.extend(information.map(|information| (narrative_code, information)));
+            }
+        }
+    }
+
+    mt.sender_to_receiver_information = narrative_lines(&mut losses, &narrative);
+
+    let mut remittance = vec![];
+    if pacs.end_to_end_id != NOT_PROVIDED {
+        let reference = losses.text("EndToEndId", &pacs.end_to_end_id);
+        remittance.push(losses.truncate("EndToEndId", &format!("/ROC/{}", reference), 35));
+    }
+    let text = losses.text("RmtInf/Ustrd", &pacs.remittance_information.join(" "));
+    if !text.is_empty() {
+        let lines = 4 - remittance.len();
+        remittance.extend(losses.wrap("RmtInf/Ustrd", &text, lines, 35));
+    }
+    mt.remittance_information = remittance;
+
+    for reference in pacs.creditor_references.iter() {
+        losses.push(LossKind::Dropped, "RmtInf/Strd/CdtrRefInf/Ref", reference);
+    }
+
+    mt.details_of_charges = match pacs.charge_bearer {
+        ChargeBearer::Debtor => DetailsOfCharges::Ours,
+        ChargeBearer::Shared => DetailsOfCharges::Shared,
+        ChargeBearer::Creditor => DetailsOfCharges::Beneficiary,
+    };
+
+    for charges in pacs.charges.iter() {
+        if mt.details_of_charges != DetailsOfCharges::Ours {
+            mt.senders_charges.push(charges.amount.clone());
+        } else if mt.receivers_charges.is_none()
+            && charges.amount.currency == pacs.settlement_amount.currency
+        {
+            mt.receivers_charges = Some(charges.amount.clone());
+        } else {
+            losses.push(
+                LossKind::Dropped,
+                "ChrgsInf/Amt",
+                &charges.amount.to_string(),
+            );
+        }
+    }
+
+    // NOTE: the MT103 requires the sender's charges with BEN, and an
+    // instructed amount with any charges.
+    if mt.details_of_charges == DetailsOfCharges::Beneficiary && mt.senders_charges.is_empty() {
+        mt.senders_charges
+            .push(Money::new(0, pacs.settlement_amount.currency.clone()));
+    }
+    if (!mt.senders_charges.is_empty() || mt.receivers_charges.is_some())
+        && mt.instructed_amount.is_none()
+    {
+        mt.instructed_amount = Some(pacs.settlement_amount.clone());
+    }
+
+    if pacs.debtor_agent.bic.as_ref() != Some(&pacs.instructing_agent)
+        || pacs.debtor_agent.account.is_some()
+    {
+        mt.ordering_institution =
+            agent_to_party(&mut losses, "DbtrAgt", &pacs.debtor_agent, &["A", "D"]);
+    }
+
+    if let Some(ref settlement_account) = pacs.settlement_account {
+        mt.senders_correspondent = Some(Party::B {
+            party_identifier: account_id(Some(settlement_account)),
+            location: None,
+        });
+    }
+    if let Some(ref reimbursement) = pacs.instructing_reimbursement_agent {
+        mt.senders_correspondent = agent_to_party(
+            &mut losses,
+            "SttlmInf/InstgRmbrsmntAgt",
+            reimbursement,
+            &["A", "B", "D"],
+        );
+    }
+    if let Some(ref reimbursement) = pacs.instructed_reimbursement_agent {
+        mt.receivers_correspondent = agent_to_party(
+            &mut losses,
+            "SttlmInf/InstdRmbrsmntAgt",
+            reimbursement,
+            &["A", "B", "D"],
+        );
+    }
+    if let Some(ref reimbursement) = pacs.third_reimbursement_agent {
+        mt.third_reimbursement_institution = agent_to_party(
+            &mut losses,
+            "SttlmInf/ThrdRmbrsmntAgt",
+            reimbursement,
+            &["A", "B", "D"],
+        );
+    }
+    if let Some(ref intermediary) = pacs.intermediary_agent {
+        mt.intermediary_institution =
+            agent_to_party(&mut losses, "IntrmyAgt1", intermediary, &["A", "C", "D"]);
+    }
+
+    if pacs.creditor_agent.bic.as_ref() != Some(&pacs.instructed_agent)
+        || pacs.creditor_agent.account.is_some()
+        || mt.intermediary_institution.is_some()
+    {
+        mt.account_with_institution = agent_to_party(
+            &mut losses,
+            "CdtrAgt",
+            &pacs.creditor_agent,
+            &["A", "B", "C", "D"],
+        );
+    }
+
+    for (source, party) in [
+        ("UltmtDbtr", &pacs.ultimate_debtor),
+        ("UltmtCdtr", &pacs.ultimate_creditor),
+    ] {
+        if let Some(party) = party {
+            losses.push(
+                LossKind::Dropped,
+                source,
+                party.name.as_deref().unwrap_or_default(),
+            );
+        }
+    }
+
+    if let Some(ref purpose) = pacs.purpose {
+        losses.push(LossKind::Dropped, "Purp/Cd", purpose);
+    }
+
+    for (path, text) in pacs.unmodelled.iter() {
+        losses.push(LossKind::Dropped, path, text);
+    }
+
+    let times = &pacs.settlement_times;
+    let date_time = |time: &Option<DateTime>| time.map(|time| time.to_string()[11..].to_string());
+    for (code, source, time) in [
+        ("SNDTIME", "SttlmTmIndctn/DbtDtTm", date_time(&times.debit)),
+        ("RNCTIME", "SttlmTmIndctn/CdtDtTm", date_time(&times.credit)),
+        ("CLSTIME", "SttlmTmReq/CLSTm", times.cls.clone()),
+        ("TILTIME", "SttlmTmReq/TillTm", times.till.clone()),
+        ("FROTIME", "SttlmTmReq/FrTm", times.from.clone()),
+        ("REJTIME", "SttlmTmReq/RjctTm", times.reject.clone()),
+    ] {
+        if let Some(time) = time {
+            mt.time_indications
+                .extend(time_indication(&mut losses, source, code, &time));
+        }
+    }
+
+    let mut reportings = pacs.regulatory_reporting.iter();
+    if let Some(reporting) = reportings.next() {
+        mt.regulatory_reporting = regulatory_reporting_lines(&mut losses, reporting);
+    }
+    for reporting in reportings {
+        losses.push(
+            LossKind::Dropped,
+            "RgltryRptg",
+            &reporting.information.join(" "),
+        );
+    }
+
+    let terminal = |bic: &BIC| LogicalTerminal {
+        bic: bic.clone(),
+        terminal: 'X',
+    };
+    let uetr = match pacs.uetr {
+        Some(uetr) => uetr,
+        None => Uetr::generate()?,
+    };
+    let message = mt.to_message(
+        terminal(&pacs.instructing_agent),
+        terminal(&pacs.instructed_agent),
+        uetr,
+        false,
+    )?;
+
+    Ok(Translation { message, losses })
+}
+
+/// `party_to_customer` translates an MT ordering or beneficiary customer.
+fn party_to_customer(
+    losses: &mut LossReport,
+    tag: &str,
+    party: &Party,
+) -> (PartyIdentification, Option<Account>) {
+    let tag = format!("{}{}", tag, party.option());
+    let mut res = PartyIdentification::default();
+
+    match party {
+        Party::A {
+            party_identifier,
+            bic,
+        } => {
+            res.bic = Some(bic.clone());
+            return (res, account(party_identifier.as_deref()));
+        }
+        Party::F {
+            party_identifier,
+            name_and_address,
+        } => {
+            let mut lines: Vec<String> = Vec::new();
+            let mut country = None;
+            let mut name = String::new();
+
+            for line in name_and_address.iter() {
+                match (
+                    line.get(..2).unwrap_or_default(),
+                    line.get(2..).unwrap_or_default(),
+                ) {
+                    ("1/", text) => name.push_str(text),
+                    ("2/", text) => lines.push(text.into()),
+                    ("3/", text) => {
+                        let (code, town) = text.split_once('/').unwrap_or((text, ""));
+
+                        match code.parse() {
+                            Ok(code) if !town.is_empty() => {
+                                country = Some(code);
+                                lines.push(town.into());
+                            }
+                            _ => lines.push(text.into()),
+                        }
+                    }
+                    _ => losses.push(LossKind::Dropped, &tag, line),
+                }
+            }
+
+            let address = PostalAddress::from_lines(&lines, country);
+
+            res.name = Some(name).filter(|name| !name.is_empty());
+            res.address = Some(address).filter(|address| *address != PostalAddress::default());
+
+            return match party_identifier.strip_prefix('/') {
+                Some(id) => (res, account(Some(id))),
+                None => {
+                    // NOTE: these 50F codes identify a person, the
+                    // other ones (CUST, EMPL, TXID) may be organisations.
+                    res.private = ["ARNU", "CCPT", "DRLC", "NIDN", "SOSE"]
+                        .iter()
+                        .any(|code| party_identifier.starts_with(code));
+                    res.other_id = Some(party_identifier.clone());
+                    (res, None)
+                }
+            };
+        }
+        _ => {}
+    }
+
+    let mut lines = party.name_and_address().iter();
+
+    res.name = lines.next().cloned();
+    let address_lines: Vec<String> = lines.cloned().collect();
+    if !address_lines.is_empty() {
+        res.address = Some(PostalAddress::from_lines(&address_lines, None));
+    }
+
+    (res, account(party.account()))
+}
+
+/// `customer_to_party` translates a debtor or a creditor: option A when
+/// identified by a BIC only, option F with a name and a structured address
+/// and `unstructured` otherwise ("K" for 50a, "" for 59a).
+fn customer_to_party(
+    losses: &mut LossReport,
+    source: &str,
+    party: &PartyIdentification,
+    account: Option<&Account>,
+    unstructured: &str,
+) -> Party {
+    let account = account_id(account).map(|id| losses.text(&format!("{}Acct", source), &id));
+    let name = party
+        .name
+        .as_ref()
+        .map(|name| losses.text(&format!("{}/Nm", source), name));
+    let address = party.address.clone().unwrap_or_default();
+    // NOTE: the F option needs a "1/" name line besides the "3/" line.
+    let option_f = name.is_some() && address.is_structured();
+
+    // NOTE: 50F carries another identification when there is no account,
+    // e.g. "NIDN/DE/123456789", but the party identifier of 59F is only an
+    // account.
+    let other_id = match party.other_id {
+        Some(ref other) if account.is_none() && option_f && unstructured == "K" => {
+            Some(losses.truncate(&format!("{}/Id", source), other, 35))
+        }
+        Some(ref other) => {
+            losses.push(LossKind::Dropped, &format!("{}/Id", source), other);
+            None
+        }
+        None => None,
+    };
+
+    if let Some(ref bic) = party.bic {
+        if name.is_none() {
+            if let Some(other) = party.other_id.as_ref().filter(|_| other_id.is_some()) {
+                losses.push(LossKind::Dropped, &format!("{}/Id", source), other);
+            }
+
+            return Party::A {
+                party_identifier: account,
+                bic: bic.clone(),
+            };
+        }
+
+        losses.push(
+            LossKind::Dropped,
+            &format!("{}/Id/OrgId/AnyBIC", source),
+            &bic.to_string(),
+        );
+    }
+
+    let name = name.unwrap_or_default();
+    let address_source = format!("{}/PstlAdr", source);
+    let mut address_lines: Vec<String> = address
+        .street_lines()
+        .iter()
+        .map(|line| losses.text(&address_source, line))
+        .collect();
+    let town_line = address.town_line();
+
+    if option_f {
+        let country = address
+            .country
+            .as_ref()
+            .map(|c| c.as_str())
+            .unwrap_or_default();
+        let town = losses.text(&address_source, town_line.as_deref().unwrap_or_default());
+        let town = losses.truncate(&address_source, &format!("{}/{}", country, town), 33);
+
+        // NOTE: only some countries write the subdivision in the town line
+        if let Some(ref division) = address.country_sub_division {
+            if !town_line
+                .as_deref()
+                .unwrap_or_default()
+                .contains(division.as_str())
+            {
+                losses.push(
+                    LossKind::Dropped,
+                    &format!("{}/CtrySubDvsn", address_source),
+                    division,
+                );
+            }
+        }
+
+        // NOTE: the 3/ line is required, the name gets at least one line
+        // and the address lines share what is left.
+        if address_lines.len() > 2 {
+            for line in address_lines.drain(2..) {
+                losses.push(LossKind::Dropped, &address_source, &line);
+            }
+        }
+        let name_lines = losses.wrap(
+            &format!("{}/Nm", source),
+            &name,
+            3 - address_lines.len(),
+            33,
+        );
+
+        let mut name_and_address: Vec<String> = name_lines
+            .iter()
+            .map(|line| format!("1/{}", line))
+            .collect();
+        for line in address_lines.iter() {
+            name_and_address.push(format!("2/{}", losses.truncate(&address_source, line, 33)));
+        }
+        name_and_address.push(format!("3/{}", town));
+
+        return Party::F {
+            party_identifier: other_id
+                .unwrap_or_else(|| format!("/{}", account.as_deref().unwrap_or(NOT_PROVIDED))),
+            name_and_address,
+        };
+    }
+
+    address_lines.extend(town_line.map(|town| losses.text(&address_source, &town)));
+    address_lines.extend(address.country.as_ref().map(|c| c.as_str().to_string()));
+
+    let mut name_and_address = Vec::new();
+    if !name.is_empty() || address_lines.is_empty() {
+        name_and_address.push(losses.truncate(&format!("{}/Nm", source), &name, 35));
+    }
+    for line in address_lines.iter() {
+        if name_and_address.len() < 4 {
+            name_and_address.push(losses.truncate(&address_source, line, 35));
+        } else {
+            losses.push(LossKind::Dropped, &address_source, line);
+        }
+    }
+
+    match unstructured {
+        "K" => Party::K {
+            account,
+            name_and_address,
+        },
+        _ => Party::NoOption {
+            account,
+            name_and_address,
+        },
+    }
+}
+
+/// `party_to_agent` translates an MT institution field.
+fn party_to_agent(losses: &mut LossReport, tag: &str, party: &Party) -> FinancialInstitution {
+    let tag = format!("{}{}", tag, party.option());
+    let mut res = FinancialInstitution {
+        bic: party.bic().cloned(),
+        ..Default::default()
+    };
+
+    // NOTE: a party identifier starting with "//" is a clearing code,
+    // such as "//FW021000018".
+    match party.account() {
+        Some(id) if id.starts_with('/') => {
+            res.clearing_member_id = Some(id.trim_start_matches('/').into())
+        }
+        id => res.account = account(id),
+    }
+
+    match party {
+        Party::B {
+            location: Some(location),
+            ..
+        } => losses.push(LossKind::Dropped, &tag, location),
+        Party::D {
+            name_and_address, ..
+        } => {
+            let mut lines = name_and_address.iter();
+
+            res.name = lines.next().cloned();
+            let address_lines: Vec<String> = lines.cloned().collect();
+            if !address_lines.is_empty() {
+                res.address = Some(PostalAddress {
+                    address_lines,
+                    ..Default::default()
+                });
+            }
+        }
+        _ => {}
+    }
+
+    res
+}
+
+/// `agent_to_party` translates an agent to an institution field using one
+/// of `options`, or drops it when none fits.
+fn agent_to_party(
+    losses: &mut LossReport,
+    source: &str,
+    agent: &FinancialInstitution,
+    options: &[&str],
+) -> Option<Party> {
+    let account = account_id(agent.account.as_ref());
+    let party_identifier = match agent.clearing_member_id {
+        Some(ref member) => {
+            if let Some(ref account) = account {
+                losses.push(LossKind::Dropped, &format!("{}Acct", source), account);
+            }
+            Some(format!("/{}", member))
+        }
+        None => account,
+    };
+
+    if let Some(ref bic) = agent.bic {
+        return Some(Party::A {
+            party_identifier,
+            bic: bic.clone(),
+        });
+    }
+
+    if let Some(ref name) = agent.name {
+        let name = losses.text(source, name);
+        let mut name_and_address = vec![losses.truncate(&format!("{}/Nm", source), &name, 35)];
+        let address = agent.address.clone().unwrap_or_default();
+        let lines = address
+            .address_lines
+            .iter()
+            .cloned()
+            .chain(address.town_name.clone())
+            .chain(address.country.as_ref().map(|c| c.as_str().to_string()));
+
+        for line in lines {
+            let line = losses.text(source, &line);
+            if name_and_address.len() < 4 {
+                name_and_address.push(losses.truncate(source, &line, 35));
+            } else {
+                losses.push(LossKind::Dropped, source, &line);
+            }
+        }
+
+        return Some(Party::D {
+            party_identifier,
+            name_and_address,
+        });
+    }
+
+    match party_identifier {
+        Some(party_identifier) if options.contains(&"C") => Some(Party::C { party_identifier }),
+        Some(party_identifier) if options.contains(&"B") => Some(Party::B {
+            party_identifier: Some(party_identifier),
+            location: None,
+        }),
+        Some(party_identifier) => {
+            losses.push(LossKind::Dropped, source, &party_identifier);
+            None
+        }
+        None => None,
+    }
+}
+
+/// `set_settlement_time` sets the settlement time of a 13C time indication
+/// of the `date` settlement, e.g. "/CLSTIME/0915+0100", returning false
+/// when the code has no or an already set settlement time.
+fn set_settlement_time(times: &mut SettlementTimes, date: Date, indication: &str) -> bool {
+    let (code, time) = match indication
+        .strip_prefix('/')
+        .and_then(|indication| indication.split_once('/'))
+    {
+        Some((code, time)) if time.len() == 9 && time.is_ascii() => (code, time),
+        _ => return false,
+    };
+    let time = format!(
+        "{}:{}:00{}{}:{}",
+        &time[0..2],
+        &time[2..4],
+        &time[4..5],
+        &time[5..7],
+        &time[7..9]
+    );
+
+    let date_time = match code {
+        "SNDTIME" => &mut times.debit,
+        "RNCTIME" => &mut times.credit,
+        _ => {
+            let requested = match code {
+                "CLSTIME" => &mut times.cls,
+                "TILTIME" => &mut times.till,
+                "FROTIME" => &mut times.from,
+                "REJTIME" => &mut times.reject,
+                _ => return false,
+            };
+
+            if requested.is_some() {
+                return false;
+            }
+
+            *requested = Some(time);
+            return true;
+        }
+    };
+
+    if date_time.is_some() {
+        return false;
+    }
+
+    *date_time = format!("{}T{}", date, time).parse().ok();
+    date_time.is_some()
+}
+
+/// `time_indication` returns the 13C time indication of an ISO time with
+/// a UTC offset, e.g. "/CLSTIME/0915+0100" for "09:15:00+01:00".
+fn time_indication(
+    losses: &mut LossReport,
+    source: &str,
+    code: &str,
+    time: &str,
+) -> Option<String> {
+    let (clock, offset) = time.split_at(time.find(['+', '-', 'Z']).unwrap_or(time.len()));
+    let offset = match offset {
+        "Z" => Some("+0000".to_string()),
+        offset if offset.len() == 6 => Some(offset.replace(':', "")),
+        _ => None,
+    };
+    let hours_minutes = clock
+        .get(0..2)
+        .zip(clock.get(3..5))
+        .map(|(hours, minutes)| format!("{}{}", hours, minutes))
+        .filter(|time| time.len() == 4 && time.chars().all(|c| c.is_ascii_digit()));
+
+    match (hours_minutes, offset) {
+        (Some(hours_minutes), Some(offset)) => {
+            // NOTE: 13C has no seconds
+            if clock[5..].chars().any(|c| c.is_ascii_digit() && c != '0') {
+                losses.push(LossKind::Truncated, source, time);
+            }
+
+            Some(format!("/{}/{}{}", code, hours_minutes, offset))
+        }
+        _ => {
+            losses.push(LossKind::Dropped, source, time);
+            None
+        }
+    }
+}
+
+/// `regulatory_reporting` translates the lines of field 77B, whose first
+/// line may be coded for the ordering or the beneficiary country, e.g.
+/// "/ORDERRES/BE//MEILAAN 1, 9000 GENT".
+fn regulatory_reporting(lines: &[String]) -> RegulatoryReporting {
+    let mut res = RegulatoryReporting {
+        information: lines.to_vec(),
+        ..Default::default()
+    };
+
+    for (code, indicator) in [("/ORDERRES/", "DEBT"), ("/BENEFRES/", "CRED")] {
+        let coded = lines[0]
+            .strip_prefix(code)
+            .and_then(|rest| rest.split_once("//"));
+
+        if let Some((country, text)) = coded {
+            if let Ok(country) = country.parse() {
+                res.indicator = Some(indicator.into());
+                res.country = Some(country);
+                res.information[0] = text.into();
+                res.information.retain(|line| !line.is_empty());
+            }
+        }
+    }
+
+    res
+}
+
+/// `regulatory_reporting_lines` builds field 77B from a regulatory
+/// reporting, coding its first line when the indicator and the country
+/// allow it and cutting it to 3 lines.
+fn regulatory_reporting_lines(
+    losses: &mut LossReport,
+    reporting: &RegulatoryReporting,
+) -> Vec<String> {
+    const SOURCE: &str = "RgltryRptg/Dtls/Inf";
+
+    let mut lines: Vec<String> = reporting
+        .information
+        .iter()
+        .map(|line| losses.text(SOURCE, line))
+        .collect();
+    let code = match reporting.indicator.as_deref() {
+        Some("DEBT") => Some("ORDERRES"),
+        Some("CRED") => Some("BENEFRES"),
+        _ => None,
+    };
+
+    match (code, reporting.country.as_ref()) {
+        (Some(code), Some(country)) => {
+            let first = format!(
+                "/{}/{}//{}",
+                code,
+                country.as_str(),
+                lines.first().cloned().unwrap_or_default()
+            );
+
+            match lines.first_mut() {
+                Some(line) => *line = first,
+                None => lines.push(first),
+            }
+        }
+        _ => {
+            if let Some(ref indicator) = reporting.indicator {
+                losses.push(LossKind::Dropped, "RgltryRptg/DbtCdtRptgInd", indicator);
+            }
+            if let Some(ref country) = reporting.country {
+                losses.push(
+                    LossKind::Dropped,
+                    "RgltryRptg/Authrty/Ctry",
+                    country.as_str(),
+                );
+            }
+        }
+    }
+
+    let mut res = vec![];
+    for line in lines.iter() {
+        if res.len() < 3 {
+            res.push(losses.truncate(SOURCE, line, 35));
+        } else {
+            losses.push(LossKind::Dropped, SOURCE, line);
+        }
+    }
+
+    res
+}
+
+/// `narrative_codes` splits field 72 into its "/CODE/text" entries, joining
+/// the "//" continuation lines.
+fn narrative_codes(lines: &[String]) -> Vec<(String, String)> {
+    let mut res: Vec<(String, String)> = vec![];
+
+    for line in lines.iter() {
+        if let Some(text) = line.strip_prefix("//") {
+            if let Some((_, last)) = res.last_mut() {
+                last.push_str(text);
+                continue;
+            }
+        }
+
+        match line.strip_prefix('/').and_then(|line| line.split_once('/')) {
+            Some((code, text)) => res.push((code.into(), text.into())),
+            None => res.push((String::new(), line.clone())),
+        }
+    }
+
+    res
+}
+
+/// `narrative_lines` builds field 72 from "/CODE/text" entries, cutting
+/// it to 6 lines.
+fn narrative_lines(losses: &mut LossReport, entries: &[(&str, String)]) -> Vec<String> {
+    let mut res = vec![];
+
+    for (code, text) in entries.iter() {
+        let prefix = format!("/{}/", code);
+        let first = 35 - prefix.len();
+        let chars: Vec<char> = text.chars().collect();
+        let (head, tail) = chars.split_at(first.min(chars.len()));
+
+        res.push(format!("{}{}", prefix, head.iter().collect::<String>()));
+        for chunk in tail.chunks(33) {
+            res.push(format!("//{}", chunk.iter().collect::<String>()));
+        }
+    }
+
+    if res.len() > 6 {
+        losses.push(LossKind::Truncated, "72", &res.join("\n"));
+        res.truncate(6);
+        if let Some(last) = res.last_mut() {
+            last.pop();
+            last.push('+');
+        }
+    }
+
+    res
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{mt103_to_pacs008, pacs008_to_mt103, LossKind};
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+    #[allow(unused_imports)] // TODO
+    use crate::swift::pacs008::{ChargeBearer, Pacs008};
+    #[allow(unused_imports)] // TODO
+    use crate::swift::{Message, Party, MT103};
+
+    #[allow(dead_code)]
+    const MT_103: &str = "{1:F01BANKBEBBAXXX0000000000}{2:I103BANKDEFFXXXXN}\
+        {3:{121:eb6305c9-1f7f-49de-aed0-16487c27b42d}}\
+        {4:\r\n:20:REF12345\r\n:23B:CRED\r\n:23E:SDVA\r\n:23E:REPA\r\n\
+        :32A:240115EUR1234,56\r\n:50F:/BE71096123456769\r\n1/JOHN DOE\r\n\
+        2/RUE DE LA LOI 1\r\n3/BE/1000 BRUXELLES\r\n:59:/DE89370400440532013000\r\n\
+        JANE DOE\r\nHAUPTSTRASSE 5\r\n:70:/ROC/E2E-0001\r\nINVOICE 2024-001\r\n\
+        :71A:SHA\r\n:72:/INS/BANKBEBB\r\n/BNF/PAYMENT FOR SERVICES\r\n-}";
+
+    #[allow(dead_code)]
+    const PACS_008: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:pacs.008.001.08">
+  <FIToFICstmrCdtTrf>
+    <GrpHdr>
+      <MsgId>MSG-20240115-1</MsgId>
+      <CreDtTm>2024-01-15T09:30:00</CreDtTm>
+      <NbOfTxs>1</NbOfTxs>
+      <SttlmInf><SttlmMtd>INDA</SttlmMtd></SttlmInf>
+    </GrpHdr>
+    <CdtTrfTxInf>
+      <PmtId>
+        <InstrId>INSTRUCTION-ID-2024-0001</InstrId>
+        <EndToEndId>E2E-0001</EndToEndId>
+        <UETR>eb6305c9-1f7f-49de-aed0-16487c27b42d</UETR>
+      </PmtId>
+      <IntrBkSttlmAmt Ccy="EUR">1234.56</IntrBkSttlmAmt>
+      <IntrBkSttlmDt>2024-01-15</IntrBkSttlmDt>
+      <ChrgBr>CRED</ChrgBr>
+      <InstgAgt><FinInstnId><BICFI>BANKBEBBXXX</BICFI></FinInstnId></InstgAgt>
+      <InstdAgt><FinInstnId><BICFI>BANKDEFFXXX</BICFI></FinInstnId></InstdAgt>
+      <UltmtDbtr><Nm>Holding SA</Nm></UltmtDbtr>
+      <Dbtr>
+        <Nm>Société Générale des Transports Européens et Internationaux de Belgique</Nm>
+        <PstlAdr>
+          <StrtNm>Rue de la Loi</StrtNm>
+          <BldgNb>1</BldgNb>
+          <PstCd>1000</PstCd>
+          <TwnNm>Bruxelles</TwnNm>
+          <Ctry>BE</Ctry>
+        </PstlAdr>
+      </Dbtr>
+      <DbtrAcct><Id><IBAN>BE71096123456769</IBAN></Id></DbtrAcct>
+      <DbtrAgt><FinInstnId><BICFI>BANKBEBBXXX</BICFI></FinInstnId></DbtrAgt>
+      <CdtrAgt><FinInstnId><BICFI>BANKDEFFXXX</BICFI></FinInstnId></CdtrAgt>
+      <Cdtr>
+        <Nm>Jane Doe</Nm>
+        <PstlAdr><AdrLine>Hauptstraße 5</AdrLine><AdrLine>Frankfurt</AdrLine></PstlAdr>
+      </Cdtr>
+      <CdtrAcct><Id><IBAN>DE89370400440532013000</IBAN></Id></CdtrAcct>
+      <InstrForCdtrAgt><Cd>HOLD</Cd></InstrForCdtrAgt>
+      <InstrForCdtrAgt><InstrInf>CALL BENEFICIARY BEFORE CREDITING</InstrInf></InstrForCdtrAgt>
+      <RmtInf>
+        <Ustrd>INVOICE 2024-001 AND 2024-002 FOR CONSULTING SERVICES DELIVERED IN DECEMBER 2023 AT THE BRUSSELS OFFICE AND TRAVEL COSTS</Ustrd>
+        <Strd><CdtrRefInf><Ref>RF18539007547034</Ref></CdtrRefInf></Strd>
+      </RmtInf>
+    </CdtTrfTxInf>
+  </FIToFICstmrCdtTrf>
+</Document>"#;
+
+    #[test]
+    fn translate_mt103() -> Result<()> {
+        let translation =
+            mt103_to_pacs008(&Message::parse(MT_103)?, "2024-01-15T09:30:00".parse()?)?;
+        let pacs = &translation.message;
+
+        assert_eq!(pacs.instruction_id.as_deref(), Some("REF12345"));
+        assert_eq!(pacs.end_to_end_id, "E2E-0001");
+        assert_eq!(
+            pacs.uetr.map(|u| u.to_string()),
+            Some("eb6305c9-1f7f-49de-aed0-16487c27b42d".into())
+        );
+        assert_eq!(pacs.service_level.as_deref(), Some("SDVA"));
+        assert_eq!(pacs.charge_bearer, ChargeBearer::Shared);
+        assert_eq!(pacs.instructing_agent.to_string(), "BANKBEBBXXX");
+        assert_eq!(
+            pacs.previous_instructing_agent
+                .as_ref()
+                .map(|b| b.to_string()),
+            Some("BANKBEBB".into())
+        );
+        assert_eq!(pacs.debtor.name.as_deref(), Some("JOHN DOE"));
+
+        let address = pacs.debtor.address.as_ref().unwrap();
+        assert_eq!(address.country.as_ref().map(|c| c.as_str()), Some("BE"));
+        assert_eq!(address.post_code.as_deref(), Some("1000"));
+        assert_eq!(address.town_name.as_deref(), Some("BRUXELLES"));
+        assert_eq!(address.street_name.as_deref(), Some("RUE DE LA LOI"));
+        assert_eq!(address.building_number.as_deref(), Some("1"));
+        assert!(address.address_lines.is_empty());
+        assert_eq!(pacs.creditor.name.as_deref(), Some("JANE DOE"));
+        assert_eq!(pacs.remittance_information, vec!["INVOICE 2024-001"]);
+
+        let losses = &translation.losses.losses;
+        assert_eq!(losses.len(), 2);
+        assert_eq!(
+            (
+                losses[0].kind,
+                losses[0].source.as_str(),
+                losses[0].value.as_str()
+            ),
+            (LossKind::Dropped, "23E", "REPA")
+        );
+        assert_eq!(losses[1].value, "/BNF/PAYMENT FOR SERVICES");
+
+        // NOTE: back to MT, only the dropped values are missing
+        let back = pacs008_to_mt103(&Pacs008::parse(&pacs.to_xml())?)?;
+        let mt = MT103::from_message(&back.message)?;
+
+        assert!(back.losses.is_empty());
+        assert_eq!(back.message.uetr(), pacs.uetr);
+        assert_eq!(mt.instruction_codes, vec!["SDVA"]);
+        assert_eq!(
+            mt.ordering_customer,
+            MT103::parse(MT_103)?.ordering_customer
+        );
+        assert_eq!(
+            mt.beneficiary_customer,
+            MT103::parse(MT_103)?.beneficiary_customer
+        );
+        assert_eq!(
+            mt.remittance_information,
+            vec!["/ROC/E2E-0001", "INVOICE 2024-001"]
+        );
+        assert_eq!(mt.sender_to_receiver_information, vec!["/INS/BANKBEBB"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn translate_mt103_times_and_reporting() -> Result<()> {
+        let mt_103 = MT_103
+            .replace(
+                ":20:REF12345\r\n",
+                ":20:REF12345\r\n:13C:/CLSTIME/0915+0100\r\n:13C:/SNDTIME/1015+0100\r\n",
+            )
+            .replace(":23E:REPA\r\n", ":23E:REPA\r\n:26T:K90\r\n")
+            .replace(
+                "/BNF/PAYMENT FOR SERVICES\r\n",
+                "/BNF/PAYMENT FOR SERVICES\r\n:77B:/ORDERRES/BE//MEILAAN 1, 9000 GENT\r\n\
+                 :77T:/NARR/REMITTANCE IN ENVELOPE\r\n",
+            );
+        let translation =
+            mt103_to_pacs008(&Message::parse(&mt_103)?, "2024-01-15T09:30:00".parse()?)?;
+        let pacs = &translation.message;
+
+        assert_eq!(pacs.settlement_times.cls.as_deref(), Some("09:15:00+01:00"));
+        assert_eq!(
+            pacs.settlement_times.debit.map(|t| t.to_string()),
+            Some("2024-01-15T10:15:00+01:00".into())
+        );
+        assert_eq!(pacs.regulatory_reporting.len(), 1);
+        assert_eq!(
+            pacs.regulatory_reporting[0].indicator.as_deref(),
+            Some("DEBT")
+        );
+        assert_eq!(
+            pacs.regulatory_reporting[0].information,
+            vec!["MEILAAN 1, 9000 GENT"]
+        );
+
+        let losses = &translation.losses;
+        let dropped = |source| {
+            losses
+                .find(source)
+                .map(|loss| (loss.kind, loss.value.as_str()))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(dropped("13C").is_empty());
+        assert!(dropped("77B").is_empty());
+        assert_eq!(dropped("26T"), vec![(LossKind::Dropped, "K90")]);
+        assert_eq!(
+            dropped("77T"),
+            vec![(LossKind::Dropped, "/NARR/REMITTANCE IN ENVELOPE")]
+        );
+
+        // NOTE: back to MT, the settlement times and the regulatory
+        // reporting are carried over
+        let back = pacs008_to_mt103(&Pacs008::parse(&pacs.to_xml())?)?;
+        let mt = MT103::from_message(&back.message)?;
+
+        assert!(back.losses.is_empty());
+        assert_eq!(
+            mt.time_indications,
+            vec!["/SNDTIME/1015+0100", "/CLSTIME/0915+0100"]
+        );
+        assert_eq!(
+            mt.regulatory_reporting,
+            vec!["/ORDERRES/BE//MEILAAN 1, 9000 GENT"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn translate_pacs008() -> Result<()> {
+        let translation = pacs008_to_mt103(&Pacs008::parse(PACS_008)?)?;
+        let message = &translation.message;
+        let mt = MT103::from_message(message)?;
+
+        assert_eq!(
+            message.uetr().map(|u| u.to_string()),
+            Some("eb6305c9-1f7f-49de-aed0-16487c27b42d".into())
+        );
+        assert_eq!(
+            message.receiver().map(|b| b.to_string()),
+            Some("BANKDEFFXXX".into())
+        );
+        assert_eq!(mt.senders_reference, "INSTRUCTION-ID-+");
+        assert_eq!(
+            mt.ordering_customer,
+            Party::F {
+                party_identifier: "/BE71096123456769".into(),
+                name_and_address: vec![
+                    "1/Societe Generale des Transports E".into(),
+                    "1/uropeens et Internationaux de Be+".into(),
+                    "2/Rue de la Loi 1".into(),
+                    "3/BE/1000 Bruxelles".into(),
+                ],
+            }
+        );
+        assert_eq!(
+            mt.beneficiary_customer,
+            Party::NoOption {
+                account: Some("DE89370400440532013000".into()),
+                name_and_address: vec![
+                    "Jane Doe".into(),
+                    "Hauptstrasse 5".into(),
+                    "Frankfurt".into()
+                ],
+            }
+        );
+        assert_eq!(mt.ordering_institution, None);
+        assert_eq!(mt.account_with_institution, None);
+        assert_eq!(mt.instruction_codes, vec!["HOLD"]);
+        assert_eq!(
+            mt.sender_to_receiver_information,
+            vec!["/ACC/CALL BENEFICIARY BEFORE CREDIT", "//ING"]
+        );
+        assert_eq!(mt.remittance_information.len(), 4);
+        assert_eq!(mt.remittance_information[0], "/ROC/E2E-0001");
+        assert!(mt.remittance_information[3].ends_with('+'));
+        assert_eq!(
+            mt.senders_charges
+                .iter()
+                .map(|c| c.amount)
+                .collect::<Vec<_>>(),
+            vec![0]
+        );
+
+        let losses = &translation.losses;
+        let kinds = |source| {
+            losses
+                .find(source)
+                .map(|loss| loss.kind)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(kinds("InstrId"), vec![LossKind::Truncated]);
+        assert_eq!(
+            kinds("Dbtr/Nm"),
+            vec![LossKind::Transliterated, LossKind::Truncated]
+        );
+        assert_eq!(kinds("Cdtr/PstlAdr"), vec![LossKind::Transliterated]);
+        assert_eq!(kinds("RmtInf/Ustrd"), vec![LossKind::Truncated]);
+        assert_eq!(kinds("RmtInf/Strd/CdtrRefInf/Ref"), vec![LossKind::Dropped]);
+        assert_eq!(kinds("UltmtDbtr"), vec![LossKind::Dropped]);
+        assert_eq!(losses.losses.len(), 7);
+
+        // NOTE: the elements the pacs.008 does not read are dropped too
+        let pacs = Pacs008::parse(&PACS_008.replace(
+            "<UltmtDbtr>",
+            "<IntrmyAgt2><FinInstnId><BICFI>BANKUS33XXX</BICFI></FinInstnId></IntrmyAgt2>\n      \
+             <UltmtDbtr>",
+        ))?;
+        let translation = pacs008_to_mt103(&pacs)?;
+
+        assert_eq!(
+            translation
+                .losses
+                .find("IntrmyAgt2")
+                .map(|loss| (loss.kind, loss.value.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(LossKind::Dropped, "BANKUS33XXX")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn translate_pacs008_identification() -> Result<()> {
+        let mut pacs = Pacs008::parse(PACS_008)?;
+        let address = pacs.debtor.address.clone();
+
+        // NOTE: 59F only has an account as party identifier
+        pacs.creditor.address = address.clone();
+        pacs.creditor.other_id = Some("NIDN/DE/123456789".into());
+        pacs.creditor_account = None;
+
+        // NOTE: there is no "1/" line without a name
+        pacs.debtor.name = None;
+
+        let translation = pacs008_to_mt103(&pacs)?;
+        let mt = MT103::from_message(&translation.message)?;
+
+        assert_eq!(
+            mt.ordering_customer,
+            Party::K {
+                account: Some("BE71096123456769".into()),
+                name_and_address: vec![
+                    "Rue de la Loi 1".into(),
+                    "1000 Bruxelles".into(),
+                    "BE".into()
+                ],
+            }
+        );
+        assert_eq!(
+            mt.beneficiary_customer,
+            Party::F {
+                party_identifier: "/NOTPROVIDED".into(),
+                name_and_address: vec![
+                    "1/Jane Doe".into(),
+                    "2/Rue de la Loi 1".into(),
+                    "3/BE/1000 Bruxelles".into(),
+                ],
+            }
+        );
+        assert_eq!(
+            translation
+                .losses
+                .find("Cdtr/Id")
+                .map(|loss| loss.value.as_str())
+                .collect::<Vec<_>>(),
+            vec!["NIDN/DE/123456789"]
+        );
+
+        Ok(())
+    }
+}
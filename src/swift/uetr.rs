@@ -0,0 +1,175 @@
+//! Unique end-to-end transaction reference (UETR), the RFC 4122 version 4
+//! UUID tracking a payment through SWIFT gpi. It is carried by field 121
+//! of the MT user header and by the `UETR` elements of ISO 20022 messages.
+
+use crate::error::{check_length, Error, Reason};
+use crate::result::Result;
+use serde::{de, de::Deserializer, ser::Serializer, Deserialize, Serialize};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+/// [`Uetr`] is a UETR, written as 36 lowercase chars, e.g.
+/// "eb6305c9-1f7f-49de-aed0-16487c27b42d".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uetr([u8; 16]);
+
+impl Uetr {
+    /// `new` creates a new `Uetr` from its string form.
+    pub fn new(uetr: &str) -> Result<Uetr> {
+        Uetr::check(uetr)?;
+
+        let hex: Vec<u8> = uetr.bytes().filter(|b| *b != b'-').collect();
+        let mut bytes = [0; 16];
+        let invalid = || Error::invalid("UETR", uetr, Reason::Format("not hexadecimal".into()));
+
+        for (byte, pair) in bytes.iter_mut().zip(hex.chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+        }
+
+        Ok(Uetr(bytes))
+    }
+
+    /// `generate` creates a new random `Uetr`.
+    pub fn generate() -> Result<Uetr> {
+        let mut bytes = [0; 16];
+
+        getrandom::getrandom(&mut bytes).map_err(Error::Random)?;
+
+        Ok(Uetr::from_bytes(bytes))
+    }
+
+    /// `from_bytes` creates a new `Uetr` from 16 random bytes, setting
+    /// its version and variant bits.
+    pub fn from_bytes(mut bytes: [u8; 16]) -> Uetr {
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        Uetr(bytes)
+    }
+
+    /// `as_bytes` returns the 16 bytes of the `Uetr`.
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// `check` checks a string, returning why it is not a valid UETR.
+    pub fn check(uetr: &str) -> Result<()> {
+        check_length("UETR", uetr, 36, 36)?;
+
+        for (index, found) in uetr.chars().enumerate() {
+            let (allowed, expected) = match index {
+                8 | 13 | 18 | 23 => (found == '-', "'-'"),
+                14 => (found == '4', "'4' (version 4)"),
+                19 => (
+                    "89ab".contains(found),
+                    "'8', '9', 'a' or 'b' (RFC 4122 variant)",
+                ),
+                _ => (
+                    found.is_ascii_digit() || ('a'..='f').contains(&found),
+                    "a lower-case hex digit",
+                ),
+            };
+
+            if !allowed {
+                return Err(Error::invalid(
+                    "UETR",
+                    uetr,
+                    Reason::Character {
+                        index,
+                        found,
+                        expected,
+                    },
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `is_valid` returns if a string is a lowercase, hyphenated, version
+    /// 4 and RFC 4122 variant UUID, as required by SWIFT gpi.
+    pub fn is_valid(uetr: &str) -> bool {
+        Uetr::check(uetr).is_ok()
+    }
+}
+
+impl fmt::Display for Uetr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i == 4 || i == 6 || i == 8 || i == 10 {
+                write!(f, "-")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Uetr {
+    type Err = Error;
+
+    fn from_str(uetr: &str) -> StdResult<Self, Self::Err> {
+        Uetr::new(uetr)
+    }
+}
+
+impl Serialize for Uetr {
+    fn serialize<S: Serializer>(&self, ser: S) -> StdResult<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'a> Deserialize<'a> for Uetr {
+    fn deserialize<D: Deserializer<'a>>(des: D) -> StdResult<Self, D::Error> {
+        <&str>::deserialize(des)?.parse().map_err(de::Error::custom)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::Uetr;
+    #[allow(unused_imports)] // TODO
+    use crate::result::Result;
+
+    #[test]
+    fn validate() -> Result<()> {
+        const CASES: &[(&str, bool)] = &[
+            ("eb6305c9-1f7f-49de-aed0-16487c27b42d", true),
+            ("00000000-0000-4000-8000-000000000000", true),
+            ("EB6305C9-1F7F-49DE-AED0-16487C27B42D", false),
+            ("eb6305c91f7f49deaed016487c27b42d", false),
+            ("eb6305c9-1f7f-19de-aed0-16487c27b42d", false),
+            ("eb6305c9-1f7f-49de-ced0-16487c27b42d", false),
+            ("eb6305c9-1f7f-49de-aed0-16487c27b42", false),
+            ("eb6305c9-1f7f-49de-aed0-16487c27b42g", false),
+        ];
+
+        for (uetr, valid) in CASES.iter() {
+            assert_eq!(Uetr::is_valid(uetr), *valid, "{}", uetr);
+            assert_eq!(Uetr::new(uetr).is_ok(), *valid, "{}", uetr);
+        }
+
+        let uetr = Uetr::new("eb6305c9-1f7f-49de-aed0-16487c27b42d")?;
+        assert_eq!(uetr.to_string(), "eb6305c9-1f7f-49de-aed0-16487c27b42d");
+        assert_eq!(uetr.as_bytes()[0], 0xeb);
+
+        Ok(())
+    }
+
+    #[test]
+    fn generate() -> Result<()> {
+        let uetr = Uetr::generate()?;
+
+        assert!(Uetr::is_valid(&uetr.to_string()));
+        assert_ne!(uetr, Uetr::generate()?);
+        assert_eq!(
+            Uetr::from_bytes([0xff; 16]).to_string(),
+            "ffffffff-ffff-4fff-bfff-ffffffffffff"
+        );
+
+        Ok(())
+    }
+}
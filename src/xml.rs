@@ -0,0 +1,545 @@
+//! Minimal XML document model, used to read and write ISO 20022 messages.
+//!
+//! Only what is needed by ISO 20022 documents is supported: elements,
+//! attributes, text, CDATA sections, comments and the predefined and
+//! numeric character references. Namespace prefixes are stripped from
+//! element names, so that an element can be looked up by its local name.
+
+use crate::error::Error;
+use crate::iso4217::CurrencyCode;
+use crate::money::Money;
+use crate::result::Result;
+use std::fmt;
+use std::str::FromStr;
+
+/// `DECLARATION` is the XML declaration written at the top of documents.
+pub const DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+/// [`Element`] is an XML element, with its attributes, children and text.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    /// `new` creates a new empty `Element`.
+    pub fn new(name: &str) -> Element {
+        Element {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// `leaf` creates a new `Element` containing only text.
+    pub fn leaf(name: &str, text: &str) -> Element {
+        Element::new(name).with_text(text)
+    }
+
+    /// `with_attribute` adds an attribute to the `Element`.
+    pub fn with_attribute(mut self, name: &str, value: &str) -> Element {
+        self.attributes.push((name.into(), value.into()));
+        self
+    }
+
+    /// `with_text` sets the text of the `Element`.
+    pub fn with_text(mut self, text: &str) -> Element {
+        self.text = text.into();
+        self
+    }
+
+    /// `with_child` appends a child to the `Element`.
+    pub fn with_child(mut self, child: Element) -> Element {
+        self.children.push(child);
+        self
+    }
+
+    /// `with_leaf` appends a text only child to the `Element`.
+    pub fn with_leaf(self, name: &str, text: &str) -> Element {
+        self.with_child(Element::leaf(name, text))
+    }
+
+    /// `with_optional_leaf` appends a text only child to the `Element`
+    /// if `text` is some.
+    pub fn with_optional_leaf<T: ToString>(self, name: &str, text: Option<T>) -> Element {
+        match text {
+            Some(text) => self.with_leaf(name, &text.to_string()),
+            None => self,
+        }
+    }
+
+    /// `money` creates a new `Element` containing an amount, with its
+    /// currency as `Ccy` attribute.
+    pub fn money(name: &str, money: &Money) -> Element {
+        Element::leaf(name, &money.format('.')).with_attribute("Ccy", money.currency.as_str())
+    }
+
+    /// `push` appends a child to the `Element`.
+    pub fn push(&mut self, child: Element) {
+        self.children.push(child);
+    }
+
+    /// `attribute` returns the value of an attribute.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// `text` returns the trimmed text of the `Element`.
+    pub fn text(&self) -> &str {
+        self.text.trim()
+    }
+
+    /// `child` returns the first child with the given name.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// `children` returns the children with the given name.
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// `find` returns the first element matching a path of names separated
+    /// by '/', e.g. "GrpHdr/MsgId".
+    pub fn find(&self, path: &str) -> Option<&Element> {
+        path.split('/')
+            .try_fold(self, |element, name| element.child(name))
+    }
+
+    /// `find_all` returns all the elements matching a path of names
+    /// separated by '/'.
+    pub fn find_all(&self, path: &str) -> Vec<&Element> {
+        path.split('/').fold(vec![self], |elements, name| {
+            elements
+                .into_iter()
+                .flat_map(|element| element.children.iter().filter(move |c| c.name == name))
+                .collect()
+        })
+    }
+
+    /// `find_text` returns the trimmed text of the first element matching
+    /// a path, if any.
+    pub fn find_text(&self, path: &str) -> Option<&str> {
+        self.find(path).map(|element| element.text())
+    }
+
+    /// `require` is like `find`, but returns an error if the element is missing.
+    pub fn require(&self, path: &str) -> Result<&Element> {
+        self.find(path)
+            .ok_or_else(|| Error::MissingElement(format!("{}/{}", self.name, path)))
+    }
+
+    /// `require_text` is like `find_text`, but returns an error if the
+    /// element is missing.
+    pub fn require_text(&self, path: &str) -> Result<&str> {
+        self.require(path).map(|element| element.text())
+    }
+
+    /// `find_parsed` parses the text of the first element matching a path,
+    /// if any.
+    pub fn find_parsed<T: FromStr<Err = Error>>(&self, path: &str) -> Result<Option<T>> {
+        self.find_text(path).map(str::parse).transpose()
+    }
+
+    /// `require_parsed` is like `find_parsed`, but returns an error if the
+    /// element is missing.
+    pub fn require_parsed<T: FromStr<Err = Error>>(&self, path: &str) -> Result<T> {
+        self.require_text(path)?.parse()
+    }
+
+    /// `find_money` parses the first amount element matching a path, if any.
+    pub fn find_money(&self, path: &str) -> Result<Option<Money>> {
+        self.find(path).map(Element::to_money).transpose()
+    }
+
+    /// `require_money` is like `find_money`, but returns an error if the
+    /// element is missing.
+    pub fn require_money(&self, path: &str) -> Result<Money> {
+        self.require(path)?.to_money()
+    }
+
+    /// `to_money` parses an amount element, with its currency as `Ccy` attribute.
+    pub fn to_money(&self) -> Result<Money> {
+        let currency = self
+            .attribute("Ccy")
+            .ok_or_else(|| Error::MissingElement(format!("{}/@Ccy", self.name)))?;
+
+        Money::parse(self.text(), CurrencyCode::new(currency)?)
+    }
+
+    /// `parse` parses an XML document, returning its root element.
+    pub fn parse(xml: &str) -> Result<Element> {
+        let mut parser = Parser { xml, pos: 0 };
+
+        parser.skip_misc()?;
+        let root = parser.element()?;
+        parser.skip_misc()?;
+
+        if parser.pos != xml.len() {
+            return Err(parser.error("unexpected content after root element"));
+        }
+
+        Ok(root)
+    }
+
+    /// `to_document` serializes the `Element` as the root of an indented
+    /// XML document, including the XML declaration.
+    pub fn to_document(&self) -> String {
+        let mut res = String::from(DECLARATION);
+        res.push('\n');
+        self.write(&mut res, 0);
+        res
+    }
+
+    fn write(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+
+        out.push_str(&indent);
+        out.push('<');
+        out.push_str(&self.name);
+
+        for (name, value) in self.attributes.iter() {
+            out.push_str(&format!(" {}=\"{}\"", name, escape(value, true)));
+        }
+
+        if self.children.is_empty() && self.text.is_empty() {
+            out.push_str("/>\n");
+            return;
+        }
+
+        out.push('>');
+
+        if self.children.is_empty() {
+            out.push_str(&escape(&self.text, false));
+        } else {
+            out.push('\n');
+
+            for child in self.children.iter() {
+                child.write(out, depth + 1);
+            }
+
+            out.push_str(&indent);
+        }
+
+        out.push_str(&format!("</{}>\n", self.name));
+    }
+}
+
+impl FromStr for Element {
+    type Err = Error;
+
+    fn from_str(xml: &str) -> std::result::Result<Self, Self::Err> {
+        Element::parse(xml)
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_document())
+    }
+}
+
+/// `escape` escapes the XML special chars of a string.
+pub fn escape(text: &str, attribute: bool) -> String {
+    let mut res = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' if attribute => res.push_str("&quot;"),
+            '\'' if attribute => res.push_str("&apos;"),
+            _ => res.push(c),
+        }
+    }
+
+    res
+}
+
+/// `unescape` replaces the character references of a string.
+pub fn unescape(text: &str) -> Result<String> {
+    let mut res = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        res.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let end = rest
+            .find(';')
+            .ok_or_else(|| Error::ParseXML("unterminated character reference".into()))?;
+
+        let reference = &rest[..end];
+        let c = match reference {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = if let Some(hex) = reference.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = reference.strip_prefix('#') {
+                    dec.parse().ok()
+                } else {
+                    None
+                };
+
+                code.and_then(std::char::from_u32).ok_or_else(|| {
+                    Error::ParseXML(format!("invalid character reference: &{};", reference))
+                })?
+            }
+        };
+
+        res.push(c);
+        rest = &rest[end + 1..];
+    }
+
+    res.push_str(rest);
+
+    Ok(res)
+}
+
+/// `local_name` strips the namespace prefix from a name.
+fn local_name(name: &str) -> &str {
+    match name.find(':') {
+        Some(i) => &name[i + 1..],
+        None => name,
+    }
+}
+
+struct Parser<'a> {
+    xml: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, msg: &str) -> Error {
+        Error::ParseXML(format!("{} at byte {}", msg, self.pos))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.xml[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn skip_until(&mut self, end: &str) -> Result<&'a str> {
+        let rest = self.rest();
+        let i = rest
+            .find(end)
+            .ok_or_else(|| self.error(&format!("expected '{}'", end)))?;
+
+        self.pos += i + end.len();
+
+        Ok(&rest[..i])
+    }
+
+    /// skips whitespaces, comments, processing instructions and doctypes.
+    fn skip_misc(&mut self) -> Result<()> {
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if self.rest().starts_with("<!DOCTYPE") {
+                self.skip_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/' || c == '=')
+            .unwrap_or(rest.len());
+
+        if len == 0 {
+            return Err(self.error("expected name"));
+        }
+
+        self.pos += len;
+
+        Ok(&rest[..len])
+    }
+
+    fn element(&mut self) -> Result<Element> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected element"));
+        }
+
+        self.pos += 1;
+
+        let qname = self.name()?;
+        let mut element = Element::new(local_name(qname));
+
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+
+            let name = self.name()?;
+            self.skip_whitespace();
+
+            if !self.rest().starts_with('=') {
+                return Err(self.error("expected '='"));
+            }
+
+            self.pos += 1;
+            self.skip_whitespace();
+
+            let quote = match self.rest().chars().next() {
+                Some(q) if q == '"' || q == '\'' => q,
+                _ => return Err(self.error("expected quoted attribute value")),
+            };
+
+            self.pos += 1;
+            let value = self.skip_until(&quote.to_string())?;
+            element.attributes.push((name.into(), unescape(value)?));
+        }
+
+        loop {
+            let rest = self.rest();
+
+            if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+
+                if name != qname {
+                    return Err(self.error(&format!("expected '</{}>'", qname)));
+                }
+
+                self.skip_whitespace();
+
+                if !self.rest().starts_with('>') {
+                    return Err(self.error("expected '>'"));
+                }
+
+                self.pos += 1;
+
+                if !element.children.is_empty() && element.text.trim().is_empty() {
+                    element.text.clear();
+                }
+
+                return Ok(element);
+            } else if rest.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += 9;
+                let text = self.skip_until("]]>")?;
+                element.text.push_str(text);
+            } else if rest.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if rest.starts_with('<') {
+                let child = self.element()?;
+                element.children.push(child);
+            } else if rest.is_empty() {
+                return Err(self.error(&format!("expected '</{}>'", qname)));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                element.text.push_str(&unescape(&rest[..len])?);
+                self.pos += len;
+            }
+        }
+    }
+}
+
+mod test {
+    #[allow(unused_imports)] // TODO
+    use super::{Element, Money, Result};
+
+    #[test]
+    fn parse() -> Result<()> {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!-- a comment -->
+            <Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+                <ns:GrpHdr xmlns:ns="urn:x"><ns:MsgId>A &amp; B &#x41;</ns:MsgId></ns:GrpHdr>
+                <Amt Ccy='EUR'>10.50</Amt>
+                <Amt Ccy="USD"><![CDATA[1 < 2]]></Amt>
+                <Empty/>
+            </Document>"#;
+
+        let root = Element::parse(xml)?;
+
+        assert_eq!(root.name, "Document");
+        assert_eq!(
+            root.attribute("xmlns"),
+            Some("urn:iso:std:iso:20022:tech:xsd:camt.053.001.02")
+        );
+        assert_eq!(root.find_text("GrpHdr/MsgId"), Some("A & B A"));
+        assert_eq!(root.find_all("Amt").len(), 2);
+        assert_eq!(
+            root.find("Amt").and_then(|a| a.attribute("Ccy")),
+            Some("EUR")
+        );
+        assert_eq!(root.find_all("Amt")[1].text(), "1 < 2");
+        assert!(root.find("Empty").is_some());
+        assert!(root.require("GrpHdr/CreDtTm").is_err());
+        assert_eq!(root.require_money("Amt")?.to_string(), "10.50 EUR");
+        assert!(root.find_all("Amt")[1].to_money().is_err());
+
+        const WRONG_DOCUMENTS: &[&str] = &[
+            "",
+            "<A>",
+            "<A></B>",
+            "<A><B></A>",
+            "<A x=1/>",
+            "<A>&unknown;</A>",
+            "<A/><B/>",
+        ];
+
+        for xml in WRONG_DOCUMENTS.iter() {
+            assert!(Element::parse(xml).is_err());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_document() -> Result<()> {
+        let root = Element::new("Document")
+            .with_attribute("xmlns", "urn:x")
+            .with_child(Element::new("GrpHdr").with_leaf("MsgId", "A & B"))
+            .with_child(Element::money("Amt", &Money::new(100, "EUR".into())))
+            .with_child(Element::new("Empty"));
+
+        let xml = root.to_document();
+
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:x">
+  <GrpHdr>
+    <MsgId>A &amp; B</MsgId>
+  </GrpHdr>
+  <Amt Ccy="EUR">1.00</Amt>
+  <Empty/>
+</Document>
+"#
+        );
+        assert_eq!(Element::parse(&xml)?, root);
+
+        Ok(())
+    }
+}